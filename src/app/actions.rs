@@ -0,0 +1,113 @@
+//! Actions from the config: shell commands run on the selected issue with `!` and a key, a
+//! lightweight way to plug in other tools.
+
+use serde_json::json;
+
+use super::{App, Command, hooks};
+use crate::{error::Result, ui::popup::Popup};
+
+/// The placeholders actions can use, and the `JIRA_*` variables they stand for.
+const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("{key}", "JIRA_KEY"),
+    ("{summary}", "JIRA_SUMMARY"),
+    ("{status}", "JIRA_STATUS"),
+    ("{type}", "JIRA_TYPE"),
+    ("{priority}", "JIRA_PRIORITY"),
+    ("{assignee}", "JIRA_ASSIGNEE"),
+    ("{labels}", "JIRA_LABELS"),
+    ("{url}", "JIRA_URL"),
+];
+
+/// Runs the action bound to `key` on the selected issue.
+pub(super) fn run(app: &mut App, key: char) -> Command {
+    let Some(action) = app.actions.iter().find(|action| action.key == key) else {
+        app.notice = Some(format!("No action is bound to !{key}"));
+        return Command::None;
+    };
+    let Some(issue) = app.selected_jira_issue() else {
+        app.notice = Some(format!("Select an issue to run {} on", action.name));
+        return Command::None;
+    };
+    let Some(command) = expand(&action.command) else {
+        app.error = Some(format!(
+            "{} uses placeholders, which cmd would run as commands; read the JIRA_* \
+             variables from a script instead",
+            action.name
+        ));
+        return Command::None;
+    };
+    let input = json!({ "issue": hooks::issue_json(issue) });
+    Command::RunAction {
+        name: action.name.clone(),
+        command,
+        env: hooks::issue_env(issue),
+        input: format!("{input}\n"),
+        popup: action.popup,
+    }
+}
+
+/// Replaces the placeholders in a command with the variables holding the issue's fields, so
+/// the shell inserts them without interpreting them. cmd interprets what it inserts, so on
+/// Windows there's no safe way to, and commands with placeholders aren't run.
+fn expand(command: &str) -> Option<String> {
+    if cfg!(windows) {
+        let plain = PLACEHOLDERS
+            .iter()
+            .all(|(placeholder, _)| !command.contains(placeholder));
+        return plain.then(|| command.to_string());
+    }
+    Some(
+        PLACEHOLDERS
+            .iter()
+            .fold(command.to_string(), |command, (placeholder, variable)| {
+                command.replace(placeholder, &format!("\"${variable}\""))
+            }),
+    )
+}
+
+/// Shows the output of an action that asked for it, or reports that it failed.
+pub(super) fn finished(app: &mut App, name: String, popup: bool, result: Result<String>) {
+    match result {
+        Ok(output) if popup => app.popup = Some(Popup::new(format!(" {name} "), output)),
+        Ok(_) => app.notice = Some(format!("{name} done")),
+        Err(e) => app.error = Some(format!("{name} failed: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::ActionConfig, ui::issue::Issue};
+
+    #[test]
+    fn runs_bound_actions_on_the_selected_issue() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            ..Issue::new("Failover", "")
+        }]);
+        app.actions = vec![ActionConfig {
+            name: "Branch".to_string(),
+            key: 'b',
+            command: "git switch -c {key} && jq '{a: .issue.key}'".to_string(),
+            popup: true,
+        }];
+
+        assert_eq!(run(&mut app, 'x'), Command::None);
+        assert_eq!(app.notice.as_deref(), Some("No action is bound to !x"));
+
+        if cfg!(windows) {
+            assert_eq!(run(&mut app, 'b'), Command::None);
+            assert!(app.error.as_deref().unwrap().contains("placeholders"));
+            return;
+        }
+        let Command::RunAction { command, env, popup, .. } = run(&mut app, 'b') else {
+            panic!("expected the action to run");
+        };
+        assert_eq!(command, "git switch -c \"$JIRA_KEY\" && jq '{a: .issue.key}'");
+        assert!(env.contains(&("JIRA_KEY".to_string(), "OPS-1".to_string())));
+        assert!(popup);
+
+        finished(&mut app, "Branch".to_string(), true, Ok("Switched\n".to_string()));
+        assert_eq!(app.popup.as_ref().map(|popup| popup.text.as_str()), Some("Switched\n"));
+    }
+}
@@ -0,0 +1,106 @@
+//! The activity log: the requests to Jira with how long they took, and the errors the app
+//! showed, for a pane below the list to look into problems without leaving the app.
+
+use std::{collections::VecDeque, time::Duration};
+
+use chrono::{DateTime, FixedOffset, Local};
+
+use super::operations::Operation;
+use crate::error::Error;
+
+/// Older entries are forgotten beyond this many.
+const MAX_ENTRIES: usize = 500;
+
+/// A line of the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub when: DateTime<FixedOffset>,
+    pub text: String,
+    pub error: bool,
+}
+
+/// The log, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivityLog {
+    entries: VecDeque<LogEntry>,
+}
+
+impl ActivityLog {
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn push(&mut self, text: String, error: bool) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            when: Local::now().fixed_offset(),
+            text,
+            error,
+        });
+    }
+
+    /// Notes a request that finished after `took`, and whether it failed.
+    pub(super) fn request(
+        &mut self,
+        operation: &Operation,
+        took: Duration,
+        failed: Option<&Error>,
+    ) {
+        let took = match took.as_millis() {
+            millis @ 0..1000 => format!("{millis} ms"),
+            _ => format!("{:.1} s", took.as_secs_f64()),
+        };
+        match failed {
+            Some(e) => self.push(format!("{} failed after {took}: {e}", operation.label()), true),
+            None => self.push(format!("{} took {took}", operation.label()), false),
+        }
+    }
+
+    /// Notes an error shown in the footer.
+    pub(super) fn error(&mut self, message: &str) {
+        self.push(message.to_string(), true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logs_requests_and_errors() {
+        let mut log = ActivityLog::default();
+        log.request(&Operation::Projects, Duration::from_millis(240), None);
+        let timeout = Error::Network("timeout".to_string());
+        log.request(
+            &Operation::Issue("OPS-1".to_string()),
+            Duration::from_secs(30),
+            Some(&timeout),
+        );
+        log.error("Failed to load projects: token expired");
+        let lines: Vec<(&str, bool)> = log
+            .iter()
+            .map(|entry| (entry.text.as_str(), entry.error))
+            .collect();
+        assert_eq!(lines, [
+            ("loading projects took 240 ms", false),
+            ("loading OPS-1 failed after 30.0 s: network error: timeout", true),
+            ("Failed to load projects: token expired", true),
+        ]);
+
+        for _ in 0..MAX_ENTRIES {
+            log.error("again");
+        }
+        assert_eq!(log.len(), MAX_ENTRIES);
+        assert!(log.iter().all(|entry| entry.text == "again"));
+    }
+}
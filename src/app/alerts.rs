@@ -0,0 +1,201 @@
+//! Alerts: searches from the config run in the background every few minutes, with their result
+//! counts as badges in the status line, like the urgent issues assigned to you.
+
+use std::time::Duration;
+
+use super::{App, Command, Loadable, View, update::start_search};
+use crate::{config::AlertConfig, error::Result};
+
+/// An alert search, with the keys of the issues it found the last time it ran.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub name: String,
+    pub jql: String,
+    pub found: Loadable<Vec<String>>,
+}
+
+impl Alert {
+    /// How many issues the search found, once it ran.
+    pub fn count(&self) -> Option<usize> {
+        match &self.found {
+            Loadable::Loaded(keys) => Some(keys.len()),
+            _ => None,
+        }
+    }
+}
+
+/// The alerts from the config.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Alerts {
+    alerts: Vec<Alert>,
+    /// Whether [`Command::CheckAlerts`] is waiting to run the searches.
+    checking: bool,
+}
+
+impl Alerts {
+    /// Takes the alerts from the config, keeping what the unchanged ones found.
+    pub fn configure(&mut self, configs: &[AlertConfig]) {
+        self.alerts = configs
+            .iter()
+            .map(|config| {
+                let found = self
+                    .alerts
+                    .iter()
+                    .find(|alert| alert.name == config.name && alert.jql == config.jql)
+                    .map_or(Loadable::Loading, |alert| alert.found.clone());
+                Alert {
+                    name: config.name.clone(),
+                    jql: config.jql.clone(),
+                    found,
+                }
+            })
+            .collect();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter()
+    }
+}
+
+/// Runs the alert searches once `after` has passed, unless there are none or the connection
+/// is metered.
+pub(super) fn check(app: &mut App, after: Duration) -> Command {
+    app.alerts.checking = !app.alerts.alerts.is_empty() && !app.polling.metered;
+    if !app.alerts.checking {
+        return Command::None;
+    }
+    let searches = app
+        .alerts
+        .iter()
+        .map(|alert| (alert.name.clone(), alert.jql.clone()))
+        .collect();
+    Command::CheckAlerts { searches, after }
+}
+
+/// Starts the searches of alerts added to the config, unless they're running already.
+pub(super) fn reconfigured(app: &mut App) -> Command {
+    if app.alerts.checking {
+        return Command::None;
+    }
+    check(app, Duration::ZERO)
+}
+
+/// Takes the keys each alert search found, by alert name, and runs them again later if the
+/// config says so.
+pub(super) fn checked(app: &mut App, results: Vec<(String, Result<Vec<String>>)>) -> Command {
+    for (name, result) in results {
+        // Alerts renamed or removed from the config in the meantime have no place to go
+        if let Some(alert) = app
+            .alerts
+            .alerts
+            .iter_mut()
+            .find(|alert| alert.name == name)
+        {
+            alert.found = match result {
+                Ok(keys) => Loadable::Loaded(keys),
+                Err(e) => Loadable::Failed(e.to_string()),
+            };
+        }
+    }
+    match app.polling.alert_interval() {
+        Some(every) => check(app, every),
+        None => {
+            app.alerts.checking = false;
+            Command::None
+        }
+    }
+}
+
+/// Lists the issues of the `n`th alert, counting from 1, or of the first one that found any.
+pub(super) fn show(app: &mut App, n: Option<usize>) -> Command {
+    let alert = match n {
+        Some(n) => app.alerts.alerts.get(n.saturating_sub(1)),
+        None => app
+            .alerts
+            .iter()
+            .find(|alert| alert.count().is_some_and(|count| count > 0)),
+    };
+    let Some(alert) = alert else {
+        app.notice = Some(match n {
+            _ if app.alerts.alerts.is_empty() => "No alerts configured".to_string(),
+            Some(n) => format!("No alert {n}"),
+            None => "No alert found any issues".to_string(),
+        });
+        return Command::None;
+    };
+    let (name, jql) = (alert.name.clone(), alert.jql.clone());
+    app.view = View::List;
+    let command = start_search(app, jql);
+    app.notice = Some(format!("Listing the issues of alert {name:?}"));
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        error::Error,
+        ui::issue::Issue,
+    };
+
+    #[test]
+    fn counts_and_lists_what_alerts_find() {
+        let mut app = App::new(vec![Issue::new("Failover", "")]);
+        let alert = |name: &str, jql: &str| AlertConfig {
+            name: name.to_string(),
+            jql: jql.to_string(),
+        };
+        app.alerts.configure(&[
+            alert("Urgent", "priority = Highest AND assignee = currentUser()"),
+            alert("Unassigned", "assignee IS EMPTY"),
+        ]);
+        let Command::CheckAlerts { searches, after } = check(&mut app, Duration::ZERO) else {
+            panic!("alerts aren't checked");
+        };
+        assert_eq!(searches.len(), 2);
+        assert_eq!(after, Duration::ZERO);
+        // Running already
+        assert_eq!(reconfigured(&mut app), Command::None);
+
+        let command = update(
+            &mut app,
+            Msg::AlertsChecked(vec![
+                ("Urgent".to_string(), Ok(Vec::new())),
+                ("Unassigned".to_string(), Ok(vec!["OPS-3".to_string(), "OPS-5".to_string()])),
+            ]),
+        );
+        let every = Duration::from_secs(5 * 60);
+        assert_eq!(command, Command::CheckAlerts { searches, after: every });
+        let counts: Vec<_> = app.alerts.iter().map(Alert::count).collect();
+        assert_eq!(counts, [Some(0), Some(2)]);
+
+        // Without a count, the first alert that found issues
+        let command = show(&mut app, None);
+        assert_eq!(app.jql, "assignee IS EMPTY");
+        assert!(matches!(command, Command::FetchRemaining { .. }));
+        show(&mut app, Some(1));
+        assert_eq!(app.jql, "priority = Highest AND assignee = currentUser()");
+
+        // A failed search shows as such until the next one, and is kept across reloads
+        update(
+            &mut app,
+            Msg::AlertsChecked(vec![(
+                "Urgent".to_string(),
+                Err(Error::Jql("field 'priority' is unknown".to_string())),
+            )]),
+        );
+        app.alerts
+            .configure(&[alert("Urgent", "priority = Highest AND assignee = currentUser()")]);
+        let urgent = app.alerts.iter().next().unwrap();
+        assert!(matches!(urgent.found, Loadable::Failed(_)));
+        app.alerts.configure(&[]);
+        assert_eq!(check(&mut app, every), Command::None);
+
+        // Not even once on a metered connection
+        app.alerts
+            .configure(&[alert("Unassigned", "assignee IS EMPTY")]);
+        app.polling.metered = true;
+        assert_eq!(check(&mut app, Duration::ZERO), Command::None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Getting the attention of users looking elsewhere, like at another tmux pane, when a refresh
+//! finds something urgent: an issue of the highest priority newly assigned to them, or a new
+//! comment mentioning them. Off unless the config says how.
+
+use serde_json::Value;
+
+use super::{App, Command, Loadable};
+use crate::{config::Notify, jira::User, ui::issue::Issue};
+
+/// Animation ticks a flash of the screen lasts.
+const FLASH_TICKS: u8 = 3;
+
+/// Fetches who the user is if the config asks to get their attention, since what's urgent
+/// depends on that, unless it's known already.
+pub(super) fn know_me(app: &mut App) -> Command {
+    if app.polling.notify == Notify::Off || app.me.is_some() {
+        return Command::None;
+    }
+    app.me = Some(Loadable::Loading);
+    Command::FetchMyself
+}
+
+/// Why `new` is urgent to `me`, if it became so since it was `old`, or since it wasn't listed.
+/// Comments on issues that weren't listed are new if they were made after the last refresh.
+fn urgency(app: &App, me: &User, old: Option<&Issue>, new: &Issue) -> Option<String> {
+    let urgent = |issue: &Issue| {
+        issue.assignee.as_deref() == Some(me.display_name.as_str())
+            && issue.raw_field("priority.name").and_then(Value::as_str) == Some("Highest")
+    };
+    if urgent(new) && !old.is_some_and(urgent) {
+        return Some(format!("{} of the highest priority is assigned to you", new.id));
+    }
+    let seen = old.map(Issue::comments);
+    let since = app.connection.last_refresh;
+    let mention = format!("@{}", me.display_name);
+    new.comments()
+        .into_iter()
+        .filter(|comment| match &seen {
+            Some(seen) => !seen.contains(comment),
+            None => since
+                .zip(comment.created)
+                .is_some_and(|(since, created)| created > since),
+        })
+        .find(|comment| comment.body.contains(&mention))
+        .map(|comment| format!("{} mentioned you on {}", comment.author, new.id))
+}
+
+/// Gets the user's attention as the config says if any of the issues `found` by a refresh
+/// became urgent compared to the listed ones, saying why in the status line.
+pub(super) fn refreshed(app: &mut App, found: &[Issue]) -> Command {
+    let Some(Loadable::Loaded(me)) = &app.me else {
+        return Command::None;
+    };
+    if app.polling.notify == Notify::Off {
+        return Command::None;
+    }
+    let reasons: Vec<String> = found
+        .iter()
+        .filter_map(|new| {
+            let old = app.issues.iter().find(|old| old.id == new.id);
+            urgency(app, me, old, new)
+        })
+        .collect();
+    let Some(first) = reasons.first() else {
+        return Command::None;
+    };
+    app.notice = Some(match reasons.len() {
+        1 => first.clone(),
+        count => format!("{first}, and {} more", count - 1),
+    });
+    match app.polling.notify {
+        Notify::Off => Command::None,
+        Notify::Bell => Command::Bell,
+        Notify::Flash => {
+            app.flash = FLASH_TICKS;
+            Command::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        jira::FakeClient,
+        ui::time,
+    };
+
+    fn issue(key: &str, priority: &str, assignee: &str, comments: &[(&str, &str)]) -> Issue {
+        let mut issue = Issue::from_jira(&FakeClient::issue(key, "", "To Do"));
+        let comments: Vec<_> = comments
+            .iter()
+            .map(|(author, body)| {
+                json!({
+                    "author": { "displayName": author },
+                    "created": "2024-03-04T09:00:00.000+0000",
+                    "body": body,
+                })
+            })
+            .collect();
+        issue.assignee = Some(assignee.to_string());
+        issue
+            .raw_fields
+            .insert("priority".to_string(), json!({ "name": priority }));
+        issue
+            .raw_fields
+            .insert("comment".to_string(), json!({ "comments": comments }));
+        issue
+    }
+
+    #[test]
+    fn urgent_issues_ring_the_bell_once() {
+        let mut app = App::new(vec![
+            issue("OPS-1", "High", "Ana Lima", &[]),
+            issue("OPS-2", "Highest", "Bo Berg", &[("Bo Berg", "Looking into it")]),
+        ]);
+        app.polling.notify = Notify::Bell;
+        assert_eq!(know_me(&mut app), Command::FetchMyself);
+        assert_eq!(know_me(&mut app), Command::None);
+        update(
+            &mut app,
+            Msg::Myself(Ok(User {
+                account_id: "ana".to_string(),
+                display_name: "Ana Lima".to_string(),
+            })),
+        );
+
+        // Comments are new for being added, or for being made since the last refresh
+        app.connection.last_refresh = time::parse_timestamp("2024-03-04T08:00:00.000+0000");
+        let found = [
+            issue("OPS-1", "Highest", "Ana Lima", &[]),
+            issue("OPS-2", "Highest", "Bo Berg", &[("Bo Berg", "Looking into it")]),
+            issue("OPS-3", "Low", "Bo Berg", &[("Cy Dale", "@Ana Lima can you check?")]),
+        ];
+        assert_eq!(refreshed(&mut app, &found), Command::Bell);
+        assert_eq!(
+            app.notice.as_deref(),
+            Some("OPS-1 of the highest priority is assigned to you, and 1 more")
+        );
+
+        app.issues = found.to_vec();
+        assert_eq!(refreshed(&mut app, &found), Command::None);
+        let mut found = found.to_vec();
+        found[1] = issue("OPS-2", "Highest", "Bo Berg", &[
+            ("Bo Berg", "Looking into it"),
+            ("Bo Berg", "@Ana Lima it's the disk"),
+        ]);
+        app.polling.notify = Notify::Flash;
+        assert_eq!(refreshed(&mut app, &found), Command::None);
+        assert_eq!(app.notice.as_deref(), Some("Bo Berg mentioned you on OPS-2"));
+        assert_eq!(app.flash, FLASH_TICKS);
+        assert!(app.is_animating());
+    }
+}
@@ -0,0 +1,454 @@
+//! Editing the status, assignee, and story points of all listed issues at once, as lines of
+//! text in the user's editor, the way `git rebase -i` edits commits.
+
+use std::collections::{HashMap, HashSet};
+
+use futures::{StreamExt, stream};
+use jira_v3_openapi::models::IssueBean;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Value, json};
+
+use super::{App, Command, hooks, outbox, update::forget_cached};
+use crate::{
+    error::{Error, Result},
+    jira::{JiraClient, User, custom_fields},
+    ui::issue::Issue,
+};
+
+/// Stands for no assignee or no story points.
+const NONE: &str = "-";
+
+/// How many issues are changed at the same time, which keeps Jira from rate limiting large
+/// edits.
+const CONCURRENCY: usize = 8;
+
+/// Explains the file, above the issues.
+const INSTRUCTIONS: &str = "\
+# Change the status, assignee, or story points of the issues below, then save and quit.
+# A - stands for no assignee or no points. Removing a line leaves its issue alone, and the
+# summaries after the # are only there for reference.
+#
+";
+
+/// The editable fields of an issue, as they were when the editor opened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkRow {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub points: Option<f64>,
+}
+
+// Story points are never NaN: they come from JSON numbers, and parsing rejects NaN
+impl Eq for BulkRow {}
+
+impl From<&Issue> for BulkRow {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            key: issue.id.clone(),
+            summary: issue.summary.clone(),
+            status: issue
+                .status
+                .as_ref()
+                .map(|status| status.name.clone())
+                .unwrap_or_default(),
+            assignee: issue.assignee.clone(),
+            points: issue.story_points,
+        }
+    }
+}
+
+/// What to change about an issue; `None` leaves a field as it is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkChange {
+    pub key: String,
+    /// Name of the status to transition to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "present")]
+    pub assignee: Option<Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "present")]
+    pub points: Option<Option<f64>>,
+    /// Key of the epic or issue to move the issue under.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "present")]
+    pub parent: Option<Option<String>>,
+    /// Whether to flag the issue as an impediment or take the flag off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flagged: Option<bool>,
+    /// Name of the priority to give the issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+}
+
+/// Reads a field that is there as `Some`, even when it's `null`, which stands for removing
+/// the value. Missing fields are left alone.
+fn present<'de, D, T>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl Eq for BulkChange {}
+
+/// Opens the listed issues in the editor.
+pub(super) fn start(app: &mut App) -> Command {
+    let rows: Vec<BulkRow> = app
+        .visible_issues()
+        .filter(|issue| !issue.is_local())
+        .map(BulkRow::from)
+        .collect();
+    if rows.is_empty() {
+        app.notice = Some("There are no issues to edit".to_string());
+        return Command::None;
+    }
+    Command::BulkEdit { rows }
+}
+
+/// The file to edit: a line per issue with its fields in aligned columns.
+pub(super) fn render(rows: &[BulkRow]) -> String {
+    let points = |row: &BulkRow| {
+        row.points
+            .map_or(NONE.to_string(), |points| points.to_string())
+    };
+    let width = |field: fn(&BulkRow) -> usize| rows.iter().map(field).max().unwrap_or(0);
+    let key_width = width(|row| row.key.chars().count());
+    let status_width = width(|row| row.status.chars().count());
+    let assignee_width = width(|row| row.assignee.as_deref().unwrap_or(NONE).chars().count());
+    let points_width = rows.iter().map(|row| points(row).len()).max().unwrap_or(0);
+
+    let mut text = INSTRUCTIONS.to_string();
+    for row in rows {
+        text.push_str(&format!(
+            "{:key_width$} | {:status_width$} | {:assignee_width$} | {:points_width$} # {}\n",
+            row.key,
+            row.status,
+            row.assignee.as_deref().unwrap_or(NONE),
+            points(row),
+            row.summary
+        ));
+    }
+    text
+}
+
+/// Compares the edited file with the rows it was made of. Nothing is changed if any line
+/// can't be read.
+fn changes(text: &str, rows: &[BulkRow]) -> Result<Vec<BulkChange>> {
+    let mut seen = HashSet::new();
+    let mut changes = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_error = |message: String| Error::Input(format!("line {}: {message}", i + 1));
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = line.split_once('#').map_or(line, |(fields, _)| fields);
+        let fields: Vec<&str> = fields.split('|').map(str::trim).collect();
+        let [key, status, assignee, points] = fields[..] else {
+            return Err(line_error("expected key | status | assignee | points".to_string()));
+        };
+        let row = rows
+            .iter()
+            .find(|row| row.key.eq_ignore_ascii_case(key))
+            .ok_or_else(|| line_error(format!("{key} is not one of the issues being edited")))?;
+        if !seen.insert(&row.key) {
+            return Err(line_error(format!("{} is listed twice", row.key)));
+        }
+
+        let assignee = match assignee {
+            "" | NONE => None,
+            name => Some(name.to_string()),
+        };
+        let points = match points {
+            "" | NONE => None,
+            points => match points.parse::<f64>() {
+                Ok(points) if points.is_finite() => Some(points),
+                _ => return Err(line_error(format!("{points:?} is not a number"))),
+            },
+        };
+        let change = BulkChange {
+            key: row.key.clone(),
+            // A status can't be removed, so leaving it empty changes nothing
+            status: (!status.is_empty() && !status.eq_ignore_ascii_case(&row.status))
+                .then(|| status.to_string()),
+            assignee: (assignee != row.assignee).then_some(assignee),
+            points: (points != row.points).then_some(points),
+            parent: None,
+            flagged: None,
+            priority: None,
+        };
+        if change.status.is_some() || change.assignee.is_some() || change.points.is_some() {
+            changes.push(change);
+        }
+    }
+    Ok(changes)
+}
+
+/// Applies the edits saved in the editor, or reports why they can't be.
+pub(super) fn edited(app: &mut App, rows: &[BulkRow], result: Result<String>) -> Command {
+    match result.and_then(|text| changes(&text, rows)) {
+        Ok(changes) if changes.is_empty() => {
+            app.notice = Some("No issues were changed".to_string());
+            Command::None
+        }
+        Ok(changes) => {
+            app.notice = Some(format!("Updating {} issues…", changes.len()));
+            outbox::queue(app, changes)
+        }
+        Err(e) => {
+            app.error = Some(format!("Bulk edit not applied: {e}"));
+            Command::None
+        }
+    }
+}
+
+/// Makes all of the changes, several issues at a time, returning the outcome of each in the
+/// order of `changes`.
+pub(super) async fn apply_all(
+    client: &dyn JiraClient,
+    changes: Vec<BulkChange>,
+) -> Vec<(String, Result<IssueBean>)> {
+    stream::iter(changes)
+        .map(|change| async move {
+            let result = apply(client, &change).await;
+            (change.key, result)
+        })
+        .buffered(CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// Makes the changes to one issue, returning it as it is afterwards.
+pub(super) async fn apply(client: &dyn JiraClient, change: &BulkChange) -> Result<IssueBean> {
+    let key = &change.key;
+    if let Some(status) = &change.status {
+        let transitions = client.get_transitions(key).await?;
+        let transition = transitions
+            .iter()
+            .find(|transition| {
+                transition
+                    .to_status
+                    .as_deref()
+                    .is_some_and(|to| to.eq_ignore_ascii_case(status))
+            })
+            .ok_or_else(|| Error::Input(format!("{key} can't move to {status}")))?;
+        client.transition_issue(key, &transition.id).await?;
+    }
+
+    let mut fields = HashMap::new();
+    if let Some(assignee) = &change.assignee {
+        let assignee = match assignee {
+            Some(name) => json!({ "accountId": find_user(client, key, name).await?.account_id }),
+            None => Value::Null,
+        };
+        fields.insert("assignee".to_string(), assignee);
+    }
+    if let Some(points) = change.points {
+        fields.insert(custom_fields().story_points.clone(), json!(points));
+    }
+    if let Some(parent) = &change.parent {
+        let parent = match parent {
+            Some(key) => json!({ "key": key }),
+            None => Value::Null,
+        };
+        fields.insert("parent".to_string(), parent);
+    }
+    if let Some(flagged) = change.flagged {
+        let flag = match flagged {
+            true => json!([{ "value": "Impediment" }]),
+            false => Value::Null,
+        };
+        fields.insert(custom_fields().flagged.clone(), flag);
+    }
+    if let Some(priority) = &change.priority {
+        fields.insert("priority".to_string(), json!({ "name": priority }));
+    }
+    if !fields.is_empty() {
+        client.edit_issue(key, fields).await?;
+    }
+    client.get_issue(key).await
+}
+
+/// The user named `name` who can be assigned the issue, or the only one whose name contains
+/// it.
+async fn find_user(client: &dyn JiraClient, key: &str, name: &str) -> Result<User> {
+    let mut users = client.find_assignable_users(key, name).await?;
+    if let Some(i) = users
+        .iter()
+        .position(|user| user.display_name.eq_ignore_ascii_case(name))
+    {
+        return Ok(users.swap_remove(i));
+    }
+    match users.len() {
+        0 => Err(Error::Input(format!("no one named {name} can be assigned {key}"))),
+        1 => Ok(users.remove(0)),
+        _ => Err(Error::Input(format!("{name} could be any of several users"))),
+    }
+}
+
+/// Lists the issues as they are after the changes, reporting the ones that failed.
+pub(super) fn applied(app: &mut App, results: Vec<(String, Result<IssueBean>)>) -> Command {
+    let total = results.len();
+    let mut failures = Vec::new();
+    let mut hooks = Vec::new();
+    for (key, result) in results {
+        match result {
+            Ok(bean) => {
+                let issue = Issue::from_jira(&bean);
+                forget_cached(app, &issue);
+                if let Some(index) = app.issues.iter().position(|listed| listed.id == key) {
+                    hooks.push(hooks::issue_changed(app, &app.issues[index], &issue));
+                    app.issues[index] = issue;
+                }
+            }
+            Err(e) => failures.push(format!("{key}: {e}")),
+        }
+    }
+    app.refresh_visible();
+    if failures.is_empty() {
+        app.notice = Some(format!("Updated {total} issues"));
+    } else {
+        app.error = Some(format!(
+            "{} of {total} issues not updated: {}",
+            failures.len(),
+            failures.join("; ")
+        ));
+    }
+    hooks.retain(|hook| *hook != Command::None);
+    match hooks.len() {
+        0 => Command::None,
+        _ => Command::Batch(hooks),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::FakeClient;
+
+    fn row(key: &str, status: &str, assignee: Option<&str>, points: Option<f64>) -> BulkRow {
+        BulkRow {
+            key: key.to_string(),
+            summary: format!("Summary of {key}"),
+            status: status.to_string(),
+            assignee: assignee.map(str::to_string),
+            points,
+        }
+    }
+
+    #[test]
+    fn diffs_the_edited_lines() {
+        let rows = [
+            row("OPS-1", "To Do", Some("Ana"), Some(3.0)),
+            row("OPS-12", "In Progress", None, None),
+            row("OPS-13", "To Do", None, Some(0.5)),
+        ];
+        let text = render(&rows);
+        assert!(text.contains("\nOPS-1  | To Do       | Ana | 3   # Summary of OPS-1\n"));
+        assert_eq!(changes(&text, &rows).unwrap(), []);
+
+        let edited = "ops-1 | done | - | 3\nOPS-12 | In Progress | Ben | 5 # summary\n";
+        assert_eq!(changes(edited, &rows).unwrap(), [
+            BulkChange {
+                key: "OPS-1".to_string(),
+                status: Some("done".to_string()),
+                assignee: Some(None),
+                points: None,
+                parent: None,
+                flagged: None,
+                priority: None,
+            },
+            BulkChange {
+                key: "OPS-12".to_string(),
+                status: None,
+                assignee: Some(Some("Ben".to_string())),
+                points: Some(Some(5.0)),
+                parent: None,
+                flagged: None,
+                priority: None,
+            },
+        ]);
+
+        let err = changes("# header\nOPS-1 | To Do | Ana | many\n", &rows).unwrap_err();
+        assert_eq!(err.to_string(), "invalid input: line 2: \"many\" is not a number");
+        assert!(changes("OPS-2 | To Do | - | -\n", &rows).is_err());
+        assert!(changes("OPS-1 | To Do | - | -\nOPS-1 | Done | - | -\n", &rows).is_err());
+    }
+
+    #[test]
+    fn removals_survive_saving() {
+        let change = BulkChange {
+            key: "OPS-1".to_string(),
+            status: None,
+            assignee: Some(None),
+            points: None,
+            parent: None,
+            flagged: None,
+            priority: None,
+        };
+        let json = serde_json::to_string(&change).unwrap();
+        assert_eq!(json, r#"{"key":"OPS-1","assignee":null}"#);
+        assert_eq!(serde_json::from_str::<BulkChange>(&json).unwrap(), change);
+    }
+
+    #[tokio::test]
+    async fn applies_changes() {
+        let mut assigned = FakeClient::issue("OPS-2", "Backups", "To Do");
+        assigned
+            .fields
+            .as_mut()
+            .unwrap()
+            .insert("assignee".to_string(), json!({ "displayName": "Ana Lima" }));
+        let client = FakeClient::with_issues(vec![
+            FakeClient::issue("OPS-1", "Failover", "To Do"),
+            assigned,
+        ]);
+
+        let change = BulkChange {
+            key: "OPS-1".to_string(),
+            status: Some("in progress".to_string()),
+            assignee: Some(Some("ana".to_string())),
+            points: Some(Some(5.0)),
+            parent: None,
+            flagged: None,
+            priority: None,
+        };
+        let issue = Issue::from_jira(&apply(&client, &change).await.unwrap());
+        assert_eq!(issue.status.unwrap().name, "In Progress");
+        assert_eq!(issue.assignee.as_deref(), Some("Ana Lima"));
+        assert_eq!(issue.story_points, Some(5.0));
+
+        let change = BulkChange {
+            status: Some("Blocked".to_string()),
+            ..change
+        };
+        assert!(apply(&client, &change).await.is_err());
+
+        let changes = (0..20)
+            .map(|i| BulkChange {
+                key: if i == 7 {
+                    "OPS-9".to_string()
+                } else {
+                    "OPS-2".to_string()
+                },
+                status: None,
+                assignee: None,
+                points: Some(Some(f64::from(i))),
+                parent: None,
+                flagged: None,
+                priority: None,
+            })
+            .collect();
+        let results = apply_all(&client, changes).await;
+        assert_eq!(results.len(), 20);
+        assert!(
+            results
+                .iter()
+                .enumerate()
+                .all(|(i, (_, result))| result.is_ok() != (i == 7))
+        );
+        assert_eq!(results[7].0, "OPS-9");
+    }
+}
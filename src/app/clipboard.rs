@@ -0,0 +1,117 @@
+//! Copying text from the details of an issue, as mouse selections in a terminal take along
+//! whatever is drawn next to it on the same rows, like borders and the list, and attaching
+//! images from the clipboard, like screenshots.
+
+use chrono::Local;
+use jira_v3_openapi::models::IssueBean;
+
+use super::{App, Command, SidebarItem, update::forget_cached};
+use crate::{
+    error::Result,
+    export::{self, DocumentFormat},
+    ui::{
+        input::CopyTarget,
+        issue::Issue,
+        selection::{Selection, SelectionAction},
+        sidebar,
+    },
+};
+
+/// Copies `target` from the selected issue, or opens the sidebar's lines to pick some from.
+pub(super) fn copy(app: &mut App, target: CopyTarget) -> Command {
+    let Some(issue) = app.selected_issue() else {
+        return Command::None;
+    };
+    let (what, text) = match target {
+        CopyTarget::Issue if issue.is_local() => ("the summary".to_string(), issue.summary.clone()),
+        CopyTarget::Issue => (issue.id.clone(), format!("{} {}", issue.id, issue.summary)),
+        CopyTarget::Key if issue.is_local() => return Command::None,
+        CopyTarget::Key => (issue.id.clone(), issue.id.clone()),
+        CopyTarget::Description if issue.description.trim().is_empty() => {
+            app.error = Some(format!("{} has no description", issue.id));
+            return Command::None;
+        }
+        CopyTarget::Description => {
+            (format!("the description of {}", issue.id), issue.description.clone())
+        }
+        CopyTarget::Item => match app.selected_item() {
+            Some(SidebarItem::Reference(reference)) => {
+                (reference.key.clone(), reference.key.clone())
+            }
+            Some(SidebarItem::Child(child)) => (child.id.clone(), child.id.clone()),
+            Some(SidebarItem::Attachment(attachment)) => {
+                (attachment.filename.clone(), attachment.filename.clone())
+            }
+            None => {
+                app.error = Some(format!("{} references nothing to copy", issue.id));
+                return Command::None;
+            }
+        },
+        CopyTarget::Markdown => {
+            let url = app.connection.browse_url(&issue.id);
+            let document = export::document(issue, DocumentFormat::Markdown, url.as_deref());
+            (format!("{} as markdown", issue.id), document)
+        }
+        CopyTarget::Sidebar => {
+            let title = format!(" Copy from {} ", issue.id);
+            app.selection = Some(Selection::new(title, sidebar::sidebar_text(app)));
+            return Command::None;
+        }
+    };
+    app.notice = Some(format!("Copied {what}"));
+    Command::Copy { text }
+}
+
+/// Attaches the image on the clipboard to the selected issue, named after the time.
+pub(super) fn paste_image(app: &mut App) -> Command {
+    let Some(issue) = app.selected_jira_issue() else {
+        return Command::None;
+    };
+    Command::AttachClipboardImage {
+        key: issue.id.clone(),
+        filename: format!("screenshot-{}.png", Local::now().format("%Y%m%d-%H%M%S")),
+    }
+}
+
+/// Shows the issue an image was attached to with the attachment, or why it wasn't.
+pub(super) fn image_attached(
+    app: &mut App,
+    key: &str,
+    filename: &str,
+    result: Result<IssueBean>,
+) -> Command {
+    match result {
+        Ok(bean) => {
+            let issue = Issue::from_jira(&bean);
+            forget_cached(app, &issue);
+            if let Some(index) = app.issues.iter().position(|listed| listed.id == key) {
+                app.issues[index] = issue;
+                app.refresh_visible();
+            }
+            app.notice = Some(format!("Attached {filename} to {key}"));
+        }
+        Err(e) => app.error = Some(format!("Failed to attach an image to {key}: {e}")),
+    }
+    Command::None
+}
+
+/// Handles a key typed into the open selection.
+pub(super) fn handle_selection_key(app: &mut App, action: SelectionAction) -> Command {
+    match action {
+        SelectionAction::None => Command::None,
+        SelectionAction::Close => {
+            app.selection = None;
+            Command::None
+        }
+        SelectionAction::Copy(text) => {
+            app.selection = None;
+            let lines = text.lines().count();
+            app.notice = Some(if lines == 1 {
+                "Copied 1 line".to_string()
+            } else {
+                format!("Copied {lines} lines")
+            });
+            Command::Copy { text }
+        }
+    }
+}
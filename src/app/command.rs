@@ -0,0 +1,161 @@
+use std::{path::PathBuf, time::Duration};
+
+use super::bulk_edit::{BulkChange, BulkRow};
+use crate::{
+    config::WebhookConfig,
+    jira::{NewIssue, PageCursor, User},
+    state::UiState,
+    ui::issue::Attachment,
+};
+
+/// Side effects requested by [`update`](super::update), executed by the runtime.
+///
+/// Keeping effects out of `update` means it only ever touches [`App`](super::App) state, so
+/// new async features plug in as a command here plus a message carrying their result.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    None,
+    Quit,
+    /// Run several commands in order.
+    Batch(Vec<Command>),
+    /// Fetch the result pages of `jql` starting at `from`, or all of them, in the background.
+    /// Results are tagged with `generation` so those of an abandoned search can be dropped.
+    FetchRemaining {
+        jql: String,
+        generation: usize,
+        from: Option<PageCursor>,
+    },
+    /// Write the UI state to disk.
+    SaveState(UiState),
+    /// Fetch the changelog of an issue.
+    FetchHistory {
+        key: String,
+    },
+    /// Fetch the child issues of an epic.
+    FetchChildren {
+        key: String,
+    },
+    /// Fetch the projects of the instance.
+    FetchProjects,
+    /// Fetch the priorities issues can have.
+    FetchPriorities,
+    /// Fetch the signed-in user.
+    FetchMyself,
+    /// Fetch the transitions the issue can make from its status.
+    FetchTransitions {
+        key: String,
+    },
+    /// Fetch the users watching an issue.
+    FetchWatchers {
+        key: String,
+    },
+    /// Add `user` to the watchers of an issue, or take them off unless `watching`.
+    ChangeWatcher {
+        key: String,
+        user: User,
+        watching: bool,
+    },
+    /// Create the issue listed as `pending` in Jira.
+    CreateIssue {
+        pending: u64,
+        issue: NewIssue,
+    },
+    /// Build the report of the sprint with this name.
+    FetchSprintReport {
+        sprint: String,
+    },
+    /// Search for the users matching `query`, only those who can be assigned `issue` if
+    /// given.
+    SearchUsers {
+        query: String,
+        issue: Option<String>,
+    },
+    /// Fetch the versions of a project.
+    FetchVersions {
+        project: String,
+    },
+    /// Fetch the components of a project, with their open issue counts.
+    FetchComponents {
+        project: String,
+    },
+    /// Fetch a single issue that isn't among the search results.
+    FetchIssue {
+        key: String,
+    },
+    /// Download an attachment, unless a previous download is still around.
+    DownloadAttachment {
+        attachment: Attachment,
+    },
+    /// Attach the image on the clipboard to an issue, named `filename`.
+    AttachClipboardImage {
+        key: String,
+        filename: String,
+    },
+    /// Open a file or URL with the system's default application, handing it the terminal
+    /// until its launcher exits.
+    Open {
+        target: String,
+    },
+    /// Hand the terminal to the user's editor to edit the fields of issues.
+    BulkEdit {
+        rows: Vec<BulkRow>,
+    },
+    /// Make the changes saved in the editor, several issues at a time.
+    ApplyBulkEdit {
+        changes: Vec<BulkChange>,
+    },
+    /// Send the changes in the outbox again after [`outbox::RETRY_AFTER`].
+    ///
+    /// [`outbox::RETRY_AFTER`]: super::outbox::RETRY_AFTER
+    RetryOutbox,
+    /// Run the searches of the alerts, as `(name, jql)`, once `after` has passed.
+    CheckAlerts {
+        searches: Vec<(String, String)>,
+        after: Duration,
+    },
+    /// Refresh the list once `after` has passed.
+    ScheduleRefresh {
+        after: Duration,
+    },
+    /// Search again for the listed issues, all at once, replacing them.
+    Refresh {
+        jql: String,
+        generation: usize,
+    },
+    /// Read the config file again, at `path` or the usual place.
+    ReloadConfig {
+        path: Option<PathBuf>,
+    },
+    /// Make the requests that follow with this API token, after Jira rejected the one before.
+    SignIn {
+        api_token: String,
+    },
+    /// Put text on the clipboard.
+    Copy {
+        text: String,
+    },
+    /// Ring the terminal bell.
+    Bell,
+    /// Open an issue in the Jira web UI.
+    OpenInBrowser {
+        key: String,
+    },
+    /// Run the shell command of a hook in the background, with `env` added to its environment
+    /// and `input` on its standard input. The runtime adds the issue's `JIRA_URL`.
+    RunHook {
+        command: String,
+        env: Vec<(String, String)>,
+        input: String,
+    },
+    /// Run the shell command of an action in the background, like [`Command::RunHook`],
+    /// showing its output in a popup if `popup` is set.
+    RunAction {
+        name: String,
+        command: String,
+        env: Vec<(String, String)>,
+        input: String,
+        popup: bool,
+    },
+    /// Listen for Jira webhooks for as long as the app runs.
+    ListenForWebhooks(WebhookConfig),
+}
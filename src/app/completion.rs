@@ -0,0 +1,120 @@
+//! Completing the issue or project key being typed at the end of an input, from the issues
+//! and projects the app has seen.
+
+use std::collections::BTreeSet;
+
+use super::{App, Loadable};
+
+/// Most matches listed when a key could be completed more than one way.
+const MAX_LISTED: usize = 8;
+
+/// Completes the key at the end of the input as far as the known keys agree on, listing them
+/// in the notice when they don't agree on all of it.
+pub(super) fn complete(app: &mut App) {
+    let typed = app
+        .input
+        .chars()
+        .rev()
+        .take_while(|&c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        .map(char::len_utf8)
+        .sum::<usize>();
+    let start = app.input.len() - typed;
+    let word = app.input[start..].to_ascii_uppercase();
+    if word.is_empty() {
+        return;
+    }
+    let keys = known_keys(app);
+    let matches: Vec<&str> = keys
+        .iter()
+        .map(String::as_str)
+        .filter(|key| key.starts_with(&word))
+        .collect();
+    let Some(completed) = common_prefix(&matches) else {
+        app.notice = Some(format!("No known key starts with {word}"));
+        return;
+    };
+    app.input.replace_range(start.., completed);
+    app.input_state.cursor = app.input.len();
+    if matches.len() > 1 {
+        let mut listed = matches[..matches.len().min(MAX_LISTED)].join("  ");
+        if matches.len() > MAX_LISTED {
+            listed.push_str(&format!("  +{}", matches.len() - MAX_LISTED));
+        }
+        app.notice = Some(listed);
+    }
+}
+
+/// The keys of the listed issues and their parents, and of their projects and those in the
+/// project picker.
+fn known_keys(app: &App) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    let issues = app.issues.iter().flat_map(|issue| {
+        let parent = issue.parent.as_ref().map(|parent| parent.key.as_str());
+        std::iter::once(issue.id.as_str()).chain(parent)
+    });
+    for key in issues.filter(|key| !key.is_empty()) {
+        keys.insert(key.to_string());
+        if let Some((project, _)) = key.rsplit_once('-') {
+            keys.insert(project.to_string());
+        }
+    }
+    if let Some(Loadable::Loaded(projects)) = &app.projects {
+        keys.extend(projects.iter().map(|project| project.key.clone()));
+    }
+    keys
+}
+
+/// The longest start all of `words` share, if there are any.
+fn common_prefix<'a>(words: &[&'a str]) -> Option<&'a str> {
+    let (first, rest) = words.split_first()?;
+    let len = rest.iter().fold(first.len(), |len, word| {
+        first
+            .bytes()
+            .zip(word.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    Some(&first[..len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{jira::Project, ui::issue::Issue};
+
+    #[test]
+    fn completes_keys_as_far_as_they_agree() {
+        let issue = |key: &str| Issue {
+            id: key.to_string(),
+            ..Issue::new("Failover", "")
+        };
+        let mut app = App::new(vec![issue("OPS-12"), issue("OPS-17"), issue("CORE-3")]);
+        app.projects = Some(Loadable::Loaded(vec![Project {
+            key: "OPAL".to_string(),
+            name: "Opal".to_string(),
+        }]));
+
+        app.input = "key in (core".to_string();
+        complete(&mut app);
+        assert_eq!(app.input, "key in (CORE");
+        assert_eq!(app.input_state.cursor, app.input.len());
+
+        app.input = "project = op".to_string();
+        complete(&mut app);
+        assert_eq!(app.input, "project = OP");
+        assert_eq!(app.notice.as_deref(), Some("OPAL  OPS  OPS-12  OPS-17"));
+
+        app.input = "Follow-up to OPS-1".to_string();
+        complete(&mut app);
+        assert_eq!(app.input, "Follow-up to OPS-1");
+        app.input.push('2');
+        complete(&mut app);
+        assert_eq!(app.input, "Follow-up to OPS-12");
+
+        app.input = "key = XY".to_string();
+        complete(&mut app);
+        assert_eq!(app.input, "key = XY");
+        assert_eq!(app.notice.as_deref(), Some("No known key starts with XY"));
+    }
+}
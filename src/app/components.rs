@@ -0,0 +1,164 @@
+//! The components view: the components of a project with their leads and how many issues in
+//! each are still open, and those open issues of one of them.
+
+use super::{App, Command, Loadable, View, move_row_cursor, update::start_search};
+use crate::{
+    error::Result,
+    jira::{self, Component, JiraClient, jql},
+    ui::{input::NormalModeAction, issue::Issue},
+};
+
+/// A component along with how many of its issues are unresolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentSummary {
+    pub component: Component,
+    pub open: usize,
+}
+
+/// Fetches the components of a project, counting their unresolved issues with one search
+/// for all of them.
+pub(super) async fn fetch(client: &dyn JiraClient, project: &str) -> Result<Vec<ComponentSummary>> {
+    let components = client.get_components(project).await?;
+    let jql = format!(
+        "project = {} AND resolution = Unresolved AND component is not EMPTY",
+        jql::quote(project)
+    );
+    let open: Vec<Issue> = jira::search_all(client, &jql)
+        .await?
+        .iter()
+        .map(Issue::from_jira)
+        .collect();
+    Ok(summarize(components, &open))
+}
+
+/// Counts the `open` issues of each component.
+fn summarize(components: Vec<Component>, open: &[Issue]) -> Vec<ComponentSummary> {
+    components
+        .into_iter()
+        .map(|component| ComponentSummary {
+            open: open
+                .iter()
+                .filter(|issue| issue.components.contains(&component.name))
+                .count(),
+            component,
+        })
+        .collect()
+}
+
+/// Shows the components of the selected issue's project, fetching them the first time.
+/// Asking again while they are shown fetches them anew.
+pub(super) fn show(app: &mut App) -> Command {
+    let Some(project) = app.current_project() else {
+        app.notice = Some("Select an issue to see the components of its project".to_string());
+        return Command::None;
+    };
+
+    let refresh = app.view == View::Components;
+    app.view = View::Components;
+    app.set_view_project(&project);
+    if !refresh && app.components.contains_key(&project) {
+        return Command::None;
+    }
+    app.components.insert(project.clone(), Loadable::Loading);
+    Command::FetchComponents { project }
+}
+
+/// The components shown, once they arrived.
+pub fn summaries(app: &App) -> &[ComponentSummary] {
+    let components = app
+        .view_project
+        .as_ref()
+        .and_then(|project| app.components.get(project));
+    match components {
+        Some(Loadable::Loaded(components)) => components,
+        _ => &[],
+    }
+}
+
+/// Handles the keys that act on the components instead of the issues. Returns `None` if
+/// `action` isn't one of them.
+pub(super) fn handle(app: &mut App, action: NormalModeAction) -> Option<Command> {
+    if action == NormalModeAction::OpenItem {
+        return Some(show_issues(app));
+    }
+    let len = summaries(app).len();
+    move_row_cursor(&mut app.component_cursor, len, action, app.wrap_around)
+        .then_some(Command::None)
+}
+
+/// Searches for the open issues of the selected component and lists them.
+fn show_issues(app: &mut App) -> Command {
+    let (Some(project), Some(summary)) =
+        (app.view_project.clone(), summaries(app).get(app.component_cursor).cloned())
+    else {
+        return Command::None;
+    };
+    app.view = View::List;
+    let jql = format!(
+        "project = {} AND component = {} AND resolution = Unresolved ORDER BY Rank",
+        jql::quote(&project),
+        jql::quote(&summary.component.name)
+    );
+    start_search(app, jql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::FakeClient;
+
+    #[tokio::test]
+    async fn counts_open_issues_per_component() {
+        let mut issues = vec![
+            FakeClient::issue("OPS-1", "Failover", "In Progress"),
+            FakeClient::issue("OPS-2", "Backups", "To Do"),
+            FakeClient::issue("WEB-1", "Login", "To Do"),
+        ];
+        for (issue, component) in issues.iter_mut().zip(["Database", "Database", "Frontend"]) {
+            issue
+                .fields
+                .as_mut()
+                .unwrap()
+                .insert("components".to_string(), serde_json::json!([{ "name": component }]));
+        }
+        let client = FakeClient::with_issues(issues);
+
+        let components = client.get_components("OPS").await.unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].issues, 2);
+
+        // Only one of the issues is still open
+        let open = [Issue {
+            components: vec!["Database".to_string()],
+            ..Issue::new("Failover", "")
+        }];
+        let summaries = summarize(components, &open);
+        assert_eq!(summaries[0].open, 1);
+    }
+
+    #[test]
+    fn lists_the_open_issues_of_a_component() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            ..Issue::new("Failover", "")
+        }]);
+        assert_eq!(show(&mut app), Command::FetchComponents { project: "OPS".to_string() });
+
+        let component = Component {
+            id: "1".to_string(),
+            name: "Database".to_string(),
+            lead: None,
+            issues: 2,
+        };
+        app.components.insert(
+            "OPS".to_string(),
+            Loadable::Loaded(vec![ComponentSummary { component, open: 1 }]),
+        );
+        let command = handle(&mut app, NormalModeAction::OpenItem);
+        assert!(matches!(command, Some(Command::FetchRemaining { .. })));
+        assert_eq!(
+            app.jql,
+            "project = \"OPS\" AND component = \"Database\" AND resolution = Unresolved ORDER BY Rank"
+        );
+    }
+}
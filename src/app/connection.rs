@@ -0,0 +1,153 @@
+//! What the status bar shows about the connection to Jira: which instance the app talks to,
+//! whether it could be reached lately and accepted the credentials, and when the results were
+//! last refreshed.
+
+use chrono::{DateTime, FixedOffset};
+
+use super::Msg;
+use crate::{error::Error, jira::FetchEvent};
+
+/// The state of the connection to the Jira instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connection {
+    /// The host of the instance, empty when not known.
+    pub instance: String,
+    /// The URL of the instance, empty when not known.
+    pub base_url: String,
+    /// Whether the last request got a response, whatever it was.
+    pub online: bool,
+    /// Whether Jira rejected the credentials of the last request.
+    pub signed_out: bool,
+    /// When the last search finished fetching all of its results.
+    pub last_refresh: Option<DateTime<FixedOffset>>,
+}
+
+impl Default for Connection {
+    fn default() -> Self {
+        Self {
+            instance: String::new(),
+            base_url: String::new(),
+            online: true,
+            signed_out: false,
+            last_refresh: None,
+        }
+    }
+}
+
+impl Connection {
+    /// The connection to the instance at `base_url`, named by its host.
+    pub fn new(base_url: &str) -> Self {
+        let host = base_url
+            .split_once("://")
+            .map_or(base_url, |(_, rest)| rest)
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or_default();
+        Self {
+            instance: host.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// The link to the issue `key` in the Jira web UI, if the instance is known.
+    pub fn browse_url(&self, key: &str) -> Option<String> {
+        (!self.base_url.is_empty()).then(|| format!("{}/browse/{key}", self.base_url))
+    }
+
+    /// Notes whether the request `msg` reports on got a response, and whether Jira accepted
+    /// the credentials. Returns `true` if it rejected them.
+    pub(super) fn observe(&mut self, msg: &Msg) -> bool {
+        match outcome(msg) {
+            None => return false,
+            Some(Err(Error::Network(_))) => self.online = false,
+            Some(Err(Error::Auth(_))) => {
+                self.online = true;
+                self.signed_out = true;
+                return true;
+            }
+            Some(_) => {
+                self.online = true;
+                self.signed_out = false;
+            }
+        }
+        false
+    }
+}
+
+/// How the request `msg` reports on went, or `None` if it doesn't report on one.
+pub(super) fn outcome(msg: &Msg) -> Option<Result<(), &Error>> {
+    fn error<T>(result: &Result<T, Error>) -> Result<(), &Error> {
+        result.as_ref().map(|_| ())
+    }
+    match msg {
+        Msg::Fetch { event, .. } => Some(match event {
+            FetchEvent::Failed(e) => Err(e),
+            _ => Ok(()),
+        }),
+        Msg::Error(e) => Some(Err(e)),
+        Msg::History { result, .. } => Some(error(result)),
+        Msg::Children { result, .. } => Some(error(result)),
+        Msg::Projects(result) => Some(error(result)),
+        Msg::Priorities(result) => Some(error(result)),
+        Msg::Myself(result) => Some(error(result)),
+        Msg::Transitions { result, .. } => Some(error(result)),
+        Msg::Watchers { result, .. } => Some(error(result)),
+        Msg::WatcherChanged { result, .. } => Some(error(result)),
+        Msg::IssueCreated { result, .. } => Some(error(result)),
+        Msg::SprintReport { result, .. } => Some(error(result)),
+        Msg::UsersFound { result, .. } => Some(error(result)),
+        Msg::Versions { result, .. } => Some(error(result)),
+        Msg::Components { result, .. } => Some(error(result)),
+        Msg::IssueFetched { result, .. } => Some(error(result)),
+        Msg::AttachmentDownloaded { result, .. } => Some(error(result)),
+        // Without an image on the clipboard, there was no request
+        Msg::ImageAttached { result: Err(Error::Io(_)), .. } => None,
+        Msg::ImageAttached { result, .. } => Some(error(result)),
+        Msg::BulkApplied { results } => results.last().map(|(_, result)| error(result)),
+        Msg::AlertsChecked(results) => results.last().map(|(_, result)| error(result)),
+        Msg::Refreshed { result, .. } => Some(error(result)),
+        Msg::Key(_)
+        | Msg::Mouse(_)
+        | Msg::Resize
+        | Msg::Tick
+        | Msg::Shutdown
+        | Msg::Webhook(_)
+        | Msg::BulkEdited { .. }
+        | Msg::ActionFinished { .. }
+        | Msg::RetryOutbox
+        | Msg::RefreshDue
+        | Msg::ConfigReloaded(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_whether_jira_responds() {
+        let mut connection = Connection::new("https://example.atlassian.net/jira/");
+        assert_eq!(connection.instance, "example.atlassian.net");
+
+        let failed = Msg::Projects(Err(Error::Network("connection refused".to_string())));
+        connection.observe(&failed);
+        assert!(!connection.online);
+        connection.observe(&Msg::Tick);
+        assert!(!connection.online);
+
+        // Errors from Jira still mean it's there
+        let fetched = Msg::Fetch {
+            generation: 0,
+            event: FetchEvent::Failed(Error::Jql("bad".to_string())),
+        };
+        connection.observe(&fetched);
+        assert!(connection.online);
+
+        let rejected = Msg::Error(Error::Auth("expired token".to_string()));
+        assert!(connection.observe(&rejected));
+        assert!(connection.signed_out);
+        assert!(!connection.observe(&Msg::Projects(Ok(Vec::new()))));
+        assert!(!connection.signed_out);
+    }
+}
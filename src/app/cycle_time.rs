@@ -0,0 +1,255 @@
+//! Cycle time, from starting work on an issue to finishing it, and lead time, from creating it
+//! to finishing it, of the listed issues that are done, worked out from their changelogs for
+//! retro metrics.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, TimeDelta};
+
+use super::{App, Command, Loadable, View, move_row_cursor, update::open_issue};
+use crate::{
+    jira::HistoryEntry,
+    ui::{
+        input::NormalModeAction,
+        issue::{Issue, StatusCategory},
+    },
+};
+
+/// The times of one issue that is done.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueTimes {
+    pub key: String,
+    pub summary: String,
+    /// `None` if it never went through a status that isn't to do.
+    pub cycle: Option<TimeDelta>,
+    /// `None` if when it was created isn't known.
+    pub lead: Option<TimeDelta>,
+}
+
+/// How a set of times is spread out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distribution {
+    pub count: usize,
+    pub median: TimeDelta,
+    /// The time 85% of the issues took at most.
+    pub p85: TimeDelta,
+    pub longest: TimeDelta,
+}
+
+impl Distribution {
+    /// The spread of `times`, unless there are none. Percentiles are the nearest rank, so
+    /// each is one of the times.
+    pub fn of(mut times: Vec<TimeDelta>) -> Option<Self> {
+        times.sort();
+        let longest = *times.last()?;
+        let rank = |percent: usize| times[(times.len() * percent).div_ceil(100).max(1) - 1];
+        Some(Self {
+            count: times.len(),
+            median: rank(50),
+            p85: rank(85),
+            longest,
+        })
+    }
+}
+
+/// The times of the listed issues that are done, slowest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CycleReport {
+    pub issues: Vec<IssueTimes>,
+    pub cycle: Option<Distribution>,
+    pub lead: Option<Distribution>,
+    /// Done issues whose changelogs haven't arrived yet.
+    pub loading: usize,
+    /// Done issues whose changelogs failed to load or weren't asked for, like those listed
+    /// since the view was shown.
+    pub missing: usize,
+}
+
+/// Works out the times of `issue`, done, from its `history`, newest first. Work starts with
+/// the first move to a status that isn't in `categories` as to do, and ends with the move
+/// to the status it's in.
+fn issue_times(
+    issue: &Issue,
+    history: &[HistoryEntry],
+    categories: &HashMap<String, StatusCategory>,
+) -> Option<IssueTimes> {
+    let moves: Vec<(DateTime<FixedOffset>, &str)> = history
+        .iter()
+        .rev()
+        .filter_map(|entry| Some((entry.created?, &entry.changes)))
+        .flat_map(|(when, changes)| {
+            changes
+                .iter()
+                .filter(|change| change.field == "status")
+                .filter_map(move |change| Some((when, change.to.as_deref()?)))
+        })
+        .collect();
+    let (done, _) = *moves.last()?;
+    let started = moves
+        .iter()
+        .find(|(_, status)| categories.get(&status.to_lowercase()) != Some(&StatusCategory::ToDo));
+    Some(IssueTimes {
+        key: issue.id.clone(),
+        summary: issue.summary.clone(),
+        cycle: started.map(|(started, _)| done - *started),
+        lead: issue.created.map(|created| done - created),
+    })
+}
+
+/// The times of the listed issues that are done, as far as their changelogs arrived.
+pub fn report(app: &App) -> CycleReport {
+    // Changelogs only name statuses, so their categories come from the issues in them
+    let categories: HashMap<String, StatusCategory> = app
+        .issues
+        .iter()
+        .filter_map(|issue| issue.status.as_ref())
+        .map(|status| (status.name.to_lowercase(), status.category))
+        .collect();
+    let mut report = CycleReport::default();
+    for issue in app.visible_issues().filter(|issue| issue.is_done()) {
+        match app.histories.get(&issue.id) {
+            Some(Loadable::Loaded(history)) => {
+                report
+                    .issues
+                    .extend(issue_times(issue, history, &categories));
+            }
+            Some(Loadable::Loading) => report.loading += 1,
+            Some(Loadable::Failed(_)) | None => report.missing += 1,
+        }
+    }
+    report
+        .issues
+        .sort_by_key(|times| std::cmp::Reverse((times.cycle, times.lead)));
+    report.cycle = Distribution::of(report.issues.iter().filter_map(|t| t.cycle).collect());
+    report.lead = Distribution::of(report.issues.iter().filter_map(|t| t.lead).collect());
+    report
+}
+
+/// Shows the times of the listed issues, fetching the changelogs of those done that haven't
+/// been yet, or failed to be.
+pub(super) fn show(app: &mut App) -> Command {
+    app.view = View::CycleTime;
+    let keys: Vec<String> = app
+        .visible_issues()
+        .filter(|issue| issue.is_done() && !issue.is_local())
+        .filter(|issue| {
+            !matches!(app.histories.get(&issue.id), Some(Loadable::Loaded(_) | Loadable::Loading))
+        })
+        .map(|issue| issue.id.clone())
+        .collect();
+    let commands: Vec<Command> = keys
+        .into_iter()
+        .map(|key| {
+            app.histories.insert(key.clone(), Loadable::Loading);
+            Command::FetchHistory { key }
+        })
+        .collect();
+    match commands.is_empty() {
+        true => Command::None,
+        false => Command::Batch(commands),
+    }
+}
+
+/// Handles the keys that act on the rows of the report instead of the issues: Enter shows
+/// the selected issue in the list. Returns `None` if `action` isn't one of them.
+pub(super) fn handle(app: &mut App, action: NormalModeAction) -> Option<Command> {
+    let report = report(app);
+    if action == NormalModeAction::OpenItem {
+        let Some(times) = report.issues.get(app.cycle_cursor) else {
+            return Some(Command::None);
+        };
+        app.view = View::List;
+        return Some(open_issue(app, &times.key));
+    }
+    move_row_cursor(&mut app.cycle_cursor, report.issues.len(), action, app.wrap_around)
+        .then_some(Command::None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        jira::FieldChange,
+        ui::{issue::Status, time},
+    };
+
+    fn moved(when: &str, from: &str, to: &str) -> HistoryEntry {
+        HistoryEntry {
+            author: "Ana Lima".to_string(),
+            created: time::parse_timestamp(when),
+            changes: vec![FieldChange {
+                field: "status".to_string(),
+                from: Some(from.to_string()),
+                to: Some(to.to_string()),
+            }],
+        }
+    }
+
+    fn issue(id: &str, status: &str, category: StatusCategory) -> Issue {
+        Issue {
+            id: id.to_string(),
+            status: Some(Status { name: status.to_string(), category }),
+            created: time::parse_timestamp("2024-03-01T09:00:00.000+0000"),
+            ..Issue::new(format!("Summary of {id}"), String::new())
+        }
+    }
+
+    #[test]
+    fn times_done_issues_from_their_changelogs() {
+        let mut app = App::new(vec![
+            issue("OPS-1", "Done", StatusCategory::Done),
+            issue("OPS-2", "Done", StatusCategory::Done),
+            issue("OPS-3", "Backlog", StatusCategory::ToDo),
+            issue("OPS-4", "Done", StatusCategory::Done),
+        ]);
+        let fetch = |key: &str| Command::FetchHistory { key: key.to_string() };
+        assert_eq!(
+            show(&mut app),
+            Command::Batch(vec![fetch("OPS-1"), fetch("OPS-2"), fetch("OPS-4")])
+        );
+        assert_eq!((report(&app).loading, report(&app).missing), (3, 0));
+
+        // Newest first, as fetched; moving between to do statuses isn't work yet
+        app.histories.insert(
+            "OPS-1".to_string(),
+            Loadable::Loaded(vec![
+                moved("2024-03-06T09:00:00.000+0000", "In Progress", "Done"),
+                moved("2024-03-04T09:00:00.000+0000", "Backlog", "In Progress"),
+                moved("2024-03-02T09:00:00.000+0000", "To Do", "Backlog"),
+            ]),
+        );
+        app.histories.insert(
+            "OPS-2".to_string(),
+            Loadable::Loaded(vec![moved("2024-03-02T09:00:00.000+0000", "Backlog", "Done")]),
+        );
+        app.histories
+            .insert("OPS-4".to_string(), Loadable::Failed("timed out".to_string()));
+        let report = report(&app);
+        let keys: Vec<_> = report.issues.iter().map(|t| t.key.as_str()).collect();
+        assert_eq!(keys, ["OPS-1", "OPS-2"]);
+        assert_eq!(report.issues[0].cycle, Some(TimeDelta::days(2)));
+        assert_eq!(report.issues[0].lead, Some(TimeDelta::days(5)));
+        assert_eq!(report.issues[1].cycle, Some(TimeDelta::zero()));
+        assert_eq!((report.loading, report.missing), (0, 1));
+        let lead = report.lead.unwrap();
+        assert_eq!(
+            (lead.count, lead.median, lead.p85),
+            (2, TimeDelta::days(1), TimeDelta::days(5))
+        );
+
+        // Enter shows the slowest in the list
+        assert_eq!(handle(&mut app, NormalModeAction::OpenItem), Some(Command::None));
+        assert_eq!(app.view, View::List);
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-1");
+    }
+
+    #[test]
+    fn percentiles_are_nearest_ranks() {
+        let days = |days: &[i64]| days.iter().map(|&d| TimeDelta::days(d)).collect();
+        let spread = Distribution::of(days(&[8, 1, 3, 2, 5, 13, 21, 1, 2, 4])).unwrap();
+        assert_eq!(spread.median, TimeDelta::days(3));
+        assert_eq!(spread.p85, TimeDelta::days(13));
+        assert_eq!(spread.longest, TimeDelta::days(21));
+        assert_eq!(Distribution::of(Vec::new()), None);
+    }
+}
@@ -0,0 +1,306 @@
+//! Filter expressions typed into the text filter prompt, like
+//! `status:in-progress assignee:me points:>5 label:infra`, checked against the loaded issues
+//! without a round trip to Jira.
+//!
+//! Terms are separated by spaces and all have to match; `-` in front of one negates it, and
+//! values with spaces go in double quotes. Names match ignoring case, with `-` and `_`
+//! standing for spaces. Terms without a field are looked for in the key and summary.
+
+use std::fmt;
+
+use super::find;
+use crate::ui::issue::Issue;
+
+/// The fields a term can test, for the error listing them.
+const FIELDS: &[&str] = &[
+    "status",
+    "assignee",
+    "points",
+    "label",
+    "component",
+    "sprint",
+    "type",
+    "priority",
+    "key",
+    "epic",
+];
+
+/// A parsed expression, matching the issues every term matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    terms: Vec<(bool, Test)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Test {
+    Text(String),
+    Status(String),
+    Assignee(Person),
+    Points(Option<(Comparison, f64)>),
+    Label(String),
+    Component(String),
+    Sprint(String),
+    Type(String),
+    Priority(String),
+    Key(String),
+    Epic(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Person {
+    /// The signed-in user.
+    Me,
+    Nobody,
+    Named(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+/// Why an expression couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Whether `text` has a term with a field, rather than being text to look for.
+pub fn is_expression(text: &str) -> bool {
+    words(text).iter().any(|word| {
+        let word = word.strip_prefix('-').unwrap_or(word);
+        word.split_once(':')
+            .is_some_and(|(field, _)| !field.is_empty() && field.chars().all(char::is_alphabetic))
+    })
+}
+
+/// Splits `text` at spaces outside double quotes, dropping the quotes.
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// A name as compared: lowercase, with `-` and `_` as spaces.
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace(['-', '_'], " ")
+}
+
+fn parse_points(value: &str) -> Result<Option<(Comparison, f64)>, ParseError> {
+    if normalize(value) == "none" {
+        return Ok(None);
+    }
+    let (comparison, number) = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        ("=", Comparison::Equal),
+    ]
+    .into_iter()
+    .find_map(|(op, comparison)| Some((comparison, value.strip_prefix(op)?)))
+    .unwrap_or((Comparison::Equal, value));
+    match number.parse() {
+        Ok(number) => Ok(Some((comparison, number))),
+        Err(_) => Err(ParseError(format!("points:{value} is not a number"))),
+    }
+}
+
+impl Expression {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut terms = Vec::new();
+        for word in words(text) {
+            let (negated, word) = match word.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, word.as_str()),
+            };
+            let Some((field, value)) = word.split_once(':') else {
+                terms.push((negated, Test::Text(word.to_string())));
+                continue;
+            };
+            let name = normalize(value);
+            let test = match field.to_lowercase().as_str() {
+                "status" => Test::Status(name),
+                "assignee" => Test::Assignee(match name.as_str() {
+                    "me" => Person::Me,
+                    "none" | "nobody" => Person::Nobody,
+                    _ => Person::Named(name),
+                }),
+                "points" => Test::Points(parse_points(value)?),
+                "label" => Test::Label(name),
+                "component" => Test::Component(name),
+                "sprint" => Test::Sprint(name),
+                "type" => Test::Type(name),
+                "priority" => Test::Priority(name),
+                "key" => Test::Key(value.to_string()),
+                "epic" => Test::Epic(name),
+                _ => {
+                    return Err(ParseError(format!(
+                        "unknown field {field:?}; try {}",
+                        FIELDS.join(", ")
+                    )));
+                }
+            };
+            terms.push((negated, test));
+        }
+        Ok(Self { terms })
+    }
+
+    /// Whether a term is about the signed-in user, who has to be known to match it.
+    pub fn mentions_me(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|(_, test)| *test == Test::Assignee(Person::Me))
+    }
+
+    /// Whether `issue` matches every term. `me` is the account id of the signed-in user, if
+    /// known yet.
+    pub fn matches(&self, issue: &Issue, me: Option<&str>) -> bool {
+        self.terms
+            .iter()
+            .all(|(negated, test)| test.matches(issue, me) != *negated)
+    }
+}
+
+impl Test {
+    fn matches(&self, issue: &Issue, me: Option<&str>) -> bool {
+        let is =
+            |name: Option<&str>, wanted: &str| name.is_some_and(|name| normalize(name) == wanted);
+        let any =
+            |names: &[String], wanted: &str| names.iter().any(|name| normalize(name) == wanted);
+        match self {
+            Test::Text(text) => find::matches(issue, text),
+            Test::Status(wanted) => is(issue.status.as_ref().map(|status| status.as_str()), wanted),
+            Test::Assignee(Person::Me) => {
+                let account = issue
+                    .raw_field("assignee.accountId")
+                    .and_then(|id| id.as_str());
+                me.is_some_and(|me| account == Some(me))
+            }
+            Test::Assignee(Person::Nobody) => issue.assignee.is_none(),
+            Test::Assignee(Person::Named(wanted)) => issue
+                .assignee
+                .as_deref()
+                .is_some_and(|name| normalize(name).contains(wanted.as_str())),
+            Test::Points(None) => issue.story_points.is_none(),
+            Test::Points(Some((comparison, wanted))) => {
+                issue.story_points.is_some_and(|points| match comparison {
+                    Comparison::Less => points < *wanted,
+                    Comparison::LessOrEqual => points <= *wanted,
+                    Comparison::Equal => points == *wanted,
+                    Comparison::GreaterOrEqual => points >= *wanted,
+                    Comparison::Greater => points > *wanted,
+                })
+            }
+            Test::Label(wanted) => any(&issue.labels, wanted),
+            Test::Component(wanted) => any(&issue.components, wanted),
+            Test::Sprint(wanted) => any(&issue.sprints, wanted),
+            Test::Type(wanted) => is(issue.issue_type.as_deref(), wanted),
+            Test::Priority(wanted) => is(
+                issue
+                    .raw_field("priority.name")
+                    .and_then(|name| name.as_str()),
+                wanted,
+            ),
+            Test::Key(prefix) => issue.id.to_lowercase().starts_with(&prefix.to_lowercase()),
+            Test::Epic(wanted) => issue.epic().is_some_and(|epic| {
+                epic.key.to_lowercase() == *wanted
+                    || normalize(&epic.summary).contains(wanted.as_str())
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::ui::issue::{Status, StatusCategory};
+
+    fn issue(id: &str, status: &str, points: Option<f64>, labels: &[&str]) -> Issue {
+        Issue {
+            id: id.to_string(),
+            status: Some(Status {
+                name: status.to_string(),
+                category: StatusCategory::Unknown,
+            }),
+            story_points: points,
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            ..Issue::new(format!("Summary of {id}"), String::new())
+        }
+    }
+
+    fn matching(expression: &str, issues: &[Issue], me: Option<&str>) -> Vec<String> {
+        let expression = Expression::parse(expression).unwrap();
+        issues
+            .iter()
+            .filter(|issue| expression.matches(issue, me))
+            .map(|issue| issue.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn matches_every_term() {
+        let mut mine = issue("OPS-1", "In Progress", Some(8.0), &["infra"]);
+        mine.assignee = Some("Ana Lima".to_string());
+        mine.raw_fields.insert(
+            "assignee".to_string(),
+            json!({ "accountId": "ana", "displayName": "Ana Lima" }),
+        );
+        let issues = [
+            mine,
+            issue("OPS-2", "In Progress", Some(3.0), &["infra"]),
+            issue("OPS-3", "To Do", None, &["on-call"]),
+        ];
+
+        let expression = "status:in-progress assignee:me points:>5 label:infra";
+        assert_eq!(matching(expression, &issues, Some("ana")), ["OPS-1"]);
+        // Nobody is "me" until the account is known
+        assert!(matching(expression, &issues, None).is_empty());
+        assert_eq!(matching("assignee:ana", &issues, None), ["OPS-1"]);
+        assert_eq!(matching("-label:infra", &issues, None), ["OPS-3"]);
+        assert_eq!(matching("label:on_call points:none", &issues, None), ["OPS-3"]);
+        assert_eq!(matching("status:\"to do\" OPS", &issues, None), ["OPS-3"]);
+        assert_eq!(matching("points:<=3 key:ops-", &issues, None), ["OPS-2"]);
+    }
+
+    #[test]
+    fn tells_expressions_from_text_and_reports_mistakes() {
+        assert!(is_expression("failover status:done"));
+        assert!(is_expression("-label:infra"));
+        assert!(!is_expression("failover at 10:30"));
+        assert!(Expression::parse("assignee:me").unwrap().mentions_me());
+
+        let err = Expression::parse("colour:red").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("unknown field \"colour\"; try status, assignee")
+        );
+        let err = Expression::parse("points:>many").unwrap_err();
+        assert_eq!(err.to_string(), "points:>many is not a number");
+    }
+}
@@ -0,0 +1,216 @@
+//! The failures view: requests to Jira that failed, with when and why, to retry or dismiss one
+//! by one, so a blip in the connection doesn't lose a change without a trace.
+
+use chrono::{DateTime, FixedOffset, Local};
+
+use super::{
+    App, Command, Loadable, Msg, bulk_edit::BulkChange, move_row_cursor, operations::Operation,
+    outbox, projects, update::start_search,
+};
+use crate::{
+    error::Error,
+    jira::{FetchEvent, NewIssue},
+    ui::input::NormalModeAction,
+};
+
+/// Older failures are forgotten beyond this many.
+const MAX_FAILURES: usize = 100;
+
+/// A request that failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// What the request was for.
+    pub what: String,
+    pub when: DateTime<FixedOffset>,
+    pub error: String,
+    retry: Retry,
+}
+
+/// How to make a failed request again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Retry {
+    Operation(Operation),
+    Change(BulkChange),
+    /// Creating the issue listed as `pending`.
+    Create {
+        pending: u64,
+        issue: NewIssue,
+    },
+}
+
+/// Logs the failure `msg` reports, if it's one.
+pub(super) fn record(app: &mut App, msg: &Msg) {
+    let (operation, error) = match msg {
+        Msg::Fetch {
+            generation,
+            event: FetchEvent::Failed(e),
+        } if *generation == app.search_generation => (Operation::Search, e),
+        Msg::History { key, result: Err(e) } => (Operation::History(key.clone()), e),
+        Msg::Children { key, result: Err(e) } => (Operation::Children(key.clone()), e),
+        Msg::Projects(Err(e)) => (Operation::Projects, e),
+        Msg::Priorities(Err(e)) => (Operation::Priorities, e),
+        Msg::Myself(Err(e)) => (Operation::Myself, e),
+        Msg::Transitions { key, result: Err(e) } => (Operation::Transitions(key.clone()), e),
+        Msg::Watchers { key, result: Err(e) } => (Operation::Watchers(key.clone()), e),
+        Msg::SprintReport { sprint, result: Err(e) } => {
+            (Operation::SprintReport(sprint.clone()), e)
+        }
+        Msg::Versions { project, result: Err(e) } => (Operation::Versions(project.clone()), e),
+        Msg::Components { project, result: Err(e) } => (Operation::Components(project.clone()), e),
+        Msg::IssueFetched { key, result: Err(e) } => (Operation::Issue(key.clone()), e),
+        Msg::IssueCreated { pending, issue, result: Err(e) } => {
+            let operation = Operation::Create(issue.summary.clone());
+            let retry = Retry::Create {
+                pending: *pending,
+                issue: issue.clone(),
+            };
+            add(app, operation.label(), e, retry);
+            return;
+        }
+        _ => return,
+    };
+    add(app, operation.label(), error, Retry::Operation(operation));
+}
+
+/// Logs a change to an issue that couldn't be made.
+pub(super) fn change_failed(app: &mut App, change: BulkChange, error: &Error) {
+    let what = format!("changing {}", change.key);
+    add(app, what, error, Retry::Change(change));
+}
+
+fn add(app: &mut App, what: String, error: &Error, retry: Retry) {
+    app.failures.push(Failure {
+        what,
+        when: Local::now().fixed_offset(),
+        error: error.to_string(),
+        retry,
+    });
+    if app.failures.len() > MAX_FAILURES {
+        app.failures.remove(0);
+    }
+}
+
+/// Handles the keys that act on the failures instead of the issues: Enter retries the
+/// selected one and `x` dismisses it. Returns `None` if `action` isn't one of them.
+pub(super) fn handle(app: &mut App, action: NormalModeAction) -> Option<Command> {
+    match action {
+        NormalModeAction::OpenItem => Some(retry(app)),
+        NormalModeAction::RemoveFilter(_) => {
+            dismiss(app);
+            Some(Command::None)
+        }
+        _ => move_row_cursor(&mut app.failure_cursor, app.failures.len(), action, app.wrap_around)
+            .then_some(Command::None),
+    }
+}
+
+/// Takes the selected failure off the list.
+fn dismiss(app: &mut App) -> Option<Failure> {
+    if app.failure_cursor >= app.failures.len() {
+        return None;
+    }
+    let failure = app.failures.remove(app.failure_cursor);
+    app.failure_cursor = app.failure_cursor.min(app.failures.len().saturating_sub(1));
+    Some(failure)
+}
+
+/// Makes the selected failed request again. It's logged anew if it fails again.
+fn retry(app: &mut App) -> Command {
+    let Some(failure) = dismiss(app) else {
+        return Command::None;
+    };
+    app.notice = Some(format!("Retrying {}", failure.what));
+    match failure.retry {
+        Retry::Change(change) => outbox::queue(app, vec![change]),
+        Retry::Create { pending, issue } => Command::CreateIssue { pending, issue },
+        Retry::Operation(operation) => match operation {
+            Operation::Search => start_search(app, app.jql.clone()),
+            Operation::History(key) => {
+                app.histories.insert(key.clone(), Loadable::Loading);
+                Command::FetchHistory { key }
+            }
+            Operation::Children(key) => {
+                app.children.insert(key.clone(), Loadable::Loading);
+                Command::FetchChildren { key }
+            }
+            Operation::Projects => {
+                app.projects = None;
+                projects::open(app)
+            }
+            Operation::Priorities => {
+                app.priorities = Some(Loadable::Loading);
+                Command::FetchPriorities
+            }
+            Operation::Myself => {
+                app.me = Some(Loadable::Loading);
+                Command::FetchMyself
+            }
+            Operation::Transitions(key) => Command::FetchTransitions { key },
+            Operation::Watchers(key) => {
+                app.watchers.insert(key.clone(), Loadable::Loading);
+                Command::FetchWatchers { key }
+            }
+            Operation::SprintReport(sprint) => Command::FetchSprintReport { sprint },
+            Operation::Versions(project) => {
+                app.versions.insert(project.clone(), Loadable::Loading);
+                Command::FetchVersions { project }
+            }
+            Operation::Components(project) => {
+                app.components.insert(project.clone(), Loadable::Loading);
+                Command::FetchComponents { project }
+            }
+            Operation::Issue(key) => Command::FetchIssue { key },
+            // Not logged, or logged with what to retry them with
+            Operation::Watch(_)
+            | Operation::Create(_)
+            | Operation::Download(_)
+            | Operation::Attach(_)
+            | Operation::BulkApply(_) => Command::None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{View, update},
+        ui::issue::Issue,
+    };
+
+    #[test]
+    fn retries_and_dismisses_failures() {
+        let mut app = App::new(vec![Issue::new("Failover", "")]);
+        update(&mut app, Msg::History {
+            key: "OPS-1".to_string(),
+            result: Err(Error::Network("timeout".to_string())),
+        });
+        let change = BulkChange {
+            key: "OPS-2".to_string(),
+            status: Some("Done".to_string()),
+            assignee: None,
+            points: None,
+            parent: None,
+            flagged: None,
+            priority: None,
+        };
+        change_failed(&mut app, change, &Error::Permission("no".to_string()));
+        assert_eq!(
+            app.failures
+                .iter()
+                .map(|failure| failure.what.as_str())
+                .collect::<Vec<_>>(),
+            ["loading history of OPS-1", "changing OPS-2"]
+        );
+
+        app.view = View::Failures;
+        assert_eq!(
+            handle(&mut app, NormalModeAction::OpenItem),
+            Some(Command::FetchHistory { key: "OPS-1".to_string() })
+        );
+        assert_eq!(app.histories["OPS-1"], Loadable::Loading);
+        assert_eq!(handle(&mut app, NormalModeAction::RemoveFilter(None)), Some(Command::None));
+        assert!(app.failures.is_empty());
+        assert_eq!(handle(&mut app, NormalModeAction::OpenItem), Some(Command::None));
+    }
+}
@@ -0,0 +1,505 @@
+//! Narrowing the loaded issues down without a new search: by label, component, sprint,
+//! assignee, text in the key or summary, or an expression testing several fields, and
+//! with the quick filters toggled in the filter bar.
+
+use itertools::Itertools;
+use regex::Regex;
+
+use super::{
+    App, Command, Loadable, OpenPicker, PickerPurpose,
+    expression::{self, Expression},
+    find,
+};
+use crate::ui::{input::InputMode, issue::Issue, picker::Picker};
+
+/// The issue fields the list can be narrowed down by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind {
+    Label,
+    Component,
+    Sprint,
+    Assignee,
+    /// Text typed into a prompt, found in the key or summary, or an expression.
+    Text,
+}
+
+impl FilterKind {
+    pub const ALL: [FilterKind; 5] = [
+        FilterKind::Label,
+        FilterKind::Component,
+        FilterKind::Sprint,
+        FilterKind::Assignee,
+        FilterKind::Text,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FilterKind::Label => "label",
+            FilterKind::Component => "component",
+            FilterKind::Sprint => "sprint",
+            FilterKind::Assignee => "assignee",
+            FilterKind::Text => "text",
+        }
+    }
+
+    fn values(self, issue: &Issue) -> &[String] {
+        match self {
+            FilterKind::Label => &issue.labels,
+            FilterKind::Component => &issue.components,
+            FilterKind::Sprint => &issue.sprints,
+            FilterKind::Assignee => issue.assignee.as_slice(),
+            FilterKind::Text => &[],
+        }
+    }
+}
+
+/// An active filter, shown as a chip above the list. Only issues matching every filter are
+/// listed.
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub kind: FilterKind,
+    pub value: String,
+    matcher: Matcher,
+}
+
+/// How a [`Filter`] tests its value, parsed once when the filter is added.
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// One of the values of the kind of field is the value.
+    Value,
+    /// The key or summary contains the value.
+    Text,
+    Regex(Regex),
+    Expression(Expression),
+}
+
+impl PartialEq for Filter {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.value == other.value
+            && std::mem::discriminant(&self.matcher) == std::mem::discriminant(&other.matcher)
+    }
+}
+
+impl Eq for Filter {}
+
+impl Filter {
+    pub fn new(kind: FilterKind, value: impl Into<String>) -> Self {
+        let matcher = match kind {
+            FilterKind::Text => Matcher::Text,
+            _ => Matcher::Value,
+        };
+        Self { kind, value: value.into(), matcher }
+    }
+
+    /// A text filter keeping the issues whose key or summary matches the regular expression
+    /// `pattern`.
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            matcher: Matcher::Regex(Regex::new(pattern)?),
+            ..Self::new(FilterKind::Text, pattern)
+        })
+    }
+
+    /// A text filter keeping the issues matching a filter expression like
+    /// `status:in-progress label:infra`.
+    pub fn expression(text: &str) -> Result<Self, expression::ParseError> {
+        Ok(Self {
+            matcher: Matcher::Expression(Expression::parse(text)?),
+            ..Self::new(FilterKind::Text, text)
+        })
+    }
+
+    /// What the chip calls the filter.
+    pub fn name(&self) -> &'static str {
+        match self.matcher {
+            Matcher::Regex(_) => "regex",
+            Matcher::Expression(_) => "where",
+            Matcher::Value | Matcher::Text => self.kind.name(),
+        }
+    }
+
+    /// Whether `issue` passes the filter. `me` is the account id of the signed-in user, once
+    /// known.
+    pub fn matches(&self, issue: &Issue, me: Option<&str>) -> bool {
+        match &self.matcher {
+            Matcher::Value => self.kind.values(issue).contains(&self.value),
+            Matcher::Text => find::matches(issue, &self.value),
+            Matcher::Regex(regex) => regex.is_match(&issue.id) || regex.is_match(&issue.summary),
+            Matcher::Expression(expression) => expression.matches(issue, me),
+        }
+    }
+
+    /// Whether the filter needs to know who the signed-in user is.
+    fn mentions_me(&self) -> bool {
+        matches!(&self.matcher, Matcher::Expression(expression) if expression.mentions_me())
+    }
+}
+
+/// Predefined filters that can be toggled on and off, like the quick filters of Jira boards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickFilter {
+    Bugs,
+    Blocked,
+    Unestimated,
+    /// The `n`th of the script filters from the config, counting from 0.
+    Script(usize),
+}
+
+impl QuickFilter {
+    /// The built-in quick filters.
+    pub const ALL: [QuickFilter; 3] =
+        [QuickFilter::Bugs, QuickFilter::Blocked, QuickFilter::Unestimated];
+
+    /// The built-in quick filters followed by the configured ones.
+    pub fn available(app: &App) -> Vec<QuickFilter> {
+        let scripts = (0..app.scripts.filter_names().count()).map(QuickFilter::Script);
+        QuickFilter::ALL.into_iter().chain(scripts).collect()
+    }
+
+    pub fn name(self, app: &App) -> &str {
+        match self {
+            QuickFilter::Bugs => "Bugs",
+            QuickFilter::Blocked => "Blocked",
+            QuickFilter::Unestimated => "Unestimated",
+            QuickFilter::Script(n) => app.scripts.filter_names().nth(n).unwrap_or_default(),
+        }
+    }
+
+    /// Whether `app.issues[index]` passes the filter.
+    pub fn matches(self, app: &App, index: usize) -> bool {
+        let issue = &app.issues[index];
+        match self {
+            QuickFilter::Bugs => issue
+                .issue_type
+                .as_deref()
+                .is_some_and(|name| name.eq_ignore_ascii_case("bug")),
+            QuickFilter::Blocked => {
+                let blocked = |name: &str| name.to_lowercase().contains("block");
+                issue.status.as_ref().is_some_and(|s| blocked(s.as_str()))
+                    || issue.labels.iter().any(|label| blocked(label))
+            }
+            QuickFilter::Unestimated => issue.story_points.is_none(),
+            QuickFilter::Script(n) => app.computed.filter(index, n),
+        }
+    }
+}
+
+/// Number of issues passing the filter chips that `quick` would leave in the list if it were
+/// the only quick filter on.
+pub fn quick_filter_count(app: &App, quick: QuickFilter) -> usize {
+    (0..app.issues.len())
+        .filter(|&i| !(app.hide_done && app.issues[i].is_done()))
+        .filter(|&i| {
+            app.filters
+                .iter()
+                .all(|filter| filter.matches(&app.issues[i], app.account_id()))
+        })
+        .filter(|&i| quick.matches(app, i))
+        .count()
+}
+
+/// Turns the `n`th quick filter (counting from 1) on or off.
+pub fn toggle_quick_filter(app: &mut App, n: usize) {
+    let available = QuickFilter::available(app);
+    let Some(&quick) = n.checked_sub(1).and_then(|i| available.get(i)) else {
+        return;
+    };
+    match app.quick_filters.iter().position(|&q| q == quick) {
+        Some(i) => {
+            app.quick_filters.remove(i);
+        }
+        None => app.quick_filters.push(quick),
+    }
+    app.refresh_visible();
+}
+
+/// Asks which kind of filter to add.
+pub fn open(app: &mut App) {
+    let kinds = FilterKind::ALL
+        .iter()
+        .map(|kind| kind.name().to_string())
+        .collect();
+    app.picker = Some(OpenPicker {
+        purpose: PickerPurpose::FilterKind,
+        picker: Picker::new("Narrow by", kinds),
+    });
+}
+
+/// Moves on after something was picked in one of the filter pickers.
+pub fn picked(app: &mut App, purpose: PickerPurpose, choice: &str) {
+    match purpose {
+        PickerPurpose::FilterKind => {
+            let Some(kind) = FilterKind::ALL
+                .into_iter()
+                .find(|kind| kind.name() == choice)
+            else {
+                return;
+            };
+            if kind == FilterKind::Text {
+                app.input.clear();
+                app.input_state.cursor = 0;
+                app.input_mode = InputMode::Filter;
+                return;
+            }
+            // Only values that would leave something in the list
+            let values = app
+                .visible_issues()
+                .flat_map(|issue| kind.values(issue))
+                .unique()
+                .sorted()
+                .cloned()
+                .collect();
+            app.picker = Some(OpenPicker {
+                purpose: PickerPurpose::FilterValue(kind),
+                picker: Picker::new(format!("Narrow by {}", kind.name()), values),
+            });
+        }
+        PickerPurpose::FilterValue(kind) => {
+            add(app, Filter::new(kind, choice));
+        }
+        _ => {}
+    }
+}
+
+fn add(app: &mut App, filter: Filter) {
+    if !app.filters.contains(&filter) {
+        app.filters.push(filter);
+        app.refresh_visible();
+    }
+}
+
+/// Adds the text filter typed into the prompt: a regular expression if the prompt was
+/// switched to those with Ctrl-R, an expression if it has terms like `label:infra`, and text
+/// otherwise. Those that don't parse are left to fix. Expressions about the signed-in user
+/// fetch who that is.
+pub(super) fn text_entered(app: &mut App) -> Command {
+    let text = app.input.trim().to_string();
+    let filter = if app.filter_regex {
+        Filter::regex(&text).map_err(|e| {
+            // The last line says what's wrong, under the lines pointing at where
+            let message = e.to_string();
+            let problem = message.lines().last().unwrap_or_default();
+            format!("Invalid regex: {}", problem.trim_start_matches("error: "))
+        })
+    } else if expression::is_expression(&text) {
+        Filter::expression(&text).map_err(|e| format!("Invalid filter: {e}"))
+    } else {
+        Ok(Filter::new(FilterKind::Text, text.as_str()))
+    };
+    let filter = match filter {
+        Ok(filter) => filter,
+        Err(e) => {
+            app.error = Some(e);
+            return Command::None;
+        }
+    };
+    app.input.clear();
+    app.input_state.cursor = 0;
+    app.input_mode = InputMode::Normal;
+    if text.is_empty() {
+        return Command::None;
+    }
+    let fetch_me = filter.mentions_me() && app.me.is_none();
+    add(app, filter);
+    if !fetch_me {
+        return Command::None;
+    }
+    app.me = Some(Loadable::Loading);
+    Command::FetchMyself
+}
+
+/// Removes the `n`th filter (counting from 1), or the last one.
+pub fn remove(app: &mut App, n: Option<usize>) {
+    let index = match n {
+        Some(n) => n.saturating_sub(1),
+        None => app.filters.len().saturating_sub(1),
+    };
+    if index < app.filters.len() {
+        app.filters.remove(index);
+        app.refresh_visible();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        app::{Msg, script::Scripts, update},
+        config::FilterConfig,
+        jira::User,
+        ui::issue::{Status, StatusCategory},
+    };
+
+    fn issue(summary: &str, labels: &[&str]) -> Issue {
+        Issue {
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            ..Issue::new(summary, "")
+        }
+    }
+
+    #[test]
+    fn filters_narrow_the_list() {
+        let mut app = App::new(vec![
+            issue("A", &["infra", "db"]),
+            issue("B", &["frontend"]),
+            issue("C", &["infra"]),
+        ]);
+        app.list_state.select(Some(2));
+
+        open(&mut app);
+        picked(&mut app, PickerPurpose::FilterKind, "label");
+        let values = &app.picker.as_ref().unwrap().picker.items;
+        assert_eq!(values, &["db", "frontend", "infra"]);
+        picked(&mut app, PickerPurpose::FilterValue(FilterKind::Label), "infra");
+
+        let summaries: Vec<_> = app.visible_issues().map(|i| i.summary.as_str()).collect();
+        assert_eq!(summaries, ["A", "C"]);
+        // The selected issue stays selected
+        assert_eq!(app.selected_issue().unwrap().summary, "C");
+
+        remove(&mut app, None);
+        assert_eq!(app.visible_issues().count(), 3);
+        assert_eq!(app.selected_issue().unwrap().summary, "C");
+        remove(&mut app, Some(1));
+    }
+
+    #[test]
+    fn text_filters_match_keys_and_summaries() {
+        let issue = |id: &str, summary: &str| Issue {
+            id: id.to_string(),
+            ..Issue::new(summary, "")
+        };
+        let mut app = App::new(vec![
+            issue("OPS-123", "Failover"),
+            issue("OPS-12", "Backups"),
+            issue("WEB-1", "Notes for releasing 2.0"),
+        ]);
+        let typed = |app: &mut App, text: &str| {
+            picked(app, PickerPurpose::FilterKind, "text");
+            assert_eq!(app.input_mode, InputMode::Filter);
+            app.input = text.to_string();
+            text_entered(app);
+        };
+        typed(&mut app, "ops-12");
+        assert_eq!(app.visible_issues().count(), 2);
+        remove(&mut app, None);
+
+        app.filter_regex = true;
+        typed(&mut app, r"^OPS-1\d\d|releasin");
+        let keys: Vec<_> = app.visible_issues().map(|i| i.id.as_str()).collect();
+        assert_eq!(keys, ["OPS-123", "WEB-1"]);
+        assert_eq!(app.filters[0].name(), "regex");
+
+        // Left in the prompt to fix
+        typed(&mut app, "(OPS");
+        assert_eq!(app.input_mode, InputMode::Filter);
+        assert_eq!(app.error.as_deref(), Some("Invalid regex: unclosed group"));
+        assert_eq!(app.filters.len(), 1);
+    }
+
+    #[test]
+    fn expressions_about_me_wait_for_the_account() {
+        let mut mine = Issue {
+            story_points: Some(8.0),
+            ..issue("A", &["infra"])
+        };
+        mine.raw_fields
+            .insert("assignee".to_string(), json!({ "accountId": "ana" }));
+        let mut app = App::new(vec![mine, issue("B", &["infra"])]);
+        picked(&mut app, PickerPurpose::FilterKind, "text");
+        app.input = "label:infra colour:red".to_string();
+        assert_eq!(text_entered(&mut app), Command::None);
+        assert_eq!(app.input_mode, InputMode::Filter);
+        assert!(
+            app.error
+                .as_deref()
+                .unwrap()
+                .starts_with("Invalid filter: unknown field")
+        );
+
+        app.input = "label:infra assignee:me points:>5".to_string();
+        assert_eq!(text_entered(&mut app), Command::FetchMyself);
+        assert_eq!(app.filters[0].name(), "where");
+        assert_eq!(app.visible_issues().count(), 0);
+        let me = User {
+            account_id: "ana".to_string(),
+            display_name: "Ana Lima".to_string(),
+        };
+        update(&mut app, Msg::Myself(Ok(me)));
+        let summaries: Vec<_> = app.visible_issues().map(|i| i.summary.as_str()).collect();
+        assert_eq!(summaries, ["A"]);
+    }
+
+    #[test]
+    fn quick_filters_toggle_and_count() {
+        let mut app = App::new(vec![
+            Issue {
+                issue_type: Some("Bug".to_string()),
+                story_points: Some(3.0),
+                ..issue("A", &["infra"])
+            },
+            Issue {
+                issue_type: Some("Bug".to_string()),
+                ..issue("B", &["blocked"])
+            },
+            issue("C", &["infra"]),
+        ]);
+        let counts = QuickFilter::ALL.map(|quick| quick_filter_count(&app, quick));
+        assert_eq!(counts, [2, 1, 2]);
+
+        toggle_quick_filter(&mut app, 1);
+        toggle_quick_filter(&mut app, 3);
+        let summaries: Vec<_> = app.visible_issues().map(|i| i.summary.as_str()).collect();
+        assert_eq!(summaries, ["B"]);
+
+        // Counts follow the filter chips, not the other quick filters
+        app.filters.push(Filter::new(FilterKind::Label, "infra"));
+        assert_eq!(quick_filter_count(&app, QuickFilter::Bugs), 1);
+
+        toggle_quick_filter(&mut app, 3);
+        toggle_quick_filter(&mut app, 9);
+        assert_eq!(app.quick_filters, [QuickFilter::Bugs]);
+    }
+
+    #[test]
+    fn done_issues_can_be_hidden() {
+        let done = Issue {
+            status: Some(Status {
+                name: "Closed".to_string(),
+                category: StatusCategory::Done,
+            }),
+            ..issue("A", &[])
+        };
+        let mut app = App::new(vec![done, issue("B", &[])]);
+        for c in ['t', 'd'] {
+            update(&mut app, Msg::Key(KeyEvent::from(KeyCode::Char(c))));
+        }
+        assert!(app.hide_done);
+        assert_eq!(app.hidden_done_count(), 1);
+        let summaries: Vec<_> = app.visible_issues().map(|i| i.summary.as_str()).collect();
+        assert_eq!(summaries, ["B"]);
+        assert_eq!(quick_filter_count(&app, QuickFilter::Unestimated), 1);
+    }
+
+    #[test]
+    fn script_filters_follow_the_built_in_ones() {
+        let mut app = App::new(vec![issue("A", &["infra"]), issue("B", &[])]);
+        let filters = [FilterConfig {
+            name: "Tagged".to_string(),
+            script: "labels.len() > 0".to_string(),
+        }];
+        app.scripts = Scripts::compile(&[], &filters).unwrap();
+        app.refresh_visible();
+
+        let quick = QuickFilter::Script(0);
+        assert_eq!(QuickFilter::available(&app)[3], quick);
+        assert_eq!((quick.name(&app), quick_filter_count(&app, quick)), ("Tagged", 1));
+        toggle_quick_filter(&mut app, 4);
+        let summaries: Vec<_> = app.visible_issues().map(|i| i.summary.as_str()).collect();
+        assert_eq!(summaries, ["A"]);
+    }
+}
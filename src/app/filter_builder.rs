@@ -0,0 +1,150 @@
+//! The guided JQL builder: pick a field, then an operator, then a value, and get the query
+//! with the new clause to edit and run.
+
+use itertools::Itertools;
+
+use super::{App, OpenPicker, PickerPurpose, users};
+use crate::{
+    jira::jql::{self, FIELDS, JqlField, Operator},
+    ui::{input::InputMode, picker::Picker},
+};
+
+/// Starts the builder by asking for a field.
+pub fn open(app: &mut App) {
+    let fields = FIELDS.iter().map(|field| field.label.to_string()).collect();
+    app.picker = Some(OpenPicker {
+        purpose: PickerPurpose::JqlField,
+        picker: Picker::new("Filter on", fields),
+    });
+}
+
+/// Moves the builder along after something was picked in one of its pickers.
+pub fn picked(app: &mut App, purpose: PickerPurpose, choice: &str) {
+    match purpose {
+        PickerPurpose::JqlField => {
+            let Some(field) = FIELDS.iter().find(|field| field.label == choice) else {
+                return;
+            };
+            let operators = Operator::for_field(field)
+                .iter()
+                .map(|op| op.as_str().to_string())
+                .collect();
+            app.picker = Some(OpenPicker {
+                purpose: PickerPurpose::JqlOperator(*field),
+                picker: Picker::new(format!("{} …", field.label), operators),
+            });
+        }
+        PickerPurpose::JqlOperator(field) => {
+            let Some(&operator) = Operator::for_field(&field)
+                .iter()
+                .find(|op| op.as_str() == choice)
+            else {
+                return;
+            };
+            if operator.takes_value() && matches!(field.name, "assignee" | "reporter") {
+                users::pick_for_query(app, field, operator);
+            } else if operator.takes_value() {
+                let title = format!("{} {} …", field.label, operator.as_str());
+                let values = known_values(app, &field);
+                app.picker = Some(OpenPicker {
+                    purpose: PickerPurpose::JqlValue(field, operator),
+                    picker: Picker::new(title, values).allow_custom(),
+                });
+            } else {
+                edit_query(app, &jql::clause(&field, operator, ""));
+            }
+        }
+        PickerPurpose::JqlValue(field, operator) => {
+            edit_query(app, &jql::clause(&field, operator, choice));
+        }
+        _ => {}
+    }
+}
+
+/// Opens the JQL editor with `clause` added to the current query.
+pub(super) fn edit_query(app: &mut App, clause: &str) {
+    app.input = jql::and(&app.jql, clause);
+    app.input_state.cursor = app.input.len();
+    app.input_mode = InputMode::Jql;
+}
+
+/// Values to suggest for `field`, taken from the loaded issues. Anything else can be typed.
+fn known_values(app: &App, field: &JqlField) -> Vec<String> {
+    let values: Vec<String> = match field.name {
+        "status" => app
+            .issues
+            .iter()
+            .filter_map(|issue| issue.status.as_ref())
+            .map(|status| status.as_str().to_string())
+            .collect(),
+        "priority" => app
+            .issues
+            .iter()
+            .filter_map(|issue| issue.priority.as_ref())
+            .map(|priority| priority.as_str().to_string())
+            .collect(),
+        "issuetype" => app
+            .issues
+            .iter()
+            .filter_map(|issue| issue.issue_type.clone())
+            .collect(),
+        "project" => app
+            .issues
+            .iter()
+            .filter_map(|issue| issue.id.split_once('-'))
+            .map(|(project, _)| project.to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+    values.into_iter().unique().sorted().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{issue::Issue, picker::PickerAction};
+
+    fn pick(app: &mut App, choice: &str) {
+        let open = app.picker.take().unwrap();
+        assert!(open.picker.items.iter().any(|item| item == choice), "{choice}");
+        picked(app, open.purpose, choice);
+    }
+
+    #[test]
+    fn builds_a_clause_step_by_step() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            ..Issue::new("Failover", "")
+        }]);
+        open(&mut app);
+        pick(&mut app, "Project");
+        pick(&mut app, "!=");
+        pick(&mut app, "OPS");
+        assert_eq!(app.input_mode, InputMode::Jql);
+        assert_eq!(
+            app.input,
+            "assignee = currentUser() AND resolution = Unresolved AND project != \"OPS\" ORDER BY updated DESC"
+        );
+    }
+
+    #[test]
+    fn values_can_be_typed() {
+        let mut app = App::new(Vec::new());
+        open(&mut app);
+        pick(&mut app, "Label");
+        pick(&mut app, "=");
+        let mut open = app.picker.take().unwrap();
+        for c in "infra".chars() {
+            open.picker
+                .handle_key(&crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char(c)));
+        }
+        let PickerAction::Pick(value) = open
+            .picker
+            .handle_key(&crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Enter))
+        else {
+            panic!("typed value is pickable");
+        };
+        picked(&mut app, open.purpose, &value);
+        assert!(app.input.contains("labels = \"infra\""), "{}", app.input);
+    }
+}
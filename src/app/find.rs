@@ -0,0 +1,209 @@
+//! Finding issues in the list with `/`, like in vim: the selection jumps to the next issue
+//! whose key or summary contains what is typed, without hiding the others, and `n` and `N`
+//! go on to the next and previous ones.
+
+use std::ops::Range;
+
+use super::{App, update::record_jump};
+use crate::ui::{input::InputMode, issue::Issue};
+
+/// Where `query` is in `text`, ignoring case.
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query: Vec<char> = query.chars().collect();
+    let mut ranges = Vec::new();
+    if query.is_empty() {
+        return ranges;
+    }
+    let mut start = 0;
+    while start < text.len() {
+        let mut chars = text[start..].char_indices();
+        let matched = query.iter().all(|q| {
+            chars
+                .next()
+                .is_some_and(|(_, c)| c.to_lowercase().eq(q.to_lowercase()))
+        });
+        if matched {
+            let end = chars.next().map_or(text.len(), |(i, _)| start + i);
+            ranges.push(start..end);
+            start = end;
+        } else {
+            start += text[start..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    ranges
+}
+
+/// Whether the key or summary of `issue` contains `query`, ignoring case.
+pub(super) fn matches(issue: &Issue, query: &str) -> bool {
+    !match_ranges(&issue.id, query).is_empty() || !match_ranges(&issue.summary, query).is_empty()
+}
+
+/// What the list marks: what is being typed after `/`, or else the last search.
+pub fn highlighted(app: &App) -> &str {
+    match app.input_mode {
+        InputMode::Find => &app.input,
+        _ => &app.find_query,
+    }
+}
+
+/// The next listed row after `from`, or before it going `back`, whose issue matches
+/// `query`, wrapping around at the ends; `from` itself comes last.
+fn find_row(app: &App, query: &str, from: usize, back: bool) -> Option<usize> {
+    let len = app.visible.len();
+    (1..=len)
+        .map(|step| match back {
+            true => (from + len - step % len) % len,
+            false => (from + step) % len,
+        })
+        .find(|&row| matches(&app.issues[app.visible[row]], query))
+}
+
+/// Starts typing what to find, from the selected row.
+pub(super) fn start(app: &mut App) {
+    app.find_origin = app.list_state.selected();
+    app.input.clear();
+    app.input_state.cursor = 0;
+    app.input_mode = InputMode::Find;
+}
+
+/// Selects the first match of what is typed so far, or the row the search started from.
+pub(super) fn typed(app: &mut App) {
+    app.input_state.cursor = app.input.len();
+    let origin = app.find_origin.unwrap_or(0);
+    let row = match app.input.is_empty() {
+        true => None,
+        false => find_row(app, &app.input, origin, false),
+    };
+    app.list_state.select(row.or(app.find_origin));
+}
+
+/// Keeps the match of what was typed as the search `n` and `N` repeat. Nothing typed
+/// repeats the last search.
+pub(super) fn submit(app: &mut App) {
+    let typed = std::mem::take(&mut app.input);
+    app.input_state.cursor = 0;
+    app.input_mode = InputMode::Normal;
+    if !typed.is_empty() {
+        app.find_query = typed;
+    }
+    let origin = app.find_origin.take();
+    app.list_state.select(origin);
+    let found = match app.find_query.is_empty() {
+        true => None,
+        false => find_row(app, &app.find_query, origin.unwrap_or(0), false),
+    };
+    match found {
+        Some(row) => {
+            record_jump(app);
+            app.list_state.select(Some(row));
+        }
+        None if !app.find_query.is_empty() => {
+            app.notice = Some(format!("No issue matches {:?}", app.find_query));
+        }
+        None => {}
+    }
+}
+
+/// Goes back to the row the search started from.
+pub(super) fn cancel(app: &mut App) {
+    app.input.clear();
+    app.input_state.cursor = 0;
+    app.input_mode = InputMode::Normal;
+    app.list_state.select(app.find_origin.take());
+}
+
+/// Selects the `count`th next match of the last search, or previous one going `back`.
+pub(super) fn next(app: &mut App, count: usize, back: bool) {
+    if app.find_query.is_empty() {
+        app.notice = Some("Nothing to find again; / starts a search".to_string());
+        return;
+    }
+    let Some(mut row) = app.list_state.selected() else {
+        return;
+    };
+    for _ in 0..count {
+        match find_row(app, &app.find_query, row, back) {
+            Some(found) => row = found,
+            None => {
+                app.notice = Some(format!("No issue matches {:?}", app.find_query));
+                return;
+            }
+        }
+    }
+    record_jump(app);
+    app.list_state.select(Some(row));
+}
+
+/// Forgets the last search, to stop marking its matches.
+pub(super) fn clear(app: &mut App) {
+    app.find_query.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        let issue = |id: &str, summary: &str| Issue {
+            id: id.to_string(),
+            ..Issue::new(summary, "")
+        };
+        let mut app = App::new(vec![
+            issue("OPS-1", "Failover drill"),
+            issue("OPS-2", "Backups"),
+            issue("OPS-3", "Document the failover"),
+            issue("OPS-4", "Rotate keys"),
+        ]);
+        app.list_state.select(Some(1));
+        app
+    }
+
+    fn typing(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.input.push(c);
+            typed(app);
+        }
+    }
+
+    #[test]
+    fn matches_ignoring_case() {
+        assert_eq!(match_ranges("Fail over, FAILOVER failover", "failover"), [11..19, 20..28]);
+        assert_eq!(match_ranges("ÄÖ äö", "äö"), [0..4, 5..9]);
+        assert!(match_ranges("Backups", "").is_empty());
+    }
+
+    #[test]
+    fn finds_as_typed_and_again_with_n() {
+        let mut app = app();
+        start(&mut app);
+        typing(&mut app, "fail");
+        assert_eq!(app.list_state.selected(), Some(2));
+        assert_eq!(highlighted(&app), "fail");
+        // Nothing matches, so back where it started
+        typing(&mut app, "x");
+        assert_eq!(app.list_state.selected(), Some(1));
+        app.input.pop();
+        submit(&mut app);
+        assert_eq!(app.list_state.selected(), Some(2));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.visible.len(), 4);
+
+        // Wrapping around at the end, both ways
+        next(&mut app, 1, false);
+        assert_eq!(app.list_state.selected(), Some(0));
+        next(&mut app, 1, true);
+        assert_eq!(app.list_state.selected(), Some(2));
+        next(&mut app, 2, true);
+        assert_eq!(app.list_state.selected(), Some(2));
+
+        // By key too, and Esc goes back
+        start(&mut app);
+        typing(&mut app, "ops-4");
+        assert_eq!(app.list_state.selected(), Some(3));
+        cancel(&mut app);
+        assert_eq!(app.list_state.selected(), Some(2));
+        assert_eq!(highlighted(&app), "fail");
+        clear(&mut app);
+        assert_eq!(highlighted(&app), "");
+    }
+}
@@ -0,0 +1,163 @@
+//! Running the shell commands configured as hooks when something happens to the issues, so
+//! users can integrate other tools without changing the app.
+
+use serde_json::{Value, json};
+
+use super::{App, Command};
+use crate::{config::HookEvent, ui::issue::Issue};
+
+/// Runs the hooks of `event`, which happened to `issue`.
+pub(super) fn issue_event(app: &App, event: HookEvent, issue: &Issue) -> Command {
+    if !has_hooks(app, event) {
+        return Command::None;
+    }
+    let mut env = vec![("JIRA_EVENT".to_string(), event.as_str().to_string())];
+    env.extend(issue_env(issue));
+    let input = json!({ "event": event.as_str(), "issue": issue_json(issue) });
+    run_all(app, event, env, &input)
+}
+
+/// The fields of an issue as `JIRA_*` environment variables, empty when not set.
+pub(super) fn issue_env(issue: &Issue) -> Vec<(String, String)> {
+    let text = |value: Option<&str>| value.unwrap_or_default().to_string();
+    [
+        ("KEY", issue.id.clone()),
+        ("SUMMARY", issue.summary.clone()),
+        ("STATUS", text(issue.status.as_ref().map(|status| status.name.as_str()))),
+        ("TYPE", text(issue.issue_type.as_deref())),
+        ("PRIORITY", text(issue.priority.as_ref().map(|priority| priority.as_str()))),
+        ("ASSIGNEE", text(issue.assignee.as_deref())),
+        ("LABELS", issue.labels.join(",")),
+    ]
+    .into_iter()
+    .map(|(name, value)| (format!("JIRA_{name}"), value))
+    .collect()
+}
+
+/// Runs the hooks of [`HookEvent::Selected`] if the selected issue isn't `previous` anymore.
+pub(super) fn selection_changed(app: &App, previous: Option<&str>) -> Command {
+    match app.selected_jira_issue() {
+        Some(issue) if previous != Some(issue.id.as_str()) => {
+            issue_event(app, HookEvent::Selected, issue)
+        }
+        _ => Command::None,
+    }
+}
+
+/// Runs the hooks of [`HookEvent::Transitioned`] if `new`, a newer version of `old`, has
+/// another status.
+pub(super) fn issue_changed(app: &App, old: &Issue, new: &Issue) -> Command {
+    let status = |issue: &Issue| issue.status.as_ref().map(|status| status.name.clone());
+    if status(old) == status(new) {
+        return Command::None;
+    }
+    issue_event(app, HookEvent::Transitioned, new)
+}
+
+/// Runs the hooks of [`HookEvent::Refreshed`], with all of the search's results.
+pub(super) fn refreshed(app: &App) -> Command {
+    let event = HookEvent::Refreshed;
+    if !has_hooks(app, event) {
+        return Command::None;
+    }
+    let env = [
+        ("JIRA_EVENT", event.as_str().to_string()),
+        ("JIRA_JQL", app.jql.clone()),
+        ("JIRA_COUNT", app.issues.len().to_string()),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect();
+    let issues: Vec<Value> = app.issues.iter().map(issue_json).collect();
+    let input = json!({ "event": event.as_str(), "jql": app.jql, "issues": issues });
+    run_all(app, event, env, &input)
+}
+
+fn has_hooks(app: &App, event: HookEvent) -> bool {
+    app.hooks.iter().any(|hook| hook.event == event)
+}
+
+fn run_all(app: &App, event: HookEvent, env: Vec<(String, String)>, input: &Value) -> Command {
+    let mut commands: Vec<Command> = app
+        .hooks
+        .iter()
+        .filter(|hook| hook.event == event)
+        .map(|hook| Command::RunHook {
+            command: hook.command.clone(),
+            env: env.clone(),
+            input: format!("{input}\n"),
+        })
+        .collect();
+    match commands.len() {
+        0 => Command::None,
+        1 => commands.remove(0),
+        _ => Command::Batch(commands),
+    }
+}
+
+/// The fields of an issue handed to hooks and actions.
+pub(super) fn issue_json(issue: &Issue) -> Value {
+    json!({
+        "key": issue.id,
+        "summary": issue.summary,
+        "type": issue.issue_type,
+        "status": issue.status.as_ref().map(|status| &status.name),
+        "priority": issue.priority.as_ref().map(|priority| priority.as_str()),
+        "assignee": issue.assignee,
+        "story_points": issue.story_points,
+        "labels": issue.labels,
+        "components": issue.components,
+        "parent": issue.parent.as_ref().map(|parent| &parent.key),
+        "due": issue.due.map(|due| due.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        config::HookConfig,
+        ui::issue::{Status, StatusCategory},
+    };
+
+    fn hook(event: HookEvent) -> HookConfig {
+        HookConfig {
+            event,
+            command: format!("echo {}", event.as_str()),
+        }
+    }
+
+    #[test]
+    fn runs_the_hooks_of_events() {
+        let issues = ["OPS-1", "OPS-2"].map(|key| Issue {
+            id: key.to_string(),
+            ..Issue::new(format!("Summary of {key}"), String::new())
+        });
+        let mut app = App::new(issues.to_vec());
+        app.hooks = vec![hook(HookEvent::Selected), hook(HookEvent::Refreshed)];
+
+        let command = update(&mut app, Msg::Key(KeyEvent::from(KeyCode::Char('j'))));
+        let Command::Batch(commands) = command else {
+            panic!("expected the hook to run, got {command:?}");
+        };
+        let Some(Command::RunHook { command, env, input }) = commands.last() else {
+            panic!("expected the hook to run, got {commands:?}");
+        };
+        assert_eq!(command, "echo selected");
+        assert!(env.contains(&("JIRA_KEY".to_string(), "OPS-2".to_string())));
+        let input: Value = serde_json::from_str(input).unwrap();
+        assert_eq!(input["issue"]["summary"], "Summary of OPS-2");
+
+        // Nothing was configured for transitions
+        let mut moved = issues[0].clone();
+        moved.status = Some(Status {
+            name: "Done".to_string(),
+            category: StatusCategory::Done,
+        });
+        assert_eq!(issue_changed(&app, &issues[0], &moved), Command::None);
+        assert!(matches!(refreshed(&app), Command::RunHook { .. }));
+    }
+}
@@ -0,0 +1,161 @@
+//! The jump list: issues the cursor jumped away from, so the jumps can be undone with
+//! Ctrl-O and redone with Ctrl-I, like in vim. Also the labels put on the rows on screen to
+//! jump to one by typing its label.
+
+/// Most jumps remembered in each direction.
+const MAX_JUMPS: usize = 100;
+
+/// Issue keys visited before and after the current position.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JumpList {
+    back: Vec<String>,
+    forward: Vec<String>,
+}
+
+impl JumpList {
+    /// Remembers `from` as the place a new jump starts at. Jumps undone before are forgotten.
+    pub fn push(&mut self, from: &str) {
+        // Issues created locally have no key to come back to
+        if from.is_empty() || self.back.last().is_some_and(|last| last == from) {
+            return;
+        }
+        self.back.push(from.to_string());
+        if self.back.len() > MAX_JUMPS {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// The issue to go back to from `current`.
+    pub fn back(&mut self, current: Option<&str>) -> Option<String> {
+        let target = self.back.pop()?;
+        if let Some(current) = current.filter(|key| !key.is_empty()) {
+            self.forward.push(current.to_string());
+        }
+        Some(target)
+    }
+
+    /// The issue to return to after going back from it.
+    pub fn forward(&mut self, current: Option<&str>) -> Option<String> {
+        let target = self.forward.pop()?;
+        if let Some(current) = current.filter(|key| !key.is_empty()) {
+            self.back.push(current.to_string());
+        }
+        Some(target)
+    }
+}
+
+/// Letters rows are labelled with, those under the fingers first.
+const LABEL_LETTERS: &[u8] = b"asdfghjklqwertyuiopzxcvbnm";
+
+/// Labels of one or, when there are more rows on screen than letters, two letters on the
+/// rows on screen, like avy and easymotion put on words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpLabels {
+    /// The first row on screen.
+    first: usize,
+    /// How many rows are on screen.
+    rows: usize,
+    /// The first letter of a two-letter label, once typed.
+    typed: Option<usize>,
+}
+
+/// Where typing a letter of a label got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelMatch {
+    /// The letter starts some labels; another one tells them apart.
+    Partial,
+    /// The row with the typed label.
+    Row(usize),
+    /// No label starts with what was typed.
+    None,
+}
+
+impl JumpLabels {
+    pub fn new(first: usize, rows: usize) -> Self {
+        Self { first, rows, typed: None }
+    }
+
+    fn two_letters(&self) -> bool {
+        self.rows > LABEL_LETTERS.len()
+    }
+
+    /// The letters of the label of `row` still to be typed, if it's on screen and its label
+    /// starts with what was typed.
+    pub fn label(&self, row: usize) -> Option<String> {
+        let n = row.checked_sub(self.first).filter(|&n| n < self.rows)?;
+        let letter = |i: usize| LABEL_LETTERS[i] as char;
+        let count = LABEL_LETTERS.len();
+        match self.typed {
+            _ if !self.two_letters() => Some(letter(n).to_string()),
+            None => Some([letter(n / count), letter(n % count)].iter().collect()),
+            Some(typed) if n / count == typed => Some(letter(n % count).to_string()),
+            Some(_) => None,
+        }
+    }
+
+    /// Takes the next letter typed.
+    pub fn type_letter(&mut self, c: char) -> LabelMatch {
+        let Some(i) = LABEL_LETTERS.iter().position(|&letter| letter as char == c) else {
+            return LabelMatch::None;
+        };
+        let n = match self.typed {
+            _ if !self.two_letters() => i,
+            None if i * LABEL_LETTERS.len() < self.rows => {
+                self.typed = Some(i);
+                return LabelMatch::Partial;
+            }
+            None => return LabelMatch::None,
+            Some(typed) => typed * LABEL_LETTERS.len() + i,
+        };
+        if n < self.rows {
+            LabelMatch::Row(self.first + n)
+        } else {
+            LabelMatch::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_and_forward() {
+        let mut jumps = JumpList::default();
+        jumps.push("OPS-1");
+        jumps.push("OPS-2");
+        assert_eq!(jumps.back(Some("OPS-3")).as_deref(), Some("OPS-2"));
+        assert_eq!(jumps.back(Some("OPS-2")).as_deref(), Some("OPS-1"));
+        assert_eq!(jumps.back(Some("OPS-1")), None);
+        assert_eq!(jumps.forward(Some("OPS-1")).as_deref(), Some("OPS-2"));
+        assert_eq!(jumps.forward(Some("OPS-2")).as_deref(), Some("OPS-3"));
+        assert_eq!(jumps.forward(Some("OPS-3")), None);
+
+        // A new jump drops the ones ahead
+        jumps.back(Some("OPS-3"));
+        jumps.push("OPS-2");
+        assert_eq!(jumps.forward(Some("OPS-9")), None);
+    }
+
+    #[test]
+    fn labels_rows_with_one_or_two_letters() {
+        let mut labels = JumpLabels::new(10, 5);
+        assert_eq!(labels.label(9), None);
+        assert_eq!(labels.label(10).as_deref(), Some("a"));
+        assert_eq!(labels.label(12).as_deref(), Some("d"));
+        assert_eq!(labels.label(15), None);
+        assert_eq!(labels.type_letter('d'), LabelMatch::Row(12));
+        assert_eq!(labels.type_letter('h'), LabelMatch::None);
+
+        // More rows than letters
+        let mut labels = JumpLabels::new(0, 30);
+        assert_eq!(labels.label(0).as_deref(), Some("aa"));
+        assert_eq!(labels.label(27).as_deref(), Some("ss"));
+        assert_eq!(labels.type_letter('d'), LabelMatch::None);
+        assert_eq!(labels.type_letter('s'), LabelMatch::Partial);
+        assert_eq!(labels.label(0), None);
+        assert_eq!(labels.label(27).as_deref(), Some("s"));
+        assert_eq!(labels.type_letter('s'), LabelMatch::Row(27));
+    }
+}
@@ -0,0 +1,719 @@
+//! Application state and the event loop driving it.
+//!
+//! The app follows a message-based architecture: terminal input and results of background
+//! work arrive as [`Msg`]s, [`update`] applies them to the [`App`] state, and any side effects
+//! it asks for are returned as [`Command`]s for the runtime to execute.
+
+mod actions;
+pub mod activity;
+pub mod alerts;
+mod attention;
+mod bulk_edit;
+mod clipboard;
+mod command;
+mod completion;
+pub mod components;
+pub mod connection;
+pub mod cycle_time;
+mod expression;
+pub mod failures;
+pub mod filter;
+mod filter_builder;
+pub mod find;
+mod hooks;
+mod jumps;
+mod mouse;
+mod new_issue;
+mod open;
+pub mod operations;
+pub mod outbox;
+mod parents;
+mod projects;
+mod refresh;
+pub mod releases;
+mod runtime;
+pub mod script;
+mod sign_in;
+mod sprints;
+mod transitions;
+mod triage;
+mod update;
+mod users;
+mod watchers;
+pub mod workload;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
+
+use chrono::Utc;
+use ratatui::{layout::Rect, widgets::ListState};
+use serde::Deserialize;
+
+use self::{
+    activity::ActivityLog,
+    alerts::Alerts,
+    components::ComponentSummary,
+    connection::Connection,
+    failures::Failure,
+    filter::{Filter, FilterKind, QuickFilter},
+    jumps::JumpList,
+    operations::Operations,
+    outbox::Outbox,
+    script::{Computed, Scripts},
+};
+pub use self::{
+    bulk_edit::BulkChange,
+    command::Command,
+    jumps::{JumpLabels, LabelMatch},
+    runtime::run_app,
+    update::{Msg, init, init_failed, update},
+};
+use crate::{
+    config::{ActionConfig, ColumnConfig, Config, HookConfig, PollingConfig, ProfileConfig},
+    jira::{
+        ASSIGNED_JQL, HistoryEntry, PAGE_SIZE, Project, User, Version,
+        jql::{JqlField, Operator},
+    },
+    state::UiState,
+    ui::{
+        context_menu::ContextMenu,
+        input::{InputMode, NormalModeAction, PendingKeys, TextInputState},
+        inspector::Inspector,
+        issue::{Attachment, Issue, IssueRef},
+        issue_list::IssueListCache,
+        picker::Picker,
+        popup::Popup,
+        selection::Selection,
+        theme::Appearance,
+        timeline::TimelineState,
+    },
+};
+
+/// The complete state of the running application.
+pub struct App {
+    /// The query `issues` are the results of.
+    pub jql: String,
+    /// Bumped on every new search, so pages still arriving for an older one are ignored.
+    pub search_generation: usize,
+    pub issues: Vec<Issue>,
+    /// Filters narrowing down the listed issues, in the order they were added.
+    pub filters: Vec<Filter>,
+    /// Whether the text filter prompt takes a regular expression rather than text.
+    pub filter_regex: bool,
+    /// Quick filters that are switched on.
+    pub quick_filters: Vec<QuickFilter>,
+    /// Leave the issues that are done out of the list.
+    pub hide_done: bool,
+    /// Show the query above the list.
+    pub jql_bar_visible: bool,
+    /// Show the labels of the issues in a column of their own.
+    pub labels_visible: bool,
+    /// See [`Config::wrap_around`].
+    pub wrap_around: bool,
+    /// Indices into `issues` of the listed rows, which `list_state` refers to.
+    pub visible: Vec<usize>,
+    pub list_state: ListState,
+    /// Where jumps started, for going back and forth with Ctrl-O and Ctrl-I.
+    pub jumps: JumpList,
+    /// Keys of the issues marked with `m` and a letter, to go back to with `'` and the letter.
+    pub marks: BTreeMap<char, String>,
+    /// Labels on the rows on screen while waiting for one to be typed.
+    pub jump_labels: Option<JumpLabels>,
+    pub input_mode: InputMode,
+    pub view: View,
+    pub timeline: TimelineState,
+    /// The selected row of the workload view.
+    pub workload_cursor: usize,
+    /// The selected row of the cycle time view.
+    pub cycle_cursor: usize,
+    /// See [`Config::workload_capacity`].
+    pub workload_capacity: Option<f64>,
+    /// See [`Config::stuck_after_days`].
+    pub stuck_after_days: u32,
+    /// The project the releases and components views show.
+    pub view_project: Option<String>,
+    /// The projects of the instance for the project picker, fetched when first opened.
+    pub projects: Option<Loadable<Vec<Project>>>,
+    /// The project last picked in the project picker.
+    pub last_project: Option<String>,
+    /// The names of the priorities, the highest first, fetched when first changing one.
+    pub priorities: Option<Loadable<Vec<String>>>,
+    /// Steps to raise the selected issue's priority by once the priorities arrive.
+    pub priority_bump: Option<isize>,
+    /// The signed-in user, fetched when first needed, like for `assignee:me`.
+    pub me: Option<Loadable<User>>,
+    /// The issue to assign to the signed-in user once they're known.
+    pub assign_to_me: Option<String>,
+    /// The selected row of the releases view.
+    pub version_cursor: usize,
+    /// Versions for the releases view, by project key, fetched when first shown.
+    pub versions: HashMap<String, Loadable<Vec<Version>>>,
+    /// The selected row of the components view.
+    pub component_cursor: usize,
+    /// Components for the components view, by project key, fetched when first shown.
+    pub components: HashMap<String, Loadable<Vec<ComponentSummary>>>,
+    /// Requests that failed, oldest first, for the failures view.
+    pub failures: Vec<Failure>,
+    /// Requests to Jira and errors shown, for the log pane.
+    pub activity: ActivityLog,
+    /// Whether the log pane is shown below the list.
+    pub log_visible: bool,
+    /// The last search typed after `/`, which `n` and `N` repeat.
+    pub find_query: String,
+    /// The row selected when `/` was pressed, to go back to.
+    pub find_origin: Option<usize>,
+    /// The selected row of the failures view.
+    pub failure_cursor: usize,
+    pub input: String,
+    pub input_state: TextInputState,
+    pub sidebar_visible: bool,
+    pub sidebar_tab: SidebarTab,
+    /// Sidebar width in percent of the screen width.
+    pub sidebar_width: u16,
+    /// Where the sidebar was last drawn next to the list, to drag its edge.
+    pub sidebar_area: Option<Rect>,
+    /// Whether the edge of the sidebar is being dragged.
+    pub resizing_sidebar: bool,
+    pub appearance: Appearance,
+    pub pending_keys: PendingKeys,
+    /// Horizontal scroll of a long summary as `(row, characters)`, only applied while that
+    /// row is selected.
+    pub summary_scroll: Option<(usize, usize)>,
+    /// Which of the selected issue's [`SidebarItem`]s Enter opens, as `(row, index)`; the
+    /// first one when not set for the selected row.
+    pub item_cursor: Option<(usize, usize)>,
+    /// Progress of the background fetch of the remaining result pages, while it runs.
+    pub fetch_progress: Option<FetchProgress>,
+    /// How many issues the search matched, as Jira counts them. Unknown for search endpoints
+    /// that don't count.
+    pub result_total: Option<usize>,
+    pub issue_list_cache: IssueListCache,
+    /// Advanced on every animation tick.
+    pub spinner_frame: usize,
+    /// Animation ticks left of a flash of the screen, to get attention.
+    pub flash: u8,
+    /// Last error from background work, shown in the footer.
+    pub error: Option<String>,
+    /// A message for the user, shown in the footer until the next key press.
+    pub notice: Option<String>,
+    /// Unsent composer text, by composer; see [`UiState::drafts`].
+    pub drafts: BTreeMap<String, String>,
+    /// Changelogs for the history tab, by issue key, fetched when first shown.
+    pub histories: HashMap<String, Loadable<Vec<HistoryEntry>>>,
+    /// Child issues of epics, by epic key, fetched when the epic is first shown.
+    pub children: HashMap<String, Loadable<Vec<Issue>>>,
+    /// The users watching issues, by issue key, fetched when the details are first shown.
+    pub watchers: HashMap<String, Loadable<Vec<User>>>,
+    /// The popup picker, which gets all keys while open.
+    pub picker: Option<OpenPicker>,
+    /// The users the last search from a user picker found, to tell who was picked.
+    pub found_users: Vec<User>,
+    /// The epic the issue being written goes in.
+    pub new_issue_parent: Option<IssueRef>,
+    /// See [`Config::issue_type`].
+    pub issue_type: String,
+    /// What the next issue typed into the new issue input is told apart by while Jira
+    /// creates it.
+    pub next_pending: u64,
+    /// A popup showing text, like an action's output, which gets all keys while open.
+    pub popup: Option<Popup>,
+    /// The JSON of an issue, which gets all keys while open.
+    pub inspector: Option<Inspector>,
+    /// Lines of the sidebar to pick some of to copy, which gets all keys while open.
+    pub selection: Option<Selection>,
+    /// The menu opened by right-clicking an issue, which gets all keys while open.
+    pub context_menu: Option<ContextMenu>,
+    /// See [`Config::hooks`].
+    pub hooks: Vec<HookConfig>,
+    /// See [`Config::actions`].
+    pub actions: Vec<ActionConfig>,
+    /// See [`Config::columns`].
+    pub columns: Vec<ColumnConfig>,
+    /// The scripts of [`Config::columns`] and [`Config::filters`].
+    pub scripts: Scripts,
+    /// What `scripts` made of `issues`, updated along with `visible`.
+    pub computed: Computed,
+    /// Shown in the status bar.
+    pub connection: Connection,
+    /// The profile signed in with, whose state is saved.
+    pub profile: Option<String>,
+    /// The config file given on the command line, read again by Ctrl-R.
+    pub config_path: Option<PathBuf>,
+    /// Colors were turned off on the command line, whatever the config says.
+    pub no_color: bool,
+    /// The requests to Jira running in the background.
+    pub operations: Operations,
+    /// Changes to issues not made in Jira yet.
+    pub outbox: Outbox,
+    pub alerts: Alerts,
+    /// See [`Config::polling`].
+    pub polling: PollingConfig,
+    /// Whether a refresh of the list in the background is scheduled or running.
+    pub refreshing: bool,
+}
+
+/// A picker on screen, along with what its choice is for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenPicker {
+    pub purpose: PickerPurpose,
+    pub picker: Picker,
+}
+
+/// What a picker is choosing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerPurpose {
+    /// The field of a new JQL condition.
+    JqlField,
+    /// The operator of a new JQL condition on the field.
+    JqlOperator(JqlField),
+    /// The value of a new JQL condition.
+    JqlValue(JqlField, Operator),
+    /// The kind of a new list filter.
+    FilterKind,
+    /// The value of a new list filter.
+    FilterValue(FilterKind),
+    /// The project to scope the search to.
+    Project,
+    /// The user a new JQL condition on the field compares to.
+    JqlUser(JqlField, Operator),
+    /// The user to assign the selected issue to.
+    Assignee,
+    /// The epic a new issue goes in.
+    Epic,
+    /// The issue to move the selected one under.
+    Parent,
+    /// The status to move the selected issue to.
+    Status,
+    /// A user to add to the watchers of the selected issue.
+    Watcher,
+    /// A watcher to take off the selected issue.
+    Unwatch,
+}
+
+/// An entry in the details of an issue that can be selected and opened.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SidebarItem<'a> {
+    /// Another issue, opened in the app.
+    Reference(&'a IssueRef),
+    /// A child of an epic, opened in the app.
+    Child(&'a Issue),
+    /// A file attached to the issue, opened with the system's default application.
+    Attachment(&'a Attachment),
+}
+
+/// How the listed issues are shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum View {
+    #[default]
+    List,
+    /// As bars across the calendar, from their start to their due date.
+    Timeline,
+    /// Summed up per assignee.
+    Workload,
+    /// Not the issues but the versions of a project, to pick the issues of one.
+    Releases,
+    /// Not the issues but the components of a project, to pick the issues of one.
+    Components,
+    /// Not the issues but the requests that failed, to retry or dismiss them.
+    Failures,
+    /// How long the listed issues that are done took, from their changelogs.
+    CycleTime,
+}
+
+/// The tabs of the sidebar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SidebarTab {
+    #[default]
+    Details,
+    History,
+}
+
+impl SidebarTab {
+    pub const ALL: [SidebarTab; 2] = [SidebarTab::Details, SidebarTab::History];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SidebarTab::Details => "Details",
+            SidebarTab::History => "History",
+        }
+    }
+
+    /// The tab `steps` to the right, wrapping around.
+    pub fn cycle(self, steps: isize) -> Self {
+        let index = Self::ALL.iter().position(|&tab| tab == self).unwrap_or(0);
+        let len = Self::ALL.len() as isize;
+        Self::ALL[(index as isize + steps).rem_euclid(len) as usize]
+    }
+}
+
+/// The row `steps` down from `current` of `len` rows, stopping at the first and last ones.
+/// With `wrap`, moving on from the first or last one continues at the other end.
+fn step_row(current: usize, steps: isize, len: usize, wrap: bool) -> usize {
+    let last = len.saturating_sub(1);
+    let target = current as isize + steps;
+    let past_end = (current == 0 && target < 0) || (current == last && target > last as isize);
+    if wrap && past_end && len > 0 {
+        target.rem_euclid(len as isize) as usize
+    } else {
+        target.clamp(0, last as isize) as usize
+    }
+}
+
+/// Moves the cursor through the `len` rows of a view other than the issue list, for the keys
+/// that move through the issues, wrapping around with `wrap`. Returns whether `action` was one
+/// of them.
+fn move_row_cursor(cursor: &mut usize, len: usize, action: NormalModeAction, wrap: bool) -> bool {
+    let last = len.saturating_sub(1);
+    match action {
+        NormalModeAction::Jump(offset) => *cursor = step_row(*cursor, offset, len, wrap),
+        NormalModeAction::GotoTop => *cursor = 0,
+        NormalModeAction::GotoBottom => *cursor = last,
+        _ => return false,
+    }
+    true
+}
+
+/// Data fetched in the background on demand.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Loadable<T> {
+    Loading,
+    Loaded(T),
+    Failed(String),
+}
+
+/// How many issues of the full result set have been loaded so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchProgress {
+    pub fetched: usize,
+    /// Unknown until the first page arrives, and for search endpoints that don't count.
+    pub total: Option<usize>,
+}
+
+impl App {
+    pub fn new(issues: Vec<Issue>) -> Self {
+        let mut list_state = ListState::default();
+        if !issues.is_empty() {
+            list_state.select(Some(0));
+        }
+        Self {
+            jql: ASSIGNED_JQL.to_string(),
+            search_generation: 0,
+            visible: (0..issues.len()).collect(),
+            issues,
+            filters: Vec::new(),
+            filter_regex: false,
+            quick_filters: Vec::new(),
+            hide_done: false,
+            jql_bar_visible: true,
+            labels_visible: false,
+            wrap_around: false,
+            list_state,
+            jumps: JumpList::default(),
+            marks: BTreeMap::new(),
+            jump_labels: None,
+            input_mode: InputMode::Normal,
+            view: View::default(),
+            timeline: TimelineState::default(),
+            workload_cursor: 0,
+            cycle_cursor: 0,
+            workload_capacity: None,
+            stuck_after_days: Config::default().stuck_after_days,
+            view_project: None,
+            projects: None,
+            last_project: None,
+            priorities: None,
+            priority_bump: None,
+            me: None,
+            assign_to_me: None,
+            version_cursor: 0,
+            versions: HashMap::new(),
+            component_cursor: 0,
+            components: HashMap::new(),
+            failures: Vec::new(),
+            activity: ActivityLog::default(),
+            log_visible: false,
+            find_query: String::new(),
+            find_origin: None,
+            failure_cursor: 0,
+            input: String::new(),
+            input_state: TextInputState::default(),
+            sidebar_visible: false,
+            sidebar_tab: SidebarTab::default(),
+            sidebar_width: Config::default().sidebar_width,
+            sidebar_area: None,
+            resizing_sidebar: false,
+            appearance: Appearance::default(),
+            pending_keys: PendingKeys::default(),
+            summary_scroll: None,
+            item_cursor: None,
+            fetch_progress: None,
+            result_total: None,
+            issue_list_cache: IssueListCache::default(),
+            spinner_frame: 0,
+            flash: 0,
+            error: None,
+            notice: None,
+            drafts: BTreeMap::new(),
+            histories: HashMap::new(),
+            children: HashMap::new(),
+            watchers: HashMap::new(),
+            picker: None,
+            found_users: Vec::new(),
+            new_issue_parent: None,
+            issue_type: Config::default().issue_type,
+            next_pending: 0,
+            popup: None,
+            inspector: None,
+            selection: None,
+            context_menu: None,
+            hooks: Vec::new(),
+            actions: Vec::new(),
+            columns: Vec::new(),
+            scripts: Scripts::default(),
+            computed: Computed::default(),
+            connection: Connection::default(),
+            profile: None,
+            config_path: None,
+            no_color: false,
+            operations: Operations::default(),
+            outbox: Outbox::default(),
+            alerts: Alerts::default(),
+            polling: PollingConfig::default(),
+            refreshing: false,
+        }
+    }
+
+    /// Applies the user's settings and the state saved by a previous run.
+    pub fn configure(&mut self, config: &Config, state: &UiState) {
+        self.sidebar_width = state.sidebar_width.unwrap_or(config.sidebar_width);
+        self.apply_config(config);
+        self.drafts = state.drafts.clone();
+        self.last_project = state.last_project.clone();
+        self.outbox = Outbox::new(state.outbox.clone());
+        self.profile = state.profile.clone();
+    }
+
+    /// Applies the settings that can change while running: the looks, hooks, actions, alerts,
+    /// polling, and the columns and quick filters.
+    pub fn apply_config(&mut self, config: &Config) {
+        self.appearance = Appearance::from_config(config);
+        self.workload_capacity = config.workload_capacity;
+        self.stuck_after_days = config.stuck_after_days;
+        self.issue_type = config.issue_type.clone();
+        self.wrap_around = config.wrap_around;
+        self.hooks = config.hooks.clone();
+        self.actions = config.actions.clone();
+        self.columns = config.columns.clone();
+        self.alerts.configure(&config.alerts);
+        self.polling = config.polling.clone();
+        match Scripts::compile(&config.columns, &config.filters) {
+            Ok(scripts) => self.scripts = scripts,
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Starts out where `profile` says: with its search, in its view, on its project.
+    pub fn start_with(&mut self, profile: &ProfileConfig) {
+        self.jql = profile.startup_jql();
+        self.view = profile.view.unwrap_or_default();
+        if let Some(project) = &profile.project {
+            self.set_view_project(project);
+        }
+    }
+
+    /// The part of the state that is persisted between runs.
+    pub fn ui_state(&self) -> UiState {
+        UiState {
+            sidebar_width: Some(self.sidebar_width),
+            drafts: self.drafts.clone(),
+            last_project: self.last_project.clone(),
+            outbox: self.outbox.changes(),
+            profile: self.profile.clone(),
+        }
+    }
+
+    /// How many issues are left out of the list for being done.
+    pub fn hidden_done_count(&self) -> usize {
+        if !self.hide_done {
+            return 0;
+        }
+        self.issues.iter().filter(|issue| issue.is_done()).count()
+    }
+
+    /// The issue under the cursor, if any.
+    pub fn selected_issue(&self) -> Option<&Issue> {
+        let row = self.list_state.selected()?;
+        self.visible.get(row).map(|&i| &self.issues[i])
+    }
+
+    /// The selected issue, unless it only exists locally.
+    pub fn selected_jira_issue(&self) -> Option<&Issue> {
+        self.selected_issue().filter(|issue| !issue.is_local())
+    }
+
+    /// The key of the selected issue, unless it only exists locally.
+    pub fn selected_key(&self) -> Option<String> {
+        self.selected_jira_issue().map(|issue| issue.id.clone())
+    }
+
+    /// The listed issues, in order.
+    pub fn visible_issues(&self) -> impl Iterator<Item = &Issue> {
+        self.visible.iter().map(|&i| &self.issues[i])
+    }
+
+    /// The result page of the selected issue and how many pages the search has, counting the
+    /// ones not loaded, as 1-based `(page, pages)`.
+    pub fn result_page(&self) -> (usize, usize) {
+        let page_size = PAGE_SIZE as usize;
+        let total = self.issues.len().max(self.result_total.unwrap_or(0));
+        let page = self
+            .list_state
+            .selected()
+            .and_then(|row| self.visible.get(row))
+            .map_or(0, |&i| i / page_size);
+        (page + 1, total.div_ceil(page_size).max(1))
+    }
+
+    /// Selects the first listed issue of the result page `pages` away from the selected one,
+    /// or the last listed one when that page isn't loaded.
+    pub fn jump_page(&mut self, pages: isize) {
+        let Some(&last) = self.visible.last() else {
+            return;
+        };
+        let (page, _) = self.result_page();
+        let target = (page as isize - 1 + pages).max(0) as usize * PAGE_SIZE as usize;
+        let row = self
+            .visible
+            .iter()
+            .position(|&i| i >= target.min(last))
+            .unwrap_or(0);
+        self.list_state.select(Some(row));
+    }
+
+    /// Selects the row of `issues[index]`, if it is listed.
+    pub fn select_issue(&mut self, index: usize) {
+        if let Some(row) = self.visible.iter().position(|&i| i == index) {
+            self.list_state.select(Some(row));
+        }
+    }
+
+    /// The account id of the signed-in user, once fetched.
+    pub fn account_id(&self) -> Option<&str> {
+        match &self.me {
+            Some(Loadable::Loaded(user)) => Some(&user.account_id),
+            _ => None,
+        }
+    }
+
+    /// Recomputes which issues are listed after the issues or filters changed, keeping the
+    /// selected issue selected when it is still listed.
+    pub fn refresh_visible(&mut self) {
+        let selected = self
+            .list_state
+            .selected()
+            .and_then(|row| self.visible.get(row).copied());
+        if !self.scripts.is_empty() {
+            self.computed = self.scripts.evaluate(&self.issues, Utc::now());
+            if let Some(e) = &self.computed.error {
+                self.error = Some(format!("Script failed: {e}"));
+            }
+        }
+        self.visible = (0..self.issues.len())
+            .filter(|&i| {
+                let issue = &self.issues[i];
+                self.filters
+                    .iter()
+                    .all(|filter| filter.matches(issue, self.account_id()))
+                    && self
+                        .quick_filters
+                        .iter()
+                        .all(|quick| quick.matches(self, i))
+                    && !(self.hide_done && issue.is_done())
+            })
+            .collect();
+
+        let row = match selected.and_then(|i| self.visible.iter().position(|&v| v == i)) {
+            Some(row) => Some(row),
+            None if self.visible.is_empty() => None,
+            None => Some(
+                self.list_state
+                    .selected()
+                    .unwrap_or(0)
+                    .min(self.visible.len() - 1),
+            ),
+        };
+        if row != self.list_state.selected() {
+            self.summary_scroll = None;
+        }
+        self.list_state.select(row);
+    }
+
+    /// How many characters of the selected issue's summary are scrolled out of view.
+    pub fn selected_summary_scroll(&self) -> usize {
+        match self.summary_scroll {
+            Some((row, scroll)) if self.list_state.selected() == Some(row) => scroll,
+            _ => 0,
+        }
+    }
+
+    /// The selectable entries of the selected issue: the issues it references, its children if
+    /// it is an epic, and its attachments.
+    pub fn sidebar_items(&self) -> Vec<SidebarItem<'_>> {
+        let Some(issue) = self.selected_issue() else {
+            return Vec::new();
+        };
+        let references = issue
+            .references()
+            .into_iter()
+            .map(|(_, reference)| SidebarItem::Reference(reference));
+        let children = match self.children.get(&issue.id) {
+            Some(Loadable::Loaded(children)) => children.as_slice(),
+            _ => &[],
+        };
+        let children = children.iter().map(SidebarItem::Child);
+        let attachments = issue.attachments.iter().map(SidebarItem::Attachment);
+        references.chain(children).chain(attachments).collect()
+    }
+
+    /// Index into [`App::sidebar_items`] of the one Enter opens.
+    pub fn selected_item_index(&self) -> usize {
+        match self.item_cursor {
+            Some((row, index)) if self.list_state.selected() == Some(row) => index,
+            _ => 0,
+        }
+    }
+
+    /// The entry Enter opens.
+    pub fn selected_item(&self) -> Option<SidebarItem<'_>> {
+        self.sidebar_items()
+            .get(self.selected_item_index())
+            .copied()
+    }
+
+    /// The project of the selected issue, or the one the releases and components views
+    /// showed last.
+    pub fn current_project(&self) -> Option<String> {
+        self.selected_issue()
+            .and_then(|issue| issue.id.split_once('-'))
+            .map(|(project, _)| project.to_string())
+            .or_else(|| self.view_project.clone())
+    }
+
+    /// Points the releases and components views at `project`, starting them at the top if
+    /// it's another one than before.
+    pub fn set_view_project(&mut self, project: &str) {
+        if self.view_project.as_deref() != Some(project) {
+            self.view_project = Some(project.to_string());
+            self.version_cursor = 0;
+            self.component_cursor = 0;
+        }
+    }
+
+    /// Whether anything on screen animates, requiring periodic redraws.
+    pub fn is_animating(&self) -> bool {
+        self.fetch_progress.is_some() || !self.operations.is_empty() || self.flash > 0
+    }
+}
@@ -0,0 +1,221 @@
+//! Pointing at the list with the mouse: clicking a row selects its issue, right-clicking one
+//! opens a menu of the common actions on it, and dragging the edge of the sidebar resizes
+//! it.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use super::{App, Command, View, clipboard, transitions, update::SIDEBAR_WIDTH_RANGE, users};
+use crate::ui::{
+    context_menu::{ContextMenu, MenuAction, MenuItem},
+    input::{CopyTarget, InputMode},
+};
+
+/// Handles a press, drag, or release of a mouse button at where it happened.
+pub(super) fn handle(app: &mut App, mouse: &MouseEvent) -> Command {
+    match mouse.kind {
+        MouseEventKind::Down(button) => pressed(app, button, mouse),
+        MouseEventKind::Drag(MouseButton::Left) if app.resizing_sidebar => {
+            resize_sidebar(app, mouse.column);
+            Command::None
+        }
+        // Saved once it's let go, rather than at every column on the way
+        MouseEventKind::Up(MouseButton::Left) if app.resizing_sidebar => {
+            app.resizing_sidebar = false;
+            Command::SaveState(app.ui_state())
+        }
+        _ => Command::None,
+    }
+}
+
+/// Makes the sidebar as wide as from `column` to the right of the screen.
+fn resize_sidebar(app: &mut App, column: u16) {
+    let Some(sidebar) = app.sidebar_area else {
+        return;
+    };
+    let screen = u32::from(sidebar.right().max(1));
+    let width = u32::from(sidebar.right().saturating_sub(column)) * 100 / screen;
+    app.sidebar_width = (width as u16).clamp(SIDEBAR_WIDTH_RANGE.0, SIDEBAR_WIDTH_RANGE.1);
+}
+
+fn pressed(app: &mut App, button: MouseButton, mouse: &MouseEvent) -> Command {
+    if let Some(menu) = &app.context_menu {
+        let action = menu.click(mouse.column, mouse.row);
+        match (button, action) {
+            (MouseButton::Left, action) => return menu_action(app, action),
+            // Right-clicking another issue opens its menu instead
+            (_, MenuAction::Close) => app.context_menu = None,
+            _ => return Command::None,
+        }
+    }
+    // Popups and prompts get all keys, so leave them be
+    let busy = app.picker.is_some()
+        || app.popup.is_some()
+        || app.inspector.is_some()
+        || app.selection.is_some()
+        || app.input_mode != InputMode::Normal;
+    if busy {
+        return Command::None;
+    }
+    let on_edge = app
+        .sidebar_area
+        .is_some_and(|sidebar| sidebar.x == mouse.column && sidebar.y <= mouse.row);
+    if button == MouseButton::Left && on_edge {
+        app.resizing_sidebar = true;
+        return Command::None;
+    }
+    if app.view != View::List {
+        return Command::None;
+    }
+    let Some(on_screen) = app.issue_list_cache.row_at(mouse.column, mouse.row) else {
+        return Command::None;
+    };
+    let row = app.list_state.offset() + on_screen;
+    if row >= app.visible.len() {
+        return Command::None;
+    }
+    app.list_state.select(Some(row));
+    if let Some(key) = app.selected_key().filter(|_| button == MouseButton::Right) {
+        app.context_menu = Some(ContextMenu::new(key, mouse.column, mouse.row));
+    }
+    Command::None
+}
+
+/// Does what was picked from the context menu to its issue, which is the selected one.
+pub(super) fn menu_action(app: &mut App, action: MenuAction) -> Command {
+    let item = match action {
+        MenuAction::None => return Command::None,
+        MenuAction::Close => {
+            app.context_menu = None;
+            return Command::None;
+        }
+        MenuAction::Pick(item) => item,
+    };
+    let Some(menu) = app.context_menu.take() else {
+        return Command::None;
+    };
+    match item {
+        MenuItem::OpenInBrowser => Command::OpenInBrowser { key: menu.key },
+        MenuItem::ChangeStatus => transitions::pick_status(app),
+        MenuItem::AssignToMe => users::assign_to_me(app),
+        MenuItem::CopyKey => clipboard::copy(app, CopyTarget::Key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+    use crate::{
+        app::{Loadable, Msg, PickerPurpose, update},
+        jira::{Transition, User},
+        ui::{issue::Issue, render_ui},
+    };
+
+    fn app() -> App {
+        let issue = |id: &str| Issue {
+            id: id.to_string(),
+            ..Issue::new(format!("Summary of {id}"), String::new())
+        };
+        App::new(vec![issue("OPS-1"), issue("OPS-2"), issue("OPS-3")])
+    }
+
+    fn press(button: MouseButton, column: u16, row: u16) -> Msg {
+        Msg::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(button),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn right_clicking_a_row_offers_actions_on_it() {
+        let mut app = app();
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|f| render_ui(f, &mut app)).unwrap();
+
+        // Under the filter and JQL bars
+        update(&mut app, press(MouseButton::Left, 10, 3));
+        assert_eq!(app.list_state.selected(), Some(1));
+        update(&mut app, press(MouseButton::Right, 10, 4));
+        assert_eq!(app.list_state.selected(), Some(2));
+        assert_eq!(app.context_menu.as_ref().map(|menu| menu.key.as_str()), Some("OPS-3"));
+
+        terminal.draw(|f| render_ui(f, &mut app)).unwrap();
+        // The second item, under the top border
+        assert_eq!(update(&mut app, press(MouseButton::Left, 12, 6)), Command::FetchTransitions {
+            key: "OPS-3".to_string()
+        });
+        assert!(app.context_menu.is_none());
+        let transitions = ["Start", "Done"].map(|name| Transition {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            to_status: Some(name.replace("Start", "In Progress")),
+        });
+        update(&mut app, Msg::Transitions {
+            key: "OPS-3".to_string(),
+            result: Ok(transitions.to_vec()),
+        });
+        let open = app.picker.as_ref().unwrap();
+        assert_eq!(open.purpose, PickerPurpose::Status);
+        assert_eq!(open.picker.items, ["In Progress", "Done"]);
+        update(&mut app, Msg::Key(KeyEvent::from(KeyCode::Esc)));
+
+        // Assigning waits for who "me" is
+        update(&mut app, press(MouseButton::Right, 10, 2));
+        for code in [KeyCode::Down, KeyCode::Down, KeyCode::Enter] {
+            update(&mut app, Msg::Key(KeyEvent::from(code)));
+        }
+        assert_eq!(app.me, Some(Loadable::Loading));
+        assert_eq!(app.assign_to_me.as_deref(), Some("OPS-1"));
+        let me = User {
+            account_id: "ana".to_string(),
+            display_name: "Ana Lima".to_string(),
+        };
+        update(&mut app, Msg::Myself(Ok(me)));
+        assert!(app.outbox.is_pending("OPS-1"));
+        assert_eq!(app.notice.as_deref(), Some("Assigning OPS-1 to you…"));
+    }
+
+    #[test]
+    fn dragging_the_edge_of_the_sidebar_resizes_it() {
+        let mut app = app();
+        app.sidebar_visible = true;
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|f| render_ui(f, &mut app)).unwrap();
+        let edge = app.sidebar_area.unwrap().x;
+        assert_eq!(edge, 72);
+
+        update(&mut app, press(MouseButton::Left, edge, 10));
+        let drag = |column, kind| {
+            Msg::Mouse(MouseEvent {
+                kind,
+                column,
+                row: 12,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        update(&mut app, drag(60, MouseEventKind::Drag(MouseButton::Left)));
+        assert_eq!(app.sidebar_width, 50);
+        // No further than the keys go
+        update(&mut app, drag(5, MouseEventKind::Drag(MouseButton::Left)));
+        assert_eq!(app.sidebar_width, SIDEBAR_WIDTH_RANGE.1);
+        let command = update(&mut app, drag(5, MouseEventKind::Up(MouseButton::Left)));
+        assert_eq!(command, Command::SaveState(app.ui_state()));
+        assert!(!app.resizing_sidebar);
+        assert_eq!(app.ui_state().sidebar_width, Some(SIDEBAR_WIDTH_RANGE.1));
+    }
+
+    #[test]
+    fn clicks_outside_the_list_do_nothing() {
+        let mut app = app();
+        app.issue_list_cache.hide();
+        update(&mut app, press(MouseButton::Right, 10, 2));
+        assert!(app.context_menu.is_none());
+        app.context_menu = Some(ContextMenu::new("OPS-1", 10, 2));
+        assert_eq!(menu_action(&mut app, MenuAction::Close), Command::None);
+        assert!(app.context_menu.is_none());
+    }
+}
@@ -0,0 +1,207 @@
+//! Creating issues in Jira from the new issue input. They're listed right away, told apart by
+//! a number of their own until Jira created them, and then replaced by the copy Jira has.
+
+use super::{App, Command, hooks};
+use crate::{
+    config::HookEvent,
+    error::Result,
+    jira::{CreatedIssue, NewIssue},
+    ui::issue::Issue,
+};
+
+/// Lists an issue with the summary typed into the input, in the epic picked for it if any,
+/// and creates it in the project of the selected issue, or else the one switched to last.
+pub(super) fn submit(app: &mut App) -> Command {
+    let summary = app.input.trim().to_string();
+    let project = app.current_project().or_else(|| app.last_project.clone());
+    let pending = app.next_pending;
+    app.next_pending += 1;
+    let mut issue = Issue::new(summary.clone(), String::new());
+    issue.pending = Some(pending);
+    if let Some(epic) = app.new_issue_parent.take() {
+        issue.parent_epic = Some(epic.summary.clone());
+        issue.parent = Some(epic);
+    }
+    let new = project.map(|project_key| NewIssue {
+        project_key,
+        issue_type: app.issue_type.clone(),
+        summary: summary.clone(),
+        description: String::new(),
+        labels: Vec::new(),
+        story_points: None,
+        parent: issue.parent.as_ref().map(|epic| epic.key.clone()),
+    });
+    app.issues.push(issue);
+    app.refresh_visible();
+    // Select the newly added issue
+    app.select_issue(app.issues.len() - 1);
+    app.input.clear();
+    match new {
+        Some(issue) => {
+            app.notice = Some(format!("Creating \"{summary}\"…"));
+            Command::CreateIssue { pending, issue }
+        }
+        None => {
+            app.error =
+                Some(format!("No project to create \"{summary}\" in; switch to one with P"));
+            Command::None
+        }
+    }
+}
+
+/// Gives the issue listed as `pending` the key Jira created it with, running the hooks of
+/// [`HookEvent::Created`] and fetching what Jira made of it, or reports why it wasn't created.
+/// The failures view retries it.
+pub(super) fn created(
+    app: &mut App,
+    pending: u64,
+    new: &NewIssue,
+    result: Result<CreatedIssue>,
+) -> Command {
+    let created = match result {
+        Ok(created) => created,
+        Err(e) => {
+            app.error = Some(format!(
+                "Failed to create \"{}\": {e}; retry it from the failures view (vf)",
+                new.summary
+            ));
+            return Command::None;
+        }
+    };
+    app.notice = Some(format!("Created {} {}", created.key, created.url));
+    // Gone with a new search
+    let Some(issue) = app
+        .issues
+        .iter_mut()
+        .find(|issue| issue.pending == Some(pending))
+    else {
+        return Command::None;
+    };
+    issue.id = created.key.clone();
+    let issue = issue.clone();
+    let hook = hooks::issue_event(app, HookEvent::Created, &issue);
+    Command::Batch(vec![hook, Command::FetchIssue { key: created.key }])
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+    use crate::{
+        app::{Msg, View, update},
+        config::Config,
+        error::Error,
+        jira::FakeClient,
+    };
+
+    fn press(app: &mut App, code: KeyCode) -> Command {
+        update(app, Msg::Key(KeyEvent::from(code)))
+    }
+
+    fn type_summary(app: &mut App, summary: &str) -> Command {
+        press(app, KeyCode::Char('i'));
+        for c in summary.chars() {
+            press(app, KeyCode::Char(c));
+        }
+        match press(app, KeyCode::Enter) {
+            Command::Batch(mut commands) => match commands.pop() {
+                Some(command) => command,
+                None => Command::None,
+            },
+            command => command,
+        }
+    }
+
+    #[test]
+    fn creates_issues_in_the_project_of_the_selected_one() {
+        let mut app = App::new(vec![Issue::from_jira(&FakeClient::issue("OPS-1", "", "To Do"))]);
+        let Command::CreateIssue { pending: first, issue } = type_summary(&mut app, "Rotate keys")
+        else {
+            panic!("the issue wasn't created");
+        };
+        assert_eq!((issue.project_key.as_str(), issue.summary.as_str()), ("OPS", "Rotate keys"));
+        assert_eq!(issue.issue_type, "Task");
+        assert_eq!(app.selected_issue().unwrap().summary, "Rotate keys");
+
+        // The same summary again is another issue
+        app.select_issue(0);
+        let Command::CreateIssue { pending, issue: again } = type_summary(&mut app, "Rotate keys")
+        else {
+            panic!("the issue wasn't created");
+        };
+        assert_ne!(pending, first);
+        let command = update(&mut app, Msg::IssueCreated {
+            pending,
+            issue: again,
+            result: Ok(CreatedIssue {
+                id: "10002".to_string(),
+                key: "OPS-2".to_string(),
+                url: "https://acme.atlassian.net/browse/OPS-2".to_string(),
+            }),
+        });
+        let ids: Vec<_> = app.issues.iter().map(|issue| issue.id.as_str()).collect();
+        assert_eq!(ids, ["OPS-1", "", "OPS-2"]);
+        assert_eq!(
+            app.notice.as_deref(),
+            Some("Created OPS-2 https://acme.atlassian.net/browse/OPS-2")
+        );
+
+        // What Jira made of it replaces it, without moving the selection
+        let Command::Batch(commands) = command else {
+            panic!("the created issue isn't fetched");
+        };
+        assert!(commands.contains(&Command::FetchIssue { key: "OPS-2".to_string() }));
+        app.select_issue(0);
+        update(&mut app, Msg::IssueFetched {
+            key: "OPS-2".to_string(),
+            result: Ok(FakeClient::issue("OPS-2", "Rotate keys", "To Do")),
+        });
+        assert_eq!(app.issues.len(), 3);
+        assert_eq!(app.issues[2].pending, None);
+        assert_eq!(app.issues[2].status.as_ref().unwrap().name, "To Do");
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-1");
+
+        // A failed one stays listed as it was typed, to retry from the failures view
+        update(&mut app, Msg::IssueCreated {
+            pending: first,
+            issue: issue.clone(),
+            result: Err(Error::Network("timed out".to_string())),
+        });
+        assert!(app.issues[1].id.is_empty());
+        assert!(
+            app.error
+                .as_deref()
+                .unwrap()
+                .starts_with("Failed to create")
+        );
+        app.view = View::Failures;
+        let retry = update(&mut app, Msg::Key(KeyEvent::from(KeyCode::Enter)));
+        assert_eq!(retry, Command::CreateIssue { pending: first, issue });
+    }
+
+    #[test]
+    fn creates_issues_of_the_configured_type() {
+        let mut app = App::new(vec![Issue::from_jira(&FakeClient::issue("OPS-1", "", "To Do"))]);
+        app.apply_config(&Config {
+            issue_type: "Story".to_string(),
+            ..Config::default()
+        });
+        let Command::CreateIssue { issue, .. } = type_summary(&mut app, "Rotate keys") else {
+            panic!("the issue wasn't created");
+        };
+        assert_eq!(issue.issue_type, "Story");
+    }
+
+    #[test]
+    fn issues_need_a_project() {
+        let mut app = App::new(Vec::new());
+        let command = type_summary(&mut app, "Rotate keys");
+        assert!(!matches!(command, Command::CreateIssue { .. }));
+        assert_eq!(app.issues.len(), 1);
+        assert_eq!(
+            app.error.as_deref(),
+            Some("No project to create \"Rotate keys\" in; switch to one with P")
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Handing attachments and links over to the system's default application, text to the
+//! user's editor or the clipboard, and commands to the shell, and taking images from the
+//! clipboard.
+
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::{self, Stdio},
+};
+
+use jira_v3_openapi::models::IssueBean;
+use tokio::io::AsyncWriteExt;
+
+use crate::{error::Result, jira::JiraClient, ui::issue::Attachment};
+
+/// Where an attachment is kept once downloaded, so it is only fetched once.
+fn cached_path(attachment: &Attachment) -> io::Result<PathBuf> {
+    let cache = dirs::cache_dir()
+        .ok_or_else(|| io::Error::other("no cache directory to download attachments to"))?;
+    // The filename comes from the server, so it must not escape the directory
+    let filename = attachment.filename.replace(['/', '\\'], "_");
+    let filename = match filename.as_str() {
+        "" | "." | ".." => "attachment".to_string(),
+        _ => filename,
+    };
+    Ok(cache
+        .join("jira-tui")
+        .join("attachments")
+        .join(&attachment.id)
+        .join(filename))
+}
+
+/// Downloads an attachment unless it already was, returning where it is stored.
+pub(super) async fn download(client: &dyn JiraClient, attachment: &Attachment) -> Result<PathBuf> {
+    let path = cached_path(attachment)?;
+    if tokio::fs::try_exists(&path).await? {
+        return Ok(path);
+    }
+    let content = client.download_attachment(&attachment.url).await?;
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    // Written under another name first, so an interrupted download isn't mistaken for a
+    // complete one next time
+    let partial = path.with_extension("part");
+    tokio::fs::write(&partial, content).await?;
+    tokio::fs::rename(&partial, &path).await?;
+    Ok(path)
+}
+
+/// Attaches the image on the clipboard to the issue `key` as `filename`, returning the issue
+/// with the new attachment.
+pub(super) async fn attach_clipboard_image(
+    client: &dyn JiraClient,
+    key: &str,
+    filename: &str,
+) -> Result<IssueBean> {
+    let image = paste_image().await?;
+    client.add_attachment(key, filename, image).await?;
+    client.get_issue(key).await
+}
+
+/// Reads the image on the clipboard as PNG: with `wl-paste` on Wayland, `xclip` on X11, and
+/// `pngpaste` on macOS, whose `pbpaste` only pastes text.
+async fn paste_image() -> io::Result<Vec<u8>> {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+        ("pngpaste", &["-"])
+    } else if env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-paste", &["--no-newline", "--type", "image/png"])
+    } else {
+        ("xclip", &["-selection", "clipboard", "-target", "image/png", "-out"])
+    };
+    // Nothing may reach the terminal, or it would garble the screen
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|e| io::Error::new(e.kind(), format!("can't run {program}: {e}")))?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(io::Error::other(format!("{program} found no image on the clipboard")));
+    }
+    Ok(output.stdout)
+}
+
+/// Opens a file or URL with the system's default application, waiting for the launcher to
+/// exit. Launchers of GUI applications return right away; terminal applications keep the
+/// terminal until they quit.
+pub(super) fn open_with_system_handler(target: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = process::Command::new("cmd");
+        // The empty argument is the window title `start` would otherwise take the target for
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        process::Command::new("xdg-open")
+    };
+    let status = command.arg(target).status()?;
+    if !status.success() {
+        let program = command.get_program().to_string_lossy().into_owned();
+        return Err(io::Error::other(format!("{program} failed to open {target} ({status})")));
+    }
+    Ok(())
+}
+
+/// Puts `text` on the clipboard of the terminal with the OSC 52 escape sequence, which works
+/// over SSH too. Terminals that don't support it ignore it.
+pub(super) fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()
+}
+
+/// Encodes `bytes` in standard, padded base64.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Lets the user edit `text` in their editor, `$VISUAL` or `$EDITOR`, returning what they
+/// saved.
+pub(super) fn edit_in_editor(text: &str) -> io::Result<String> {
+    let path = env::temp_dir().join(format!("jira-tui-{}.txt", process::id()));
+    fs::write(&path, text)?;
+    let edited = run_editor(&path).and_then(|()| fs::read_to_string(&path));
+    let _ = fs::remove_file(&path);
+    edited
+}
+
+/// A command running `script` with the system's shell, the way commands from the user's
+/// settings are run.
+pub(super) fn shell(script: &str) -> process::Command {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut command = process::Command::new(shell);
+    command.args([flag, script]);
+    command
+}
+
+/// Runs `script` with the shell in the background, with `env` added to its environment and
+/// `input` on its standard input, returning what it printed. Fails with the last line it
+/// printed to standard error if it does.
+pub(super) async fn run_shell(
+    script: &str,
+    env: Vec<(String, String)>,
+    input: &str,
+) -> io::Result<String> {
+    // Nothing may reach the terminal, or it would garble the screen
+    let mut child = tokio::process::Command::from(shell(script))
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Commands that don't read their input may exit before it is written, which is fine
+        let _ = stdin.write_all(input.as_bytes()).await;
+    }
+    let output = child.wait_with_output().await?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = match stderr.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => format!("{script:?} failed: {}", line.trim()),
+        None => format!("{script:?} failed ({})", output.status),
+    };
+    Err(io::Error::other(message))
+}
+
+/// Runs the user's editor on a file until it quits.
+fn run_editor(path: &std::path::Path) -> io::Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    // Editors are often set along with arguments, like `code --wait`
+    let mut command = if cfg!(windows) {
+        shell(&editor)
+    } else {
+        let mut command = shell(&format!("{editor} \"$1\""));
+        command.arg("sh");
+        command
+    };
+    let status = command.arg(path).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{editor} failed ({status})")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_path_stays_in_the_cache() {
+        let attachment = Attachment {
+            id: "30001".to_string(),
+            filename: "../../.bashrc".to_string(),
+            size: 0,
+            url: String::new(),
+        };
+        let Ok(path) = cached_path(&attachment) else {
+            return;
+        };
+        assert!(path.ends_with("jira-tui/attachments/30001/.._.._.bashrc"));
+    }
+
+    #[test]
+    fn encodes_base64() {
+        let encoded: Vec<String> = ["", "f", "fo", "foo", "OPS-1 ✓"]
+            .iter()
+            .map(|text| base64(text.as_bytes()))
+            .collect();
+        assert_eq!(encoded, ["", "Zg==", "Zm8=", "Zm9v", "T1BTLTEg4pyT"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_shell_commands() {
+        let env = vec![("JIRA_KEY".to_string(), "OPS-1".to_string())];
+        let output = run_shell("read input && echo \"$JIRA_KEY $input\"", env.clone(), "{}\n")
+            .await
+            .unwrap();
+        assert_eq!(output, "OPS-1 {}\n");
+        let err = run_shell("echo \"no $JIRA_KEY\" >&2; exit 3", env, "")
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "\"echo \\\"no $JIRA_KEY\\\" >&2; exit 3\" failed: no OPS-1");
+    }
+}
@@ -0,0 +1,189 @@
+//! Keeping track of the requests to Jira running in the background, so the footer can tell
+//! what the app is waiting for.
+
+use std::time::{Duration, Instant};
+
+use super::{Command, Msg};
+use crate::jira::FetchEvent;
+
+/// A request to Jira started by a [`Command`], finished by the [`Msg`] carrying its result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// Fetching the result pages of a search.
+    Search,
+    History(String),
+    Children(String),
+    Projects,
+    Priorities,
+    Myself,
+    Transitions(String),
+    Watchers(String),
+    /// Building the report of this sprint.
+    SprintReport(String),
+    /// Adding a watcher to this issue, or taking one off.
+    Watch(String),
+    /// Creating an issue with this summary.
+    Create(String),
+    Versions(String),
+    Components(String),
+    Issue(String),
+    /// Downloading the attachment with this file name.
+    Download(String),
+    /// Attaching an image to this issue.
+    Attach(String),
+    /// Saving the changes to this many issues.
+    BulkApply(usize),
+}
+
+impl Operation {
+    /// What the operation is doing, for the footer.
+    pub fn label(&self) -> String {
+        match self {
+            Operation::Search => "searching".to_string(),
+            Operation::History(key) => format!("loading history of {key}"),
+            Operation::Children(key) => format!("loading children of {key}"),
+            Operation::Projects => "loading projects".to_string(),
+            Operation::Priorities => "loading priorities".to_string(),
+            Operation::Myself => "loading your account".to_string(),
+            Operation::Transitions(key) => format!("loading the transitions of {key}"),
+            Operation::Watchers(key) => format!("loading the watchers of {key}"),
+            Operation::Watch(key) => format!("changing the watchers of {key}"),
+            Operation::Create(summary) => format!("creating \"{summary}\""),
+            Operation::SprintReport(sprint) => format!("building the report of {sprint}"),
+            Operation::Versions(project) => format!("loading {project} versions"),
+            Operation::Components(project) => format!("loading {project} components"),
+            Operation::Issue(key) => format!("loading {key}"),
+            Operation::Download(filename) => format!("downloading {filename}"),
+            Operation::Attach(key) => format!("attaching image to {key}"),
+            Operation::BulkApply(1) => "saving 1 issue".to_string(),
+            Operation::BulkApply(count) => format!("saving {count} issues"),
+        }
+    }
+
+    /// The operation `command` starts, if it's a request to Jira.
+    fn started_by(command: &Command) -> Option<Self> {
+        Some(match command {
+            Command::FetchRemaining { .. } => Operation::Search,
+            Command::FetchHistory { key } => Operation::History(key.clone()),
+            Command::FetchChildren { key } => Operation::Children(key.clone()),
+            Command::FetchProjects => Operation::Projects,
+            Command::FetchPriorities => Operation::Priorities,
+            Command::FetchMyself => Operation::Myself,
+            Command::FetchTransitions { key } => Operation::Transitions(key.clone()),
+            Command::FetchWatchers { key } => Operation::Watchers(key.clone()),
+            Command::ChangeWatcher { key, .. } => Operation::Watch(key.clone()),
+            Command::CreateIssue { issue, .. } => Operation::Create(issue.summary.clone()),
+            Command::FetchSprintReport { sprint } => Operation::SprintReport(sprint.clone()),
+            Command::FetchVersions { project } => Operation::Versions(project.clone()),
+            Command::FetchComponents { project } => Operation::Components(project.clone()),
+            Command::FetchIssue { key } => Operation::Issue(key.clone()),
+            Command::DownloadAttachment { attachment } => {
+                Operation::Download(attachment.filename.clone())
+            }
+            Command::AttachClipboardImage { key, .. } => Operation::Attach(key.clone()),
+            Command::ApplyBulkEdit { changes } => Operation::BulkApply(changes.len()),
+            _ => return None,
+        })
+    }
+
+    /// The operation `msg` reports the end of.
+    fn finished_by(msg: &Msg) -> Option<Self> {
+        Some(match msg {
+            Msg::Fetch {
+                event: FetchEvent::Done | FetchEvent::Failed(_),
+                ..
+            } => Operation::Search,
+            Msg::History { key, .. } => Operation::History(key.clone()),
+            Msg::Children { key, .. } => Operation::Children(key.clone()),
+            Msg::Projects(_) => Operation::Projects,
+            Msg::Priorities(_) => Operation::Priorities,
+            Msg::Myself(_) => Operation::Myself,
+            Msg::Transitions { key, .. } => Operation::Transitions(key.clone()),
+            Msg::Watchers { key, .. } => Operation::Watchers(key.clone()),
+            Msg::WatcherChanged { key, .. } => Operation::Watch(key.clone()),
+            Msg::IssueCreated { issue, .. } => Operation::Create(issue.summary.clone()),
+            Msg::SprintReport { sprint, .. } => Operation::SprintReport(sprint.clone()),
+            Msg::Versions { project, .. } => Operation::Versions(project.clone()),
+            Msg::Components { project, .. } => Operation::Components(project.clone()),
+            Msg::IssueFetched { key, .. } => Operation::Issue(key.clone()),
+            Msg::AttachmentDownloaded { filename, .. } => Operation::Download(filename.clone()),
+            Msg::ImageAttached { key, .. } => Operation::Attach(key.clone()),
+            Msg::BulkApplied { results } => Operation::BulkApply(results.len()),
+            _ => return None,
+        })
+    }
+}
+
+/// The operations in flight, oldest first, with when they started. The same one can run more
+/// than once, like searches whose results are no longer wanted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Operations(Vec<(Operation, Instant)>);
+
+impl Operations {
+    /// Notes the operations `command` starts.
+    pub(super) fn start(&mut self, command: &Command) {
+        match command {
+            Command::Batch(commands) => commands.iter().for_each(|command| self.start(command)),
+            command => {
+                let started = Operation::started_by(command);
+                self.0
+                    .extend(started.map(|operation| (operation, Instant::now())));
+            }
+        }
+    }
+
+    /// Notes the end of the operation `msg` reports on, returning it with how long it took.
+    pub(super) fn finish(&mut self, msg: &Msg) -> Option<(Operation, Duration)> {
+        let operation = Operation::finished_by(msg)?;
+        let i = self
+            .0
+            .iter()
+            .position(|(running, _)| *running == operation)?;
+        let (operation, started) = self.0.remove(i);
+        Some((operation, started.elapsed()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The operation started last, which the footer names.
+    pub fn latest(&self) -> Option<&Operation> {
+        self.0.last().map(|(operation, _)| operation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn tracks_operations_until_their_results_arrive() {
+        let mut operations = Operations::default();
+        operations.start(&Command::Batch(vec![
+            Command::FetchHistory { key: "OPS-1".to_string() },
+            Command::SaveState(Default::default()),
+            Command::FetchProjects,
+        ]));
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations.latest().map(Operation::label).as_deref(), Some("loading projects"));
+
+        operations.finish(&Msg::Projects(Err(Error::Network("timeout".to_string()))));
+        let unknown = operations.finish(&Msg::History {
+            key: "OPS-2".to_string(),
+            result: Ok(Vec::new()),
+        });
+        assert!(unknown.is_none());
+        assert_eq!(operations.latest(), Some(&Operation::History("OPS-1".to_string())));
+        operations.finish(&Msg::History {
+            key: "OPS-1".to_string(),
+            result: Ok(Vec::new()),
+        });
+        assert!(operations.is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Changes to issues waiting to be made in Jira. They are kept across restarts and sent again
+//! when they failed for lack of a connection, so going offline doesn't lose them.
+
+use std::time::Duration;
+
+use jira_v3_openapi::models::IssueBean;
+
+use super::{App, Command, bulk_edit, bulk_edit::BulkChange, failures};
+use crate::error::{Error, Result};
+
+/// How long to wait before sending the changes again after Jira couldn't be reached.
+pub const RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// The changes not made yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outbox {
+    /// Oldest first, with whether the change is being sent.
+    entries: Vec<(BulkChange, bool)>,
+    /// Whether [`Command::RetryOutbox`] is waiting to send the changes again.
+    retrying: bool,
+}
+
+impl Outbox {
+    /// The outbox holding the changes left over from the last run.
+    pub fn new(changes: Vec<BulkChange>) -> Self {
+        Self {
+            entries: changes.into_iter().map(|change| (change, false)).collect(),
+            retrying: false,
+        }
+    }
+
+    /// The changes, for saving them.
+    pub fn changes(&self) -> Vec<BulkChange> {
+        self.entries
+            .iter()
+            .map(|(change, _)| change.clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the issue `key` has changes that weren't made in Jira yet.
+    pub fn is_pending(&self, key: &str) -> bool {
+        self.entries.iter().any(|(change, _)| change.key == key)
+    }
+}
+
+/// Adds `changes` to the outbox and sends them.
+pub(super) fn queue(app: &mut App, changes: Vec<BulkChange>) -> Command {
+    app.outbox
+        .entries
+        .extend(changes.into_iter().map(|change| (change, false)));
+    Command::Batch(vec![Command::SaveState(app.ui_state()), send(app)])
+}
+
+/// Sends the changes that aren't being sent already.
+pub(super) fn send(app: &mut App) -> Command {
+    let changes: Vec<BulkChange> = app
+        .outbox
+        .entries
+        .iter_mut()
+        .filter(|(_, sending)| !*sending)
+        .map(|(change, sending)| {
+            *sending = true;
+            change.clone()
+        })
+        .collect();
+    if changes.is_empty() {
+        return Command::None;
+    }
+    Command::ApplyBulkEdit { changes }
+}
+
+/// Sends the changes again once [`RETRY_AFTER`] has passed.
+pub(super) fn retry(app: &mut App) -> Command {
+    app.outbox.retrying = false;
+    send(app)
+}
+
+/// Takes the changes that were made, or can't be, out of the outbox, logging the latter as
+/// failures. The ones that failed for lack of a connection stay, to be sent again.
+pub(super) fn applied(app: &mut App, results: Vec<(String, Result<IssueBean>)>) -> Command {
+    let mut finished = Vec::new();
+    let mut waiting = 0;
+    for (key, result) in results {
+        let entry = app
+            .outbox
+            .entries
+            .iter()
+            .position(|(change, sending)| *sending && change.key == key);
+        match (entry, result) {
+            (Some(i), Err(Error::Network(_))) => {
+                app.outbox.entries[i].1 = false;
+                waiting += 1;
+            }
+            (entry, result) => {
+                if let Some(i) = entry {
+                    let (change, _) = app.outbox.entries.remove(i);
+                    if let Err(e) = &result {
+                        failures::change_failed(app, change, e);
+                    }
+                }
+                finished.push((key, result));
+            }
+        }
+    }
+
+    let mut commands = vec![Command::SaveState(app.ui_state())];
+    if !finished.is_empty() {
+        commands.push(bulk_edit::applied(app, finished));
+    }
+    if waiting > 0 {
+        app.notice =
+            Some(format!("Jira can't be reached; {waiting} changes will be sent once it can"));
+        if !app.outbox.retrying {
+            app.outbox.retrying = true;
+            commands.push(Command::RetryOutbox);
+        }
+    }
+    Command::Batch(commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{jira::FakeClient, ui::issue::Issue};
+
+    fn change(key: &str) -> BulkChange {
+        BulkChange {
+            key: key.to_string(),
+            status: Some("Done".to_string()),
+            assignee: None,
+            points: None,
+            parent: None,
+            flagged: None,
+            priority: None,
+        }
+    }
+
+    #[test]
+    fn keeps_changes_until_they_are_made() {
+        let mut app = App::new(vec![Issue::new("Failover", "")]);
+        let Command::Batch(commands) = queue(&mut app, vec![change("OPS-1"), change("OPS-2")])
+        else {
+            panic!("expected the changes to be saved and sent");
+        };
+        assert!(matches!(&commands[0], Command::SaveState(state) if state.outbox.len() == 2));
+        assert!(matches!(&commands[1], Command::ApplyBulkEdit { changes } if changes.len() == 2));
+        assert!(app.outbox.is_pending("OPS-1"));
+        // Nothing is sent twice
+        assert_eq!(send(&mut app), Command::None);
+
+        let offline = Err(Error::Network("connection refused".to_string()));
+        let done = Ok(FakeClient::issue("OPS-2", "Backups", "Done"));
+        let Command::Batch(commands) =
+            applied(&mut app, vec![("OPS-1".to_string(), offline), ("OPS-2".to_string(), done)])
+        else {
+            panic!("expected the outbox to be saved");
+        };
+        assert!(commands.contains(&Command::RetryOutbox));
+        assert!(app.outbox.is_pending("OPS-1"));
+        assert!(!app.outbox.is_pending("OPS-2"));
+        assert_eq!(app.ui_state().outbox, [change("OPS-1")]);
+
+        assert!(
+            matches!(retry(&mut app), Command::ApplyBulkEdit { changes } if changes.len() == 1)
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Picking parents: the epic a new issue goes in, from the epics among the listed issues and
+//! those they are in, and the issue to move the selected one under.
+
+use itertools::Itertools;
+
+use super::{App, BulkChange, Command, OpenPicker, PickerPurpose, outbox};
+use crate::ui::{issue::IssueRef, picker::Picker};
+
+/// The choice that creates the issue outside any epic.
+const NO_EPIC: &str = "No epic";
+
+/// The choice that takes the issue out of its epic or parent.
+const NO_PARENT: &str = "No parent";
+
+/// The epics the listed issues are or are in, by key.
+fn known_epics(app: &App) -> Vec<IssueRef> {
+    app.issues
+        .iter()
+        .filter_map(|issue| issue.epic())
+        .filter(|epic| !epic.key.is_empty())
+        .unique_by(|epic| epic.key.clone())
+        .sorted_by(|a, b| a.key.cmp(&b.key))
+        .collect()
+}
+
+/// Asks which epic the issue being written goes in.
+pub(super) fn pick_epic(app: &mut App) {
+    let items = std::iter::once(NO_EPIC.to_string())
+        .chain(
+            known_epics(app)
+                .iter()
+                .map(|epic| format!("{}  {}", epic.key, epic.summary)),
+        )
+        .collect();
+    app.picker = Some(OpenPicker {
+        purpose: PickerPurpose::Epic,
+        picker: Picker::new("Create in epic", items),
+    });
+}
+
+/// Puts the issue being written in the picked epic.
+pub(super) fn epic_picked(app: &mut App, choice: &str) {
+    app.new_issue_parent = match choice.split_once("  ") {
+        _ if choice == NO_EPIC => None,
+        Some((key, summary)) => Some(IssueRef {
+            key: key.to_string(),
+            summary: summary.to_string(),
+        }),
+        None => return,
+    };
+}
+
+/// Asks which issue to move the selected one under: one of the listed epics or, to make it
+/// a subtask, one of the other listed issues.
+pub(super) fn pick_parent(app: &mut App) {
+    let Some(key) = app
+        .selected_issue()
+        .map(|issue| issue.id.clone())
+        .filter(|key| !key.is_empty())
+    else {
+        return;
+    };
+    let candidates = app
+        .visible_issues()
+        .filter(|issue| !issue.is_local() && issue.id != key && !issue.is_subtask())
+        .sorted_by_key(|issue| !issue.is_epic())
+        .map(|issue| format!("{}  {}", issue.id, issue.summary));
+    let items = std::iter::once(NO_PARENT.to_string())
+        .chain(candidates)
+        .collect();
+    app.picker = Some(OpenPicker {
+        purpose: PickerPurpose::Parent,
+        picker: Picker::new(format!("Move {key} under"), items),
+    });
+}
+
+/// Moves the selected issue under the picked one. Jira refuses what its issue types don't
+/// allow, like taking a subtask out of its parent.
+pub(super) fn parent_picked(app: &mut App, choice: &str) -> Command {
+    let Some(key) = app
+        .selected_issue()
+        .map(|issue| issue.id.clone())
+        .filter(|key| !key.is_empty())
+    else {
+        return Command::None;
+    };
+    let parent = match choice.split_whitespace().next() {
+        _ if choice == NO_PARENT => None,
+        Some(parent) => Some(parent.to_string()),
+        None => return Command::None,
+    };
+    app.notice = Some(match &parent {
+        Some(parent) => format!("Moving {key} under {parent}…"),
+        None => format!("Taking {key} out of its parent…"),
+    });
+    outbox::queue(app, vec![BulkChange {
+        key,
+        status: None,
+        assignee: None,
+        points: None,
+        parent: Some(parent),
+        flagged: None,
+        priority: None,
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::issue::Issue;
+
+    #[test]
+    fn picks_among_the_listed_epics() {
+        let issue = |key: &str, summary: &str, issue_type: &str| Issue {
+            id: key.to_string(),
+            issue_type: Some(issue_type.to_string()),
+            ..Issue::new(summary, "")
+        };
+        let mut child = issue("OPS-2", "Failover", "Task");
+        child.parent = Some(IssueRef {
+            key: "OPS-1".to_string(),
+            summary: "Resilience".to_string(),
+        });
+        let mut app = App::new(vec![
+            issue("OPS-9", "Cost cutting", "Epic"),
+            issue("OPS-1", "Resilience", "Epic"),
+            child,
+            issue("OPS-3", "Backups", "Task"),
+        ]);
+
+        pick_epic(&mut app);
+        let open = app.picker.take().unwrap();
+        assert_eq!(open.picker.items, [NO_EPIC, "OPS-1  Resilience", "OPS-9  Cost cutting"]);
+        epic_picked(&mut app, "OPS-9  Cost cutting");
+        assert_eq!(app.new_issue_parent.as_ref().map(|epic| epic.key.as_str()), Some("OPS-9"));
+        epic_picked(&mut app, NO_EPIC);
+        assert_eq!(app.new_issue_parent, None);
+    }
+
+    #[test]
+    fn moves_the_selected_issue_under_another() {
+        let issue = |key: &str, summary: &str, issue_type: &str| Issue {
+            id: key.to_string(),
+            issue_type: Some(issue_type.to_string()),
+            ..Issue::new(summary, "")
+        };
+        let mut app = App::new(vec![
+            issue("OPS-2", "Failover", "Task"),
+            issue("OPS-3", "Backups", "Task"),
+            issue("OPS-1", "Resilience", "Epic"),
+        ]);
+
+        pick_parent(&mut app);
+        let open = app.picker.take().unwrap();
+        assert_eq!(open.picker.title, "Move OPS-2 under");
+        assert_eq!(open.picker.items, [NO_PARENT, "OPS-1  Resilience", "OPS-3  Backups"]);
+        let Command::Batch(commands) = parent_picked(&mut app, "OPS-3  Backups") else {
+            panic!("the move isn't sent");
+        };
+        assert!(commands.contains(&Command::ApplyBulkEdit {
+            changes: vec![BulkChange {
+                key: "OPS-2".to_string(),
+                status: None,
+                assignee: None,
+                points: None,
+                parent: Some(Some("OPS-3".to_string())),
+                flagged: None,
+                priority: None,
+            }],
+        }));
+    }
+}
@@ -0,0 +1,110 @@
+//! Switching the project the search is scoped to, picked from the projects of the instance.
+
+use super::{
+    App, Command, Loadable, OpenPicker, PickerPurpose, View, components, releases,
+    update::start_search,
+};
+use crate::{
+    error::Result,
+    jira::{Project, jql},
+    ui::picker::Picker,
+};
+
+/// Asks which project to switch to, fetching the projects the first time.
+pub(super) fn open(app: &mut App) -> Command {
+    match &app.projects {
+        Some(Loadable::Loaded(projects)) => {
+            // The last one picked comes first, so switching back is a single Enter
+            let mut items: Vec<String> = projects
+                .iter()
+                .map(|project| format!("{}  {}", project.key, project.name))
+                .collect();
+            if let Some(i) = projects
+                .iter()
+                .position(|project| Some(&project.key) == app.last_project.as_ref())
+            {
+                let last = items.remove(i);
+                items.insert(0, last);
+            }
+            app.picker = Some(OpenPicker {
+                purpose: PickerPurpose::Project,
+                picker: Picker::new("Switch to project", items),
+            });
+            Command::None
+        }
+        Some(Loadable::Loading) => Command::None,
+        Some(Loadable::Failed(_)) | None => {
+            app.projects = Some(Loadable::Loading);
+            app.notice = Some("Loading projects…".to_string());
+            Command::FetchProjects
+        }
+    }
+}
+
+/// Shows the picker once the projects arrived, or reports why they didn't. Failures are
+/// fetched again the next time.
+pub(super) fn loaded(app: &mut App, result: Result<Vec<Project>>) -> Command {
+    match result {
+        Ok(projects) => {
+            app.projects = Some(Loadable::Loaded(projects));
+            open(app)
+        }
+        Err(e) => {
+            app.error = Some(format!("Failed to load projects: {e}"));
+            app.projects = Some(Loadable::Failed(e.to_string()));
+            Command::None
+        }
+    }
+}
+
+/// Scopes the search to the picked project, remembering it for next time. The releases and
+/// components views move to it as well.
+pub(super) fn picked(app: &mut App, choice: &str) -> Command {
+    let Some(key) = choice.split_whitespace().next().map(str::to_string) else {
+        return Command::None;
+    };
+    app.last_project = Some(key.clone());
+    let search = start_search(app, jql::with_project(&app.jql, &key));
+    app.set_view_project(&key);
+    let view = match app.view {
+        View::Releases => releases::show(app),
+        View::Components => components::show(app),
+        _ => Command::None,
+    };
+    Command::Batch(vec![Command::SaveState(app.ui_state()), search, view])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::issue::Issue;
+
+    fn project(key: &str, name: &str) -> Project {
+        Project {
+            key: key.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn picks_a_project_to_search_in() {
+        let mut app = App::new(vec![Issue::new("Failover", "")]);
+        app.jql = "project = OPS ORDER BY updated DESC".to_string();
+        app.last_project = Some("OPS".to_string());
+
+        assert_eq!(open(&mut app), Command::FetchProjects);
+        let projects = vec![project("DATA", "Data Platform"), project("OPS", "Operations")];
+        assert_eq!(loaded(&mut app, Ok(projects)), Command::None);
+        let picker = &app.picker.as_ref().unwrap().picker;
+        assert_eq!(picker.items, ["OPS  Operations", "DATA  Data Platform"]);
+
+        let Command::Batch(commands) = picked(&mut app, "DATA  Data Platform") else {
+            panic!("expected a new search");
+        };
+        assert_eq!(app.jql, "project = \"DATA\" ORDER BY updated DESC");
+        assert_eq!(app.last_project.as_deref(), Some("DATA"));
+        assert!(matches!(commands[0], Command::SaveState(ref state)
+            if state.last_project.as_deref() == Some("DATA")));
+        assert!(matches!(commands[1], Command::FetchRemaining { .. }));
+    }
+}
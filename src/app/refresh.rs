@@ -0,0 +1,158 @@
+//! Searching again in the background every so often, for users without webhooks. The results
+//! replace the listed issues in place, keeping the selection, unlike a new search.
+
+use chrono::Local;
+use jira_v3_openapi::models::IssueBean;
+
+use super::{App, Command, attention, hooks, update::forget_cached};
+use crate::{
+    error::Result,
+    ui::{input::InputMode, issue::Issue},
+};
+
+/// Refreshes the list once the interval from the config has passed, unless it says not to.
+pub(super) fn schedule(app: &mut App) -> Command {
+    match app.polling.refresh_interval() {
+        Some(after) => {
+            app.refreshing = true;
+            Command::ScheduleRefresh { after }
+        }
+        None => {
+            app.refreshing = false;
+            Command::None
+        }
+    }
+}
+
+/// Starts refreshing if the reloaded config asks for it, unless it's scheduled already.
+pub(super) fn reconfigured(app: &mut App) -> Command {
+    if app.refreshing {
+        return Command::None;
+    }
+    schedule(app)
+}
+
+/// Searches again, unless the list is busy: still fetching, or being typed into.
+pub(super) fn due(app: &mut App) -> Command {
+    if app.polling.refresh_interval().is_none() {
+        app.refreshing = false;
+        return Command::None;
+    }
+    if app.fetch_progress.is_some() || app.input_mode != InputMode::Normal {
+        return schedule(app);
+    }
+    let refresh = Command::Refresh {
+        jql: app.jql.clone(),
+        generation: app.search_generation,
+    };
+    match attention::know_me(app) {
+        Command::None => refresh,
+        fetch => Command::Batch(vec![fetch, refresh]),
+    }
+}
+
+/// Replaces the listed issues with the results of the refresh, unless another search started
+/// in the meantime, and schedules the next one.
+pub(super) fn refreshed(
+    app: &mut App,
+    generation: usize,
+    result: Result<Vec<IssueBean>>,
+) -> Command {
+    // A failed refresh shows in the status bar as being offline, and the next may go better
+    let mut commands = match result {
+        Ok(found) if generation == app.search_generation => replace(app, &found),
+        _ => Vec::new(),
+    };
+    commands.push(schedule(app));
+    Command::Batch(commands)
+}
+
+/// Lists `found` instead of the issues from Jira, along with the ones only created locally,
+/// and runs the hooks of what changed.
+fn replace(app: &mut App, found: &[IssueBean]) -> Vec<Command> {
+    let selected = app.selected_issue().map(|issue| issue.id.clone());
+    let mut hooks = Vec::new();
+    let mut issues: Vec<Issue> = found.iter().map(Issue::from_jira).collect();
+    hooks.push(attention::refreshed(app, &issues));
+    for issue in &issues {
+        let Some(old) = app.issues.iter().find(|listed| listed.id == issue.id) else {
+            continue;
+        };
+        if old != issue {
+            hooks.push(hooks::issue_changed(app, old, issue));
+            forget_cached(app, issue);
+        }
+    }
+    issues.extend(app.issues.drain(..).filter(Issue::is_local));
+    app.issues = issues;
+    app.result_total = Some(found.len());
+
+    // Indices changed, so the selection is found again by key
+    app.visible.clear();
+    app.refresh_visible();
+    if let Some(i) = app
+        .issues
+        .iter()
+        .position(|issue| Some(&issue.id) == selected.as_ref())
+    {
+        app.select_issue(i);
+    }
+    app.connection.last_refresh = Some(Local::now().fixed_offset());
+    hooks.push(hooks::refreshed(app));
+    hooks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        jira::FakeClient,
+    };
+
+    #[test]
+    fn replaces_the_list_keeping_the_selection() {
+        let issues = ["OPS-1", "OPS-2", "OPS-3"]
+            .map(|key| Issue::from_jira(&FakeClient::issue(key, "", "To Do")));
+        let mut app = App::new(issues.to_vec());
+        app.select_issue(1);
+        assert_eq!(schedule(&mut app), Command::None);
+        app.polling.refresh_every = 60;
+        assert_eq!(schedule(&mut app), Command::ScheduleRefresh { after: Duration::from_secs(60) });
+
+        let generation = app.search_generation;
+        assert_eq!(update(&mut app, Msg::RefreshDue), Command::Refresh {
+            jql: app.jql.clone(),
+            generation
+        });
+        let found = vec![
+            FakeClient::issue("OPS-4", "", "To Do"),
+            FakeClient::issue("OPS-2", "Renamed", "In Progress"),
+        ];
+        update(&mut app, Msg::Refreshed {
+            generation,
+            result: Ok(found.clone()),
+        });
+        let keys: Vec<&str> = app.issues.iter().map(|issue| issue.id.as_str()).collect();
+        assert_eq!(keys, ["OPS-4", "OPS-2"]);
+        assert_eq!(app.selected_issue().unwrap().summary, "Renamed");
+        assert!(app.connection.last_refresh.is_some());
+
+        // Results of a refresh from before another search are of no use
+        app.search_generation += 1;
+        let command = update(&mut app, Msg::Refreshed {
+            generation,
+            result: Ok(found[..1].to_vec()),
+        });
+        assert_eq!(app.issues.len(), 2);
+        assert_eq!(
+            command,
+            Command::Batch(vec![Command::ScheduleRefresh { after: Duration::from_secs(60) }])
+        );
+        app.polling.metered = true;
+        assert_eq!(update(&mut app, Msg::RefreshDue), Command::None);
+        assert!(!app.refreshing);
+    }
+}
@@ -0,0 +1,111 @@
+//! The releases view: the versions of a project with how far along each one is, and the
+//! issues to be released in one of them.
+
+use super::{App, Command, Loadable, View, move_row_cursor, update::start_search};
+use crate::{
+    jira::{Version, jql},
+    ui::input::NormalModeAction,
+};
+
+/// Shows the releases of the selected issue's project, fetching them the first time. Asking
+/// again while they are shown fetches them anew.
+pub(super) fn show(app: &mut App) -> Command {
+    let Some(project) = app.current_project() else {
+        app.notice = Some("Select an issue to see the releases of its project".to_string());
+        return Command::None;
+    };
+
+    let refresh = app.view == View::Releases;
+    app.view = View::Releases;
+    app.set_view_project(&project);
+    if !refresh && app.versions.contains_key(&project) {
+        return Command::None;
+    }
+    app.versions.insert(project.clone(), Loadable::Loading);
+    Command::FetchVersions { project }
+}
+
+/// The versions shown, once they arrived.
+pub fn versions(app: &App) -> &[Version] {
+    let versions = app
+        .view_project
+        .as_ref()
+        .and_then(|project| app.versions.get(project));
+    match versions {
+        Some(Loadable::Loaded(versions)) => versions,
+        _ => &[],
+    }
+}
+
+/// Handles the keys that act on the versions instead of the issues. Returns `None` if
+/// `action` isn't one of them.
+pub(super) fn handle(app: &mut App, action: NormalModeAction) -> Option<Command> {
+    if action == NormalModeAction::OpenItem {
+        return Some(show_issues(app));
+    }
+    let len = versions(app).len();
+    move_row_cursor(&mut app.version_cursor, len, action, app.wrap_around).then_some(Command::None)
+}
+
+/// Searches for the issues of the selected version and lists them.
+fn show_issues(app: &mut App) -> Command {
+    let (Some(project), Some(version)) =
+        (app.view_project.clone(), versions(app).get(app.version_cursor).cloned())
+    else {
+        return Command::None;
+    };
+    app.view = View::List;
+    let jql = format!(
+        "project = {} AND fixVersion = {} ORDER BY Rank",
+        jql::quote(&project),
+        jql::quote(&version.name)
+    );
+    start_search(app, jql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        ui::issue::Issue,
+    };
+
+    fn version(name: &str) -> Version {
+        Version {
+            id: name.to_string(),
+            name: name.to_string(),
+            released: false,
+            release_date: None,
+            done: 0,
+            issues: 0,
+        }
+    }
+
+    #[test]
+    fn lists_the_issues_of_a_version() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            ..Issue::new("Failover", "")
+        }]);
+        assert_eq!(show(&mut app), Command::FetchVersions { project: "OPS".to_string() });
+        assert_eq!(app.view, View::Releases);
+
+        update(&mut app, Msg::Versions {
+            project: "OPS".to_string(),
+            result: Ok(vec![version("2.1"), version("2.0")]),
+        });
+        assert_eq!(versions(&app).len(), 2);
+        handle(&mut app, NormalModeAction::Jump(5));
+        assert_eq!(app.version_cursor, 1);
+
+        let command = handle(&mut app, NormalModeAction::OpenItem);
+        assert!(matches!(command, Some(Command::FetchRemaining { .. })));
+        assert_eq!(app.view, View::List);
+        assert_eq!(app.jql, "project = \"OPS\" AND fixVersion = \"2.0\" ORDER BY Rank");
+
+        // Cached until asked for again in the view
+        assert_eq!(show(&mut app), Command::None);
+        assert_eq!(show(&mut app), Command::FetchVersions { project: "OPS".to_string() });
+    }
+}
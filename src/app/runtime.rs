@@ -0,0 +1,555 @@
+use std::{
+    io,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use crossterm::{
+    cursor::Show,
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, MouseEventKind},
+    execute,
+    style::Print,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{Terminal, backend::Backend};
+use tokio::{
+    net::TcpListener,
+    sync::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        watch,
+    },
+    time::MissedTickBehavior,
+};
+
+use super::{App, Command, Msg, bulk_edit, components, open, outbox, update};
+use crate::{
+    config::Config,
+    error::Error,
+    jira::{self, JiraClient, jql, webhook},
+    report,
+    state::UiState,
+};
+
+/// How often animated parts of the UI (like the fetch spinner) are redrawn.
+const ANIMATION_TICK: Duration = Duration::from_millis(100);
+
+/// How long the input thread waits for an event before checking whether it should pause.
+const INPUT_POLL: Duration = Duration::from_millis(50);
+
+/// Executes [`Command`]s, feeding their results back into the event loop as [`Msg`]s.
+struct Runtime {
+    tx: UnboundedSender<Msg>,
+    client: Arc<dyn JiraClient>,
+    input: Arc<InputGate>,
+    /// The latest state to save, written by [`save_ui_states`].
+    ui_state: watch::Sender<UiState>,
+}
+
+/// Stops the input thread from reading while another program has the terminal, so the
+/// keys typed into that program don't end up in the app.
+#[derive(Default)]
+struct InputGate {
+    paused: AtomicBool,
+    /// Held by the input thread while it reads.
+    reading: Mutex<()>,
+}
+
+/// Keeps the input thread paused until dropped.
+struct InputPause<'a> {
+    gate: &'a InputGate,
+    _reading: MutexGuard<'a, ()>,
+}
+
+impl InputGate {
+    /// Pauses the input thread, waiting for a read in progress to time out.
+    fn pause(&self) -> InputPause<'_> {
+        self.paused.store(true, Ordering::SeqCst);
+        InputPause {
+            gate: self,
+            _reading: self.reading.lock().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for InputPause<'_> {
+    fn drop(&mut self) {
+        self.gate.paused.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Runtime {
+    /// Starts a command. Returns `false` if the app should quit.
+    fn execute<B: Backend>(&mut self, command: Command, terminal: &mut Terminal<B>) -> bool {
+        match command {
+            Command::None => {}
+            Command::Quit => return false,
+            Command::Batch(commands) => {
+                for command in commands {
+                    if !self.execute(command, terminal) {
+                        return false;
+                    }
+                }
+            }
+            Command::FetchRemaining { jql, generation, from } => {
+                let (fetch_tx, mut fetch_rx) = mpsc::unbounded_channel();
+                tokio::spawn(jira::stream_issues(self.client.clone(), jql, from, fetch_tx));
+
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = fetch_rx.recv().await {
+                        if tx.send(Msg::Fetch { generation, event }).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            Command::FetchHistory { key } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = client.get_history(&key).await;
+                    let _ = tx.send(Msg::History { key, result });
+                });
+            }
+            Command::FetchChildren { key } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let jql = format!("parent = {} ORDER BY Rank", jql::quote(&key));
+                    let result = jira::search_all(client.as_ref(), &jql).await;
+                    let _ = tx.send(Msg::Children { key, result });
+                });
+            }
+            Command::FetchProjects => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let _ = tx.send(Msg::Projects(client.get_projects().await));
+                });
+            }
+            Command::FetchPriorities => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let _ = tx.send(Msg::Priorities(client.get_priorities().await));
+                });
+            }
+            Command::Bell => {
+                let _ = execute!(io::stdout(), Print('\x07'));
+            }
+            Command::FetchMyself => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let _ = tx.send(Msg::Myself(client.myself().await));
+                });
+            }
+            Command::FetchTransitions { key } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = client.get_transitions(&key).await;
+                    let _ = tx.send(Msg::Transitions { key, result });
+                });
+            }
+            Command::FetchWatchers { key } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = client.get_watchers(&key).await;
+                    let _ = tx.send(Msg::Watchers { key, result });
+                });
+            }
+            Command::ChangeWatcher { key, user, watching } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = match watching {
+                        true => client.add_watcher(&key, &user.account_id).await,
+                        false => client.remove_watcher(&key, &user.account_id).await,
+                    };
+                    let _ = tx.send(Msg::WatcherChanged { key, user, watching, result });
+                });
+            }
+            Command::CreateIssue { pending, issue } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = client.create_issue(&issue).await;
+                    let _ = tx.send(Msg::IssueCreated { pending, issue, result });
+                });
+            }
+            Command::FetchSprintReport { sprint } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = report::sprint_report(client.as_ref(), &sprint).await;
+                    let _ = tx.send(Msg::SprintReport { sprint, result });
+                });
+            }
+            Command::SearchUsers { query, issue } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = match &issue {
+                        Some(key) => client.find_assignable_users(key, &query).await,
+                        None => client.find_users(&query).await,
+                    };
+                    let _ = tx.send(Msg::UsersFound { query, result });
+                });
+            }
+            Command::FetchVersions { project } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = client.get_versions(&project).await;
+                    let _ = tx.send(Msg::Versions { project, result });
+                });
+            }
+            Command::FetchComponents { project } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = components::fetch(client.as_ref(), &project).await;
+                    let _ = tx.send(Msg::Components { project, result });
+                });
+            }
+            Command::FetchIssue { key } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = client.get_issue(&key).await;
+                    let _ = tx.send(Msg::IssueFetched { key, result });
+                });
+            }
+            Command::DownloadAttachment { attachment } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = open::download(client.as_ref(), &attachment).await;
+                    let _ = tx.send(Msg::AttachmentDownloaded {
+                        filename: attachment.filename,
+                        result,
+                    });
+                });
+            }
+            Command::AttachClipboardImage { key, filename } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result =
+                        open::attach_clipboard_image(client.as_ref(), &key, &filename).await;
+                    let _ = tx.send(Msg::ImageAttached { key, filename, result });
+                });
+            }
+            Command::ListenForWebhooks(config) => {
+                let (event_tx, mut events) = mpsc::unbounded_channel();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let served = match TcpListener::bind(&config.listen).await {
+                        Ok(listener) => webhook::serve(listener, config.token, event_tx).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = served {
+                        let _ = tx.send(Msg::Error(Error::Config(format!(
+                            "can't listen for webhooks on {}: {e}",
+                            config.listen
+                        ))));
+                    }
+                });
+
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = events.recv().await {
+                        if tx.send(Msg::Webhook(event)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            Command::Open { target } => self.open(&target, terminal),
+            Command::Copy { text } => {
+                if let Err(e) = open::copy_to_clipboard(&text) {
+                    let _ = self.tx.send(Msg::Error(e.into()));
+                }
+            }
+            Command::OpenInBrowser { key } => self.open(&self.client.browse_url(&key), terminal),
+            Command::BulkEdit { rows } => {
+                let text = bulk_edit::render(&rows);
+                let result = self
+                    .suspended(terminal, || open::edit_in_editor(&text))
+                    .map_err(Into::into);
+                let _ = self.tx.send(Msg::BulkEdited { rows, result });
+            }
+            Command::ApplyBulkEdit { changes } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let results = bulk_edit::apply_all(client.as_ref(), changes).await;
+                    let _ = tx.send(Msg::BulkApplied { results });
+                });
+            }
+            Command::RetryOutbox => {
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(outbox::RETRY_AFTER).await;
+                    let _ = tx.send(Msg::RetryOutbox);
+                });
+            }
+            Command::CheckAlerts { searches, after } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(after).await;
+                    let mut results = Vec::new();
+                    for (name, jql) in searches {
+                        let keys = jira::search_all(client.as_ref(), &jql).await.map(|issues| {
+                            issues.into_iter().filter_map(|issue| issue.key).collect()
+                        });
+                        results.push((name, keys));
+                    }
+                    let _ = tx.send(Msg::AlertsChecked(results));
+                });
+            }
+            Command::ScheduleRefresh { after } => {
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(after).await;
+                    let _ = tx.send(Msg::RefreshDue);
+                });
+            }
+            Command::Refresh { jql, generation } => {
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = jira::search_all(client.as_ref(), &jql).await;
+                    let _ = tx.send(Msg::Refreshed { generation, result });
+                });
+            }
+            Command::ReloadConfig { path } => {
+                let result = match path {
+                    Some(path) => Config::load_from(&path),
+                    None => Config::load(),
+                };
+                let _ = self.tx.send(Msg::ConfigReloaded(result));
+            }
+            Command::SignIn { api_token } => {
+                // Requests already running finish with the old token
+                if let Some(client) = self.client.with_api_token(&api_token) {
+                    self.client = client;
+                }
+            }
+            Command::RunHook { command, mut env, input } => {
+                self.add_url(&mut env);
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = open::run_shell(&command, env, &input).await {
+                        let _ =
+                            tx.send(Msg::Error(Error::Io(io::Error::other(format!("hook {e}")))));
+                    }
+                });
+            }
+            Command::RunAction { name, command, mut env, input, popup } => {
+                self.add_url(&mut env);
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let result = open::run_shell(&command, env, &input)
+                        .await
+                        .map_err(Into::into);
+                    let _ = tx.send(Msg::ActionFinished { name, popup, result });
+                });
+            }
+            Command::SaveState(state) => {
+                self.ui_state.send_replace(state);
+            }
+        }
+        true
+    }
+
+    /// Adds the link to the issue of `JIRA_KEY` to the environment of a command as `JIRA_URL`.
+    fn add_url(&self, env: &mut Vec<(String, String)>) {
+        let key = env.iter().find(|(name, _)| name == "JIRA_KEY");
+        if let Some((_, key)) = key.filter(|(_, key)| !key.is_empty()) {
+            env.push(("JIRA_URL".to_string(), self.client.browse_url(key)));
+        }
+    }
+
+    /// Opens `target` with the system's default application, reporting failures as errors.
+    fn open<B: Backend>(&self, target: &str, terminal: &mut Terminal<B>) {
+        let result = self.suspended(terminal, || open::open_with_system_handler(target));
+        if let Err(e) = result {
+            let _ = self.tx.send(Msg::Error(e.into()));
+        }
+    }
+
+    /// Hands the terminal to another program for the duration of `run`, then restores the UI.
+    fn suspended<B: Backend, T>(
+        &self,
+        terminal: &mut Terminal<B>,
+        run: impl FnOnce() -> io::Result<T>,
+    ) -> io::Result<T> {
+        let _pause = self.input.pause();
+        disable_raw_mode()?;
+        execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+
+        let result = run();
+
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        // Whatever the program left on screen is unknown to ratatui's diffing
+        terminal.clear()?;
+        result
+    }
+}
+
+/// Runs the app until it quits, starting with the `init` command.
+///
+/// The screen is only redrawn after a message was handled, so an idle app sleeps.
+pub async fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut app: App,
+    client: Arc<dyn JiraClient>,
+    init: Command,
+) -> io::Result<()> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (input_tx, input_rx) = mpsc::unbounded_channel();
+    let input = Arc::new(InputGate::default());
+    let gate = input.clone();
+    std::thread::spawn(move || forward_terminal_events(input_tx, &gate));
+
+    tokio::spawn(forward_shutdown_signal(tx.clone()));
+
+    let (ui_state, ui_states) = watch::channel(app.ui_state());
+    let saver = tokio::spawn(save_ui_states(ui_states, tx.clone()));
+
+    let mut runtime = Runtime { tx, client, input, ui_state };
+    let result = handle_messages(terminal, &mut app, &mut runtime, init, rx, input_rx).await;
+
+    // Drafts and changes waiting in the outbox are only on disk once the last save finished,
+    // which closing the channel lets the saver get to
+    runtime.ui_state.send_replace(app.ui_state());
+    drop(runtime);
+    let _ = saver.await;
+    result
+}
+
+/// Handles messages until the app quits, starting with the `init` command.
+async fn handle_messages<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    runtime: &mut Runtime,
+    init: Command,
+    mut rx: UnboundedReceiver<Msg>,
+    mut input_rx: UnboundedReceiver<io::Result<Msg>>,
+) -> io::Result<()> {
+    if !runtime.execute(init, terminal) {
+        return Ok(());
+    }
+
+    let mut ticker = tokio::time::interval(ANIMATION_TICK);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        terminal.draw(|f| crate::ui::render_ui(f, app))?;
+
+        // Sleep until something happens; the ticker only runs while the UI is animating.
+        let msg = tokio::select! {
+            Some(msg) = rx.recv() => msg,
+            Some(msg) = input_rx.recv() => msg?,
+            _ = ticker.tick(), if app.is_animating() => Msg::Tick,
+            else => return Ok(()),
+        };
+
+        if !runtime.execute(update(app, msg), terminal) {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads terminal events on a dedicated thread, since crossterm's reader is blocking.
+///
+/// Events that don't affect the screen (mouse motion and scrolling, focus changes) are
+/// dropped here so they don't cause redraws. Reading stops while `gate` is paused.
+fn forward_terminal_events(tx: UnboundedSender<io::Result<Msg>>, gate: &InputGate) {
+    loop {
+        if gate.is_paused() {
+            std::thread::sleep(INPUT_POLL);
+            continue;
+        }
+        let event = {
+            let _reading = gate.reading.lock().unwrap_or_else(|e| e.into_inner());
+            match event::poll(INPUT_POLL) {
+                Ok(true) => event::read(),
+                Ok(false) => continue,
+                Err(e) => Err(e),
+            }
+        };
+        let msg = match event {
+            Ok(Event::Key(key)) => Ok(Msg::Key(key)),
+            Ok(Event::Mouse(mouse))
+                if matches!(
+                    mouse.kind,
+                    MouseEventKind::Down(_) | MouseEventKind::Drag(_) | MouseEventKind::Up(_)
+                ) =>
+            {
+                Ok(Msg::Mouse(mouse))
+            }
+            Ok(Event::Resize(..)) => Ok(Msg::Resize),
+            Ok(_) => continue,
+            Err(e) => Err(e),
+        };
+        let failed = msg.is_err();
+        if tx.send(msg).is_err() || failed {
+            return;
+        }
+    }
+}
+
+/// Writes the UI state handed over by [`Command::SaveState`], one save at a time. States
+/// sent while a save runs are coalesced, so the last one always wins, even with drafts saved
+/// on every key press. Returns once the last state is saved after the sender is dropped.
+async fn save_ui_states(mut states: watch::Receiver<UiState>, tx: UnboundedSender<Msg>) {
+    while states.changed().await.is_ok() {
+        let state = states.borrow_and_update().clone();
+        if let Ok(Err(e)) = tokio::task::spawn_blocking(move || state.save()).await {
+            let _ = tx.send(Msg::Error(e));
+        }
+    }
+}
+
+/// Turns a termination signal into [`Msg::Shutdown`], so the app exits through its normal
+/// quit path and the terminal gets restored.
+async fn forward_shutdown_signal(tx: UnboundedSender<Msg>) {
+    if wait_for_shutdown_signal().await.is_ok() {
+        let _ = tx.send(Msg::Shutdown);
+    }
+}
+
+/// Waits for SIGINT, SIGTERM, or SIGHUP (the terminal was closed).
+#[cfg(unix)]
+async fn wait_for_shutdown_signal() -> io::Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut hangup = signal(SignalKind::hangup())?;
+    tokio::select! {
+        _ = interrupt.recv() => {}
+        _ = terminate.recv() => {}
+        _ = hangup.recv() => {}
+    }
+    Ok(())
+}
+
+/// Waits for Ctrl-C or the console window being closed.
+#[cfg(not(unix))]
+async fn wait_for_shutdown_signal() -> io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
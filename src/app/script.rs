@@ -0,0 +1,336 @@
+//! Columns and quick filters computed by small [Rhai](https://rhai.rs) scripts from the
+//! config, for what the built-in ones don't cover, like an issue's age in days.
+//!
+//! Scripts see the fields of the issue as variables (`key`, `summary`, `status`, `points`,
+//! `age_days`, ...) and every loaded issue as `issues`, an array of maps with the same
+//! fields, for sums like the points of the assignee.
+
+use chrono::{DateTime, Utc};
+use rhai::{AST, Array, Dynamic, Engine, Map, Scope};
+
+use crate::{
+    config::{ColumnConfig, FilterConfig},
+    ui::issue::Issue,
+};
+
+/// Stops scripts that loop forever from freezing the app.
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// A script compiled from the config, with the name it goes by.
+struct Compiled {
+    name: String,
+    ast: AST,
+}
+
+/// The configured scripts, compiled once when the config is loaded.
+pub struct Scripts {
+    engine: Engine,
+    /// By column, with nothing for the columns that show a field instead.
+    columns: Vec<Option<Compiled>>,
+    filters: Vec<Compiled>,
+}
+
+/// What the scripts made of the loaded issues, by index into them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Computed {
+    columns: Vec<Vec<String>>,
+    filters: Vec<Vec<bool>>,
+    /// The first script that failed, and why.
+    pub error: Option<String>,
+}
+
+impl Computed {
+    /// Text of the `column`th computed column for `issues[index]`.
+    pub fn column(&self, index: usize, column: usize) -> &str {
+        self.columns
+            .get(index)
+            .and_then(|columns| columns.get(column))
+            .map_or("", String::as_str)
+    }
+
+    /// Whether `issues[index]` passes the `filter`th script filter.
+    pub fn filter(&self, index: usize, filter: usize) -> bool {
+        self.filters
+            .get(index)
+            .and_then(|filters| filters.get(filter))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+impl Default for Scripts {
+    fn default() -> Self {
+        Self {
+            engine: engine(),
+            columns: Vec::new(),
+            filters: Vec::new(),
+        }
+    }
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        // Misspelled fields are reported when loading the config, not on every row
+        .set_strict_variables(true);
+    engine
+}
+
+impl Scripts {
+    /// Compiles the scripts of the configured columns and filters, or describes the first one
+    /// that doesn't.
+    pub fn compile(columns: &[ColumnConfig], filters: &[FilterConfig]) -> Result<Self, String> {
+        let engine = engine();
+        // Only the names are known yet. Constants would have their values folded into the
+        // scripts.
+        let mut scope = Scope::new();
+        for name in issue_map(&Issue::new("", ""), Utc::now()).keys() {
+            scope.push_dynamic(name.as_str(), Dynamic::UNIT);
+        }
+        scope.push_dynamic("issues", Dynamic::UNIT);
+        let compile = |kind: &str, name: &str, script: &str| {
+            let ast = engine
+                .compile_with_scope(&scope, script)
+                .map_err(|e| format!("{kind} {name:?}: {e}"))?;
+            Ok(Compiled { name: name.to_string(), ast })
+        };
+        let columns = columns
+            .iter()
+            .map(|column| {
+                column
+                    .script
+                    .as_ref()
+                    .map(|script| compile("column", &column.name, script))
+                    .transpose()
+            })
+            .collect::<Result<_, String>>()?;
+        let filters = filters
+            .iter()
+            .map(|filter| compile("filter", &filter.name, &filter.script))
+            .collect::<Result<_, String>>()?;
+        Ok(Self { engine, columns, filters })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.iter().all(Option::is_none) && self.filters.is_empty()
+    }
+
+    /// Names of the script filters, in the order of the config.
+    pub fn filter_names(&self) -> impl Iterator<Item = &str> {
+        self.filters.iter().map(|filter| filter.name.as_str())
+    }
+
+    /// Runs every script on every issue, as of `now`.
+    pub fn evaluate(&self, issues: &[Issue], now: DateTime<Utc>) -> Computed {
+        let mut computed = Computed::default();
+        if self.is_empty() {
+            return computed;
+        }
+        let maps: Vec<Map> = issues.iter().map(|issue| issue_map(issue, now)).collect();
+        // Shared, so that handing it to each issue's scripts doesn't copy it
+        let all = Dynamic::from_array(maps.iter().cloned().map(Dynamic::from_map).collect())
+            .into_shared();
+
+        for map in &maps {
+            let mut scope = scope(map, all.clone());
+            let fields = scope.len();
+            let mut run = |kind: &str, script: &Compiled| {
+                let result = self
+                    .engine
+                    .eval_ast_with_scope::<Dynamic>(&mut scope, &script.ast);
+                // Variables a script declared aren't for the next one
+                scope.rewind(fields);
+                if let Err(e) = &result {
+                    computed
+                        .error
+                        .get_or_insert_with(|| format!("{kind} {}: {e}", script.name));
+                }
+                result.ok()
+            };
+            let columns = self
+                .columns
+                .iter()
+                .map(|column| {
+                    let column = column.as_ref()?;
+                    run("column", column).map(display)
+                })
+                .map(Option::unwrap_or_default)
+                .collect();
+            let filters = self
+                .filters
+                .iter()
+                .map(|filter| {
+                    run("filter", filter).is_some_and(|value| value.as_bool().unwrap_or(false))
+                })
+                .collect();
+            computed.columns.push(columns);
+            computed.filters.push(filters);
+        }
+        computed
+    }
+}
+
+/// The fields of an issue scripts can use. Missing values are `()`.
+fn issue_map(issue: &Issue, now: DateTime<Utc>) -> Map {
+    let text =
+        |value: Option<&str>| value.map_or(Dynamic::UNIT, |value| Dynamic::from(value.to_string()));
+    let list = |values: &[String]| {
+        Dynamic::from_array(values.iter().cloned().map(Dynamic::from).collect())
+    };
+    let days = |date: Option<DateTime<Utc>>| {
+        date.map_or(Dynamic::UNIT, |date| Dynamic::from_int((now - date).num_days()))
+    };
+    let created = issue.created.map(|created| created.to_utc());
+    let updated = issue.updated.map(|updated| updated.to_utc());
+    let status = issue.status.as_ref();
+    [
+        ("key", Dynamic::from(issue.id.clone())),
+        ("summary", Dynamic::from(issue.summary.clone())),
+        ("type", text(issue.issue_type.as_deref())),
+        ("status", text(status.map(|status| status.name.as_str()))),
+        ("done", Dynamic::from_bool(issue.is_done())),
+        ("priority", text(issue.priority.as_ref().map(|priority| priority.as_str()))),
+        ("assignee", text(issue.assignee.as_deref())),
+        (
+            "points",
+            issue
+                .story_points
+                .map_or(Dynamic::UNIT, Dynamic::from_float),
+        ),
+        ("labels", list(&issue.labels)),
+        ("components", list(&issue.components)),
+        ("sprints", list(&issue.sprints)),
+        ("epic", text(issue.parent_epic.as_deref())),
+        ("created", text(created.map(|created| created.to_rfc3339()).as_deref())),
+        ("updated", text(updated.map(|updated| updated.to_rfc3339()).as_deref())),
+        ("due", text(issue.due.map(|due| due.to_string()).as_deref())),
+        ("age_days", days(created)),
+        ("idle_days", days(updated)),
+        (
+            "due_in_days",
+            issue.due.map_or(Dynamic::UNIT, |due| {
+                Dynamic::from_int((due - now.date_naive()).num_days())
+            }),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.into(), value))
+    .collect()
+}
+
+/// The variables a script runs with: the issue's fields, and all issues.
+fn scope(issue: &Map, all: Dynamic) -> Scope<'static> {
+    let mut scope = Scope::new();
+    for (name, value) in issue {
+        scope.push_constant_dynamic(name.as_str(), value.clone());
+    }
+    scope.push_constant_dynamic("issues", all);
+    scope
+}
+
+/// How a script's result is shown in a column: whole numbers without decimals, nothing for
+/// `()`, and arrays as lists.
+fn display(value: Dynamic) -> String {
+    if value.is_unit() {
+        String::new()
+    } else if let Ok(number) = value.as_float() {
+        if number.fract() == 0.0 {
+            format!("{number:.0}")
+        } else {
+            format!("{number:.1}")
+        }
+    } else if value.is_array() {
+        let items: Array = value.cast();
+        items
+            .into_iter()
+            .map(display)
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::ui::issue::{Status, StatusCategory};
+
+    fn column(name: &str, script: &str) -> ColumnConfig {
+        ColumnConfig {
+            name: name.to_string(),
+            script: Some(script.to_string()),
+            field: None,
+            width: 8,
+        }
+    }
+
+    #[test]
+    fn evaluates_columns_and_filters() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+        let issue = |key: &str, assignee: &str, points: f64| Issue {
+            id: key.to_string(),
+            assignee: Some(assignee.to_string()),
+            story_points: Some(points),
+            created: Some(
+                Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0)
+                    .unwrap()
+                    .fixed_offset(),
+            ),
+            ..Issue::new(format!("Summary of {key}"), String::new())
+        };
+        let mut issues = vec![issue("OPS-1", "Ana", 3.0), issue("OPS-2", "Ana", 2.5)];
+        issues.push(Issue {
+            status: Some(Status {
+                name: "Done".to_string(),
+                category: StatusCategory::Done,
+            }),
+            created: None,
+            ..issue("OPS-3", "Ben", 1.0)
+        });
+
+        let scripts = Scripts::compile(
+            &[
+                column("Age", "age_days"),
+                column(
+                    "Load",
+                    "issues.filter(|i| i.assignee == assignee).reduce(|sum, i| sum + i.points, 0.0)",
+                ),
+                column("Tags", "labels + [key, type]"),
+            ],
+            &[FilterConfig {
+                name: "Old".to_string(),
+                script: "!done && age_days > 7".to_string(),
+            }],
+        )
+        .unwrap();
+        let computed = scripts.evaluate(&issues, now);
+        assert_eq!(computed.error, None);
+        assert_eq!(computed.column(0, 0), "9");
+        assert_eq!(computed.column(1, 1), "5.5");
+        assert_eq!(computed.column(2, 0), "");
+        assert_eq!(computed.column(2, 2), "OPS-3");
+        assert_eq!([0, 1, 2].map(|i| computed.filter(i, 0)), [true, true, false]);
+
+        // Fields that don't exist are caught when compiling
+        let err = Scripts::compile(&[column("Age", "age_in_days")], &[])
+            .err()
+            .unwrap();
+        assert!(err.starts_with("column \"Age\": "), "{err}");
+
+        let scripts = Scripts::compile(&[column("Loop", "loop {}")], &[]).unwrap();
+        let computed = scripts.evaluate(&issues, now);
+        assert!(
+            computed
+                .error
+                .as_ref()
+                .unwrap()
+                .starts_with("column Loop: ")
+        );
+        assert_eq!(computed.column(0, 0), "");
+    }
+}
@@ -0,0 +1,84 @@
+//! Signing in again when Jira rejects the credentials in the middle of a session, like after
+//! the API token expired, instead of letting every request that follows fail the same way.
+
+use crossterm::event::KeyEvent;
+
+use super::{App, Command};
+use crate::ui::input::{EditingModeAction, InputMode, handle_editing_mode_key};
+
+/// Asks for a new API token, unless something else is being typed.
+pub(super) fn prompt(app: &mut App) {
+    if app.input_mode != InputMode::Normal {
+        return;
+    }
+    app.input.clear();
+    app.input_state.cursor = 0;
+    app.input_mode = InputMode::SignIn;
+}
+
+/// Handles a key typed into the prompt.
+pub(super) fn handle_key(app: &mut App, key: &KeyEvent) -> Command {
+    match handle_editing_mode_key(key, &mut app.input) {
+        EditingModeAction::Submit => {
+            let api_token = app.input.trim().to_string();
+            app.input.clear();
+            app.input_state.cursor = 0;
+            app.input_mode = InputMode::Normal;
+            if api_token.is_empty() {
+                return Command::None;
+            }
+            // Until a request says otherwise
+            app.connection.signed_out = false;
+            app.notice = Some(if app.failures.is_empty() {
+                "Signed in with the new token".to_string()
+            } else {
+                "Signed in with the new token; retry what failed in the failures view (vf)"
+                    .to_string()
+            });
+            Command::SignIn { api_token }
+        }
+        EditingModeAction::Cancel => {
+            app.input.clear();
+            app.input_state.cursor = 0;
+            app.input_mode = InputMode::Normal;
+            Command::None
+        }
+        EditingModeAction::Edited => {
+            app.input_state.cursor = app.input.len();
+            Command::None
+        }
+        EditingModeAction::Complete
+        | EditingModeAction::PickEpic
+        | EditingModeAction::ToggleRegex
+        | EditingModeAction::None => Command::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        error::Error,
+        ui::issue::Issue,
+    };
+
+    #[test]
+    fn asks_for_a_new_token_when_jira_rejects_the_old_one() {
+        let mut app = App::new(vec![Issue::new("Failover", "")]);
+        update(&mut app, Msg::Projects(Err(Error::Auth("token expired".to_string()))));
+        assert_eq!(app.input_mode, InputMode::SignIn);
+        assert!(app.connection.signed_out);
+
+        for c in "s3cret".chars() {
+            update(&mut app, Msg::Key(KeyEvent::from(KeyCode::Char(c))));
+        }
+        let command = update(&mut app, Msg::Key(KeyEvent::from(KeyCode::Enter)));
+        assert_eq!(command, Command::SignIn { api_token: "s3cret".to_string() });
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.input.is_empty());
+        assert!(!app.connection.signed_out);
+    }
+}
@@ -0,0 +1,76 @@
+//! The report of a sprint, shown in a popup for the retro and copied from there as markdown.
+
+use super::{App, Command};
+use crate::{
+    error::Result,
+    report::{ReportFormat, SprintReport},
+    ui::popup::Popup,
+};
+
+/// Builds the report of the last sprint the selected issue was in.
+pub(super) fn report(app: &mut App) -> Command {
+    let Some(issue) = app.selected_issue() else {
+        return Command::None;
+    };
+    let Some(sprint) = issue.sprints.last().cloned() else {
+        app.notice = Some(format!("{} isn't in a sprint", issue.id));
+        return Command::None;
+    };
+    app.notice = Some(format!("Building the report of {sprint}…"));
+    Command::FetchSprintReport { sprint }
+}
+
+/// Shows the report that was built.
+pub(super) fn loaded(app: &mut App, sprint: &str, result: Result<SprintReport>) {
+    match result {
+        Ok(report) => {
+            app.notice = None;
+            let popup = Popup::new(format!(" {sprint} "), report.render(ReportFormat::Text));
+            let what = format!("the report of {sprint} as markdown");
+            app.popup = Some(popup.copying(what, report.render(ReportFormat::Markdown)));
+        }
+        Err(e) => app.error = Some(format!("Failed to build the report of {sprint}: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        ui::issue::Issue,
+    };
+
+    #[test]
+    fn shows_the_report_to_copy_as_markdown() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            sprints: vec!["Sprint 4".to_string(), "Sprint 5".to_string()],
+            ..Issue::new("Failover", "")
+        }]);
+        let press = |app: &mut App, c| update(app, Msg::Key(KeyEvent::from(KeyCode::Char(c))));
+        assert_eq!(press(&mut app, 'S'), Command::FetchSprintReport {
+            sprint: "Sprint 5".to_string()
+        });
+        update(&mut app, Msg::SprintReport {
+            sprint: "Sprint 5".to_string(),
+            result: Ok(SprintReport::new("Sprint 5", &[])),
+        });
+        assert!(
+            app.popup
+                .as_ref()
+                .unwrap()
+                .text
+                .starts_with("Sprint 5 (not started)")
+        );
+
+        let Command::Copy { text } = press(&mut app, 'y') else {
+            panic!("the report wasn't copied");
+        };
+        assert!(text.starts_with("# Sprint 5\n"));
+        assert!(app.popup.is_none());
+        assert_eq!(app.notice.as_deref(), Some("Copied the report of Sprint 5 as markdown"));
+    }
+}
@@ -0,0 +1,116 @@
+//! Moving the selected issue to another status, picked from the transitions Jira allows from
+//! the one it's in.
+
+use itertools::Itertools;
+
+use super::{App, BulkChange, Command, OpenPicker, PickerPurpose, outbox};
+use crate::{error::Result, jira::Transition, ui::picker::Picker};
+
+/// Fetches the transitions of the selected issue, to pick one once they arrive.
+pub(super) fn pick_status(app: &mut App) -> Command {
+    let Some(key) = app.selected_key() else {
+        return Command::None;
+    };
+    app.notice = Some(format!("Loading the transitions of {key}…"));
+    Command::FetchTransitions { key }
+}
+
+/// Asks which status to move the issue to, unless another one has been selected since.
+pub(super) fn loaded(app: &mut App, key: &str, result: Result<Vec<Transition>>) -> Command {
+    let transitions = match result {
+        Ok(transitions) => transitions,
+        Err(e) => {
+            app.error = Some(format!("Failed to load the transitions of {key}: {e}"));
+            return Command::None;
+        }
+    };
+    if app.selected_key().as_deref() != Some(key) {
+        return Command::None;
+    }
+    app.notice = None;
+    // Several transitions can lead to the same status
+    let statuses: Vec<String> = transitions
+        .into_iter()
+        .map(|transition| transition.to_status.unwrap_or(transition.name))
+        .unique()
+        .collect();
+    if statuses.is_empty() {
+        app.notice = Some(format!("{key} can't move to another status"));
+        return Command::None;
+    }
+    app.picker = Some(OpenPicker {
+        purpose: PickerPurpose::Status,
+        picker: Picker::new(format!("Move {key} to"), statuses),
+    });
+    Command::None
+}
+
+/// Moves the selected issue to the picked status.
+pub(super) fn picked(app: &mut App, status: &str) -> Command {
+    let Some(key) = app.selected_key() else {
+        return Command::None;
+    };
+    app.notice = Some(format!("Moving {key} to {status}…"));
+    outbox::queue(app, vec![BulkChange {
+        key,
+        status: Some(status.to_string()),
+        assignee: None,
+        points: None,
+        parent: None,
+        flagged: None,
+        priority: None,
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        jira::FakeClient,
+        ui::issue::Issue,
+    };
+
+    fn press(app: &mut App, code: KeyCode) -> Command {
+        update(app, Msg::Key(KeyEvent::from(code)))
+    }
+
+    #[test]
+    fn moves_the_selected_issue_to_the_picked_status() {
+        let mut app = App::new(vec![Issue::from_jira(&FakeClient::issue("OPS-1", "", "To Do"))]);
+        press(&mut app, KeyCode::Char('t'));
+        assert_eq!(press(&mut app, KeyCode::Char('t')), Command::FetchTransitions {
+            key: "OPS-1".to_string()
+        });
+        let transitions = ["Start", "Finish"].map(|name| Transition {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            to_status: Some(
+                name.replace("Start", "In Progress")
+                    .replace("Finish", "Done"),
+            ),
+        });
+        update(&mut app, Msg::Transitions {
+            key: "OPS-1".to_string(),
+            result: Ok(transitions.to_vec()),
+        });
+        assert_eq!(app.picker.as_ref().unwrap().picker.items, ["In Progress", "Done"]);
+
+        let Command::Batch(commands) = press(&mut app, KeyCode::Enter) else {
+            panic!("the change wasn't queued");
+        };
+        let Some(Command::ApplyBulkEdit { changes }) = commands.last() else {
+            panic!("the change wasn't sent");
+        };
+        assert_eq!(changes[0].status.as_deref(), Some("In Progress"));
+
+        // The list and the sidebar show the issue as Jira returns it
+        update(&mut app, Msg::BulkApplied {
+            results: vec![("OPS-1".to_string(), Ok(FakeClient::issue("OPS-1", "", "In Progress")))],
+        });
+        let status = app.selected_issue().unwrap().status.as_ref().unwrap();
+        assert_eq!(status.name, "In Progress");
+    }
+}
@@ -0,0 +1,188 @@
+//! Quick changes to the selected issue, for going through the list one issue after another.
+
+use serde_json::json;
+
+use super::{App, BulkChange, Command, Loadable, outbox};
+use crate::{error::Result, ui::issue::Priority};
+
+/// Flags the selected issue as an impediment, or takes the flag off.
+pub(super) fn toggle_flag(app: &mut App) -> Command {
+    let Some(issue) = app.selected_jira_issue() else {
+        return Command::None;
+    };
+    let (key, flagged) = (issue.id.clone(), !issue.is_flagged());
+    app.notice = Some(match flagged {
+        true => format!("Flagging {key}…"),
+        false => format!("Taking the flag off {key}…"),
+    });
+    outbox::queue(app, vec![BulkChange {
+        key,
+        status: None,
+        assignee: None,
+        points: None,
+        parent: None,
+        flagged: Some(flagged),
+        priority: None,
+    }])
+}
+
+/// Raises the priority of the selected issue by `steps` through the priorities of the
+/// instance, or lowers it for negative steps, fetching the priorities the first time.
+pub(super) fn bump_priority(app: &mut App, steps: isize) -> Command {
+    let Some(issue) = app.selected_jira_issue() else {
+        return Command::None;
+    };
+    let priorities = match &app.priorities {
+        Some(Loadable::Loaded(priorities)) => priorities,
+        Some(Loadable::Loading) => {
+            app.priority_bump = Some(steps);
+            return Command::None;
+        }
+        Some(Loadable::Failed(_)) | None => {
+            app.priorities = Some(Loadable::Loading);
+            app.priority_bump = Some(steps);
+            app.notice = Some("Loading priorities…".to_string());
+            return Command::FetchPriorities;
+        }
+    };
+    let key = issue.id.clone();
+    let current = issue
+        .raw_field("priority.name")
+        .and_then(|name| name.as_str());
+    let Some(i) = priorities
+        .iter()
+        .position(|name| Some(name.as_str()) == current)
+    else {
+        app.notice = Some(format!("{key} has none of the priorities of the instance"));
+        return Command::None;
+    };
+    // The highest comes first, so raising moves towards the start
+    let last = priorities.len() as isize - 1;
+    let to = (i as isize - steps).clamp(0, last) as usize;
+    if to == i {
+        app.notice = Some(match steps > 0 {
+            true => format!("{key} has the highest priority already"),
+            false => format!("{key} has the lowest priority already"),
+        });
+        return Command::None;
+    }
+    let priority = priorities[to].clone();
+    app.notice = Some(format!("Changing the priority of {key} to {priority}…"));
+    // Shown right away, so pressing again goes on from there
+    if let Some(issue) = app.issues.iter_mut().find(|issue| issue.id == key) {
+        issue.priority = Some(Priority::from_jira_str(&priority));
+        issue
+            .raw_fields
+            .insert("priority".to_string(), json!({ "name": priority }));
+    }
+    outbox::queue(app, vec![BulkChange {
+        key,
+        status: None,
+        assignee: None,
+        points: None,
+        parent: None,
+        flagged: None,
+        priority: Some(priority),
+    }])
+}
+
+/// Makes the priority change asked for while the priorities were loading, or reports why
+/// they didn't arrive. Failures are fetched again the next time.
+pub(super) fn priorities_loaded(app: &mut App, result: Result<Vec<String>>) -> Command {
+    let steps = app.priority_bump.take();
+    match result {
+        Ok(priorities) => {
+            app.priorities = Some(Loadable::Loaded(priorities));
+            steps.map_or(Command::None, |steps| bump_priority(app, steps))
+        }
+        Err(e) => {
+            app.error = Some(format!("Failed to load priorities: {e}"));
+            app.priorities = Some(Loadable::Failed(e.to_string()));
+            Command::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jira_v3_openapi::models::IssueBean;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        jira::{FakeClient, custom_fields},
+        ui::issue::Issue,
+    };
+
+    #[test]
+    fn flags_and_unflags_the_selected_issue() {
+        let bean = |flag: serde_json::Value| IssueBean {
+            key: Some("OPS-1".to_string()),
+            fields: Some(
+                serde_json::from_value(
+                    json!({ "summary": "Failover", custom_fields().flagged.as_str(): flag }),
+                )
+                .unwrap(),
+            ),
+            ..IssueBean::default()
+        };
+        let mut app = App::new(vec![Issue::from_jira(&bean(json!(null)))]);
+        let Command::Batch(commands) = toggle_flag(&mut app) else {
+            panic!("the flag isn't sent");
+        };
+        let Some(Command::ApplyBulkEdit { changes }) = commands.last() else {
+            panic!("the flag isn't sent");
+        };
+        assert_eq!(changes[0].flagged, Some(true));
+
+        let flagged = bean(json!([{ "value": "Impediment" }]));
+        update(&mut app, Msg::BulkApplied {
+            results: vec![("OPS-1".to_string(), Ok(flagged))],
+        });
+        assert!(app.issues[0].is_flagged());
+        let Command::Batch(commands) = toggle_flag(&mut app) else {
+            panic!("the flag isn't sent");
+        };
+        assert!(matches!(
+            commands.last(),
+            Some(Command::ApplyBulkEdit { changes }) if changes[0].flagged == Some(false)
+        ));
+    }
+
+    #[test]
+    fn bumps_the_priority_through_those_of_the_instance() {
+        let bean = IssueBean {
+            key: Some("OPS-1".to_string()),
+            fields: Some(
+                serde_json::from_value(json!({
+                    "summary": "Failover",
+                    "priority": { "name": "Medium" },
+                }))
+                .unwrap(),
+            ),
+            ..IssueBean::default()
+        };
+        let mut app = App::new(vec![Issue::from_jira(&bean)]);
+        assert_eq!(bump_priority(&mut app, 1), Command::FetchPriorities);
+        let priorities = FakeClient::PRIORITIES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let sent = |command: Command| match command {
+            Command::Batch(commands) => match commands.last() {
+                Some(Command::ApplyBulkEdit { changes }) => changes[0].priority.clone(),
+                _ => None,
+            },
+            _ => None,
+        };
+        let command = update(&mut app, Msg::Priorities(Ok(priorities)));
+        assert_eq!(sent(command), Some("High".to_string()));
+
+        // Going on from the new one before Jira confirms it
+        assert_eq!(sent(bump_priority(&mut app, -2)), Some("Low".to_string()));
+        assert_eq!(sent(bump_priority(&mut app, -3)), Some("Lowest".to_string()));
+        assert_eq!(bump_priority(&mut app, -1), Command::None);
+        assert_eq!(app.notice.as_deref(), Some("OPS-1 has the lowest priority already"));
+    }
+}
@@ -0,0 +1,1707 @@
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use jira_v3_openapi::models::IssueBean;
+
+use super::{
+    App, Command, FetchProgress, JumpLabels, LabelMatch, Loadable, PickerPurpose, SidebarItem,
+    SidebarTab, View, actions, alerts,
+    bulk_edit::{self, BulkRow},
+    clipboard, completion,
+    components::{self, ComponentSummary},
+    connection, cycle_time, failures, filter,
+    filter::QuickFilter,
+    filter_builder, find, hooks, mouse, new_issue, outbox, parents, projects, refresh, releases,
+    sign_in, sprints, step_row, transitions, triage, users, watchers, workload,
+};
+use crate::{
+    config::{Config, HookEvent},
+    error::{Error, Result},
+    jira::{
+        CreatedIssue, FetchEvent, HistoryEntry, NewIssue, PageCursor, Project, Transition, User,
+        Version, jql, webhook::WebhookEvent,
+    },
+    report::SprintReport,
+    ui::{
+        help_text,
+        input::{EditingModeAction, InputMode, NormalModeAction},
+        inspector::Inspector,
+        issue::{Issue, IssueRef},
+        issue_list::{IssueListCache, placed_offset},
+        picker::PickerAction,
+        popup::Popup,
+    },
+};
+
+/// Everything that can change the app state.
+#[derive(Debug)]
+pub enum Msg {
+    Key(KeyEvent),
+    /// A mouse button was pressed, dragged, or released.
+    Mouse(MouseEvent),
+    /// The terminal was resized; only a redraw is needed.
+    Resize,
+    /// Progress from the background page fetch of search number `generation`.
+    Fetch {
+        generation: usize,
+        event: FetchEvent,
+    },
+    /// Periodic tick, only sent while [`App::is_animating`] is true.
+    Tick,
+    /// The process was asked to terminate (SIGINT, SIGTERM, SIGHUP, or Ctrl-C on Windows).
+    Shutdown,
+    /// Background work failed in a way the user should know about.
+    Error(Error),
+    /// The changelog of an issue arrived.
+    History {
+        key: String,
+        result: Result<Vec<HistoryEntry>>,
+    },
+    /// The child issues of an epic arrived.
+    Children {
+        key: String,
+        result: Result<Vec<IssueBean>>,
+    },
+    /// The projects of the instance arrived.
+    Projects(Result<Vec<Project>>),
+    /// The names of the priorities arrived, the highest first.
+    Priorities(Result<Vec<String>>),
+    /// The signed-in user arrived.
+    Myself(Result<User>),
+    /// The transitions an issue can make from its status arrived.
+    Transitions {
+        key: String,
+        result: Result<Vec<Transition>>,
+    },
+    /// The users watching an issue arrived.
+    Watchers {
+        key: String,
+        result: Result<Vec<User>>,
+    },
+    /// `user` was added to the watchers of an issue, or taken off unless `watching`.
+    WatcherChanged {
+        key: String,
+        user: User,
+        watching: bool,
+        result: Result<()>,
+    },
+    /// The issue typed into the new issue input listed as `pending` was created, or failed
+    /// to be.
+    IssueCreated {
+        pending: u64,
+        issue: NewIssue,
+        result: Result<CreatedIssue>,
+    },
+    /// The report of a sprint was built.
+    SprintReport {
+        sprint: String,
+        result: Result<SprintReport>,
+    },
+    /// A search for the users matching `query` found these.
+    UsersFound {
+        query: String,
+        result: Result<Vec<User>>,
+    },
+    /// The versions of a project arrived.
+    Versions {
+        project: String,
+        result: Result<Vec<Version>>,
+    },
+    /// The components of a project arrived.
+    Components {
+        project: String,
+        result: Result<Vec<ComponentSummary>>,
+    },
+    /// An issue opened from outside the search results arrived.
+    IssueFetched {
+        key: String,
+        result: Result<IssueBean>,
+    },
+    /// Jira reported a change through a webhook.
+    Webhook(WebhookEvent),
+    /// An attachment to open was downloaded to `result`.
+    AttachmentDownloaded {
+        filename: String,
+        result: Result<PathBuf>,
+    },
+    /// The image on the clipboard was attached to an issue as `filename`, which is now
+    /// `result`.
+    ImageAttached {
+        key: String,
+        filename: String,
+        result: Result<IssueBean>,
+    },
+    /// The editor opened for `rows` was closed, leaving `result` in the file.
+    BulkEdited {
+        rows: Vec<BulkRow>,
+        result: Result<String>,
+    },
+    /// The command of an action exited, having printed `result`.
+    ActionFinished {
+        name: String,
+        popup: bool,
+        result: Result<String>,
+    },
+    /// The changes saved in the editor were made, with the issues as they are afterwards.
+    BulkApplied {
+        results: Vec<(String, Result<IssueBean>)>,
+    },
+    /// It's time to send the changes in the outbox again.
+    RetryOutbox,
+    /// The config file was read again.
+    ConfigReloaded(Result<Config>),
+    /// The alert searches ran, finding the issues with these keys, by alert name.
+    AlertsChecked(Vec<(String, Result<Vec<String>>)>),
+    /// It's time to refresh the list.
+    RefreshDue,
+    /// Searching again for the issues of search `generation` found these.
+    Refreshed {
+        generation: usize,
+        result: Result<Vec<IssueBean>>,
+    },
+}
+
+/// Smallest and largest sidebar width, in percent of the screen width.
+pub(super) const SIDEBAR_WIDTH_RANGE: (u16, u16) = (20, 80);
+
+/// Characters scrolled by one `zl`/`zh`.
+const SUMMARY_SCROLL_STEP: usize = 8;
+
+/// Key of the new issue composer in [`App::drafts`].
+const NEW_ISSUE_DRAFT: &str = "new-issue";
+
+/// Returns the command to run at startup, given where the results following the first page
+/// in `app.issues` start and how many there are in total, and the key of an issue to open
+/// right away.
+pub fn init(
+    app: &mut App,
+    next: Option<PageCursor>,
+    total: Option<usize>,
+    issue: Option<&str>,
+) -> Command {
+    app.result_total = total;
+    let open = match issue {
+        Some(key) => {
+            app.sidebar_visible = true;
+            show_issue(app, key)
+        }
+        None => Command::None,
+    };
+
+    // Changes left over from the last run
+    let open = match outbox::send(app) {
+        Command::None => open,
+        send => Command::Batch(vec![open, send]),
+    };
+    let open = match alerts::check(app, Duration::ZERO) {
+        Command::None => open,
+        check => Command::Batch(vec![open, check]),
+    };
+    let open = match refresh::schedule(app) {
+        Command::None => open,
+        refresh => Command::Batch(vec![open, refresh]),
+    };
+    // Views of a project need it loaded, when starting in one
+    let open = match app.view {
+        View::Releases => Command::Batch(vec![open, releases::show(app)]),
+        View::Components => Command::Batch(vec![open, components::show(app)]),
+        _ => open,
+    };
+    let command = match next {
+        None => open,
+        Some(next) => {
+            app.fetch_progress = Some(FetchProgress { fetched: app.issues.len(), total });
+            let fetch = Command::FetchRemaining {
+                jql: app.jql.clone(),
+                generation: app.search_generation,
+                from: Some(next),
+            };
+            match open {
+                Command::None => fetch,
+                open => Command::Batch(vec![open, fetch]),
+            }
+        }
+    };
+    app.operations.start(&command);
+    command
+}
+
+/// Returns the command to run at startup when the first page of results failed to load, like
+/// with Jira out of reach. The app starts without issues anyway, showing why, with the search
+/// to retry from the failures view.
+pub fn init_failed(app: &mut App, error: Error, issue: Option<&str>) -> Command {
+    let init = init(app, None, None, issue);
+    let generation = app.search_generation;
+    let failed = update(app, Msg::Fetch {
+        generation,
+        event: FetchEvent::Failed(error),
+    });
+    if let Some(e) = &app.error {
+        app.error = Some(format!("{e}; retry it from the failures view (vf)"));
+    }
+    Command::Batch(vec![init, failed])
+}
+
+/// Applies the config file read again, keeping what was set on the command line. Settings of
+/// the connection only take effect on the next start.
+fn config_reloaded(app: &mut App, result: Result<Config>) -> Command {
+    let mut config = match result {
+        Ok(config) => config,
+        Err(e) => {
+            app.error = Some(format!("Failed to reload the config: {e}"));
+            return Command::None;
+        }
+    };
+    config.no_color |= app.no_color;
+    app.apply_config(&config);
+    // Filters from scripts that are gone can't stay on
+    let scripts = app.scripts.filter_names().count();
+    app.quick_filters
+        .retain(|quick| !matches!(quick, QuickFilter::Script(n) if *n >= scripts));
+    app.issue_list_cache = IssueListCache::default();
+    app.refresh_visible();
+    app.notice = Some("Reloaded the config".to_string());
+    match (alerts::reconfigured(app), refresh::reconfigured(app)) {
+        (Command::None, command) | (command, Command::None) => command,
+        (alerts, refresh) => Command::Batch(vec![alerts, refresh]),
+    }
+}
+
+/// Replaces the list with the results of `jql`, which arrive in the background.
+pub(super) fn start_search(app: &mut App, jql: String) -> Command {
+    app.search_generation += 1;
+    app.jql = jql;
+    app.issues.clear();
+    app.visible.clear();
+    app.list_state.select(None);
+    *app.list_state.offset_mut() = 0;
+    app.summary_scroll = None;
+    app.error = None;
+    app.fetch_progress = Some(FetchProgress { fetched: 0, total: None });
+    app.result_total = None;
+    Command::FetchRemaining {
+        jql: app.jql.clone(),
+        generation: app.search_generation,
+        from: None,
+    }
+}
+
+/// Applies a message to the app state, returning any side effects to perform.
+pub fn update(app: &mut App, msg: Msg) -> Command {
+    let selected = app.selected_issue().map(|issue| issue.id.clone());
+    let was_online = app.connection.online;
+    if app.connection.observe(&msg) {
+        sign_in::prompt(app);
+    }
+    if let Some((operation, took)) = app.operations.finish(&msg) {
+        let failed = connection::outcome(&msg).and_then(Result::err);
+        app.activity.request(&operation, took, failed);
+    }
+    failures::record(app, &msg);
+    let error = app.error.clone();
+    let mut command = handle_msg(app, msg);
+    if let Some(e) = app.error.as_ref().filter(|e| Some(*e) != error.as_ref()) {
+        app.activity.error(e);
+    }
+    // Changes that waited for the connection don't have to wait for the next retry
+    if !was_online && app.connection.online {
+        command = Command::Batch(vec![command, outbox::send(app)]);
+    }
+    let command = match hooks::selection_changed(app, selected.as_deref()) {
+        Command::None => command,
+        hooks => Command::Batch(vec![command, hooks]),
+    };
+    app.operations.start(&command);
+    command
+}
+
+fn handle_msg(app: &mut App, msg: Msg) -> Command {
+    match msg {
+        Msg::Key(key) => {
+            app.notice = None;
+            let command = handle_key(app, &key);
+            match load_sidebar(app) {
+                Command::None => command,
+                load => Command::Batch(vec![command, load]),
+            }
+        }
+        Msg::Mouse(mouse) => {
+            app.notice = None;
+            let command = mouse::handle(app, &mouse);
+            match load_sidebar(app) {
+                Command::None => command,
+                load => Command::Batch(vec![command, load]),
+            }
+        }
+        Msg::Resize => Command::None,
+        Msg::Fetch { generation, event } => {
+            if generation != app.search_generation {
+                return Command::None;
+            }
+            apply_fetch_event(app, event)
+        }
+        Msg::Tick => {
+            app.spinner_frame = app.spinner_frame.wrapping_add(1);
+            app.flash = app.flash.saturating_sub(1);
+            Command::None
+        }
+        Msg::Shutdown => Command::Quit,
+        Msg::Error(e) => {
+            app.error = Some(e.to_string());
+            Command::None
+        }
+        Msg::History { key, result } => {
+            let history = match result {
+                Ok(history) => Loadable::Loaded(history),
+                Err(e) => Loadable::Failed(e.to_string()),
+            };
+            app.histories.insert(key, history);
+            Command::None
+        }
+        Msg::Children { key, result } => {
+            let children = match result {
+                Ok(children) => Loadable::Loaded(children.iter().map(Issue::from_jira).collect()),
+                Err(e) => Loadable::Failed(e.to_string()),
+            };
+            app.children.insert(key, children);
+            Command::None
+        }
+        Msg::Projects(result) => projects::loaded(app, result),
+        Msg::Priorities(result) => triage::priorities_loaded(app, result),
+        Msg::Myself(result) => users::me_loaded(app, result),
+        Msg::Transitions { key, result } => transitions::loaded(app, &key, result),
+        Msg::Watchers { key, result } => {
+            watchers::loaded(app, key, result);
+            Command::None
+        }
+        Msg::SprintReport { sprint, result } => {
+            sprints::loaded(app, &sprint, result);
+            Command::None
+        }
+        Msg::WatcherChanged { key, user, watching, result } => {
+            watchers::changed(app, key, &user, watching, result)
+        }
+        Msg::IssueCreated { pending, issue, result } => {
+            new_issue::created(app, pending, &issue, result)
+        }
+        Msg::UsersFound { query, result } => {
+            users::found(app, &query, result);
+            Command::None
+        }
+        Msg::Versions { project, result } => {
+            let versions = match result {
+                Ok(versions) => Loadable::Loaded(versions),
+                Err(e) => Loadable::Failed(e.to_string()),
+            };
+            app.versions.insert(project, versions);
+            Command::None
+        }
+        Msg::Components { project, result } => {
+            let components = match result {
+                Ok(components) => Loadable::Loaded(components),
+                Err(e) => Loadable::Failed(e.to_string()),
+            };
+            app.components.insert(project, components);
+            Command::None
+        }
+        Msg::IssueFetched { key, result } => {
+            match result {
+                Ok(bean) => {
+                    // It may have arrived with a result page in the meantime, or be one just
+                    // created, which takes the place of the copy typed into the input
+                    match app.issues.iter().position(|issue| issue.id == key) {
+                        Some(index) if app.issues[index].pending.is_some() => {
+                            app.issues[index] = Issue::from_jira(&bean);
+                            app.refresh_visible();
+                            return Command::None;
+                        }
+                        Some(_) => {}
+                        None => {
+                            app.issues.push(Issue::from_jira(&bean));
+                            app.refresh_visible();
+                        }
+                    }
+                    show_issue(app, &key);
+                }
+                Err(e) => app.error = Some(format!("Failed to open {key}: {e}")),
+            }
+            Command::None
+        }
+        Msg::Webhook(event) => apply_webhook_event(app, event),
+        Msg::AttachmentDownloaded { filename, result } => match result {
+            Ok(path) => Command::Open {
+                target: path.to_string_lossy().into_owned(),
+            },
+            Err(e) => {
+                app.error = Some(format!("Failed to download {filename}: {e}"));
+                Command::None
+            }
+        },
+        Msg::ImageAttached { key, filename, result } => {
+            clipboard::image_attached(app, &key, &filename, result)
+        }
+        Msg::BulkEdited { rows, result } => bulk_edit::edited(app, &rows, result),
+        Msg::BulkApplied { results } => outbox::applied(app, results),
+        Msg::RetryOutbox => outbox::retry(app),
+        Msg::ConfigReloaded(result) => config_reloaded(app, result),
+        Msg::AlertsChecked(results) => alerts::checked(app, results),
+        Msg::RefreshDue => refresh::due(app),
+        Msg::Refreshed { generation, result } => refresh::refreshed(app, generation, result),
+        Msg::ActionFinished { name, popup, result } => {
+            actions::finished(app, name, popup, result);
+            Command::None
+        }
+    }
+}
+
+/// Keeps the input as the draft of `composer`, or drops the draft once the input is empty.
+/// Saved on every change, so not even a crash loses it.
+fn save_draft(app: &mut App, composer: &str) -> Command {
+    if app.input.trim().is_empty() {
+        if app.drafts.remove(composer).is_none() {
+            return Command::None;
+        }
+    } else if app.drafts.get(composer) == Some(&app.input) {
+        return Command::None;
+    } else {
+        app.drafts.insert(composer.to_string(), app.input.clone());
+    }
+    Command::SaveState(app.ui_state())
+}
+
+/// Starts fetching whatever the visible sidebar tab needs for the selected issue.
+fn load_sidebar(app: &mut App) -> Command {
+    if !app.sidebar_visible {
+        return Command::None;
+    }
+    let Some(issue) = app.selected_jira_issue() else {
+        return Command::None;
+    };
+    let key = issue.id.clone();
+    let children = issue.is_epic() && !app.children.contains_key(&key);
+    // Both tabs show the history: the details sum up the time in each status from it
+    let mut commands = Vec::new();
+    if !app.histories.contains_key(&key) {
+        app.histories.insert(key.clone(), Loadable::Loading);
+        commands.push(Command::FetchHistory { key: key.clone() });
+    }
+    if app.sidebar_tab == SidebarTab::Details && children {
+        app.children.insert(key.clone(), Loadable::Loading);
+        commands.push(Command::FetchChildren { key: key.clone() });
+    }
+    if app.sidebar_tab == SidebarTab::Details && !app.watchers.contains_key(&key) {
+        app.watchers.insert(key.clone(), Loadable::Loading);
+        commands.push(Command::FetchWatchers { key });
+    }
+    if app.polling.prefetches() {
+        commands.extend(prefetch_neighbours(app));
+    }
+    match commands.len() {
+        0 => Command::None,
+        1 => commands.remove(0),
+        _ => Command::Batch(commands),
+    }
+}
+
+/// Starts fetching the changelogs of the rows above and below the selected one, so they show
+/// right away when moving there.
+fn prefetch_neighbours(app: &mut App) -> Vec<Command> {
+    let Some(row) = app.list_state.selected() else {
+        return Vec::new();
+    };
+    let neighbours: Vec<String> = [row.checked_sub(1), Some(row + 1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|row| app.visible.get(row))
+        .map(|&i| app.issues[i].id.clone())
+        .filter(|key| !key.is_empty() && !app.histories.contains_key(key))
+        .collect();
+    neighbours
+        .into_iter()
+        .map(|key| {
+            app.histories.insert(key.clone(), Loadable::Loading);
+            Command::FetchHistory { key }
+        })
+        .collect()
+}
+
+/// Whether `issue` is in the project of a listed issue, or the one the releases and components
+/// views show.
+fn in_listed_project(app: &App, issue: &Issue) -> bool {
+    let project = |key: &str| key.split_once('-').map(|(project, _)| project.to_string());
+    let Some(own) = project(&issue.id) else {
+        return false;
+    };
+    app.view_project.as_ref() == Some(&own)
+        || app
+            .issues
+            .iter()
+            .any(|listed| project(&listed.id).as_ref() == Some(&own))
+}
+
+fn apply_fetch_event(app: &mut App, event: FetchEvent) -> Command {
+    match event {
+        FetchEvent::Page { issues, total } => {
+            // Issues opened on their own may turn up in the results later
+            let known: HashSet<String> = app.issues.iter().map(|issue| issue.id.clone()).collect();
+            app.issues.extend(
+                issues
+                    .iter()
+                    .map(Issue::from_jira)
+                    .filter(|issue| !known.contains(&issue.id)),
+            );
+            app.refresh_visible();
+            app.fetch_progress = Some(FetchProgress { fetched: app.issues.len(), total });
+            app.result_total = total.or(app.result_total);
+        }
+        FetchEvent::Done => {
+            app.fetch_progress = None;
+            app.connection.last_refresh = Some(Local::now().fixed_offset());
+            return hooks::refreshed(app);
+        }
+        FetchEvent::Failed(e) => {
+            app.fetch_progress = None;
+            app.error = Some(if app.issues.is_empty() {
+                format!("Search failed: {e}")
+            } else {
+                format!("Failed to fetch more issues: {e}")
+            });
+        }
+    }
+    Command::None
+}
+
+/// Applies a change reported by Jira to the listed issues. New issues are listed if they're in
+/// the project of a listed one, or the one the releases and components views show, since Jira
+/// reports those of every project the webhook covers.
+fn apply_webhook_event(app: &mut App, event: WebhookEvent) -> Command {
+    match event {
+        WebhookEvent::IssueChanged { issue, created } => {
+            let issue = Issue::from_jira(&issue);
+            forget_cached(app, &issue);
+            let index = app.issues.iter().position(|listed| listed.id == issue.id);
+            if created && index.is_none() && !in_listed_project(app, &issue) {
+                return Command::None;
+            }
+            let hook = match index {
+                _ if created => hooks::issue_event(app, HookEvent::Created, &issue),
+                Some(index) => hooks::issue_changed(app, &app.issues[index], &issue),
+                None => Command::None,
+            };
+            match index {
+                Some(index) => app.issues[index] = issue,
+                None if created => app.issues.push(issue),
+                None => return hook,
+            }
+            app.refresh_visible();
+            return hook;
+        }
+        WebhookEvent::IssueDeleted { key } => {
+            let Some(index) = app.issues.iter().position(|issue| issue.id == key) else {
+                return Command::None;
+            };
+            let selected = app.selected_issue().map(|issue| issue.id.clone());
+            app.issues.remove(index);
+            // Indices past the removed issue shifted, so the selection is found again by key
+            app.visible.clear();
+            app.refresh_visible();
+            if let Some(i) = app
+                .issues
+                .iter()
+                .position(|issue| Some(&issue.id) == selected.as_ref())
+            {
+                app.select_issue(i);
+            }
+        }
+        WebhookEvent::Commented { key, author } => {
+            if app.issues.iter().any(|issue| issue.id == key) {
+                app.notice = Some(format!("{author} commented on {key}"));
+            }
+        }
+    }
+    Command::None
+}
+
+/// Drops what was fetched for the sidebar that a change to `issue` outdates: its changelog
+/// and the progress of its epic.
+pub(super) fn forget_cached(app: &mut App, issue: &Issue) {
+    app.histories.remove(&issue.id);
+    if let Some(parent) = &issue.parent {
+        app.children.remove(&parent.key);
+    }
+}
+
+/// Starts writing a new issue, restoring what was left unsent.
+fn enter_input(app: &mut App) {
+    app.input_mode = InputMode::Insert;
+    if let (true, Some(draft)) = (app.input.is_empty(), app.drafts.get(NEW_ISSUE_DRAFT)) {
+        app.input = draft.clone();
+        app.input_state.cursor = app.input.len();
+        app.notice = Some("Restored unsent draft (^U to discard)".to_string());
+    }
+}
+
+/// Remembers the selected issue as the start of a jump.
+pub(super) fn record_jump(app: &mut App) {
+    if let Some(key) = app.selected_issue().map(|issue| issue.id.clone()) {
+        app.jumps.push(&key);
+    }
+}
+
+/// Jumps to the issue with the key `key`, remembering where the jump started.
+pub(super) fn open_issue(app: &mut App, key: &str) -> Command {
+    record_jump(app);
+    show_issue(app, key)
+}
+
+/// Selects the issue with the key `key`, fetching it first if it isn't loaded.
+fn show_issue(app: &mut App, key: &str) -> Command {
+    if let Some(row) = app.visible.iter().position(|&i| app.issues[i].id == key) {
+        app.list_state.select(Some(row));
+        Command::None
+    } else if app.issues.iter().any(|issue| issue.id == key) {
+        app.error = Some(format!("{key} is hidden by the filters"));
+        Command::None
+    } else {
+        Command::FetchIssue { key: key.to_string() }
+    }
+}
+
+fn handle_key(app: &mut App, key: &KeyEvent) -> Command {
+    if let Some(popup) = &mut app.popup {
+        if let (KeyCode::Char('y'), Some((what, text))) = (key.code, &popup.copy) {
+            app.notice = Some(format!("Copied {what}"));
+            let text = text.clone();
+            app.popup = None;
+            return Command::Copy { text };
+        }
+        if !popup.handle_key(key) {
+            app.popup = None;
+        }
+        return Command::None;
+    }
+    if let Some(inspector) = &mut app.inspector {
+        if !inspector.handle_key(key) {
+            app.inspector = None;
+        }
+        return Command::None;
+    }
+    if let Some(selection) = &mut app.selection {
+        let action = selection.handle_key(key);
+        return clipboard::handle_selection_key(app, action);
+    }
+    if let Some(menu) = &mut app.context_menu {
+        let action = menu.handle_key(key);
+        return mouse::menu_action(app, action);
+    }
+    if let Some(open) = &mut app.picker {
+        let query = open.picker.query.clone();
+        match open.picker.handle_key(key) {
+            PickerAction::None if open.picker.query != query => return users::search(app),
+            PickerAction::None => {}
+            PickerAction::Cancel => app.picker = None,
+            PickerAction::Pick(choice) => {
+                let purpose = open.purpose;
+                app.picker = None;
+                match purpose {
+                    PickerPurpose::FilterKind | PickerPurpose::FilterValue(_) => {
+                        filter::picked(app, purpose, &choice)
+                    }
+                    PickerPurpose::Project => return projects::picked(app, &choice),
+                    PickerPurpose::JqlUser(..) | PickerPurpose::Assignee => {
+                        return users::picked(app, purpose, &choice);
+                    }
+                    PickerPurpose::Epic => parents::epic_picked(app, &choice),
+                    PickerPurpose::Parent => return parents::parent_picked(app, &choice),
+                    PickerPurpose::Status => return transitions::picked(app, &choice),
+                    PickerPurpose::Watcher | PickerPurpose::Unwatch => {
+                        return watchers::picked(app, purpose, &choice);
+                    }
+                    _ => filter_builder::picked(app, purpose, &choice),
+                }
+            }
+        }
+        return Command::None;
+    }
+    if let Some(labels) = &mut app.jump_labels {
+        let found = match key.code {
+            KeyCode::Char(c) => labels.type_letter(c),
+            _ => LabelMatch::None,
+        };
+        match found {
+            LabelMatch::Partial => {}
+            LabelMatch::Row(row) => {
+                app.jump_labels = None;
+                record_jump(app);
+                app.list_state.select(Some(row));
+            }
+            LabelMatch::None => app.jump_labels = None,
+        }
+        return Command::None;
+    }
+
+    match app.input_mode {
+        InputMode::Normal => {
+            let action = crate::ui::input::handle_normal_mode_key(key, &mut app.pending_keys);
+            if app.view == View::Workload && workload::handle(app, action) {
+                return Command::None;
+            }
+            let view_command = match app.view {
+                View::Releases => releases::handle(app, action),
+                View::Components => components::handle(app, action),
+                View::Failures => failures::handle(app, action),
+                View::CycleTime => cycle_time::handle(app, action),
+                _ => None,
+            };
+            if let Some(command) = view_command {
+                return command;
+            }
+            match action {
+                NormalModeAction::Quit => return Command::Quit,
+                NormalModeAction::Jump(offset) => {
+                    let len = app.visible.len();
+                    if len == 0 {
+                        app.list_state.select(None);
+                    } else {
+                        let current = app.list_state.selected().unwrap_or(0);
+                        let new_idx = step_row(current, offset, len, app.wrap_around);
+                        // Landing at the other end is as far a jump as g and G make
+                        if new_idx != step_row(current, offset, len, false) {
+                            record_jump(app);
+                        }
+                        app.list_state.select(Some(new_idx));
+                    }
+                }
+                NormalModeAction::Scroll(scroll) => {
+                    let len = app.visible.len();
+                    if len == 0 {
+                        // nothing to scroll
+                    } else {
+                        let offset = app.list_state.offset_mut();
+                        let max_offset = len.saturating_sub(1);
+                        let new_offset =
+                            (*offset as isize + scroll).clamp(0, max_offset as isize) as usize;
+                        *offset = new_offset;
+
+                        // Drag the selection along so it stays on screen
+                        let rows = app.issue_list_cache.visible_rows().max(1);
+                        if let Some(selected) = app.list_state.selected() {
+                            let selected = selected
+                                .clamp(new_offset, new_offset + rows - 1)
+                                .min(len - 1);
+                            app.list_state.select(Some(selected));
+                        }
+                    }
+                }
+                NormalModeAction::GotoTop => {
+                    if !app.visible.is_empty() {
+                        record_jump(app);
+                        app.list_state.select(Some(0));
+                    }
+                }
+                NormalModeAction::GotoBottom => {
+                    if !app.visible.is_empty() {
+                        record_jump(app);
+                        app.list_state.select(Some(app.visible.len() - 1));
+                    }
+                }
+                NormalModeAction::ReloadConfig => {
+                    return Command::ReloadConfig { path: app.config_path.clone() };
+                }
+                NormalModeAction::JumpPage(pages) => {
+                    if !app.visible.is_empty() {
+                        record_jump(app);
+                        app.jump_page(pages);
+                    }
+                }
+                NormalModeAction::JumpBack => {
+                    let current = app.selected_issue().map(|issue| issue.id.clone());
+                    if let Some(key) = app.jumps.back(current.as_deref()) {
+                        return show_issue(app, &key);
+                    }
+                }
+                NormalModeAction::SetMark(mark) => {
+                    if let Some(key) = app.selected_key() {
+                        app.notice = Some(format!("Marked {key} as {mark}"));
+                        app.marks.insert(mark, key);
+                    }
+                }
+                NormalModeAction::JumpToMark(mark) => match app.marks.get(&mark).cloned() {
+                    Some(key) => return open_issue(app, &key),
+                    None => app.notice = Some(format!("Nothing is marked as {mark}")),
+                },
+                NormalModeAction::JumpForward => {
+                    let current = app.selected_issue().map(|issue| issue.id.clone());
+                    if let Some(key) = app.jumps.forward(current.as_deref()) {
+                        return show_issue(app, &key);
+                    }
+                }
+                NormalModeAction::ShowJumpLabels => {
+                    let first = app.list_state.offset();
+                    let rows = app
+                        .issue_list_cache
+                        .visible_rows()
+                        .min(app.visible.len().saturating_sub(first));
+                    if app.view == View::List && rows > 0 {
+                        app.jump_labels = Some(JumpLabels::new(first, rows));
+                    }
+                }
+                NormalModeAction::EnterInput => {
+                    app.new_issue_parent = None;
+                    enter_input(app);
+                }
+                NormalModeAction::CreateInEpic => {
+                    match app.selected_issue().and_then(Issue::epic) {
+                        Some(epic) => {
+                            app.new_issue_parent = Some(epic);
+                            enter_input(app);
+                        }
+                        None => app.notice = Some("The issue isn't in an epic".to_string()),
+                    }
+                }
+                NormalModeAction::ToggleSidebar => {
+                    app.sidebar_visible = !app.sidebar_visible;
+                }
+                NormalModeAction::ResizeSidebar(delta) => {
+                    let width = (app.sidebar_width as i16 + delta)
+                        .clamp(SIDEBAR_WIDTH_RANGE.0 as i16, SIDEBAR_WIDTH_RANGE.1 as i16)
+                        as u16;
+                    if width != app.sidebar_width {
+                        app.sidebar_width = width;
+                        app.sidebar_visible = true;
+                        return Command::SaveState(app.ui_state());
+                    }
+                }
+                NormalModeAction::SwitchSidebarTab(steps) => {
+                    app.sidebar_tab = app.sidebar_tab.cycle(steps);
+                    app.sidebar_visible = true;
+                }
+                NormalModeAction::ScrollSummary(steps) => {
+                    if let (Some(selected), Some(issue)) =
+                        (app.list_state.selected(), app.selected_issue())
+                    {
+                        let len = issue.summary.chars().count();
+                        let scroll = (app.selected_summary_scroll() as isize
+                            + steps * SUMMARY_SCROLL_STEP as isize)
+                            .clamp(0, len.saturating_sub(1) as isize);
+                        app.summary_scroll = Some((selected, scroll as usize));
+                    }
+                }
+                NormalModeAction::PlaceSelection(place) => {
+                    if let Some(selected) = app.list_state.selected() {
+                        let height = app.issue_list_cache.visible_rows();
+                        *app.list_state.offset_mut() = placed_offset(selected, height, place);
+                    }
+                }
+                NormalModeAction::BuildFilter => filter_builder::open(app),
+                NormalModeAction::NarrowList => filter::open(app),
+                NormalModeAction::RemoveFilter(n) => filter::remove(app, n),
+                NormalModeAction::ToggleQuickFilter(n) => filter::toggle_quick_filter(app, n),
+                NormalModeAction::ToggleJqlBar => app.jql_bar_visible = !app.jql_bar_visible,
+                NormalModeAction::ToggleLog => app.log_visible = !app.log_visible,
+                NormalModeAction::ToggleLabels => app.labels_visible = !app.labels_visible,
+                NormalModeAction::Find if app.view == View::List => find::start(app),
+                NormalModeAction::FindNext(count) => find::next(app, count, false),
+                NormalModeAction::FindPrevious(count) => find::next(app, count, true),
+                NormalModeAction::ClearFind => find::clear(app),
+                NormalModeAction::Find => {}
+                NormalModeAction::EditJql => {
+                    app.input = app.jql.clone();
+                    app.input_state.cursor = app.input.len();
+                    app.input_mode = InputMode::Jql;
+                }
+                NormalModeAction::ToggleHideDone => {
+                    app.hide_done = !app.hide_done;
+                    app.refresh_visible();
+                }
+                NormalModeAction::ToggleResolved => {
+                    let jql = jql::toggle_unresolved(&app.jql);
+                    app.notice = Some(if jql::is_unresolved_only(&jql) {
+                        "Searching unresolved issues only".to_string()
+                    } else {
+                        "Searching resolved issues too".to_string()
+                    });
+                    return start_search(app, jql);
+                }
+                NormalModeAction::MoveItemCursor(steps) => {
+                    let len = app.sidebar_items().len() as isize;
+                    if let (Some(row), true) = (app.list_state.selected(), len > 0) {
+                        let index =
+                            (app.selected_item_index() as isize + steps).rem_euclid(len) as usize;
+                        app.item_cursor = Some((row, index));
+                        app.sidebar_tab = SidebarTab::Details;
+                        app.sidebar_visible = true;
+                    }
+                }
+                NormalModeAction::OpenItem => match app.selected_item() {
+                    Some(
+                        SidebarItem::Reference(IssueRef { key, .. })
+                        | SidebarItem::Child(Issue { id: key, .. }),
+                    ) => {
+                        let key = key.clone();
+                        app.sidebar_visible = true;
+                        return open_issue(app, &key);
+                    }
+                    Some(SidebarItem::Attachment(attachment)) => {
+                        return Command::DownloadAttachment { attachment: attachment.clone() };
+                    }
+                    None => {}
+                },
+                NormalModeAction::OpenItemExternally => match app.selected_item() {
+                    Some(
+                        SidebarItem::Reference(IssueRef { key, .. })
+                        | SidebarItem::Child(Issue { id: key, .. }),
+                    ) => {
+                        return Command::OpenInBrowser { key: key.clone() };
+                    }
+                    Some(SidebarItem::Attachment(attachment)) => {
+                        return Command::DownloadAttachment { attachment: attachment.clone() };
+                    }
+                    None => {}
+                },
+                NormalModeAction::OpenInBrowser => {
+                    if let Some(issue) = app.selected_jira_issue() {
+                        return Command::OpenInBrowser { key: issue.id.clone() };
+                    }
+                }
+                NormalModeAction::BulkEdit => return bulk_edit::start(app),
+                NormalModeAction::SwitchProject => return projects::open(app),
+                NormalModeAction::ChangeStatus => return transitions::pick_status(app),
+                NormalModeAction::Assign => users::pick_assignee(app),
+                NormalModeAction::SprintReport => return sprints::report(app),
+                NormalModeAction::AddWatcher => watchers::pick_watcher(app),
+                NormalModeAction::RemoveWatcher => return watchers::pick_unwatch(app),
+                NormalModeAction::ChangeParent => parents::pick_parent(app),
+                NormalModeAction::ToggleFlag => return triage::toggle_flag(app),
+                NormalModeAction::Help => {
+                    app.popup = Some(Popup::new(" Keys ", help_text(app)));
+                }
+                NormalModeAction::InspectJson => {
+                    if let Some(issue) = app.selected_jira_issue() {
+                        app.inspector =
+                            Some(Inspector::new(format!(" {} JSON ", issue.id), &issue.raw_json()));
+                    }
+                }
+                NormalModeAction::RunAction(key) => return actions::run(app, key),
+                NormalModeAction::ShowAlert(n) => return alerts::show(app, n),
+                NormalModeAction::Copy(target) => return clipboard::copy(app, target),
+                NormalModeAction::PasteImage => return clipboard::paste_image(app),
+                NormalModeAction::SwitchView(View::Releases) => return releases::show(app),
+                NormalModeAction::SwitchView(View::Components) => return components::show(app),
+                NormalModeAction::SwitchView(View::CycleTime) => return cycle_time::show(app),
+                NormalModeAction::SwitchView(view) => app.view = view,
+                NormalModeAction::ScrollTimeline(steps) => {
+                    if app.view == View::Timeline {
+                        let today = app.appearance.time.today();
+                        app.timeline.scroll(steps, today);
+                    }
+                }
+                NormalModeAction::Increase(steps) => match app.view {
+                    View::Timeline => app.timeline.zoom(steps),
+                    View::List => return triage::bump_priority(app, steps),
+                    _ => {}
+                },
+                NormalModeAction::None => {}
+            }
+        }
+        InputMode::SignIn => return sign_in::handle_key(app, key),
+        InputMode::Find => match crate::ui::input::handle_editing_mode_key(key, &mut app.input) {
+            EditingModeAction::Submit => find::submit(app),
+            EditingModeAction::Cancel => find::cancel(app),
+            EditingModeAction::Edited => find::typed(app),
+            EditingModeAction::Complete
+            | EditingModeAction::PickEpic
+            | EditingModeAction::ToggleRegex
+            | EditingModeAction::None => {}
+        },
+        InputMode::Filter => match crate::ui::input::handle_editing_mode_key(key, &mut app.input) {
+            EditingModeAction::Submit => return filter::text_entered(app),
+            EditingModeAction::Cancel => {
+                app.input.clear();
+                app.input_state.cursor = 0;
+                app.input_mode = InputMode::Normal;
+            }
+            EditingModeAction::Edited => app.input_state.cursor = app.input.len(),
+            EditingModeAction::ToggleRegex => app.filter_regex = !app.filter_regex,
+            EditingModeAction::Complete | EditingModeAction::PickEpic | EditingModeAction::None => {
+            }
+        },
+        InputMode::Jql => match crate::ui::input::handle_editing_mode_key(key, &mut app.input) {
+            EditingModeAction::Submit => {
+                let jql = app.input.trim().to_string();
+                app.input.clear();
+                app.input_state.cursor = 0;
+                app.input_mode = InputMode::Normal;
+                if !jql.is_empty() {
+                    return start_search(app, jql);
+                }
+            }
+            EditingModeAction::Cancel => {
+                app.input.clear();
+                app.input_state.cursor = 0;
+                app.input_mode = InputMode::Normal;
+            }
+            EditingModeAction::Edited => {
+                app.input_state.cursor = app.input.len();
+            }
+            EditingModeAction::Complete => completion::complete(app),
+            EditingModeAction::PickEpic
+            | EditingModeAction::ToggleRegex
+            | EditingModeAction::None => {}
+        },
+        InputMode::Insert => {
+            match crate::ui::input::handle_editing_mode_key(key, &mut app.input) {
+                EditingModeAction::Submit => {
+                    let mut create = Command::None;
+                    if !app.input.trim().is_empty() {
+                        create = new_issue::submit(app);
+                    }
+                    app.input_mode = InputMode::Normal;
+                    app.input_state.cursor = 0;
+                    return match create {
+                        Command::None => save_draft(app, NEW_ISSUE_DRAFT),
+                        create => Command::Batch(vec![save_draft(app, NEW_ISSUE_DRAFT), create]),
+                    };
+                }
+                EditingModeAction::Cancel => {
+                    app.input_mode = InputMode::Normal;
+                    app.input_state.cursor = 0;
+                }
+                EditingModeAction::Edited => {
+                    // Always update cursor to end of input after edit
+                    app.input_state.cursor = app.input.len();
+                    return save_draft(app, NEW_ISSUE_DRAFT);
+                }
+                EditingModeAction::Complete => {
+                    completion::complete(app);
+                    return save_draft(app, NEW_ISSUE_DRAFT);
+                }
+                EditingModeAction::PickEpic => parents::pick_epic(app),
+                EditingModeAction::ToggleRegex | EditingModeAction::None => {}
+            }
+        }
+    }
+    Command::None
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use serde_json::json;
+
+    use super::*;
+    use crate::{config::FilterConfig, jira::FakeClient, state::UiState, ui::issue::Attachment};
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn app_with(n: usize) -> App {
+        App::new(
+            (0..n)
+                .map(|i| Issue::new(format!("Issue {i}"), String::new()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn init_streams_remaining_pages() {
+        let mut app = app_with(3);
+        assert_eq!(init(&mut app, None, Some(3), None), Command::None);
+        let next = Some(PageCursor::Token("page-2".to_string()));
+        assert_eq!(init(&mut app, next.clone(), None, None), Command::FetchRemaining {
+            jql: app.jql.clone(),
+            generation: 0,
+            from: next,
+        });
+        assert_eq!(app.fetch_progress, Some(FetchProgress { fetched: 3, total: None }));
+    }
+
+    #[test]
+    fn starts_without_issues_when_the_first_search_fails() {
+        let mut app = App::new(Vec::new());
+        init_failed(&mut app, Error::Network("dns error".to_string()), None);
+        assert_eq!(
+            app.error.as_deref(),
+            Some("Search failed: network error: dns error; retry it from the failures view (vf)")
+        );
+        assert!(!app.connection.online);
+        assert_eq!(app.failures.len(), 1);
+
+        update(&mut app, key(KeyCode::Char('v')));
+        update(&mut app, key(KeyCode::Char('f')));
+        let command = update(&mut app, key(KeyCode::Enter));
+        assert!(matches!(command, Command::FetchRemaining { from: None, .. }));
+        assert!(app.failures.is_empty());
+    }
+
+    #[test]
+    fn count_prefix_jumps() {
+        let mut app = app_with(10);
+        update(&mut app, key(KeyCode::Char('3')));
+        update(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.list_state.selected(), Some(3));
+        update(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.list_state.selected(), Some(2));
+        assert_eq!(update(&mut app, key(KeyCode::Char('q'))), Command::Quit);
+    }
+
+    #[test]
+    fn resizing_sidebar_saves_state() {
+        let mut app = app_with(1);
+        let command = update(&mut app, key(KeyCode::Char('<')));
+        assert_eq!(app.sidebar_width, 45);
+        assert_eq!(
+            command,
+            Command::SaveState(UiState {
+                sidebar_width: Some(45),
+                ..UiState::default()
+            })
+        );
+
+        for _ in 0..20 {
+            update(&mut app, key(KeyCode::Char('>')));
+        }
+        assert_eq!(app.sidebar_width, SIDEBAR_WIDTH_RANGE.0);
+        assert_eq!(update(&mut app, key(KeyCode::Char('>'))), Command::None);
+    }
+
+    #[test]
+    fn new_issue_drafts_are_saved_and_restored() {
+        let mut app = app_with(1);
+        update(&mut app, key(KeyCode::Char('i')));
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('F'))),
+            Command::SaveState(UiState {
+                sidebar_width: Some(app.sidebar_width),
+                drafts: [("new-issue".to_string(), "F".to_string())].into(),
+                ..UiState::default()
+            })
+        );
+        update(&mut app, key(KeyCode::Char('i')));
+        update(&mut app, key(KeyCode::Esc));
+
+        // As if the app restarted
+        let drafts = app.drafts.clone();
+        let mut app = app_with(1);
+        app.drafts = drafts;
+        update(&mut app, key(KeyCode::Char('i')));
+        assert_eq!(app.input, "Fi");
+        assert!(app.notice.is_some());
+
+        update(&mut app, key(KeyCode::Enter));
+        assert!(app.drafts.is_empty());
+    }
+
+    #[test]
+    fn z_prefix_scrolls_selected_summary() {
+        let mut app = App::new(vec![
+            Issue::new("A summary that is far too long to fit".to_string(), String::new()),
+            Issue::new("Short".to_string(), String::new()),
+        ]);
+        update(&mut app, key(KeyCode::Char('2')));
+        update(&mut app, key(KeyCode::Char('z')));
+        update(&mut app, key(KeyCode::Char('l')));
+        assert_eq!(app.selected_summary_scroll(), 16);
+        update(&mut app, key(KeyCode::Char('z')));
+        update(&mut app, key(KeyCode::Char('h')));
+        assert_eq!(app.selected_summary_scroll(), 8);
+
+        // The scroll belongs to the row it was made on
+        update(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.selected_summary_scroll(), 0);
+        update(&mut app, key(KeyCode::Char('z')));
+        update(&mut app, key(KeyCode::Char('l')));
+        assert_eq!(app.selected_summary_scroll(), 4);
+    }
+
+    #[test]
+    fn history_tab_fetches_once_per_issue() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            ..Issue::new("Failover", "")
+        }]);
+        let command = update(&mut app, key(KeyCode::Char(']')));
+        assert_eq!(app.sidebar_tab, SidebarTab::History);
+        assert_eq!(
+            command,
+            Command::Batch(vec![Command::None, Command::FetchHistory { key: "OPS-1".to_string() }])
+        );
+
+        update(&mut app, Msg::History {
+            key: "OPS-1".to_string(),
+            result: Ok(Vec::new()),
+        });
+        assert_eq!(app.histories["OPS-1"], Loadable::Loaded(Vec::new()));
+        assert_eq!(update(&mut app, key(KeyCode::Char('j'))), Command::None);
+    }
+
+    #[test]
+    fn prefetches_the_history_of_neighbours() {
+        let issues = ["OPS-1", "OPS-2", "OPS-3"]
+            .map(|key| Issue::from_jira(&FakeClient::issue(key, "", "To Do")));
+        let mut app = App::new(issues.to_vec());
+        app.polling.prefetch = true;
+        app.sidebar_visible = true;
+        let fetch = |key: &str| Command::FetchHistory { key: key.to_string() };
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('j'))),
+            Command::Batch(vec![
+                Command::None,
+                Command::Batch(vec![
+                    fetch("OPS-2"),
+                    Command::FetchWatchers { key: "OPS-2".to_string() },
+                    fetch("OPS-1"),
+                    fetch("OPS-3")
+                ])
+            ])
+        );
+        // Their history was fetched ahead of time, but not their watchers
+        assert_eq!(
+            update(&mut app, key(KeyCode::Char('k'))),
+            Command::Batch(vec![Command::None, Command::FetchWatchers {
+                key: "OPS-1".to_string()
+            }])
+        );
+    }
+
+    #[test]
+    fn wraps_around_the_ends_of_the_list() {
+        let issues =
+            (1..=5).map(|i| Issue::from_jira(&FakeClient::issue(&format!("OPS-{i}"), "", "To Do")));
+        let mut app = App::new(issues.collect());
+        let press = |app: &mut App, c| {
+            update(app, key(KeyCode::Char(c)));
+            app.list_state.selected()
+        };
+        assert_eq!(press(&mut app, 'k'), Some(0));
+        app.wrap_around = true;
+        assert_eq!(press(&mut app, 'k'), Some(4));
+        assert_eq!(press(&mut app, 'j'), Some(0));
+        // Counts that run past the end from elsewhere still stop there
+        press(&mut app, '3');
+        assert_eq!(press(&mut app, 'j'), Some(3));
+        press(&mut app, '3');
+        assert_eq!(press(&mut app, 'j'), Some(4));
+
+        // Wrapping is a jump, like g and G
+        assert_eq!(press(&mut app, 'j'), Some(0));
+        update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)));
+        assert_eq!(app.list_state.selected(), Some(4));
+    }
+
+    #[test]
+    fn pages_through_results() {
+        let mut app = app_with(250);
+        init(&mut app, Some(PageCursor::Token("page-4".to_string())), Some(437), None);
+        assert_eq!(app.result_page(), (1, 5));
+
+        let ctrl = |c| Msg::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+        update(&mut app, ctrl('f'));
+        assert_eq!(app.list_state.selected(), Some(100));
+        assert_eq!(app.result_page(), (2, 5));
+        // Pages not loaded yet end at the last issue loaded
+        update(&mut app, ctrl('f'));
+        assert_eq!(app.list_state.selected(), Some(200));
+        update(&mut app, ctrl('f'));
+        assert_eq!(app.list_state.selected(), Some(249));
+        update(&mut app, ctrl('b'));
+        assert_eq!(app.list_state.selected(), Some(100));
+
+        // A new search doesn't know its total until the first page arrives
+        start_search(&mut app, "project = OPS".to_string());
+        assert_eq!(app.result_total, None);
+    }
+
+    #[test]
+    fn reloading_the_config_applies_it() {
+        let mut app = app_with(2);
+        let ctrl_r = Msg::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(update(&mut app, ctrl_r), Command::ReloadConfig { path: None });
+
+        let mut config = Config::default();
+        config.filters.push(FilterConfig {
+            name: "Everything".to_string(),
+            script: "true".to_string(),
+        });
+        update(&mut app, Msg::ConfigReloaded(Ok(config)));
+        assert_eq!(app.scripts.filter_names().collect::<Vec<_>>(), ["Everything"]);
+        app.quick_filters.push(QuickFilter::Script(0));
+
+        // The filter is gone, and colors stay off as asked on the command line
+        app.no_color = true;
+        update(&mut app, Msg::ConfigReloaded(Ok(Config::default())));
+        assert!(app.quick_filters.is_empty());
+        assert!(app.appearance.no_color);
+    }
+
+    #[test]
+    fn query_is_edited_in_place() {
+        let mut app = app_with(2);
+        app.jql = "project = OPS".to_string();
+        update(&mut app, key(KeyCode::Char(':')));
+        assert_eq!(app.input, "project = OPS");
+        for c in " AND labels = ops".chars() {
+            update(&mut app, key(KeyCode::Char(c)));
+        }
+        let command = update(&mut app, key(KeyCode::Enter));
+        assert!(matches!(command, Command::FetchRemaining { jql, .. }
+            if jql == "project = OPS AND labels = ops"));
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn built_filter_runs_as_a_new_search() {
+        let mut app = app_with(2);
+        update(&mut app, key(KeyCode::Char('F')));
+        for code in [KeyCode::Char('l'), KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Enter] {
+            update(&mut app, key(code));
+        }
+        // `is EMPTY` needs no value
+        for _ in 0..2 {
+            update(&mut app, key(KeyCode::Down));
+        }
+        update(&mut app, key(KeyCode::Enter));
+        assert!(app.picker.is_none());
+        assert_eq!(app.input_mode, InputMode::Jql);
+
+        let command = update(&mut app, key(KeyCode::Enter));
+        let jql = "assignee = currentUser() AND resolution = Unresolved AND labels is EMPTY \
+                   ORDER BY updated DESC";
+        assert_eq!(command, Command::FetchRemaining {
+            jql: jql.to_string(),
+            generation: 1,
+            from: None,
+        });
+        assert!(app.issues.is_empty());
+
+        // Pages of the previous search are dropped
+        update(&mut app, Msg::Fetch {
+            generation: 0,
+            event: FetchEvent::Done,
+        });
+        assert!(app.fetch_progress.is_some());
+        update(&mut app, Msg::Fetch {
+            generation: 1,
+            event: FetchEvent::Done,
+        });
+        assert!(app.fetch_progress.is_none());
+    }
+
+    #[test]
+    fn ctrl_o_returns_from_jumps() {
+        let mut app = App::new(
+            (1..=5)
+                .map(|i| Issue {
+                    id: format!("OPS-{i}"),
+                    ..Issue::new(format!("Issue {i}"), String::new())
+                })
+                .collect(),
+        );
+        update(&mut app, key(KeyCode::Char('j')));
+        update(&mut app, key(KeyCode::Char('G')));
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-5");
+
+        update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)));
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-2");
+        update(&mut app, key(KeyCode::Tab));
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-5");
+    }
+
+    #[test]
+    fn marks_follow_the_issue() {
+        let mut app = App::new(
+            (1..=5)
+                .map(|i| Issue {
+                    id: format!("OPS-{i}"),
+                    ..Issue::new(format!("Issue {i}"), String::new())
+                })
+                .collect(),
+        );
+        let typing = |app: &mut App, keys: &str| {
+            for c in keys.chars() {
+                update(app, key(KeyCode::Char(c)));
+            }
+        };
+        typing(&mut app, "jma");
+        assert_eq!(app.notice.as_deref(), Some("Marked OPS-2 as a"));
+        typing(&mut app, "G'a");
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-2");
+
+        // Still the same issue after the list is sorted differently
+        app.issues.reverse();
+        app.refresh_visible();
+        typing(&mut app, "g`a");
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-2");
+        assert_eq!(app.list_state.selected(), Some(3));
+        typing(&mut app, "'b");
+        assert_eq!(app.notice.as_deref(), Some("Nothing is marked as b"));
+        update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)));
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-5");
+    }
+
+    #[test]
+    fn enter_opens_referenced_issues() {
+        let reference = |key: &str| IssueRef {
+            key: key.to_string(),
+            summary: String::new(),
+        };
+        let mut app = App::new(vec![
+            Issue {
+                id: "OPS-1".to_string(),
+                subtasks: vec![reference("OPS-2"), reference("OPS-9")],
+                ..Issue::new("Epic", "")
+            },
+            Issue {
+                id: "OPS-2".to_string(),
+                ..Issue::new("Listed", "")
+            },
+        ]);
+        // The details show the time in status and the watchers of the opened issue
+        assert_eq!(
+            update(&mut app, key(KeyCode::Enter)),
+            Command::Batch(vec![
+                Command::None,
+                Command::Batch(vec![
+                    Command::FetchHistory { key: "OPS-2".to_string() },
+                    Command::FetchWatchers { key: "OPS-2".to_string() },
+                ])
+            ])
+        );
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-2");
+
+        update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)));
+        update(&mut app, key(KeyCode::Char('}')));
+        assert_eq!(update(&mut app, key(KeyCode::Enter)), Command::FetchIssue {
+            key: "OPS-9".to_string()
+        });
+        update(&mut app, Msg::IssueFetched {
+            key: "OPS-9".to_string(),
+            result: Ok(FakeClient::issue("OPS-9", "Unlisted", "To Do")),
+        });
+        assert_eq!(app.selected_issue().unwrap().summary, "Unlisted");
+        assert!(app.sidebar_visible);
+    }
+
+    #[test]
+    fn timeline_scrolls_only_when_shown() {
+        let mut app = app_with(3);
+        update(&mut app, key(KeyCode::Char('+')));
+        update(&mut app, key(KeyCode::Char('-')));
+        assert_eq!(app.timeline, Default::default());
+
+        update(&mut app, key(KeyCode::Char('v')));
+        update(&mut app, key(KeyCode::Char('t')));
+        assert_eq!(app.view, View::Timeline);
+        update(&mut app, key(KeyCode::Char('-')));
+        assert_eq!(app.timeline.days_per_column(), 3);
+        // Moving through the issues works as in the list
+        update(&mut app, key(KeyCode::Char('j')));
+        assert_eq!(app.list_state.selected(), Some(1));
+
+        update(&mut app, key(KeyCode::Char('v')));
+        update(&mut app, key(KeyCode::Char('l')));
+        assert_eq!(app.view, View::List);
+    }
+
+    #[test]
+    fn epics_list_their_children() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-100".to_string(),
+            issue_type: Some("Epic".to_string()),
+            ..Issue::new("Reliability", "")
+        }]);
+        let command = update(&mut app, key(KeyCode::Char('s')));
+        assert_eq!(
+            command,
+            Command::Batch(vec![
+                Command::None,
+                Command::Batch(vec![
+                    Command::FetchHistory { key: "OPS-100".to_string() },
+                    Command::FetchChildren { key: "OPS-100".to_string() },
+                    Command::FetchWatchers { key: "OPS-100".to_string() },
+                ])
+            ])
+        );
+        update(&mut app, Msg::Children {
+            key: "OPS-100".to_string(),
+            result: Ok(vec![
+                FakeClient::issue("OPS-1", "Failover", "Done"),
+                FakeClient::issue("OPS-2", "Backups", "To Do"),
+            ]),
+        });
+        // Fetched only once
+        assert_eq!(update(&mut app, key(KeyCode::Char('j'))), Command::None);
+
+        update(&mut app, key(KeyCode::Char('}')));
+        assert_eq!(update(&mut app, key(KeyCode::Enter)), Command::FetchIssue {
+            key: "OPS-2".to_string()
+        });
+    }
+
+    #[test]
+    fn attachments_open_after_downloading() {
+        let attachment = Attachment {
+            id: "30001".to_string(),
+            filename: "failover.log".to_string(),
+            size: 2048,
+            url: "https://example.invalid/attachment/30001".to_string(),
+        };
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            parent: Some(IssueRef {
+                key: "OPS-100".to_string(),
+                summary: String::new(),
+            }),
+            attachments: vec![attachment.clone()],
+            ..Issue::new("Failover", "")
+        }]);
+        assert_eq!(update(&mut app, key(KeyCode::Char('o'))), Command::OpenInBrowser {
+            key: "OPS-100".to_string()
+        });
+
+        // Attachments come after the references
+        update(&mut app, key(KeyCode::Char('}')));
+        assert_eq!(update(&mut app, key(KeyCode::Enter)), Command::DownloadAttachment {
+            attachment
+        });
+        let command = update(&mut app, Msg::AttachmentDownloaded {
+            filename: "failover.log".to_string(),
+            result: Ok(PathBuf::from("/cache/failover.log")),
+        });
+        assert_eq!(command, Command::Open {
+            target: "/cache/failover.log".to_string()
+        });
+
+        assert_eq!(update(&mut app, key(KeyCode::Char('O'))), Command::OpenInBrowser {
+            key: "OPS-1".to_string()
+        });
+    }
+
+    #[test]
+    fn copies_from_the_details() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            parent: Some(IssueRef {
+                key: "OPS-100".to_string(),
+                summary: "Resilience".to_string(),
+            }),
+            ..Issue::new("Failover", "Drain the primary\nSwitch over")
+        }]);
+        let copy = |app: &mut App, keys: &str| {
+            let mut command = Command::None;
+            for c in keys.chars() {
+                command = update(app, key(KeyCode::Char(c)));
+            }
+            command
+        };
+        assert_eq!(copy(&mut app, "yd"), Command::Copy {
+            text: "Drain the primary\nSwitch over".to_string()
+        });
+        assert_eq!(app.notice.as_deref(), Some("Copied the description of OPS-1"));
+        assert_eq!(copy(&mut app, "yi"), Command::Copy { text: "OPS-100".to_string() });
+
+        // The last lines of the details are the description
+        assert_eq!(copy(&mut app, "ys"), Command::None);
+        assert!(app.selection.is_some());
+        assert_eq!(copy(&mut app, "Gvky"), Command::Copy {
+            text: "Drain the primary\nSwitch over".to_string()
+        });
+        assert!(app.selection.is_none());
+        assert_eq!(app.notice.as_deref(), Some("Copied 2 lines"));
+    }
+
+    #[test]
+    fn attaches_images_from_the_clipboard() {
+        let mut app =
+            App::new(vec![Issue::from_jira(&FakeClient::issue("OPS-1", "Failover", "To Do"))]);
+        let Command::AttachClipboardImage { key: issue, filename } =
+            update(&mut app, key(KeyCode::Char('p')))
+        else {
+            panic!("nothing is attached");
+        };
+        assert_eq!(issue, "OPS-1");
+        assert!(filename.starts_with("screenshot-") && filename.ends_with(".png"));
+
+        let mut bean = FakeClient::issue("OPS-1", "Failover", "To Do");
+        let attachment = json!([{
+            "id": "1",
+            "filename": filename,
+            "size": 2048,
+            "content": "https://example.atlassian.net/rest/api/3/attachment/content/1",
+        }]);
+        bean.fields
+            .as_mut()
+            .unwrap()
+            .insert("attachment".to_string(), attachment);
+        update(&mut app, Msg::ImageAttached {
+            key: "OPS-1".to_string(),
+            filename: filename.clone(),
+            result: Ok(bean),
+        });
+        assert_eq!(app.issues[0].attachments.len(), 1);
+        assert_eq!(app.notice, Some(format!("Attached {filename} to OPS-1")));
+
+        update(&mut app, Msg::ImageAttached {
+            key: "OPS-1".to_string(),
+            filename,
+            result: Err(Error::Io(std::io::Error::other("no image on the clipboard"))),
+        });
+        assert!(app.error.unwrap().contains("no image on the clipboard"));
+    }
+
+    #[test]
+    fn webhook_events_update_the_list() {
+        let mut app = App::new(
+            (0..3)
+                .map(|i| Issue::from_jira(&FakeClient::issue(&format!("OPS-{i}"), "", "To Do")))
+                .collect(),
+        );
+        update(&mut app, key(KeyCode::Char('G')));
+
+        let changed = |key: &str, summary: &str, created| {
+            Msg::Webhook(WebhookEvent::IssueChanged {
+                issue: Box::new(FakeClient::issue(key, summary, "Done")),
+                created,
+            })
+        };
+        update(&mut app, changed("OPS-1", "Renamed", false));
+        assert_eq!(app.issues[1].summary, "Renamed");
+        // Edits of unlisted issues don't concern the list
+        update(&mut app, changed("OPS-8", "Elsewhere", false));
+        update(&mut app, changed("OPS-9", "Brand new", true));
+        assert_eq!(app.issues.len(), 4);
+        // Nor are issues created in other projects
+        update(&mut app, changed("WEB-1", "Elsewhere", true));
+        assert_eq!(app.issues.len(), 4);
+
+        update(&mut app, Msg::Webhook(WebhookEvent::IssueDeleted { key: "OPS-0".to_string() }));
+        assert_eq!(app.issues.len(), 3);
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-2");
+    }
+
+    #[test]
+    fn init_opens_the_issue_given_on_the_command_line() {
+        let mut app = app_with(2);
+        let command = init(&mut app, None, Some(2), Some("OPS-7"));
+        assert_eq!(command, Command::FetchIssue { key: "OPS-7".to_string() });
+        assert!(app.sidebar_visible);
+
+        update(&mut app, Msg::IssueFetched {
+            key: "OPS-7".to_string(),
+            result: Ok(FakeClient::issue("OPS-7", "Linked from chat", "To Do")),
+        });
+        assert_eq!(app.selected_issue().unwrap().id, "OPS-7");
+
+        // Not listed twice when the search results include it after all
+        update(&mut app, Msg::Fetch {
+            generation: 0,
+            event: FetchEvent::Page {
+                issues: vec![FakeClient::issue("OPS-7", "Linked from chat", "To Do")],
+                total: Some(3),
+            },
+        });
+        assert_eq!(app.issues.len(), 3);
+    }
+}
@@ -0,0 +1,264 @@
+//! Picking users from what Jira's user search finds as their name is typed, for conditions
+//! on people in the query builder, for assigning the selected issue, and for adding watchers
+//! to it.
+
+use super::{
+    App, BulkChange, Command, Loadable, OpenPicker, PickerPurpose, filter_builder, outbox,
+};
+use crate::{
+    error::Result,
+    jira::{
+        User,
+        jql::{self, JqlField, Operator},
+    },
+    ui::picker::Picker,
+};
+
+/// The choice in the assignee picker that leaves the issue unassigned.
+const UNASSIGNED: &str = "Unassigned";
+
+/// The choice in the query builder standing for whoever runs the search.
+const CURRENT_USER: &str = "currentUser()";
+
+/// Asks who `field` should compare to in a new query condition. Anything else can be typed,
+/// like another JQL function.
+pub(super) fn pick_for_query(app: &mut App, field: JqlField, operator: Operator) {
+    let title = format!("{} {} …", field.label, operator.as_str());
+    open(
+        app,
+        PickerPurpose::JqlUser(field, operator),
+        Picker::new(title, vec![CURRENT_USER.to_string()]).allow_custom(),
+    );
+}
+
+/// Asks who to assign the selected issue to.
+pub(super) fn pick_assignee(app: &mut App) {
+    let Some(key) = app.selected_key() else {
+        return;
+    };
+    let title = format!("Assign {key} to");
+    open(app, PickerPurpose::Assignee, Picker::new(title, vec![UNASSIGNED.to_string()]));
+}
+
+pub(super) fn open(app: &mut App, purpose: PickerPurpose, picker: Picker) {
+    app.found_users.clear();
+    app.picker = Some(OpenPicker { purpose, picker });
+}
+
+/// Assigns the selected issue to the signed-in user, fetching who that is the first time.
+pub(super) fn assign_to_me(app: &mut App) -> Command {
+    let Some(key) = app.selected_key() else {
+        return Command::None;
+    };
+    let name = match &app.me {
+        Some(Loadable::Loaded(me)) => me.display_name.clone(),
+        Some(Loadable::Loading) => {
+            app.assign_to_me = Some(key);
+            return Command::None;
+        }
+        Some(Loadable::Failed(_)) | None => {
+            app.me = Some(Loadable::Loading);
+            app.assign_to_me = Some(key);
+            return Command::FetchMyself;
+        }
+    };
+    app.notice = Some(format!("Assigning {key} to you…"));
+    outbox::queue(app, vec![BulkChange {
+        key,
+        status: None,
+        assignee: Some(Some(name)),
+        points: None,
+        parent: None,
+        flagged: None,
+        priority: None,
+    }])
+}
+
+/// Takes the signed-in user, to list their issues under filters about them and assign them
+/// the issue that waited for them.
+pub(super) fn me_loaded(app: &mut App, result: Result<User>) -> Command {
+    let waiting = app.assign_to_me.take();
+    match result {
+        Ok(user) => {
+            app.me = Some(Loadable::Loaded(user));
+            app.refresh_visible();
+        }
+        Err(e) => {
+            app.error = Some(format!("Failed to load your account: {e}"));
+            app.me = Some(Loadable::Failed(e.to_string()));
+            return Command::None;
+        }
+    }
+    // Still the selected issue, since that's what gets assigned
+    match waiting {
+        Some(key) if app.selected_key().as_ref() == Some(&key) => assign_to_me(app),
+        _ => Command::None,
+    }
+}
+
+/// Searches for the users matching what's typed into the open user picker. Only the users
+/// who can be assigned the issue are offered for it.
+pub(super) fn search(app: &App) -> Command {
+    let Some(open) = &app.picker else {
+        return Command::None;
+    };
+    let issue = match open.purpose {
+        PickerPurpose::JqlUser(..) | PickerPurpose::Watcher => None,
+        PickerPurpose::Assignee => app.selected_key(),
+        _ => return Command::None,
+    };
+    let query = open.picker.query.trim();
+    if query.is_empty() {
+        return Command::None;
+    }
+    Command::SearchUsers { query: query.to_string(), issue }
+}
+
+/// Lists the users a search for `query` found in the open user picker, unless something
+/// else has been typed since.
+pub(super) fn found(app: &mut App, query: &str, result: Result<Vec<User>>) {
+    let Some(open) = app
+        .picker
+        .as_mut()
+        .filter(|open| {
+            matches!(
+                open.purpose,
+                PickerPurpose::JqlUser(..) | PickerPurpose::Assignee | PickerPurpose::Watcher
+            )
+        })
+        .filter(|open| open.picker.query.trim() == query)
+    else {
+        return;
+    };
+    let users = match result {
+        Ok(users) => users,
+        Err(e) => {
+            app.error = Some(format!("Failed to search for users: {e}"));
+            return;
+        }
+    };
+    let fixed = match open.purpose {
+        PickerPurpose::Assignee => Some(UNASSIGNED),
+        PickerPurpose::JqlUser(..) => Some(CURRENT_USER),
+        _ => None,
+    };
+    open.picker.items = fixed
+        .map(str::to_string)
+        .into_iter()
+        .chain(users.iter().map(|user| user.display_name.clone()))
+        .collect();
+    open.picker.selected = 0;
+    app.found_users = users;
+}
+
+/// Adds the condition on the picked user to the query, or assigns the issue to them.
+pub(super) fn picked(app: &mut App, purpose: PickerPurpose, choice: &str) -> Command {
+    let user = app
+        .found_users
+        .iter()
+        .find(|user| user.display_name == choice);
+    match purpose {
+        PickerPurpose::JqlUser(field, operator) => {
+            // Queries refer to people by account ID; anything typed goes in as it is
+            let value = user.map_or(choice, |user| user.account_id.as_str());
+            let clause = jql::clause(&field, operator, value);
+            filter_builder::edit_query(app, &clause);
+            Command::None
+        }
+        PickerPurpose::Assignee => {
+            let Some(key) = app.selected_key() else {
+                return Command::None;
+            };
+            let assignee = (choice != UNASSIGNED).then(|| choice.to_string());
+            app.notice = Some(match &assignee {
+                Some(name) => format!("Assigning {key} to {name}…"),
+                None => format!("Unassigning {key}…"),
+            });
+            outbox::queue(app, vec![BulkChange {
+                key,
+                status: None,
+                assignee: Some(assignee),
+                points: None,
+                parent: None,
+                flagged: None,
+                priority: None,
+            }])
+        }
+        _ => Command::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{app::update, error::Error, ui::issue::Issue};
+
+    fn user(account_id: &str, display_name: &str) -> User {
+        User {
+            account_id: account_id.to_string(),
+            display_name: display_name.to_string(),
+        }
+    }
+
+    fn type_query(app: &mut App, query: &str) -> Command {
+        app.picker.as_mut().unwrap().picker.query = query.to_string();
+        search(app)
+    }
+
+    #[test]
+    fn picks_users_from_the_search() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            ..Issue::new("Failover", "")
+        }]);
+        app.jql = "project = OPS".to_string();
+        let field = *jql::FIELDS
+            .iter()
+            .find(|field| field.name == "assignee")
+            .unwrap();
+        pick_for_query(&mut app, field, Operator::Equals);
+        assert_eq!(type_query(&mut app, " "), Command::None);
+        assert_eq!(type_query(&mut app, "an"), Command::SearchUsers {
+            query: "an".to_string(),
+            issue: None,
+        });
+        type_query(&mut app, "ana");
+
+        // Late results of an earlier search are of no use
+        found(&mut app, "an", Ok(vec![user("5b10a", "Ana Lima"), user("5b10b", "Dan Brown")]));
+        assert_eq!(app.picker.as_ref().unwrap().picker.items, [CURRENT_USER]);
+        found(&mut app, "ana", Ok(vec![user("5b10a", "Ana Lima")]));
+        assert_eq!(app.picker.as_ref().unwrap().picker.items, [CURRENT_USER, "Ana Lima"]);
+        let open = app.picker.take().unwrap();
+        picked(&mut app, open.purpose, "Ana Lima");
+        assert_eq!(app.input, "project = OPS AND assignee = \"5b10a\"");
+
+        // Assigning searches among those who can be assigned the issue
+        pick_assignee(&mut app);
+        assert_eq!(type_query(&mut app, "ana"), Command::SearchUsers {
+            query: "ana".to_string(),
+            issue: Some("OPS-1".to_string()),
+        });
+        update(&mut app, crate::app::Msg::UsersFound {
+            query: "ana".to_string(),
+            result: Err(Error::Network("timeout".to_string())),
+        });
+        assert!(app.error.is_some());
+        let open = app.picker.take().unwrap();
+        let command = picked(&mut app, open.purpose, UNASSIGNED);
+        let Command::Batch(commands) = command else {
+            panic!("assignment isn't sent");
+        };
+        assert!(commands.contains(&Command::ApplyBulkEdit {
+            changes: vec![BulkChange {
+                key: "OPS-1".to_string(),
+                status: None,
+                assignee: Some(None),
+                points: None,
+                parent: None,
+                flagged: None,
+                priority: None,
+            }],
+        }));
+    }
+}
@@ -0,0 +1,181 @@
+//! The watchers of the selected issue: listed in its details, with anyone found by name added
+//! to them and any of them taken off, to route an issue to the people who should follow it.
+
+use super::{App, Command, Loadable, PickerPurpose, users};
+use crate::{error::Result, jira::User, ui::picker::Picker};
+
+/// Asks who to add as a watcher of the selected issue, searching as their name is typed.
+pub(super) fn pick_watcher(app: &mut App) {
+    let Some(key) = app.selected_key() else {
+        return;
+    };
+    let title = format!("Add a watcher to {key}");
+    users::open(app, PickerPurpose::Watcher, Picker::new(title, Vec::new()));
+}
+
+/// Asks which of the watchers of the selected issue to take off, once they're known.
+pub(super) fn pick_unwatch(app: &mut App) -> Command {
+    let Some(key) = app.selected_key() else {
+        return Command::None;
+    };
+    let names = match app.watchers.get(&key) {
+        Some(Loadable::Loaded(watchers)) => watchers
+            .iter()
+            .map(|user| user.display_name.clone())
+            .collect::<Vec<_>>(),
+        Some(Loadable::Loading) => {
+            app.notice = Some(format!("Still loading the watchers of {key}"));
+            return Command::None;
+        }
+        Some(Loadable::Failed(_)) | None => {
+            app.watchers.insert(key.clone(), Loadable::Loading);
+            app.notice = Some(format!("Loading the watchers of {key}…"));
+            return Command::FetchWatchers { key };
+        }
+    };
+    if names.is_empty() {
+        app.notice = Some(format!("Nobody is watching {key}"));
+        return Command::None;
+    }
+    let title = format!("Remove a watcher from {key}");
+    users::open(app, PickerPurpose::Unwatch, Picker::new(title, names));
+    Command::None
+}
+
+/// Adds the picked user to the watchers of the selected issue, or takes the picked watcher
+/// off them.
+pub(super) fn picked(app: &mut App, purpose: PickerPurpose, choice: &str) -> Command {
+    let Some(key) = app.selected_key() else {
+        return Command::None;
+    };
+    let (candidates, watching) = match (purpose, app.watchers.get(&key)) {
+        (PickerPurpose::Watcher, _) => (app.found_users.as_slice(), true),
+        (PickerPurpose::Unwatch, Some(Loadable::Loaded(watchers))) => (watchers.as_slice(), false),
+        _ => return Command::None,
+    };
+    let Some(user) = candidates.iter().find(|user| user.display_name == choice) else {
+        return Command::None;
+    };
+    let user = user.clone();
+    app.notice = Some(match watching {
+        true => format!("Adding {} to the watchers of {key}…", user.display_name),
+        false => format!("Removing {} from the watchers of {key}…", user.display_name),
+    });
+    Command::ChangeWatcher { key, user, watching }
+}
+
+/// Takes the watchers of an issue that arrived.
+pub(super) fn loaded(app: &mut App, key: String, result: Result<Vec<User>>) {
+    let watchers = match result {
+        Ok(watchers) => Loadable::Loaded(watchers),
+        Err(e) => Loadable::Failed(e.to_string()),
+    };
+    app.watchers.insert(key, watchers);
+}
+
+/// Reloads the watchers of the issue once one was added or removed, to show them as Jira
+/// has them.
+pub(super) fn changed(
+    app: &mut App,
+    key: String,
+    user: &User,
+    watching: bool,
+    result: Result<()>,
+) -> Command {
+    let name = &user.display_name;
+    match result {
+        Ok(()) => {
+            app.notice = Some(match watching {
+                true => format!("{name} is now watching {key}"),
+                false => format!("{name} is no longer watching {key}"),
+            });
+            app.watchers.insert(key.clone(), Loadable::Loading);
+            Command::FetchWatchers { key }
+        }
+        Err(e) => {
+            app.error = Some(match watching {
+                true => format!("Failed to add {name} to the watchers of {key}: {e}"),
+                false => format!("Failed to remove {name} from the watchers of {key}: {e}"),
+            });
+            Command::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        ui::issue::Issue,
+    };
+
+    fn user(account_id: &str, display_name: &str) -> User {
+        User {
+            account_id: account_id.to_string(),
+            display_name: display_name.to_string(),
+        }
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> Command {
+        update(app, Msg::Key(KeyEvent::from(code)))
+    }
+
+    #[test]
+    fn adds_and_removes_watchers() {
+        let mut app = App::new(vec![Issue {
+            id: "OPS-1".to_string(),
+            ..Issue::new("Failover", "")
+        }]);
+        let ana = user("ana", "Ana Lima");
+        let bo = user("bo", "Bo Berg");
+
+        // Taking one off waits for who's watching
+        assert_eq!(press(&mut app, KeyCode::Char('W')), Command::FetchWatchers {
+            key: "OPS-1".to_string()
+        });
+        update(&mut app, Msg::Watchers {
+            key: "OPS-1".to_string(),
+            result: Ok(vec![ana.clone()]),
+        });
+
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.picker.as_ref().unwrap().purpose, PickerPurpose::Watcher);
+        let search = press(&mut app, KeyCode::Char('b'));
+        assert_eq!(search, Command::SearchUsers { query: "b".to_string(), issue: None });
+        update(&mut app, Msg::UsersFound {
+            query: "b".to_string(),
+            result: Ok(vec![bo.clone()]),
+        });
+        assert_eq!(app.picker.as_ref().unwrap().picker.items, ["Bo Berg"]);
+        assert_eq!(press(&mut app, KeyCode::Enter), Command::ChangeWatcher {
+            key: "OPS-1".to_string(),
+            user: bo.clone(),
+            watching: true,
+        });
+        let command = update(&mut app, Msg::WatcherChanged {
+            key: "OPS-1".to_string(),
+            user: bo.clone(),
+            watching: true,
+            result: Ok(()),
+        });
+        assert_eq!(command, Command::FetchWatchers { key: "OPS-1".to_string() });
+        assert_eq!(app.notice.as_deref(), Some("Bo Berg is now watching OPS-1"));
+        update(&mut app, Msg::Watchers {
+            key: "OPS-1".to_string(),
+            result: Ok(vec![ana.clone(), bo]),
+        });
+
+        press(&mut app, KeyCode::Char('W'));
+        let open = app.picker.as_ref().unwrap();
+        assert_eq!(open.purpose, PickerPurpose::Unwatch);
+        assert_eq!(open.picker.items, ["Ana Lima", "Bo Berg"]);
+        assert_eq!(press(&mut app, KeyCode::Enter), Command::ChangeWatcher {
+            key: "OPS-1".to_string(),
+            user: ana,
+            watching: false,
+        });
+    }
+}
@@ -0,0 +1,136 @@
+//! Summing up the listed issues per assignee, to check nobody has more planned than they can
+//! finish.
+
+use super::{
+    App, View,
+    filter::{Filter, FilterKind},
+    move_row_cursor,
+};
+use crate::ui::input::NormalModeAction;
+
+/// The part of the listed issues assigned to one person.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workload {
+    /// `None` for the unassigned issues.
+    pub assignee: Option<String>,
+    pub issues: usize,
+    /// Issues that aren't done yet.
+    pub open: usize,
+    pub points: f64,
+    /// Story points of the issues that aren't done yet, which is what counts against the
+    /// capacity.
+    pub open_points: f64,
+}
+
+impl Workload {
+    pub fn is_over(&self, capacity: Option<f64>) -> bool {
+        capacity.is_some_and(|capacity| self.open_points > capacity)
+    }
+}
+
+/// The workload of everyone the listed issues are assigned to, by name, with the unassigned
+/// issues last.
+pub fn workloads(app: &App) -> Vec<Workload> {
+    let mut workloads: Vec<Workload> = Vec::new();
+    for issue in app.visible_issues() {
+        let index = match workloads
+            .iter()
+            .position(|workload| workload.assignee == issue.assignee)
+        {
+            Some(index) => index,
+            None => {
+                workloads.push(Workload {
+                    assignee: issue.assignee.clone(),
+                    issues: 0,
+                    open: 0,
+                    points: 0.0,
+                    open_points: 0.0,
+                });
+                workloads.len() - 1
+            }
+        };
+        let workload = &mut workloads[index];
+        let points = issue.story_points.unwrap_or_default();
+        workload.issues += 1;
+        workload.points += points;
+        if !issue.is_done() {
+            workload.open += 1;
+            workload.open_points += points;
+        }
+    }
+    workloads.sort_by(|a, b| {
+        (a.assignee.is_none(), &a.assignee).cmp(&(b.assignee.is_none(), &b.assignee))
+    });
+    workloads
+}
+
+/// Handles the keys that act on the workload rows instead of the issues. Returns whether
+/// `action` was one of them.
+pub(super) fn handle(app: &mut App, action: NormalModeAction) -> bool {
+    match action {
+        NormalModeAction::OpenItem => show_issues(app),
+        action => {
+            let len = workloads(app).len();
+            return move_row_cursor(&mut app.workload_cursor, len, action, app.wrap_around);
+        }
+    }
+    true
+}
+
+/// Narrows the list down to the issues of the selected person and switches to it.
+fn show_issues(app: &mut App) {
+    let Some(workload) = workloads(app).into_iter().nth(app.workload_cursor) else {
+        return;
+    };
+    let Some(assignee) = workload.assignee else {
+        app.notice = Some("Unassigned issues can't be narrowed down to".to_string());
+        return;
+    };
+    let filter = Filter::new(FilterKind::Assignee, assignee);
+    if !app.filters.contains(&filter) {
+        app.filters.push(filter);
+        app.refresh_visible();
+    }
+    app.view = View::List;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::issue::{Issue, Status, StatusCategory};
+
+    fn issue(assignee: Option<&str>, points: f64, done: bool) -> Issue {
+        Issue {
+            assignee: assignee.map(str::to_string),
+            story_points: Some(points),
+            status: done.then(|| Status {
+                name: "Done".to_string(),
+                category: StatusCategory::Done,
+            }),
+            ..Issue::new("", "")
+        }
+    }
+
+    #[test]
+    fn sums_up_per_assignee() {
+        let mut app = App::new(vec![
+            issue(Some("Grace"), 5.0, false),
+            issue(None, 1.0, false),
+            issue(Some("Ada"), 3.0, true),
+            issue(Some("Grace"), 8.0, false),
+        ]);
+        let workloads = workloads(&app);
+        let names: Vec<_> = workloads.iter().map(|w| w.assignee.as_deref()).collect();
+        assert_eq!(names, [Some("Ada"), Some("Grace"), None]);
+        assert_eq!((workloads[0].issues, workloads[0].open, workloads[0].points), (1, 0, 3.0));
+        assert_eq!(workloads[1].open_points, 13.0);
+        assert!(workloads[1].is_over(Some(10.0)));
+        assert!(!workloads[1].is_over(None));
+
+        app.view = View::Workload;
+        handle(&mut app, NormalModeAction::Jump(1));
+        handle(&mut app, NormalModeAction::OpenItem);
+        assert_eq!(app.view, View::List);
+        assert_eq!(app.visible_issues().count(), 2);
+    }
+}
@@ -0,0 +1,255 @@
+//! Command-line arguments.
+
+use std::{
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+
+use crate::{
+    config::{Config, ProfileConfig},
+    error::{Error, Result},
+    export::{DocumentFormat, ExportFormat},
+    report::ReportFormat,
+};
+
+/// A terminal user interface for viewing and creating Jira issues.
+#[derive(Debug, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Issue to open on start, like PROJ-123, even if it isn't among the assigned issues.
+    #[arg(value_parser = parse_issue_key)]
+    pub issue: Option<String>,
+    /// Don't use colors; show states as text markers instead. Also enabled by setting the
+    /// NO_COLOR environment variable.
+    #[arg(long)]
+    pub no_color: bool,
+    /// Profile from the config file to sign in with, instead of the configured one.
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    /// Search with every profile of the config at once, listing the issues of all of their
+    /// instances together. The search and view to start with are still those of `--profile`
+    /// or the configured one.
+    #[arg(long)]
+    pub all_profiles: bool,
+    /// Config file to read instead of the one in the config directory.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Base URL of the Jira instance, instead of JIRA_TUI_URL or the profile's.
+    #[arg(long, global = true)]
+    pub url: Option<String>,
+    /// Username, instead of JIRA_TUI_USER or the profile's.
+    #[arg(long, global = true)]
+    pub user: Option<String>,
+    /// Read the token from standard input, like from a password manager, instead of from
+    /// JIRA_TUI_TOKEN, so it doesn't show up in the process list or shell history.
+    #[arg(long, global = true)]
+    pub token_stdin: bool,
+}
+
+/// Things to do instead of starting the interface.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print issues in a format other planning tools import.
+    Export {
+        #[arg(long, short, value_enum)]
+        format: ExportFormat,
+        /// JQL search to export, instead of the issues assigned to you.
+        #[arg(long)]
+        jql: Option<String>,
+    },
+    /// Print an issue with its fields, description, and comments, to paste into chat or docs.
+    View {
+        #[arg(value_parser = parse_issue_key)]
+        key: String,
+        #[arg(long, short, value_enum, default_value = "md")]
+        format: DocumentFormat,
+    },
+    /// Create an issue for each row of a CSV file with the columns summary, type,
+    /// description, labels, and points.
+    Import {
+        file: PathBuf,
+        /// Key of the project to create the issues in.
+        #[arg(long, short)]
+        project: String,
+        /// Issue type of the rows that don't name one.
+        #[arg(long, default_value = "Task")]
+        issue_type: String,
+        /// Only check the file and list the issues it would create.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create an issue from a text file, like notes taken in an editor: the first line is the
+    /// summary and the rest the description. Prints the key and link of the new issue.
+    Create {
+        /// File to read, or `-` for standard input.
+        #[arg(long, value_name = "PATH")]
+        from: PathBuf,
+        /// Key of the project to create the issue in, instead of the profile's.
+        #[arg(long, short)]
+        project: Option<String>,
+        #[arg(long = "type", default_value = "Task")]
+        issue_type: String,
+        /// Label to add; can be given more than once.
+        #[arg(long = "label", short, value_name = "LABEL")]
+        labels: Vec<String>,
+        /// Key of the epic to create the issue in, or of the issue to make it a subtask of.
+        #[arg(long, value_name = "KEY")]
+        parent: Option<String>,
+    },
+    /// Sum up the time you logged per day and issue, for timesheets.
+    Worklog {
+        /// First day, like 2024-05-06, instead of the Monday of this week.
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last day, instead of today.
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        #[arg(long, short, value_enum, default_value = "text")]
+        format: ReportFormat,
+    },
+    /// List the issues you moved, commented on, or logged time on lately, to paste into
+    /// standup notes.
+    Standup {
+        /// First day to look at, instead of the last work day.
+        #[arg(long)]
+        since: Option<NaiveDate>,
+    },
+    /// Sum up what came of the issues of a sprint: those completed, those carried over, and
+    /// those added after it started, with their story points, for the retro.
+    Sprint {
+        /// Name of the sprint, like "Sprint 5".
+        name: String,
+        #[arg(long, short, value_enum, default_value = "text")]
+        format: ReportFormat,
+    },
+    /// Check the config file, the credentials, and the connection to Jira, to find out why
+    /// the app won't start.
+    Doctor,
+}
+
+impl Cli {
+    /// Whether colors are turned off, by flag or by `NO_COLOR` (see <https://no-color.org>).
+    pub fn no_color(&self) -> bool {
+        self.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+    }
+
+    /// The config file given with `--config`, which has to exist, or else the usual one.
+    pub fn config_path(&self) -> Option<PathBuf> {
+        self.config.clone().or_else(Config::path)
+    }
+
+    /// Loads the config file given with `--config`, or else the usual one.
+    pub fn load_config(&self) -> Result<Config> {
+        match &self.config {
+            Some(path) if !path.exists() => {
+                Err(Error::Config(format!("{}: no such file", path.display())))
+            }
+            Some(path) => Config::load_from(path),
+            None => Config::load(),
+        }
+    }
+
+    /// The profile to sign in with, with what the flags set in place of its settings. Reads
+    /// the token from standard input with `--token-stdin`.
+    pub fn profile(&self, settings: &Config) -> Result<ProfileConfig> {
+        let mut profile = settings.profile(self.profile.as_deref())?;
+        profile.url = self.url.clone().or(profile.url);
+        profile.user = self.user.clone().or(profile.user);
+        if self.token_stdin {
+            let mut token = String::new();
+            io::stdin().read_to_string(&mut token)?;
+            match token.trim() {
+                "" => return Err(Error::Input("no token on standard input".to_string())),
+                token => profile.token = Some(token.to_string()),
+            }
+        }
+        Ok(profile)
+    }
+}
+
+/// Accepts issue keys like `PROJ-123` in any case, returning them uppercase.
+fn parse_issue_key(arg: &str) -> Result<String, String> {
+    let key = arg.trim().to_uppercase();
+    let valid = key.split_once('-').is_some_and(|(project, number)| {
+        project.starts_with(|c: char| c.is_ascii_alphabetic())
+            && project
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+    });
+    if valid {
+        Ok(key)
+    } else {
+        Err(format!("{arg:?} is not an issue key like PROJ-123"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_issue_keys() {
+        assert_eq!(parse_issue_key("ops-12").as_deref(), Ok("OPS-12"));
+        assert_eq!(parse_issue_key("A1_B-7").as_deref(), Ok("A1_B-7"));
+        assert!(parse_issue_key("12-OPS").is_err());
+        assert!(parse_issue_key("OPS-").is_err());
+        assert!(parse_issue_key("https://x.atlassian.net/browse/OPS-1").is_err());
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        let cli = Cli::parse_from(["jira-tui", "ops-1"]);
+        assert_eq!(cli.issue.as_deref(), Some("OPS-1"));
+        assert!(cli.command.is_none());
+
+        let cli = Cli::parse_from(["jira-tui", "export", "-f", "org", "--jql", "project = OPS"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Export {
+                format: ExportFormat::Org,
+                jql: Some(_)
+            })
+        ));
+
+        let cli = Cli::parse_from(["jira-tui", "import", "backlog.csv", "-p", "OPS"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Import { project, issue_type, dry_run: false, .. })
+                if project == "OPS" && issue_type == "Task"
+        ));
+
+        let cli = Cli::parse_from(["jira-tui", "view", "ops-1", "--format", "ansi"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::View { key, format: DocumentFormat::Ansi }) if key == "OPS-1"
+        ));
+
+        let cli = Cli::parse_from(["jira-tui", "worklog", "--from", "2024-05-06", "-f", "csv"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Worklog { from: Some(from), to: None, format: ReportFormat::Csv })
+                if from.to_string() == "2024-05-06"
+        ));
+
+        let cli = Cli::parse_from(["jira-tui", "doctor", "--profile", "oss"]);
+        assert!(matches!(cli.command, Some(Command::Doctor)));
+        assert_eq!(cli.profile.as_deref(), Some("oss"));
+
+        let cli =
+            Cli::parse_from(["jira-tui", "--url", "https://jira.example.org", "--user", "me"]);
+        let profile = cli.profile(&Config::default()).unwrap();
+        assert_eq!(profile.url.as_deref(), Some("https://jira.example.org"));
+        assert_eq!(profile.user.as_deref(), Some("me"));
+        assert!(cli.command.is_none());
+
+        let cli = Cli::parse_from(["jira-tui", "--config", "/nonexistent/jira-tui.toml"]);
+        assert!(cli.load_config().is_err());
+    }
+}
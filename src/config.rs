@@ -0,0 +1,539 @@
+//! User configuration, read from `config.toml` in the platform config directory
+//! (`~/.config/jira-tui/config.toml` on Linux).
+
+use std::{collections::BTreeMap, fs, io, net::SocketAddr, path::PathBuf, time::Duration};
+
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use crate::{
+    app::{View, script::Scripts},
+    error::{Error, Result},
+    jira::{ASSIGNED_JQL, AuthMethod, SearchApi, jql},
+    ui::{
+        icons::IconSet,
+        theme::{Density, ThemeName},
+        time::{self, TimeFormat},
+    },
+};
+
+/// Settings from the config file. Every field is optional in the file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Initial width of the sidebar, as a percentage of the screen width.
+    pub sidebar_width: u16,
+    /// Built-in color theme.
+    pub theme: ThemeName,
+    /// Symbols for issue types and priorities in the issue list.
+    pub icons: IconSet,
+    /// Don't use colors; show states as text markers like `[IN PROGRESS]` instead.
+    pub no_color: bool,
+    /// `compact` for a row per issue, or `comfortable` for two.
+    pub density: Density,
+    /// Rows to keep in view above and below the selected one while scrolling, like vim's
+    /// `scrolloff`.
+    pub scrolloff: usize,
+    /// Moving down from the last row goes on at the first one, and up from the first at the
+    /// last.
+    pub wrap_around: bool,
+    /// strftime format for timestamps.
+    pub datetime_format: String,
+    /// strftime format for dates, like due dates.
+    pub date_format: String,
+    /// IANA timezone (like `Europe/Amsterdam`) to show timestamps in, instead of the system's.
+    pub timezone: Option<String>,
+    /// Listen for Jira webhooks, applying the changes they report right away.
+    pub webhook: Option<WebhookConfig>,
+    /// What the app asks Jira for on its own, and how often.
+    pub polling: PollingConfig,
+    /// Search endpoint to use, instead of picking one by the Jira URL.
+    pub search_api: Option<SearchApi>,
+    /// How to sign in: `basic` with the username and an API token, or `bearer` with a
+    /// personal access token, as Jira Data Center expects.
+    pub auth: AuthMethod,
+    /// Story points one person can finish, like in a sprint. The workload view highlights
+    /// anyone with more open points than this.
+    pub workload_capacity: Option<f64>,
+    /// Days an issue can stay in one status before the time in status in the details warns
+    /// that it's stuck.
+    pub stuck_after_days: u32,
+    /// Type of the issues created from the new issue input, which the projects they're
+    /// created in need to have.
+    pub issue_type: String,
+    /// Shell commands to run on events, from `[[hooks]]` tables.
+    pub hooks: Vec<HookConfig>,
+    /// Shell commands to run on the selected issue with `!` and a key, from `[[actions]]`
+    /// tables.
+    pub actions: Vec<ActionConfig>,
+    /// Extra columns of the issue list, from `[[columns]]` tables.
+    pub columns: Vec<ColumnConfig>,
+    /// Extra quick filters, from `[[filters]]` tables.
+    pub filters: Vec<FilterConfig>,
+    /// Searches run in the background, counting their results in the status line, from
+    /// `[[alerts]]` tables.
+    pub alerts: Vec<AlertConfig>,
+    /// The profile to use when `--profile` doesn't name one.
+    pub profile: Option<String>,
+    /// Accounts to sign in with and where to start with each, from `[profiles.<name>]`
+    /// tables.
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// Where to listen for Jira webhooks, from the `[webhook]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// Address to listen on, like `0.0.0.0:8085`. Addresses other machines can reach need a
+    /// `token`.
+    pub listen: String,
+    /// If set, requests must carry it as a `token` query parameter, like
+    /// `http://devbox:8085/?token=...`.
+    pub token: Option<String>,
+}
+
+impl WebhookConfig {
+    /// Why anyone could push changes through the listener, if they could.
+    fn check(&self) -> Result<(), String> {
+        let loopback = match self.listen.parse::<SocketAddr>() {
+            Ok(addr) => addr.ip().is_loopback(),
+            Err(_) => self
+                .listen
+                .rsplit_once(':')
+                .is_some_and(|(host, _)| host == "localhost"),
+        };
+        if loopback || self.token.is_some() {
+            return Ok(());
+        }
+        Err(format!(
+            "the webhook listener on {} can be reached from other machines, so it needs a token",
+            self.listen
+        ))
+    }
+}
+
+/// Requests the app makes without being asked to, from the `[polling]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PollingConfig {
+    /// Seconds between searching again for the listed issues, or 0 to search only when asked
+    /// to.
+    pub refresh_every: u64,
+    /// Seconds between runs of the alert searches, or 0 to run them only at the start.
+    pub alerts_every: u64,
+    /// Fetch the changelogs of the issues next to the selected one too, so moving to them
+    /// shows their details right away.
+    pub prefetch: bool,
+    /// Make none of these requests at all, for metered connections.
+    pub metered: bool,
+    /// How to get your attention when a refresh finds an issue of the highest priority newly
+    /// assigned to you, or a new comment mentioning you: `bell`, `flash`, or `off`.
+    pub notify: Notify,
+}
+
+/// A way of getting the attention of someone looking elsewhere, like at another tmux pane.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Notify {
+    #[default]
+    Off,
+    /// Rings the terminal bell, which tmux and most terminals pass on.
+    Bell,
+    /// Flashes the screen for a moment.
+    Flash,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            refresh_every: 0,
+            alerts_every: 5 * 60,
+            prefetch: false,
+            metered: false,
+            notify: Notify::Off,
+        }
+    }
+}
+
+impl PollingConfig {
+    /// How long to wait between refreshes of the list, unless it isn't refreshed.
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        self.interval(self.refresh_every)
+    }
+
+    /// How long to wait between runs of the alert searches, unless they run only once.
+    pub fn alert_interval(&self) -> Option<Duration> {
+        self.interval(self.alerts_every)
+    }
+
+    pub fn prefetches(&self) -> bool {
+        self.prefetch && !self.metered
+    }
+
+    fn interval(&self, seconds: u64) -> Option<Duration> {
+        (seconds > 0 && !self.metered).then(|| Duration::from_secs(seconds))
+    }
+}
+
+/// An account and where to start with it, like separate ones for work and open source. What
+/// it leaves out comes from the environment variables and the rest of the config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProfileConfig {
+    /// The key of the `[profiles.<name>]` table, `None` without a profile.
+    #[serde(skip)]
+    pub name: Option<String>,
+    /// Base URL of the instance, instead of `JIRA_TUI_URL`.
+    pub url: Option<String>,
+    /// Username, instead of `JIRA_TUI_USER`.
+    pub user: Option<String>,
+    /// Environment variable holding the token, instead of `JIRA_TUI_TOKEN`.
+    pub token_env: Option<String>,
+    /// The token itself, only ever given on the command line.
+    #[serde(skip)]
+    pub token: Option<String>,
+    pub auth: Option<AuthMethod>,
+    /// Search to start with, instead of the unresolved issues assigned to you.
+    pub jql: Option<String>,
+    /// Project to scope the startup search and the releases and components views to.
+    pub project: Option<String>,
+    /// View to start in.
+    pub view: Option<View>,
+}
+
+impl ProfileConfig {
+    /// The search the app starts with.
+    pub fn startup_jql(&self) -> String {
+        let jql = self.jql.as_deref().unwrap_or(ASSIGNED_JQL);
+        match &self.project {
+            Some(project) => jql::with_project(jql, project),
+            None => jql.to_string(),
+        }
+    }
+}
+
+/// A shell command run whenever `event` happens. It gets the issue's fields as `JIRA_*`
+/// environment variables and as JSON on standard input.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookConfig {
+    pub event: HookEvent,
+    pub command: String,
+}
+
+/// A shell command run on the selected issue when typing `!` and `key`. It gets the issue
+/// like a hook does, and placeholders like `{key}` or `{summary}` in it stand for the
+/// issue's fields. cmd runs what they'd stand for as commands, so on Windows they aren't
+/// supported, and commands leave the `JIRA_*` variables to a script, like
+/// `powershell -File branch.ps1` reading `$env:JIRA_KEY`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActionConfig {
+    pub name: String,
+    pub key: char,
+    pub command: String,
+    /// Show what the command prints in a popup.
+    #[serde(default)]
+    pub popup: bool,
+}
+
+/// A column of the issue list, headed by `name`, showing either what a script returns for each
+/// issue (see [`crate::app::script`]) or a field of the issue's JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnConfig {
+    pub name: String,
+    pub script: Option<String>,
+    /// Dot-separated path into the issue's JSON, like `fields.customfield_10050.value`.
+    pub field: Option<String>,
+    /// Width in terminal columns.
+    #[serde(default = "default_column_width")]
+    pub width: u16,
+}
+
+fn default_column_width() -> u16 {
+    8
+}
+
+/// A quick filter listing the issues a script returns `true` for; see
+/// [`crate::app::script`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FilterConfig {
+    pub name: String,
+    pub script: String,
+}
+
+/// A search run every few minutes, like `priority = Highest AND assignee = currentUser()`,
+/// whose result count shows as a badge named `name`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertConfig {
+    pub name: String,
+    pub jql: String,
+}
+
+/// What a hook can run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    /// An issue was created, in the app or (as reported by a webhook) elsewhere.
+    Created,
+    /// An issue moved to another status.
+    Transitioned,
+    /// Another issue was selected in the list.
+    Selected,
+    /// A search finished loading all of its results.
+    Refreshed,
+}
+
+impl HookEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookEvent::Created => "created",
+            HookEvent::Transitioned => "transitioned",
+            HookEvent::Selected => "selected",
+            HookEvent::Refreshed => "refreshed",
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            sidebar_width: 40,
+            theme: ThemeName::default(),
+            icons: IconSet::default(),
+            no_color: false,
+            density: Density::default(),
+            scrolloff: 0,
+            wrap_around: false,
+            datetime_format: TimeFormat::default().datetime,
+            date_format: TimeFormat::default().date,
+            timezone: None,
+            webhook: None,
+            polling: PollingConfig::default(),
+            search_api: None,
+            auth: AuthMethod::default(),
+            workload_capacity: None,
+            stuck_after_days: 14,
+            issue_type: "Task".to_string(),
+            hooks: Vec::new(),
+            actions: Vec::new(),
+            columns: Vec::new(),
+            filters: Vec::new(),
+            alerts: Vec::new(),
+            profile: None,
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Location of the config file, if the platform has a config directory.
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("jira-tui").join("config.toml"))
+    }
+
+    /// Loads the config file, falling back to defaults if it doesn't exist.
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &std::path::Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents)
+                .map_err(|e| Error::Config(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(contents).map_err(|e| e.to_string())?;
+        config.time_format()?;
+        if let Some(webhook) = &config.webhook {
+            webhook.check()?;
+        }
+        for column in &config.columns {
+            if column.script.is_some() == column.field.is_some() {
+                return Err(format!("column {:?} needs either a script or a field", column.name));
+            }
+        }
+        Scripts::compile(&config.columns, &config.filters)?;
+        Ok(config)
+    }
+
+    /// The profile named `name`, or else the configured one. Without either, everything
+    /// comes from the environment variables and the rest of the config.
+    pub fn profile(&self, name: Option<&str>) -> Result<ProfileConfig> {
+        match name.or(self.profile.as_deref()) {
+            None => Ok(ProfileConfig::default()),
+            Some(name) => match self.profiles.get(name) {
+                Some(profile) => Ok(ProfileConfig {
+                    name: Some(name.to_string()),
+                    ..profile.clone()
+                }),
+                None => Err(Error::Config(format!("no profile named {name:?}"))),
+            },
+        }
+    }
+
+    /// The timestamp settings, or a description of what's wrong with them.
+    pub fn time_format(&self) -> Result<TimeFormat, String> {
+        for format in [&self.datetime_format, &self.date_format] {
+            if !time::is_valid_format(format) {
+                return Err(format!("invalid date/time format {format:?}"));
+            }
+        }
+        let timezone = match &self.timezone {
+            Some(name) => Some(
+                name.parse::<Tz>()
+                    .map_err(|_| format!("unknown timezone {name:?}"))?,
+            ),
+            None => None,
+        };
+        Ok(TimeFormat {
+            datetime: self.datetime_format.clone(),
+            date: self.date_format.clone(),
+            timezone,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert_eq!(Config::parse("sidebar_width = 55").unwrap().sidebar_width, 55);
+        assert!(Config::parse("sidebar_widht = 55").is_err());
+        assert_eq!(Config::parse("icons = \"nerd-font\"").unwrap().icons, IconSet::NerdFont);
+        assert_eq!(
+            Config::parse("theme = \"high-contrast\"").unwrap().theme,
+            ThemeName::HighContrast
+        );
+        assert_eq!(Config::parse("auth = \"bearer\"").unwrap().auth, AuthMethod::Bearer);
+        assert_eq!(
+            Config::parse("density = \"comfortable\"").unwrap().density,
+            Density::Comfortable
+        );
+    }
+
+    #[test]
+    fn profile_tables() {
+        let config = Config::parse(
+            "profile = \"work\"\n\
+             [profiles.work]\nurl = \"https://acme.atlassian.net\"\nproject = \"OPS\"\n\
+             view = \"releases\"\n\
+             [profiles.oss]\njql = \"watcher = currentUser()\"\nauth = \"bearer\"",
+        )
+        .unwrap();
+        let work = config.profile(None).unwrap();
+        assert_eq!(work.name.as_deref(), Some("work"));
+        assert_eq!(work.view, Some(View::Releases));
+        assert_eq!(
+            work.startup_jql(),
+            "assignee = currentUser() AND resolution = Unresolved AND project = \"OPS\" \
+             ORDER BY updated DESC"
+        );
+        let oss = config.profile(Some("oss")).unwrap();
+        assert_eq!(oss.startup_jql(), "watcher = currentUser()");
+        assert!(config.profile(Some("home")).is_err());
+        assert_eq!(Config::default().profile(None).unwrap(), ProfileConfig::default());
+        assert!(Config::parse("[profiles.work]\ntoken = \"s3cret\"").is_err());
+    }
+
+    #[test]
+    fn time_settings_are_validated() {
+        let config = Config::parse("timezone = \"Europe/Amsterdam\"").unwrap();
+        assert_eq!(config.time_format().unwrap().timezone, Some(chrono_tz::Europe::Amsterdam));
+        assert!(Config::parse("timezone = \"Mars/Olympus\"").is_err());
+        assert!(Config::parse("datetime_format = \"%Y-%Q\"").is_err());
+    }
+
+    #[test]
+    fn webhook_table() {
+        let config =
+            Config::parse("[webhook]\nlisten = \"0.0.0.0:8085\"\ntoken = \"s3cret\"").unwrap();
+        assert_eq!(
+            config.webhook,
+            Some(WebhookConfig {
+                listen: "0.0.0.0:8085".to_string(),
+                token: Some("s3cret".to_string()),
+            })
+        );
+        assert!(Config::parse("[webhook]\ntoken = \"s3cret\"").is_err());
+        // Only this machine can reach it without the token
+        assert!(Config::parse("[webhook]\nlisten = \"0.0.0.0:8085\"").is_err());
+        assert!(Config::parse("[webhook]\nlisten = \"127.0.0.1:8085\"").is_ok());
+        assert!(Config::parse("[webhook]\nlisten = \"localhost:8085\"").is_ok());
+    }
+
+    #[test]
+    fn hook_and_action_tables() {
+        let config = Config::parse(
+            "[[hooks]]\nevent = \"transitioned\"\ncommand = \"notify-send $JIRA_KEY\"\n\
+             [[hooks]]\nevent = \"refreshed\"\ncommand = \"true\"",
+        )
+        .unwrap();
+        assert_eq!(config.hooks.len(), 2);
+        assert_eq!(config.hooks[0].event, HookEvent::Transitioned);
+        assert!(Config::parse("[[hooks]]\nevent = \"deleted\"\ncommand = \"true\"").is_err());
+
+        let config = Config::parse(
+            "[[actions]]\nname = \"Branch\"\nkey = \"b\"\ncommand = \"git switch -c {key}\"",
+        )
+        .unwrap();
+        assert_eq!((config.actions[0].key, config.actions[0].popup), ('b', false));
+        assert!(
+            Config::parse("[[actions]]\nname = \"Branch\"\nkey = \"br\"\ncommand = \"true\"")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn column_and_filter_tables() {
+        let config = Config::parse("[[columns]]\nname = \"Age\"\nscript = \"age_days\"").unwrap();
+        assert_eq!(config.columns[0].width, 8);
+        let config = Config::parse(
+            "[[columns]]\nname = \"Team\"\nfield = \"fields.customfield_10001.value\"\nwidth = 12",
+        )
+        .unwrap();
+        assert_eq!(config.columns[0].field.as_deref(), Some("fields.customfield_10001.value"));
+        assert!(Config::parse("[[columns]]\nname = \"Team\"").is_err());
+        assert!(Config::parse("[[filters]]\nname = \"Old\"\nscript = \"age_days >\"").is_err());
+    }
+
+    #[test]
+    fn alert_tables() {
+        let config =
+            Config::parse("[[alerts]]\nname = \"Urgent\"\njql = \"priority = Highest\"").unwrap();
+        assert_eq!(config.alerts, [AlertConfig {
+            name: "Urgent".to_string(),
+            jql: "priority = Highest".to_string(),
+        }]);
+        assert!(Config::parse("[[alerts]]\nname = \"Urgent\"").is_err());
+    }
+
+    #[test]
+    fn polling_table() {
+        let config = Config::parse("[polling]\nrefresh_every = 120\nprefetch = true").unwrap();
+        assert_eq!(config.polling.refresh_interval(), Some(Duration::from_secs(120)));
+        assert_eq!(config.polling.alert_interval(), Some(Duration::from_secs(300)));
+        assert!(config.polling.prefetches());
+
+        // Nothing runs on its own on a metered connection
+        let config = Config::parse("[polling]\nrefresh_every = 120\nmetered = true").unwrap();
+        assert_eq!(config.polling.refresh_interval(), None);
+        assert_eq!(config.polling.alert_interval(), None);
+        assert_eq!(Config::default().polling.refresh_interval(), None);
+        let config = Config::parse("[polling]\nnotify = \"bell\"").unwrap();
+        assert_eq!(config.polling.notify, Notify::Bell);
+    }
+}
@@ -0,0 +1,168 @@
+//! `jira-tui doctor`: checks the setup one step at a time, from the config file to a search,
+//! to tell why the app won't start.
+
+use std::fmt;
+
+use crate::{
+    cli::Cli,
+    config::Config,
+    jira::{ApiClient, AuthMethod, JiraClient, JiraConfig},
+};
+
+/// The outcome of one step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    /// What was found, or what's wrong.
+    pub outcome: Result<String, String>,
+}
+
+impl Check {
+    fn new(name: &'static str, outcome: Result<String, impl fmt::Display>) -> Self {
+        Self {
+            name,
+            outcome: outcome.map_err(|e| e.to_string()),
+        }
+    }
+
+    fn ok(name: &'static str, found: String) -> Self {
+        Self { name, outcome: Ok(found) }
+    }
+
+    fn failed(name: &'static str, problem: impl fmt::Display) -> Self {
+        Self {
+            name,
+            outcome: Err(problem.to_string()),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.outcome {
+            Ok(found) => write!(f, "✓ {}: {found}", self.name),
+            Err(problem) => write!(f, "✗ {}: {problem}", self.name),
+        }
+    }
+}
+
+/// Runs every check with the config and connection `cli` asks for, printing each as it
+/// finishes. Returns whether all passed.
+pub async fn run(cli: &Cli) -> bool {
+    let mut checks = Vec::new();
+    let mut report = |check: Check| {
+        println!("{check}");
+        checks.push(check);
+    };
+
+    let settings = match cli.config_path() {
+        None => {
+            report(Check::ok("config file", "no config directory, using defaults".to_string()));
+            Config::default()
+        }
+        Some(path) => match cli.load_config() {
+            Ok(settings) if path.exists() => {
+                report(Check::ok("config file", format!("{} loaded", path.display())));
+                settings
+            }
+            Ok(settings) => {
+                let found = format!("none at {}, using defaults", path.display());
+                report(Check::ok("config file", found));
+                settings
+            }
+            Err(e) => {
+                report(Check::failed("config file", e));
+                Config::default()
+            }
+        },
+    };
+
+    let profile = match cli.profile(&settings) {
+        Ok(profile) => profile,
+        Err(e) => {
+            report(Check::failed("profile", e));
+            return false;
+        }
+    };
+    if let Some(name) = &profile.name {
+        report(Check::ok("profile", name.clone()));
+    }
+
+    let config = match JiraConfig::load(&settings, &profile) {
+        Ok(config) => config,
+        Err(e) => {
+            report(Check::failed("credentials", e));
+            return false;
+        }
+    };
+    let credentials = match config.auth {
+        AuthMethod::Basic => format!("{} at {}", config.username, config.base_url),
+        AuthMethod::Bearer => format!("personal access token at {}", config.base_url),
+    };
+    report(Check::ok("credentials", credentials));
+    let client = ApiClient::new(&config);
+    for check in check_jira(&client, &profile.startup_jql()).await {
+        report(check);
+    }
+
+    checks.iter().all(Check::passed)
+}
+
+/// Checks what takes a connection to Jira: the account the credentials belong to, the
+/// server, and `jql`. Stops at the account if the credentials are rejected, as nothing else
+/// works then.
+pub async fn check_jira(client: &dyn JiraClient, jql: &str) -> Vec<Check> {
+    let account = client.myself().await;
+    let rejected = account.as_ref().is_err_and(|e| e.is_auth());
+    let mut checks = vec![Check::new(
+        "account",
+        account.map(|user| format!("signed in as {} ({})", user.display_name, user.account_id)),
+    )];
+    if rejected {
+        return checks;
+    }
+
+    checks.push(Check::new(
+        "server",
+        client
+            .server_info()
+            .await
+            .map(|info| match info.deployment_type {
+                Some(deployment) => format!("Jira {} ({deployment})", info.version),
+                None => format!("Jira {}", info.version),
+            }),
+    ));
+    checks.push(Check::new(
+        "default JQL",
+        client
+            .search(jql, None, 1)
+            .await
+            .map(|page| match page.total {
+                Some(total) => format!("{jql:?} matches {total} issues"),
+                None => format!("{jql:?} is valid"),
+            }),
+    ));
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::FakeClient;
+
+    #[tokio::test]
+    async fn checks_the_connection() {
+        let client = FakeClient::with_issues(vec![FakeClient::issue("OPS-1", "Failover", "To Do")]);
+        let checks = check_jira(&client, "assignee = currentUser()").await;
+        let lines: Vec<String> = checks.iter().map(Check::to_string).collect();
+        assert_eq!(lines, [
+            "✓ account: signed in as Fake User (fake)",
+            "✓ server: Jira 1001.0.0 (Cloud)",
+            "✓ default JQL: \"assignee = currentUser()\" matches 1 issues",
+        ]);
+    }
+}
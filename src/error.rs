@@ -0,0 +1,142 @@
+//! The error type shared across the crate.
+
+use jira_v3_openapi::apis::{Error as JiraApiError, ResponseContent};
+use serde_json::Value;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Everything that can go wrong, classified by what the user can do about it.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Missing or invalid configuration.
+    #[error("configuration error: {0}")]
+    Config(String),
+    /// Jira rejected the credentials (HTTP 401).
+    #[error("authentication failed: {0}")]
+    Auth(String),
+    /// The credentials are valid but not allowed to do this (HTTP 403).
+    #[error("permission denied: {0}")]
+    Permission(String),
+    /// Jira rejected the JQL of a search.
+    #[error("invalid JQL: {0}")]
+    Jql(String),
+    /// The issue (or other resource) doesn't exist or isn't visible (HTTP 404).
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// Any other error response from Jira.
+    #[error("Jira returned HTTP {status}: {message}")]
+    Api { status: u16, message: String },
+    /// The request never got a response (DNS, connection refused, timeout, ...).
+    #[error("network error: {0}")]
+    Network(String),
+    /// Jira responded with something that couldn't be parsed.
+    #[error("unexpected response from Jira: {0}")]
+    Parse(String),
+    /// A file or other input given to a command couldn't be read as expected.
+    #[error("invalid input: {0}")]
+    Input(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl Error {
+    /// Classifies an error response by its HTTP status, extracting Jira's error messages from
+    /// the body when present.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let message = error_message(body).unwrap_or_else(|| match body.trim() {
+            "" => format!("HTTP {status}"),
+            body => body.to_string(),
+        });
+        match status {
+            401 => Error::Auth(message),
+            403 => Error::Permission(message),
+            404 => Error::NotFound(message),
+            _ => Error::Api { status, message },
+        }
+    }
+
+    /// Reinterprets a bad request as a JQL error, for errors returned by searches.
+    pub fn for_search(self) -> Self {
+        match self {
+            Error::Api { status: 400, message } => Error::Jql(message),
+            other => other,
+        }
+    }
+
+    /// Whether the same request might succeed if tried again later.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Network(_) => true,
+            Error::Api { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Whether the credentials need to be fixed before anything else will work.
+    pub fn is_auth(&self) -> bool {
+        matches!(self, Error::Auth(_))
+    }
+}
+
+impl<T> From<JiraApiError<T>> for Error {
+    fn from(err: JiraApiError<T>) -> Self {
+        match err {
+            JiraApiError::ResponseError(ResponseContent { status, content, .. }) => {
+                Error::from_response(status.as_u16(), &content)
+            }
+            JiraApiError::Reqwest(e) => Error::Network(e.to_string()),
+            JiraApiError::Serde(e) => Error::Parse(e.to_string()),
+            JiraApiError::Io(e) => Error::Io(e),
+        }
+    }
+}
+
+/// Joins the messages of a Jira error body like
+/// `{"errorMessages": ["..."], "errors": {"field": "..."}}`.
+fn error_message(body: &str) -> Option<String> {
+    let json: Value = serde_json::from_str(body).ok()?;
+
+    let general = json["errorMessages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+    let fields = json["errors"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(field, message)| {
+            message
+                .as_str()
+                .map(|message| format!("{field}: {message}"))
+        });
+
+    let messages: Vec<String> = general.map(str::to_string).chain(fields).collect();
+    (!messages.is_empty()).then(|| messages.join("; "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_responses() {
+        let body = r#"{"errorMessages":["Field 'foo' does not exist."],"errors":{}}"#;
+        assert!(matches!(Error::from_response(401, ""), Error::Auth(_)));
+        assert!(matches!(Error::from_response(403, body), Error::Permission(_)));
+        assert!(matches!(
+            Error::from_response(400, body).for_search(),
+            Error::Jql(m) if m == "Field 'foo' does not exist."
+        ));
+        assert!(Error::from_response(503, "").is_retryable());
+        assert!(!Error::from_response(404, "").is_retryable());
+    }
+
+    #[test]
+    fn joins_error_messages() {
+        let body = r#"{"errorMessages":["Bad."],"errors":{"summary":"Required."}}"#;
+        assert_eq!(error_message(body).as_deref(), Some("Bad.; summary: Required."));
+        assert_eq!(error_message("<html>"), None);
+        assert_eq!(error_message(r#"{"errorMessages":[],"errors":{}}"#), None);
+    }
+}
@@ -0,0 +1,389 @@
+//! Writing issues in the formats of other planning tools, org-mode headings and taskwarrior
+//! imports, and single issues as documents to share.
+
+use chrono::{NaiveTime, Utc};
+use clap::ValueEnum;
+use crossterm::style::Stylize;
+use serde_json::{Value, json};
+
+use crate::ui::issue::{Comment, Issue, Priority};
+
+/// A format issues can be exported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Org-mode TODO headings with the issue fields as properties.
+    Org,
+    /// JSON for `task import`, one task per line.
+    Taskwarrior,
+}
+
+/// A format a single issue can be written as a document in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DocumentFormat {
+    /// Markdown, for chat and docs.
+    #[value(name = "md")]
+    Markdown,
+    /// Text styled with terminal escape codes.
+    Ansi,
+}
+
+/// Renders `issues` in `format`. `browse_url` links each issue to the Jira web UI.
+pub fn export(
+    issues: &[Issue],
+    format: ExportFormat,
+    browse_url: impl Fn(&str) -> String,
+) -> String {
+    match format {
+        ExportFormat::Org => issues
+            .iter()
+            .map(|issue| org_heading(issue, &browse_url(&issue.id)))
+            .collect(),
+        ExportFormat::Taskwarrior => issues
+            .iter()
+            .map(|issue| format!("{}\n", taskwarrior_task(issue, &browse_url(&issue.id))))
+            .collect(),
+    }
+}
+
+/// Renders `issue` as a document in `format`: its fields, description, and comments. `url`
+/// links it to the Jira web UI, if known.
+pub fn document(issue: &Issue, format: DocumentFormat, url: Option<&str>) -> String {
+    let fields = document_fields(issue);
+    let description = issue.description.trim_end();
+    let comments = issue.comments();
+    let when = |comment: &Comment| {
+        comment
+            .created
+            .map(|created| created.format(" %Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default()
+    };
+    let mut doc = String::new();
+    match format {
+        DocumentFormat::Markdown => {
+            match url {
+                Some(url) => {
+                    doc.push_str(&format!("# [{}]({url}) {}\n\n", issue.id, issue.summary))
+                }
+                None => doc.push_str(&format!("# {} {}\n\n", issue.id, issue.summary)),
+            }
+            for (name, value) in fields {
+                doc.push_str(&format!("- **{name}:** {value}\n"));
+            }
+            if !description.trim().is_empty() {
+                doc.push_str(&format!("\n## Description\n\n{description}\n"));
+            }
+            if !comments.is_empty() {
+                doc.push_str("\n## Comments\n");
+            }
+            for comment in &comments {
+                doc.push_str(&format!("\n**{}**{}\n\n", comment.author, when(comment)));
+                // Quoted, so headings and lists in it stay part of the comment
+                for line in comment.body.trim_end().lines() {
+                    match line.trim_end() {
+                        "" => doc.push_str(">\n"),
+                        line => doc.push_str(&format!("> {line}\n")),
+                    }
+                }
+            }
+        }
+        DocumentFormat::Ansi => {
+            let key = issue.id.as_str().cyan().bold();
+            doc.push_str(&format!("{key} {}\n", issue.summary.as_str().bold()));
+            if let Some(url) = url {
+                doc.push_str(&format!("{}\n", url.dim()));
+            }
+            doc.push('\n');
+            for (name, value) in fields {
+                doc.push_str(&format!("{} {value}\n", format!("{name}:").bold()));
+            }
+            if !description.trim().is_empty() {
+                let heading = "Description".bold().underlined();
+                doc.push_str(&format!("\n{heading}\n{description}\n"));
+            }
+            if !comments.is_empty() {
+                doc.push_str(&format!("\n{}\n", "Comments".bold().underlined()));
+            }
+            for comment in &comments {
+                let author = comment.author.as_str().bold();
+                doc.push_str(&format!("\n{author}{}\n", when(comment).dim()));
+                doc.push_str(&format!("{}\n", comment.body.trim_end()));
+            }
+        }
+    }
+    doc
+}
+
+/// The fields a document shows at the top, the ones that are set.
+fn document_fields(issue: &Issue) -> Vec<(&'static str, String)> {
+    let list = |items: &[String]| (!items.is_empty()).then(|| items.join(", "));
+    let fields = [
+        ("Type", issue.issue_type.clone()),
+        ("Status", issue.status.as_ref().map(|status| status.name.clone())),
+        ("Priority", issue.priority.as_ref().map(|p| p.as_str().to_string())),
+        ("Assignee", issue.assignee.clone()),
+        ("Story points", issue.story_points.map(|points| points.to_string())),
+        (
+            "Parent",
+            issue
+                .parent
+                .as_ref()
+                .map(|parent| format!("{} {}", parent.key, parent.summary)),
+        ),
+        ("Sprint", issue.sprints.last().cloned()),
+        ("Labels", list(&issue.labels)),
+        ("Components", list(&issue.components)),
+        ("Due", issue.due.map(|due| due.to_string())),
+        (
+            "Created",
+            issue
+                .created
+                .map(|created| created.format("%Y-%m-%d %H:%M").to_string()),
+        ),
+        (
+            "Updated",
+            issue
+                .updated
+                .map(|updated| updated.format("%Y-%m-%d %H:%M").to_string()),
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect()
+}
+
+/// An issue as an org-mode heading, with its description as the body.
+fn org_heading(issue: &Issue, url: &str) -> String {
+    let keyword = if issue.is_done() { "DONE" } else { "TODO" };
+    let priority = match issue.priority {
+        Some(Priority::High) => " [#A]",
+        Some(Priority::Medium) => " [#B]",
+        Some(Priority::Low) => " [#C]",
+        _ => "",
+    };
+    let mut org = format!("* {keyword}{priority} {} {}", issue.id, issue.summary);
+    if !issue.labels.is_empty() {
+        // Tags can only hold letters, digits, and a few symbols
+        let tags: Vec<String> = issue
+            .labels
+            .iter()
+            .map(|label| {
+                label
+                    .chars()
+                    .map(|c| {
+                        if c.is_alphanumeric() || "_@#%".contains(c) {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        org.push_str(&format!(" :{}:", tags.join(":")));
+    }
+    org.push('\n');
+    if let Some(due) = issue.due {
+        org.push_str(&format!("  DEADLINE: {}\n", due.format("<%Y-%m-%d %a>")));
+    }
+
+    let mut properties = vec![("JIRA_KEY", issue.id.clone()), ("JIRA_URL", url.to_string())];
+    let mut optional = |name, value: Option<String>| {
+        if let Some(value) = value {
+            properties.push((name, value));
+        }
+    };
+    optional("TYPE", issue.issue_type.clone());
+    optional("STATUS", issue.status.as_ref().map(|status| status.name.clone()));
+    optional("PRIORITY", issue.priority.as_ref().map(|p| p.as_str().to_string()));
+    optional("ASSIGNEE", issue.assignee.clone());
+    optional("STORY_POINTS", issue.story_points.map(|points| points.to_string()));
+    optional("PARENT", issue.parent.as_ref().map(|parent| parent.key.clone()));
+    if !issue.components.is_empty() {
+        properties.push(("COMPONENTS", issue.components.join(", ")));
+    }
+    org.push_str("  :PROPERTIES:\n");
+    for (name, value) in properties {
+        org.push_str(&format!("  :{name}: {value}\n"));
+    }
+    org.push_str("  :END:\n");
+
+    // Indented, so lines starting with a star don't become headings
+    for line in issue.description.lines() {
+        match line.trim_end() {
+            "" => org.push('\n'),
+            line => org.push_str(&format!("  {line}\n")),
+        }
+    }
+    org
+}
+
+/// An issue as a taskwarrior task in the JSON `task import` reads.
+fn taskwarrior_task(issue: &Issue, url: &str) -> Value {
+    /// Taskwarrior's date format, always in UTC.
+    const DATE: &str = "%Y%m%dT%H%M%SZ";
+
+    let mut task = json!({
+        "uuid": task_uuid(url),
+        "description": format!("{} {}", issue.id, issue.summary),
+        "status": if issue.is_done() { "completed" } else { "pending" },
+        "annotations": [{ "entry": Utc::now().format(DATE).to_string(), "description": url }],
+    });
+    let project = issue.id.split_once('-').map(|(project, _)| project);
+    let priority = match issue.priority {
+        Some(Priority::High) => Some("H"),
+        Some(Priority::Medium) => Some("M"),
+        Some(Priority::Low) => Some("L"),
+        _ => None,
+    };
+    let fields = [
+        ("project", project.map(Value::from)),
+        ("priority", priority.map(Value::from)),
+        (
+            "entry",
+            issue
+                .created
+                .map(|created| created.with_timezone(&Utc).format(DATE).to_string().into()),
+        ),
+        (
+            "due",
+            issue
+                .due
+                .map(|due| due.and_time(NaiveTime::MIN).format(DATE).to_string().into()),
+        ),
+        (
+            "end",
+            issue
+                .updated
+                .filter(|_| issue.is_done())
+                .map(|updated| updated.with_timezone(&Utc).format(DATE).to_string().into()),
+        ),
+        (
+            "tags",
+            (!issue.labels.is_empty()).then(|| {
+                // Tags are single words
+                issue
+                    .labels
+                    .iter()
+                    .map(|label| label.replace(char::is_whitespace, "_"))
+                    .collect()
+            }),
+        ),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            task[name] = value;
+        }
+    }
+    task
+}
+
+/// A UUID derived from the issue's URL, so importing an issue again updates its task instead
+/// of adding another one. It is a version 8 (custom) UUID made of two FNV-1a hashes.
+fn task_uuid(url: &str) -> String {
+    let fnv = |offset: u64| {
+        url.bytes()
+            .fold(offset, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+    };
+    let high = (fnv(0xcbf2_9ce4_8422_2325) & !0xf000) | 0x8000;
+    let low = (fnv(0x6c62_272e_07bb_0142) & !(0b11 << 62)) | (0b10 << 62);
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{
+        issue::{Status, StatusCategory},
+        time,
+    };
+
+    fn issue() -> Issue {
+        Issue {
+            id: "OPS-1".to_string(),
+            priority: Some(Priority::High),
+            status: Some(Status {
+                name: "In Progress".to_string(),
+                category: StatusCategory::InProgress,
+            }),
+            labels: vec!["infra".to_string(), "on call".to_string()],
+            due: time::parse_date("2024-06-01"),
+            created: time::parse_timestamp("2024-05-02T09:15:00.000+0200"),
+            ..Issue::new("Database failover does not trigger", "Steps:\n* kill the primary")
+        }
+    }
+
+    #[test]
+    fn exports_org_headings() {
+        let org = export(&[issue()], ExportFormat::Org, |key| format!("https://jira/browse/{key}"));
+        assert_eq!(
+            org,
+            "* TODO [#A] OPS-1 Database failover does not trigger :infra:on_call:\n\
+             \x20 DEADLINE: <2024-06-01 Sat>\n\
+             \x20 :PROPERTIES:\n\
+             \x20 :JIRA_KEY: OPS-1\n\
+             \x20 :JIRA_URL: https://jira/browse/OPS-1\n\
+             \x20 :STATUS: In Progress\n\
+             \x20 :PRIORITY: High\n\
+             \x20 :END:\n\
+             \x20 Steps:\n\
+             \x20 * kill the primary\n"
+        );
+    }
+
+    #[test]
+    fn renders_markdown_documents() {
+        let mut issue = issue();
+        issue.raw_fields.insert(
+            "comment".to_string(),
+            json!({ "comments": [{
+                "author": { "displayName": "Ada" },
+                "created": "2024-05-03T10:00:00.000+0200",
+                "body": "Reproduced.\n\n# Logs\nnothing",
+            }] }),
+        );
+        let url = "https://jira/browse/OPS-1";
+        assert_eq!(
+            document(&issue, DocumentFormat::Markdown, Some(url)),
+            "# [OPS-1](https://jira/browse/OPS-1) Database failover does not trigger\n\n\
+             - **Status:** In Progress\n\
+             - **Priority:** High\n\
+             - **Labels:** infra, on call\n\
+             - **Due:** 2024-06-01\n\
+             - **Created:** 2024-05-02 09:15\n\
+             \n## Description\n\n\
+             Steps:\n* kill the primary\n\
+             \n## Comments\n\
+             \n**Ada** 2024-05-03 10:00\n\n\
+             > Reproduced.\n>\n> # Logs\n> nothing\n"
+        );
+    }
+
+    #[test]
+    fn exports_taskwarrior_tasks() {
+        let url = "https://jira/browse/OPS-1";
+        let task = taskwarrior_task(&issue(), url);
+        assert_eq!(task["description"], "OPS-1 Database failover does not trigger");
+        assert_eq!(task["status"], "pending");
+        assert_eq!(task["project"], "OPS");
+        assert_eq!(task["priority"], "H");
+        assert_eq!(task["entry"], "20240502T071500Z");
+        assert_eq!(task["due"], "20240601T000000Z");
+        assert_eq!(task["tags"], json!(["infra", "on_call"]));
+        assert_eq!(task.get("end"), None);
+
+        // Stable across exports, and different per issue
+        let uuid = task["uuid"].as_str().unwrap();
+        assert_eq!(uuid, task_uuid(url));
+        assert_ne!(uuid, task_uuid("https://jira/browse/OPS-2"));
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "8");
+    }
+}
@@ -0,0 +1,255 @@
+//! Creating issues from files: in bulk from a CSV file, like a backlog kept in a spreadsheet,
+//! or one from a text file, like notes taken in an editor.
+//!
+//! The first row of a CSV file names the columns: `summary` (required), `type`,
+//! `description`, `labels` (separated by spaces or commas), and `points`, in any order and
+//! case.
+
+use crate::{
+    error::{Error, Result},
+    jira::{CreatedIssue, JiraClient, NewIssue},
+};
+
+/// A row of the file as the issue to create from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRow {
+    /// Line of the file the row starts on, for reporting.
+    pub line: usize,
+    pub issue: NewIssue,
+}
+
+/// The columns a file can have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Summary,
+    Type,
+    Description,
+    Labels,
+    Points,
+    Parent,
+}
+
+impl Column {
+    fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "summary" => Some(Column::Summary),
+            "type" | "issue type" | "issuetype" => Some(Column::Type),
+            "description" => Some(Column::Description),
+            "labels" => Some(Column::Labels),
+            "points" | "story points" => Some(Column::Points),
+            "parent" | "epic" => Some(Column::Parent),
+            _ => None,
+        }
+    }
+}
+
+/// Splits CSV text into records of fields, each with the line it starts on. Fields in double
+/// quotes may hold commas, line breaks, and doubled quotes.
+fn parse_csv(text: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let (mut line, mut start) = (1, 1);
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push((start, std::mem::take(&mut record)));
+                line += 1;
+                start = line;
+            }
+            (_, c) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(Error::Input(format!("line {start}: unterminated quote")));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    // Blank lines, including a trailing one, hold no issue
+    records.retain(|(_, record)| record.iter().any(|field| !field.trim().is_empty()));
+    Ok(records)
+}
+
+/// Reads the issues to create in `project` from CSV text. Rows without a type get
+/// `default_type`.
+pub fn read_issues(text: &str, project: &str, default_type: &str) -> Result<Vec<ImportRow>> {
+    let mut records = parse_csv(text)?.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let columns = header
+        .iter()
+        .map(|name| {
+            Column::parse(name).ok_or_else(|| Error::Input(format!("unknown column {name:?}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if !columns.contains(&Column::Summary) {
+        return Err(Error::Input("there is no summary column".to_string()));
+    }
+
+    records
+        .map(|(line, record)| {
+            let mut issue = NewIssue {
+                project_key: project.to_string(),
+                issue_type: default_type.to_string(),
+                summary: String::new(),
+                description: String::new(),
+                labels: Vec::new(),
+                story_points: None,
+                parent: None,
+            };
+            for (&column, value) in columns.iter().zip(record) {
+                let value = value.trim();
+                match column {
+                    Column::Summary => issue.summary = value.to_string(),
+                    Column::Type if !value.is_empty() => issue.issue_type = value.to_string(),
+                    Column::Type => {}
+                    Column::Description => issue.description = value.to_string(),
+                    Column::Labels => {
+                        issue.labels = value
+                            .split([' ', ','])
+                            .filter(|label| !label.is_empty())
+                            .map(str::to_string)
+                            .collect();
+                    }
+                    Column::Parent => {
+                        issue.parent = (!value.is_empty()).then(|| value.to_string());
+                    }
+                    Column::Points if value.is_empty() => {}
+                    Column::Points => {
+                        issue.story_points = Some(value.parse().map_err(|_| {
+                            Error::Input(format!("line {line}: {value:?} is not a number"))
+                        })?);
+                    }
+                }
+            }
+            if issue.summary.is_empty() {
+                return Err(Error::Input(format!("line {line}: the summary is empty")));
+            }
+            Ok(ImportRow { line, issue })
+        })
+        .collect()
+}
+
+/// Reads an issue to create in `project` from text whose first line is the summary and the
+/// rest the description. A markdown heading as the first line is taken without its `#`s.
+pub fn read_note(text: &str, project: &str, issue_type: &str) -> Result<NewIssue> {
+    let mut lines = text.lines().skip_while(|line| line.trim().is_empty());
+    let summary = lines
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_start_matches('#')
+        .trim();
+    if summary.is_empty() {
+        return Err(Error::Input("there is no summary on the first line".to_string()));
+    }
+    let description = lines.collect::<Vec<_>>().join("\n");
+    Ok(NewIssue {
+        project_key: project.to_string(),
+        issue_type: issue_type.to_string(),
+        summary: summary.to_string(),
+        description: description.trim_matches('\n').trim_end().to_string(),
+        labels: Vec::new(),
+        story_points: None,
+        parent: None,
+    })
+}
+
+/// Creates the issues one after another, calling `report` with each outcome. Returns how
+/// many failed.
+pub async fn create_all(
+    client: &dyn JiraClient,
+    rows: &[ImportRow],
+    mut report: impl FnMut(&ImportRow, &Result<CreatedIssue>),
+) -> usize {
+    let mut failed = 0;
+    for row in rows {
+        let result = client.create_issue(&row.issue).await;
+        if result.is_err() {
+            failed += 1;
+        }
+        report(row, &result);
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::FakeClient;
+
+    #[test]
+    fn parses_quoted_fields() {
+        let records = parse_csv("a,\"b, \"\"c\"\"\",d\r\n\n\"multi\nline\",e\n").unwrap();
+        assert_eq!(records, [
+            (1, vec!["a".to_string(), "b, \"c\"".to_string(), "d".to_string()]),
+            (3, vec!["multi\nline".to_string(), "e".to_string()]),
+        ]);
+        assert!(parse_csv("\"open").is_err());
+    }
+
+    #[tokio::test]
+    async fn creates_issues_from_rows() {
+        let csv = "Summary,Type,Labels,Points,Epic\n\
+                   Rotate certificates,,infra security,3,OPS-100\n\
+                   Fix login,Bug,,,\n";
+        let rows = read_issues(csv, "OPS", "Task").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].issue.issue_type, "Task");
+        assert_eq!(rows[0].issue.labels, ["infra", "security"]);
+        assert_eq!(rows[0].issue.story_points, Some(3.0));
+        assert_eq!(rows[0].issue.parent.as_deref(), Some("OPS-100"));
+        assert_eq!(rows[1].issue.parent, None);
+        assert_eq!((rows[1].line, rows[1].issue.issue_type.as_str()), (3, "Bug"));
+
+        let client = FakeClient::default();
+        let mut created = Vec::new();
+        let failed = create_all(&client, &rows, |_, result| {
+            created.push(result.as_ref().unwrap().key.clone());
+        })
+        .await;
+        assert_eq!(failed, 0);
+        assert_eq!(created, ["OPS-1", "OPS-2"]);
+    }
+
+    #[test]
+    fn reads_notes() {
+        let issue = read_note(
+            "\n# Rotate certificates\n\nThe ones on the load balancers\n- expire in May\n\n",
+            "OPS",
+            "Task",
+        )
+        .unwrap();
+        assert_eq!(issue.summary, "Rotate certificates");
+        assert_eq!(issue.description, "The ones on the load balancers\n- expire in May");
+        assert_eq!(read_note("Fix login", "OPS", "Bug").unwrap().description, "");
+        assert!(read_note("\n  \n", "OPS", "Task").is_err());
+    }
+
+    #[test]
+    fn reports_bad_rows() {
+        let err = read_issues("summary,owner\nx,y\n", "OPS", "Task").unwrap_err();
+        assert_eq!(err.to_string(), "invalid input: unknown column \"owner\"");
+        let err = read_issues("summary,points\nx,lots\n", "OPS", "Task").unwrap_err();
+        assert_eq!(err.to_string(), "invalid input: line 2: \"lots\" is not a number");
+    }
+}
@@ -0,0 +1,127 @@
+//! Conversion between plain text and Atlassian Document Format (ADF).
+
+use serde_json::{Value, json};
+
+/// Renders an ADF document as plain text, one line per paragraph.
+///
+/// List items are prefixed with `- ` or their number, with the lines of nested blocks indented
+/// to line up after the marker. Formatting marks are dropped.
+pub fn to_plain_text(doc: &Value) -> String {
+    let mut lines = Vec::new();
+    push_block_lines(doc, &mut lines);
+    lines.join("\n")
+}
+
+fn children(node: &Value) -> impl Iterator<Item = &Value> {
+    node["content"].as_array().into_iter().flatten()
+}
+
+fn push_block_lines(node: &Value, lines: &mut Vec<String>) {
+    match node["type"].as_str() {
+        Some(list @ ("bulletList" | "orderedList")) => {
+            let start = node["attrs"]["order"].as_u64().unwrap_or(1);
+            for (i, item) in children(node).enumerate() {
+                let marker = match list {
+                    "orderedList" => format!("{}. ", start + i as u64),
+                    _ => "- ".to_string(),
+                };
+                let mut item_lines = Vec::new();
+                for child in children(item) {
+                    push_block_lines(child, &mut item_lines);
+                }
+                if item_lines.is_empty() {
+                    item_lines.push(String::new());
+                }
+                let indent = " ".repeat(marker.len());
+                for (j, line) in item_lines.into_iter().enumerate() {
+                    let prefix = if j == 0 { &marker } else { &indent };
+                    lines.push(format!("{prefix}{line}"));
+                }
+            }
+        }
+        Some("doc" | "blockquote" | "panel" | "listItem" | "expand" | "nestedExpand") => {
+            for child in children(node) {
+                push_block_lines(child, lines);
+            }
+        }
+        Some("rule") => lines.push("---".to_string()),
+        // Paragraphs, headings, code blocks, and anything unknown: flatten to text
+        _ => {
+            let mut text = String::new();
+            push_inline_text(node, &mut text);
+            lines.extend(text.split('\n').map(str::to_string));
+        }
+    }
+}
+
+fn push_inline_text(node: &Value, text: &mut String) {
+    match node["type"].as_str() {
+        Some("text") => text.push_str(node["text"].as_str().unwrap_or_default()),
+        Some("hardBreak") => text.push('\n'),
+        Some("mention" | "emoji" | "status" | "date") => {
+            text.push_str(node["attrs"]["text"].as_str().unwrap_or_default())
+        }
+        Some("inlineCard") => text.push_str(node["attrs"]["url"].as_str().unwrap_or_default()),
+        _ => {
+            for child in children(node) {
+                push_inline_text(child, text);
+            }
+        }
+    }
+}
+
+/// Wraps plain text into an ADF document, one paragraph per line.
+pub fn from_plain_text(text: &str) -> Value {
+    let content: Vec<Value> = text
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                json!({ "type": "paragraph", "content": [] })
+            } else {
+                json!({ "type": "paragraph", "content": [{ "type": "text", "text": line }] })
+            }
+        })
+        .collect();
+    json!({ "type": "doc", "version": 1, "content": content })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_keep_their_structure() {
+        let doc = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                { "type": "paragraph", "content": [
+                    { "type": "text", "text": "Steps:" },
+                    { "type": "hardBreak" },
+                    { "type": "text", "text": "see ", "marks": [{ "type": "strong" }] },
+                    { "type": "mention", "attrs": { "id": "1", "text": "@Ana" } },
+                ] },
+                { "type": "orderedList", "content": [
+                    { "type": "listItem", "content": [
+                        { "type": "paragraph", "content": [{ "type": "text", "text": "Fail over" }] },
+                        { "type": "bulletList", "content": [
+                            { "type": "listItem", "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "twice" }] },
+                            ] },
+                        ] },
+                    ] },
+                    { "type": "listItem", "content": [
+                        { "type": "paragraph", "content": [{ "type": "text", "text": "Check" }] },
+                    ] },
+                ] },
+            ],
+        });
+        assert_eq!(to_plain_text(&doc), "Steps:\nsee @Ana\n1. Fail over\n   - twice\n2. Check");
+    }
+
+    #[test]
+    fn round_trips_plain_text() {
+        let text = "First line\n\nThird line";
+        assert_eq!(to_plain_text(&from_plain_text(text)), text);
+    }
+}
@@ -0,0 +1,665 @@
+//! [`JiraClient`] implementation backed by the Jira Cloud REST API.
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use jira_v3_openapi::{
+    apis::{
+        Error as JiraApiError,
+        configuration::Configuration,
+        issue_comments_api, issue_fields_api, issue_priorities_api,
+        issue_search_api::{search_and_reconsile_issues_using_jql, search_for_issues_using_jql},
+        issue_watchers_api, issue_worklogs_api, issues_api, myself_api, project_components_api,
+        project_versions_api, projects_api, server_info_api, user_search_api,
+    },
+    models::{Comment, IssueBean, IssueTransition, IssueUpdateDetails},
+};
+use serde_json::{Value, json};
+
+use super::{
+    JiraConfig, SearchApi, adf,
+    client::{
+        Component, CreatedIssue, Field, FieldChange, HistoryEntry, JiraClient, NewIssue,
+        PageCursor, Project, SearchPage, ServerInfo, Transition, User, Version, Worklog,
+    },
+    custom_fields,
+};
+use crate::{
+    error::{Error, Result},
+    ui::time,
+};
+
+/// Talks to a Jira instance through `jira_v3_openapi`.
+pub struct ApiClient {
+    base_url: String,
+    api_config: Configuration,
+    search_api: SearchApi,
+}
+
+impl ApiClient {
+    pub fn new(config: &JiraConfig) -> Self {
+        Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            api_config: config.to_api_config(),
+            search_api: config.search_api,
+        }
+    }
+
+    /// Searches through `/rest/api/3/search`, paginated by offset.
+    async fn search_legacy(
+        &self,
+        jql: &str,
+        start_at: usize,
+        max_results: usize,
+    ) -> Result<SearchPage> {
+        let results = search_for_issues_using_jql(
+            &self.api_config,
+            Some(jql),
+            Some(start_at as i32),
+            Some(max_results as i32),
+            None, // validate_query
+            None, // fields (None = all navigable)
+            None, // expand
+            None, // properties
+            None, // fields_by_keys
+            None, // jql_context
+        )
+        .await
+        .map_err(|e| Error::from(e).for_search())?;
+
+        let issues = results.issues.unwrap_or_default();
+        let end = start_at + issues.len();
+        let total = results.total.map_or(end, |t| t as usize);
+        Ok(SearchPage {
+            next: (end < total && !issues.is_empty()).then_some(PageCursor::Offset(end)),
+            issues,
+            total: Some(total),
+        })
+    }
+
+    /// Searches through `/rest/api/3/search/jql`, paginated with tokens.
+    async fn search_enhanced(
+        &self,
+        jql: &str,
+        token: Option<&str>,
+        max_results: usize,
+    ) -> Result<SearchPage> {
+        let results = search_and_reconsile_issues_using_jql(
+            &self.api_config,
+            Some(jql),
+            token,
+            Some(max_results as i32),
+            // Unlike the legacy endpoint, only ids are returned by default
+            Some(vec!["*navigable".to_string()]),
+            None, // expand
+            None, // properties
+            None, // fields_by_keys
+            None, // fail_fast
+            None, // reconcile_issues
+        )
+        .await
+        .map_err(|e| Error::from(e).for_search())?;
+
+        // Jira leaves the token out of the last page
+        Ok(SearchPage {
+            issues: results.issues.unwrap_or_default(),
+            total: None,
+            next: results.next_page_token.map(PageCursor::Token),
+        })
+    }
+
+    /// Creates an issue with `fields`, given as the JSON Jira expects for each.
+    async fn create_with(
+        &self,
+        fields: HashMap<String, Value>,
+    ) -> Result<jira_v3_openapi::models::CreatedIssue> {
+        let details = IssueUpdateDetails {
+            fields: Some(fields),
+            ..Default::default()
+        };
+        Ok(issues_api::create_issue(&self.api_config, details, None).await?)
+    }
+}
+
+/// Whether a page of a list Jira pages through with `startAt` is the last, once `fetched` items
+/// arrived with it and the pages before. Jira says so with `isLast`, or else with the total.
+fn last_page(is_last: Option<bool>, total: Option<i64>, fetched: usize, empty: bool) -> bool {
+    empty || is_last.unwrap_or_else(|| total.is_none_or(|total| fetched as i64 >= total))
+}
+
+/// The outcome of a call Jira answers with an empty 204, which the generated client fails to
+/// parse as JSON. Other bodies it can't parse are still errors.
+fn no_content<T, E>(result: std::result::Result<T, JiraApiError<E>>) -> Result<()> {
+    match result {
+        Ok(_) => Ok(()),
+        Err(JiraApiError::Serde(e)) if e.is_eof() && e.line() == 1 && e.column() == 0 => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A `multipart/form-data` body holding `content` as the file `filename`, the way Jira takes
+/// attachments.
+fn multipart_file(boundary: &str, filename: &str, content: &[u8]) -> Vec<u8> {
+    // A quote would end the name early
+    let filename = filename.replace('"', "'");
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    body
+}
+
+#[async_trait]
+impl JiraClient for ApiClient {
+    async fn search(
+        &self,
+        jql: &str,
+        page: Option<&PageCursor>,
+        max_results: usize,
+    ) -> Result<SearchPage> {
+        match (self.search_api, page) {
+            (SearchApi::Legacy, None) => self.search_legacy(jql, 0, max_results).await,
+            (SearchApi::Legacy, Some(PageCursor::Offset(start_at))) => {
+                self.search_legacy(jql, *start_at, max_results).await
+            }
+            (SearchApi::Enhanced, None) => self.search_enhanced(jql, None, max_results).await,
+            (SearchApi::Enhanced, Some(PageCursor::Token(token))) => {
+                self.search_enhanced(jql, Some(token), max_results).await
+            }
+            (_, Some(page)) => Err(Error::Parse(format!(
+                "page {page:?} doesn't belong to the {:?} search endpoint",
+                self.search_api
+            ))),
+        }
+    }
+
+    async fn get_issue(&self, key: &str) -> Result<IssueBean> {
+        let issue = issues_api::get_issue(
+            &self.api_config,
+            key,
+            None, // fields (None = all)
+            None, // fields_by_keys
+            None, // expand
+            None, // properties
+            None, // update_history
+            None, // fail_fast
+        )
+        .await?;
+        Ok(issue)
+    }
+
+    async fn create_issue(&self, issue: &NewIssue) -> Result<CreatedIssue> {
+        let mut fields = HashMap::from([
+            ("project".to_string(), json!({ "key": issue.project_key })),
+            ("issuetype".to_string(), json!({ "name": issue.issue_type })),
+            ("summary".to_string(), json!(issue.summary)),
+            ("description".to_string(), adf::from_plain_text(&issue.description)),
+        ]);
+        // Left out when unset, since not every screen has these fields
+        if !issue.labels.is_empty() {
+            fields.insert("labels".to_string(), json!(issue.labels));
+        }
+        if let Some(points) = issue.story_points {
+            fields.insert(custom_fields().story_points.clone(), json!(points));
+        }
+        if let Some(parent) = &issue.parent {
+            fields.insert("parent".to_string(), json!({ "key": parent }));
+        }
+
+        let created = match (self.create_with(fields.clone()).await, &issue.parent) {
+            // Company-managed projects that haven't moved to the parent field put issues in
+            // epics with the Epic Link field instead
+            (Err(Error::Api { status: 400, message }), Some(epic))
+                if message.contains("parent") =>
+            {
+                fields.remove("parent");
+                fields.insert(custom_fields().epic_link.clone(), json!(epic));
+                self.create_with(fields).await?
+            }
+            (result, _) => result?,
+        };
+        let key = created
+            .key
+            .ok_or_else(|| Error::Parse("create response is missing the issue key".to_string()))?;
+        Ok(CreatedIssue {
+            id: created.id.unwrap_or_default(),
+            url: self.browse_url(&key),
+            key,
+        })
+    }
+
+    async fn edit_issue(&self, key: &str, fields: HashMap<String, Value>) -> Result<()> {
+        let details = IssueUpdateDetails {
+            fields: Some(fields),
+            ..Default::default()
+        };
+        let edited = issues_api::edit_issue(
+            &self.api_config,
+            key,
+            details,
+            None, // notify_users
+            None, // override_screen_security
+            None, // override_editable_flag
+            None, // return_issue
+            None, // expand
+        )
+        .await;
+        no_content(edited)
+    }
+
+    async fn find_assignable_users(&self, key: &str, query: &str) -> Result<Vec<User>> {
+        let users = user_search_api::find_assignable_users(
+            &self.api_config,
+            Some(query),
+            None, // session_id
+            None, // username
+            None, // account_id
+            None, // project
+            Some(key),
+            None,     // issue_id
+            None,     // start_at
+            Some(50), // max_results
+            None,     // action_descriptor_id
+            None,     // recommend
+        )
+        .await?;
+        Ok(users
+            .into_iter()
+            .filter_map(|user| {
+                Some(User {
+                    account_id: user.account_id?,
+                    display_name: user.display_name.unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    async fn find_users(&self, query: &str) -> Result<Vec<User>> {
+        let users = user_search_api::find_users(
+            &self.api_config,
+            Some(query),
+            None,     // username
+            None,     // account_id
+            None,     // start_at
+            Some(50), // max_results
+            None,     // property
+        )
+        .await?;
+        Ok(users
+            .into_iter()
+            // Apps and deactivated accounts can't be assigned or mentioned
+            .filter(|user| user.active != Some(false))
+            .filter_map(|user| {
+                Some(User {
+                    account_id: user.account_id?,
+                    display_name: user.display_name.unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>> {
+        let transitions = issues_api::get_transitions(
+            &self.api_config,
+            key,
+            None, // expand
+            None, // transition_id
+            None, // skip_remote_only_condition
+            None, // include_unavailable_transitions
+            None, // sort_by_ops_bar_and_status
+        )
+        .await?;
+
+        Ok(transitions
+            .transitions
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|t| {
+                Some(Transition {
+                    id: t.id?,
+                    name: t.name.unwrap_or_default(),
+                    to_status: t.to.and_then(|to| to.name),
+                })
+            })
+            .collect())
+    }
+
+    async fn transition_issue(&self, key: &str, transition_id: &str) -> Result<()> {
+        let details = IssueUpdateDetails {
+            transition: Some(IssueTransition {
+                id: Some(transition_id.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        no_content(issues_api::do_transition(&self.api_config, key, details).await)
+    }
+
+    async fn add_comment(&self, key: &str, body: &str) -> Result<()> {
+        let comment = Comment {
+            body: Some(Some(adf::from_plain_text(body))),
+            ..Default::default()
+        };
+        issue_comments_api::add_comment(&self.api_config, key, comment, None).await?;
+        Ok(())
+    }
+
+    async fn get_history(&self, key: &str) -> Result<Vec<HistoryEntry>> {
+        // Jira lists changes oldest first, so the newest are on the last page
+        let mut logs = Vec::new();
+        loop {
+            let page = issues_api::get_change_logs(
+                &self.api_config,
+                key,
+                Some(logs.len() as i32), // start_at
+                Some(100),               // max_results
+            )
+            .await?;
+            let values = page.values.unwrap_or_default();
+            let empty = values.is_empty();
+            logs.extend(values);
+            if last_page(page.is_last, page.total, logs.len(), empty) {
+                break;
+            }
+        }
+
+        Ok(logs
+            .into_iter()
+            .rev()
+            .map(|log| HistoryEntry {
+                author: log
+                    .author
+                    .and_then(|author| author.display_name)
+                    .unwrap_or_default(),
+                created: log.created.as_deref().and_then(time::parse_timestamp),
+                changes: log
+                    .items
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|item| FieldChange {
+                        field: item.field.unwrap_or_default(),
+                        from: item.from_string,
+                        to: item.to_string,
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    async fn get_worklogs(&self, key: &str) -> Result<Vec<Worklog>> {
+        let page = issue_worklogs_api::get_issue_worklog(
+            &self.api_config,
+            key,
+            None,       // start_at
+            Some(5000), // max_results, the most Jira returns at once
+            None,       // started_after
+            None,       // started_before
+            None,       // expand
+        )
+        .await?;
+
+        Ok(page
+            .worklogs
+            .unwrap_or_default()
+            .into_iter()
+            .map(|worklog| {
+                let author = worklog.author.map(|author| *author).unwrap_or_default();
+                Worklog {
+                    author_account_id: author.account_id.unwrap_or_default(),
+                    author: author.display_name.unwrap_or_default(),
+                    started: worklog.started.as_deref().and_then(time::parse_timestamp),
+                    seconds: worklog.time_spent_seconds.unwrap_or_default().max(0) as u64,
+                }
+            })
+            .collect())
+    }
+
+    async fn get_versions(&self, project: &str) -> Result<Vec<Version>> {
+        let mut versions = Vec::new();
+        loop {
+            let page = project_versions_api::get_project_versions_paginated(
+                &self.api_config,
+                project,
+                Some(versions.len() as i64), // start_at
+                Some(100),                   // max_results
+                Some("-sequence"),           // order_by, like the releases page
+                None,                        // query
+                Some("released,unreleased"), // status
+                Some("issuesstatus"),        // expand
+            )
+            .await?;
+            let values = page.values.unwrap_or_default();
+            let empty = values.is_empty();
+            versions.extend(values);
+            if last_page(page.is_last, page.total, versions.len(), empty) {
+                break;
+            }
+        }
+
+        Ok(versions
+            .into_iter()
+            .map(|version| {
+                let status = version.issues_status_for_fix_version.unwrap_or_default();
+                let count = |n: Option<i64>| n.unwrap_or_default().max(0) as usize;
+                Version {
+                    id: version.id.unwrap_or_default(),
+                    name: version.name.unwrap_or_default(),
+                    released: version.released.unwrap_or_default(),
+                    release_date: version.release_date.as_deref().and_then(time::parse_date),
+                    done: count(status.done),
+                    issues: count(status.done)
+                        + count(status.in_progress)
+                        + count(status.to_do)
+                        + count(status.unmapped),
+                }
+            })
+            .collect())
+    }
+
+    async fn get_watchers(&self, key: &str) -> Result<Vec<User>> {
+        let watchers = issue_watchers_api::get_issue_watchers(&self.api_config, key).await?;
+        Ok(watchers
+            .watchers
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|user| {
+                Some(User {
+                    account_id: user.account_id?,
+                    display_name: user.display_name.unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    async fn add_watcher(&self, key: &str, account_id: &str) -> Result<()> {
+        // The body is the account id as a bare JSON string
+        no_content(issue_watchers_api::add_watcher(&self.api_config, key, account_id).await)
+    }
+
+    async fn remove_watcher(&self, key: &str, account_id: &str) -> Result<()> {
+        issue_watchers_api::remove_watcher(
+            &self.api_config,
+            key,
+            None, // username
+            Some(account_id),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn get_projects(&self) -> Result<Vec<Project>> {
+        let projects = projects_api::get_all_projects(
+            &self.api_config,
+            None, // expand
+            None, // recent
+            None, // properties
+        )
+        .await?;
+
+        let mut projects: Vec<Project> = projects
+            .into_iter()
+            .filter_map(|project| {
+                Some(Project {
+                    key: project.key?,
+                    name: project.name.unwrap_or_default(),
+                })
+            })
+            .collect();
+        projects.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(projects)
+    }
+
+    async fn get_components(&self, project: &str) -> Result<Vec<Component>> {
+        let mut components = Vec::new();
+        loop {
+            let page = project_components_api::get_project_components_paginated(
+                &self.api_config,
+                project,
+                Some(components.len() as i64), // start_at
+                Some(100),                     // max_results
+                Some("name"),                  // order_by
+                None,                          // component_source
+                None,                          // query
+            )
+            .await?;
+            let values = page.values.unwrap_or_default();
+            let empty = values.is_empty();
+            components.extend(values);
+            if last_page(page.is_last, page.total, components.len(), empty) {
+                break;
+            }
+        }
+
+        Ok(components
+            .into_iter()
+            .map(|component| Component {
+                id: component.id.unwrap_or_default(),
+                name: component.name.unwrap_or_default(),
+                lead: component.lead.and_then(|lead| lead.display_name),
+                issues: component.issue_count.unwrap_or_default().max(0) as usize,
+            })
+            .collect())
+    }
+
+    async fn get_priorities(&self) -> Result<Vec<String>> {
+        // Jira lists them in the order of the priority scheme, the highest first
+        let priorities = issue_priorities_api::get_priorities(&self.api_config).await?;
+        Ok(priorities
+            .into_iter()
+            .filter_map(|priority| priority.name)
+            .collect())
+    }
+
+    async fn myself(&self) -> Result<User> {
+        let user = myself_api::get_current_user(&self.api_config, None).await?;
+        Ok(User {
+            account_id: user
+                .account_id
+                .ok_or_else(|| Error::Parse("current user without an account id".to_string()))?,
+            display_name: user.display_name.unwrap_or_default(),
+        })
+    }
+
+    async fn get_fields(&self) -> Result<Vec<Field>> {
+        let fields = issue_fields_api::get_fields(&self.api_config).await?;
+        Ok(fields
+            .into_iter()
+            .filter_map(|field| {
+                Some(Field {
+                    id: field.id?,
+                    name: field.name?,
+                    custom: field.custom.unwrap_or(false),
+                })
+            })
+            .collect())
+    }
+
+    async fn server_info(&self) -> Result<ServerInfo> {
+        let info = server_info_api::get_server_info(&self.api_config).await?;
+        Ok(ServerInfo {
+            version: info.version.unwrap_or_default(),
+            deployment_type: info.deployment_type,
+        })
+    }
+
+    async fn download_attachment(&self, url: &str) -> Result<Vec<u8>> {
+        // The generated client parses every response as JSON, so fetch the raw bytes with the
+        // HTTP client underneath it
+        let mut request = self.api_config.client.get(url);
+        if let Some((username, password)) = &self.api_config.basic_auth {
+            request = request.basic_auth(username, password.as_ref());
+        }
+        if let Some(token) = &self.api_config.oauth_access_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::from_response(status.as_u16(), &body));
+        }
+        let content = response
+            .bytes()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+        Ok(content.to_vec())
+    }
+
+    async fn add_attachment(&self, key: &str, filename: &str, content: Vec<u8>) -> Result<()> {
+        // The generated client can't send files, so build the form by hand
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let boundary = format!("jira-tui-{nanos:x}");
+        let url = format!("{}/rest/api/3/issue/{key}/attachments", self.base_url);
+        let mut request = self
+            .api_config
+            .client
+            .post(url)
+            .header("X-Atlassian-Token", "no-check")
+            .header("Content-Type", format!("multipart/form-data; boundary={boundary}"))
+            .body(multipart_file(&boundary, filename, &content));
+        if let Some((username, password)) = &self.api_config.basic_auth {
+            request = request.basic_auth(username, password.as_ref());
+        }
+        if let Some(token) = &self.api_config.oauth_access_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::from_response(status.as_u16(), &body));
+        }
+        Ok(())
+    }
+
+    fn browse_url(&self, key: &str) -> String {
+        format!("{}/browse/{key}", self.base_url)
+    }
+
+    fn with_api_token(&self, api_token: &str) -> Option<Arc<dyn JiraClient>> {
+        let mut api_config = self.api_config.clone();
+        match &mut api_config.basic_auth {
+            Some((_, password)) => *password = Some(api_token.to_string()),
+            None => api_config.oauth_access_token = Some(api_token.to_string()),
+        }
+        Some(Arc::new(Self {
+            base_url: self.base_url.clone(),
+            api_config,
+            search_api: self.search_api,
+        }))
+    }
+}
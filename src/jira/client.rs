@@ -0,0 +1,235 @@
+//! Backend-agnostic interface to a Jira instance.
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use jira_v3_openapi::models::IssueBean;
+use serde_json::Value;
+
+use crate::error::Result;
+
+/// One page of search results.
+#[derive(Debug, Clone, Default)]
+pub struct SearchPage {
+    pub issues: Vec<IssueBean>,
+    /// Size of the full result set, across all pages, if the search endpoint reports it.
+    pub total: Option<usize>,
+    /// Where the next page starts, or `None` if this is the last one.
+    pub next: Option<PageCursor>,
+}
+
+/// Where a page of search results starts. Which kind a client hands out depends on the
+/// search endpoint it uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageCursor {
+    /// The number of results before the page.
+    Offset(usize),
+    /// The opaque token the enhanced search endpoint returned with the previous page.
+    Token(String),
+}
+
+/// The fields needed to create an issue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewIssue {
+    pub project_key: String,
+    pub issue_type: String,
+    pub summary: String,
+    /// Plain text, converted to Atlassian Document Format when sent.
+    pub description: String,
+    pub labels: Vec<String>,
+    pub story_points: Option<f64>,
+    /// Key of the epic, or of the issue a subtask belongs to.
+    pub parent: Option<String>,
+}
+
+impl Eq for NewIssue {}
+
+/// A field issues can have, as Jira lists them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub id: String,
+    pub name: String,
+    /// Added to the instance, rather than one of Jira's own.
+    pub custom: bool,
+}
+
+/// An issue as returned by the create endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatedIssue {
+    pub id: String,
+    pub key: String,
+    /// Link to the issue in the Jira web UI.
+    pub url: String,
+}
+
+/// A workflow transition available on an issue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    /// Name of the status the issue ends up in.
+    pub to_status: Option<String>,
+}
+
+/// One edit of an issue, as recorded in its changelog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Display name of whoever made the change.
+    pub author: String,
+    pub created: Option<DateTime<FixedOffset>>,
+    pub changes: Vec<FieldChange>,
+}
+
+/// A field's value before and after an edit, as displayed by Jira.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Time logged on an issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worklog {
+    pub author_account_id: String,
+    /// Display name of whoever logged it.
+    pub author: String,
+    /// When the work started, in the time zone of the author.
+    pub started: Option<DateTime<FixedOffset>>,
+    pub seconds: u64,
+}
+
+/// A version of a project, which issues are released in through their fix version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Version {
+    pub id: String,
+    pub name: String,
+    pub released: bool,
+    pub release_date: Option<NaiveDate>,
+    /// How many of the issues to be released in this version are done.
+    pub done: usize,
+    /// How many issues are to be released in this version.
+    pub issues: usize,
+}
+
+/// A component of a project, which issues are sorted into by area.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub id: String,
+    pub name: String,
+    /// Display name of the component lead.
+    pub lead: Option<String>,
+    /// How many issues are in the component, resolved or not.
+    pub issues: usize,
+}
+
+/// A project the user can browse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    pub key: String,
+    pub name: String,
+}
+
+/// A user issues can be assigned to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    pub account_id: String,
+    pub display_name: String,
+}
+
+/// What a Jira instance tells about itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    pub version: String,
+    /// `Cloud`, `Server`, or `DataCenter`.
+    pub deployment_type: Option<String>,
+}
+
+/// The operations the app needs from a Jira backend.
+///
+/// [`ApiClient`](super::ApiClient) talks to a real instance; [`FakeClient`](super::FakeClient)
+/// keeps everything in memory for tests.
+#[async_trait]
+pub trait JiraClient: Send + Sync {
+    /// Runs a JQL search, returning up to `max_results` issues starting at `page`, or the first
+    /// page if `None`.
+    async fn search(
+        &self,
+        jql: &str,
+        page: Option<&PageCursor>,
+        max_results: usize,
+    ) -> Result<SearchPage>;
+
+    /// Fetches a single issue by key.
+    async fn get_issue(&self, key: &str) -> Result<IssueBean>;
+
+    async fn create_issue(&self, issue: &NewIssue) -> Result<CreatedIssue>;
+
+    /// Sets fields of an issue, given as the JSON Jira expects for each, with `null` clearing
+    /// one.
+    async fn edit_issue(&self, key: &str, fields: HashMap<String, Value>) -> Result<()>;
+
+    /// Lists the users an issue can be assigned to whose name or email address matches
+    /// `query`.
+    async fn find_assignable_users(&self, key: &str, query: &str) -> Result<Vec<User>>;
+
+    /// Lists the active users whose name or email address matches `query`.
+    async fn find_users(&self, query: &str) -> Result<Vec<User>>;
+
+    /// Lists the transitions currently available on an issue.
+    async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>>;
+
+    async fn transition_issue(&self, key: &str, transition_id: &str) -> Result<()>;
+
+    /// Adds a plain-text comment to an issue.
+    async fn add_comment(&self, key: &str, body: &str) -> Result<()>;
+
+    /// Fetches the changelog of an issue, newest first.
+    async fn get_history(&self, key: &str) -> Result<Vec<HistoryEntry>>;
+
+    /// Fetches the time logged on an issue, oldest first.
+    async fn get_worklogs(&self, key: &str) -> Result<Vec<Worklog>>;
+
+    /// Lists the users watching an issue.
+    async fn get_watchers(&self, key: &str) -> Result<Vec<User>>;
+
+    /// Makes the user with the account id watch an issue.
+    async fn add_watcher(&self, key: &str, account_id: &str) -> Result<()>;
+
+    /// Stops the user with the account id watching an issue.
+    async fn remove_watcher(&self, key: &str, account_id: &str) -> Result<()>;
+
+    /// Lists the projects the user can browse.
+    async fn get_projects(&self) -> Result<Vec<Project>>;
+
+    /// Lists the versions of a project that aren't archived, the newest first.
+    async fn get_versions(&self, project: &str) -> Result<Vec<Version>>;
+
+    /// Lists the components of a project by name.
+    async fn get_components(&self, project: &str) -> Result<Vec<Component>>;
+
+    /// Lists the names of the priorities issues can have, the highest first.
+    async fn get_priorities(&self) -> Result<Vec<String>>;
+
+    /// Fetches the account the credentials belong to.
+    async fn myself(&self) -> Result<User>;
+
+    async fn server_info(&self) -> Result<ServerInfo>;
+
+    /// Lists the fields issues can have on the instance.
+    async fn get_fields(&self) -> Result<Vec<Field>>;
+
+    /// Downloads the content of an attachment from its `content` URL.
+    async fn download_attachment(&self, url: &str) -> Result<Vec<u8>>;
+
+    /// Attaches a file named `filename` to an issue.
+    async fn add_attachment(&self, key: &str, filename: &str, content: Vec<u8>) -> Result<()>;
+
+    /// Link to an issue in the Jira web UI.
+    fn browse_url(&self, key: &str) -> String;
+
+    /// A client for the same instance and user signing in with `api_token` instead, or `None`
+    /// if the backend doesn't sign in.
+    fn with_api_token(&self, api_token: &str) -> Option<Arc<dyn JiraClient>>;
+}
@@ -0,0 +1,560 @@
+//! In-memory [`JiraClient`] for tests and offline development.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use jira_v3_openapi::models::IssueBean;
+use serde_json::{Value, json};
+
+use super::{
+    client::{
+        Component, CreatedIssue, Field, FieldChange, HistoryEntry, JiraClient, NewIssue,
+        PageCursor, Project, SearchPage, ServerInfo, Transition, User, Version, Worklog,
+    },
+    fields::{CustomFields, custom_fields},
+};
+use crate::error::{Error, Result};
+
+/// A [`JiraClient`] that keeps issues in memory.
+///
+/// Searches ignore the JQL and return every stored issue in insertion order. Every issue can
+/// move to any of the statuses in [`FakeClient::STATUSES`], which is recorded in its history.
+/// The users issues can be assigned to are their current assignees, with their display names
+/// as account ids, and the projects are those of the issues, named after their keys.
+#[derive(Default)]
+pub struct FakeClient {
+    state: Mutex<FakeState>,
+}
+
+#[derive(Default)]
+struct FakeState {
+    issues: Vec<IssueBean>,
+    comments: Vec<(String, String)>,
+    /// Changelog entries as `(issue key, entry)` pairs, oldest first.
+    history: Vec<(String, HistoryEntry)>,
+    /// Logged time as `(issue key, worklog)` pairs, oldest first.
+    worklogs: Vec<(String, Worklog)>,
+    /// Watchers as `(issue key, user)` pairs.
+    watchers: Vec<(String, User)>,
+    next_id: usize,
+}
+
+impl FakeClient {
+    /// Statuses reachable through [`JiraClient::transition_issue`]; the transition id is the
+    /// index into this list.
+    pub const STATUSES: &'static [&'static str] = &["To Do", "In Progress", "Done"];
+
+    /// The priorities of [`JiraClient::get_priorities`], the highest first.
+    pub const PRIORITIES: &'static [&'static str] = &["Highest", "High", "Medium", "Low", "Lowest"];
+
+    /// The `statusCategory` key of each of [`FakeClient::STATUSES`].
+    const CATEGORIES: &'static [&'static str] = &["new", "indeterminate", "done"];
+
+    /// Builds a `status` field, with the category looked up from [`FakeClient::STATUSES`].
+    fn status(name: &str) -> Value {
+        let category = Self::STATUSES
+            .iter()
+            .position(|status| *status == name)
+            .map_or("undefined", |i| Self::CATEGORIES[i]);
+        json!({ "name": name, "statusCategory": { "key": category } })
+    }
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a client pre-populated with issues.
+    pub fn with_issues(issues: Vec<IssueBean>) -> Self {
+        let next_id = issues.len();
+        Self {
+            state: Mutex::new(FakeState {
+                issues,
+                comments: Vec::new(),
+                history: Vec::new(),
+                worklogs: Vec::new(),
+                watchers: Vec::new(),
+                next_id,
+            }),
+        }
+    }
+
+    /// Builds an issue bean the way the search endpoint would return it.
+    pub fn issue(key: &str, summary: &str, status: &str) -> IssueBean {
+        serde_json::from_value(json!({
+            "id": key,
+            "key": key,
+            "fields": {
+                "summary": summary,
+                "status": Self::status(status),
+            },
+        }))
+        .expect("fake issue is a valid IssueBean")
+    }
+
+    /// All comments added so far, as `(issue key, body)` pairs.
+    pub fn comments(&self) -> Vec<(String, String)> {
+        self.state.lock().unwrap().comments.clone()
+    }
+
+    /// Logs time on an issue, as if someone had in Jira.
+    pub fn log_work(&self, key: &str, worklog: Worklog) {
+        let mut state = self.state.lock().unwrap();
+        state.worklogs.push((key.to_string(), worklog));
+    }
+
+    /// Whether the issue's key starts with the project key.
+    fn in_project(issue: &IssueBean, project: &str) -> bool {
+        issue
+            .key
+            .as_deref()
+            .and_then(|key| key.split_once('-'))
+            .is_some_and(|(prefix, _)| prefix == project)
+    }
+
+    fn not_found(key: &str) -> Error {
+        Error::NotFound(format!("issue {key} does not exist"))
+    }
+}
+
+#[async_trait]
+impl JiraClient for FakeClient {
+    async fn search(
+        &self,
+        _jql: &str,
+        page: Option<&PageCursor>,
+        max_results: usize,
+    ) -> Result<SearchPage> {
+        let start_at = match page {
+            None => 0,
+            Some(PageCursor::Offset(offset)) => *offset,
+            Some(PageCursor::Token(token)) => {
+                return Err(Error::Api {
+                    status: 400,
+                    message: format!("unknown page token {token}"),
+                });
+            }
+        };
+        let state = self.state.lock().unwrap();
+        let issues: Vec<_> = state
+            .issues
+            .iter()
+            .skip(start_at)
+            .take(max_results)
+            .cloned()
+            .collect();
+        let end = start_at + issues.len();
+        Ok(SearchPage {
+            issues,
+            total: Some(state.issues.len()),
+            next: (end < state.issues.len()).then_some(PageCursor::Offset(end)),
+        })
+    }
+
+    async fn get_issue(&self, key: &str) -> Result<IssueBean> {
+        let state = self.state.lock().unwrap();
+        state
+            .issues
+            .iter()
+            .find(|issue| issue.key.as_deref() == Some(key))
+            .cloned()
+            .ok_or_else(|| Self::not_found(key))
+    }
+
+    async fn create_issue(&self, issue: &NewIssue) -> Result<CreatedIssue> {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let key = format!("{}-{}", issue.project_key, state.next_id);
+
+        let mut bean = Self::issue(&key, &issue.summary, Self::STATUSES[0]);
+        let fields = bean.fields.get_or_insert_with(Default::default);
+        fields.insert("description".to_string(), Value::String(issue.description.clone()));
+        fields.insert("issuetype".to_string(), json!({ "name": issue.issue_type }));
+        fields.insert("labels".to_string(), json!(issue.labels));
+        if let Some(points) = issue.story_points {
+            fields.insert(custom_fields().story_points.clone(), json!(points));
+        }
+        if let Some(parent) = &issue.parent {
+            fields.insert("parent".to_string(), json!({ "key": parent }));
+        }
+        state.issues.push(bean);
+
+        Ok(CreatedIssue {
+            id: state.next_id.to_string(),
+            url: self.browse_url(&key),
+            key,
+        })
+    }
+
+    async fn edit_issue(&self, key: &str, fields: HashMap<String, Value>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let issue = state
+            .issues
+            .iter_mut()
+            .find(|issue| issue.key.as_deref() == Some(key))
+            .ok_or_else(|| Self::not_found(key))?;
+        let stored = issue.fields.get_or_insert_with(Default::default);
+        for (field, mut value) in fields {
+            if field == "assignee" && !value.is_null() {
+                value["displayName"] = value["accountId"].clone();
+            }
+            stored.insert(field, value);
+        }
+        Ok(())
+    }
+
+    async fn find_assignable_users(&self, key: &str, query: &str) -> Result<Vec<User>> {
+        self.get_issue(key).await?;
+        self.find_users(query).await
+    }
+
+    async fn find_users(&self, query: &str) -> Result<Vec<User>> {
+        let state = self.state.lock().unwrap();
+        let query = query.to_lowercase();
+        let mut users: Vec<User> = Vec::new();
+        for issue in &state.issues {
+            let name = issue
+                .fields
+                .as_ref()
+                .and_then(|fields| fields.get("assignee"))
+                .and_then(|assignee| assignee["displayName"].as_str());
+            if let Some(name) = name.filter(|name| name.to_lowercase().contains(&query)) {
+                if !users.iter().any(|user| user.display_name == name) {
+                    users.push(User {
+                        account_id: name.to_string(),
+                        display_name: name.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(users)
+    }
+
+    async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>> {
+        self.get_issue(key).await?;
+        Ok(Self::STATUSES
+            .iter()
+            .enumerate()
+            .map(|(id, status)| Transition {
+                id: id.to_string(),
+                name: status.to_string(),
+                to_status: Some(status.to_string()),
+            })
+            .collect())
+    }
+
+    async fn transition_issue(&self, key: &str, transition_id: &str) -> Result<()> {
+        let status = transition_id
+            .parse::<usize>()
+            .ok()
+            .and_then(|id| Self::STATUSES.get(id))
+            .ok_or_else(|| Error::Api {
+                status: 400,
+                message: format!("transition {transition_id} is not valid"),
+            })?;
+
+        let mut state = self.state.lock().unwrap();
+        let issue = state
+            .issues
+            .iter_mut()
+            .find(|issue| issue.key.as_deref() == Some(key))
+            .ok_or_else(|| Self::not_found(key))?;
+        let previous = issue
+            .fields
+            .get_or_insert_with(Default::default)
+            .insert("status".to_string(), Self::status(status));
+
+        let change = FieldChange {
+            field: "status".to_string(),
+            from: previous.and_then(|p| p["name"].as_str().map(str::to_string)),
+            to: Some(status.to_string()),
+        };
+        state.history.push((key.to_string(), HistoryEntry {
+            author: "Fake User".to_string(),
+            created: Some(chrono::Local::now().fixed_offset()),
+            changes: vec![change],
+        }));
+        Ok(())
+    }
+
+    async fn add_comment(&self, key: &str, body: &str) -> Result<()> {
+        self.get_issue(key).await?;
+        self.state
+            .lock()
+            .unwrap()
+            .comments
+            .push((key.to_string(), body.to_string()));
+        Ok(())
+    }
+
+    async fn get_history(&self, key: &str) -> Result<Vec<HistoryEntry>> {
+        self.get_issue(key).await?;
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .history
+            .iter()
+            .rev()
+            .filter(|(k, _)| k == key)
+            .map(|(_, entry)| entry.clone())
+            .collect())
+    }
+
+    async fn get_worklogs(&self, key: &str) -> Result<Vec<Worklog>> {
+        self.get_issue(key).await?;
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .worklogs
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, worklog)| worklog.clone())
+            .collect())
+    }
+
+    async fn get_watchers(&self, key: &str) -> Result<Vec<User>> {
+        self.get_issue(key).await?;
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .watchers
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, user)| user.clone())
+            .collect())
+    }
+
+    /// Users are named after their account id, like those found by name.
+    async fn add_watcher(&self, key: &str, account_id: &str) -> Result<()> {
+        self.remove_watcher(key, account_id).await?;
+        let user = User {
+            account_id: account_id.to_string(),
+            display_name: account_id.to_string(),
+        };
+        self.state
+            .lock()
+            .unwrap()
+            .watchers
+            .push((key.to_string(), user));
+        Ok(())
+    }
+
+    async fn remove_watcher(&self, key: &str, account_id: &str) -> Result<()> {
+        self.get_issue(key).await?;
+        let mut state = self.state.lock().unwrap();
+        state
+            .watchers
+            .retain(|(k, user)| !(k == key && user.account_id == account_id));
+        Ok(())
+    }
+
+    async fn get_projects(&self) -> Result<Vec<Project>> {
+        let state = self.state.lock().unwrap();
+        let mut keys: Vec<&str> = state
+            .issues
+            .iter()
+            .filter_map(|issue| Some(issue.key.as_deref()?.split_once('-')?.0))
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        Ok(keys
+            .into_iter()
+            .map(|key| Project {
+                key: key.to_string(),
+                name: key.to_string(),
+            })
+            .collect())
+    }
+
+    /// The fix versions of the project's issues, in the order they first appear. None of
+    /// them is released.
+    async fn get_versions(&self, project: &str) -> Result<Vec<Version>> {
+        let state = self.state.lock().unwrap();
+        let mut versions: Vec<Version> = Vec::new();
+        let issues = state
+            .issues
+            .iter()
+            .filter(|issue| Self::in_project(issue, project));
+        for issue in issues {
+            let fields = issue.fields.as_ref();
+            let done = fields
+                .and_then(|fields| fields.get("status"))
+                .is_some_and(|status| status["name"] == "Done");
+            let names = fields
+                .and_then(|fields| fields.get("fixVersions"))
+                .and_then(|versions| versions.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|version| version["name"].as_str());
+            for name in names {
+                let index = match versions.iter().position(|version| version.name == name) {
+                    Some(index) => index,
+                    None => {
+                        versions.push(Version {
+                            id: versions.len().to_string(),
+                            name: name.to_string(),
+                            released: false,
+                            release_date: None,
+                            done: 0,
+                            issues: 0,
+                        });
+                        versions.len() - 1
+                    }
+                };
+                versions[index].issues += 1;
+                versions[index].done += usize::from(done);
+            }
+        }
+        Ok(versions)
+    }
+
+    /// The components of the project's issues by name, without leads.
+    async fn get_components(&self, project: &str) -> Result<Vec<Component>> {
+        let state = self.state.lock().unwrap();
+        let mut components: Vec<Component> = Vec::new();
+        let names = state
+            .issues
+            .iter()
+            .filter(|issue| Self::in_project(issue, project))
+            .filter_map(|issue| issue.fields.as_ref()?.get("components")?.as_array())
+            .flatten()
+            .filter_map(|component| component["name"].as_str());
+        for name in names {
+            match components
+                .iter_mut()
+                .find(|component| component.name == name)
+            {
+                Some(component) => component.issues += 1,
+                None => components.push(Component {
+                    id: components.len().to_string(),
+                    name: name.to_string(),
+                    lead: None,
+                    issues: 1,
+                }),
+            }
+        }
+        components.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(components)
+    }
+
+    async fn get_priorities(&self) -> Result<Vec<String>> {
+        Ok(Self::PRIORITIES
+            .iter()
+            .map(|name| name.to_string())
+            .collect())
+    }
+
+    async fn myself(&self) -> Result<User> {
+        Ok(User {
+            account_id: "fake".to_string(),
+            display_name: "Fake User".to_string(),
+        })
+    }
+
+    async fn server_info(&self) -> Result<ServerInfo> {
+        Ok(ServerInfo {
+            version: "1001.0.0".to_string(),
+            deployment_type: Some("Cloud".to_string()),
+        })
+    }
+
+    /// The custom fields the app knows, with the ids of new Jira Cloud sites.
+    async fn get_fields(&self) -> Result<Vec<Field>> {
+        let ids = CustomFields::default();
+        let field = |id: String, name: &str| Field {
+            id,
+            name: name.to_string(),
+            custom: true,
+        };
+        Ok(vec![
+            field(ids.flagged, "Flagged"),
+            field(ids.epic_link, "Epic Link"),
+            field(ids.story_points, "Story point estimate"),
+            field(ids.sprint, "Sprint"),
+            field(ids.start_date, "Start date"),
+        ])
+    }
+
+    /// Attachments of fake issues have no content.
+    async fn download_attachment(&self, url: &str) -> Result<Vec<u8>> {
+        Err(Error::NotFound(format!("attachment {url} does not exist")))
+    }
+
+    async fn add_attachment(&self, key: &str, filename: &str, content: Vec<u8>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let id = state.next_id;
+        let issue = state
+            .issues
+            .iter_mut()
+            .find(|issue| issue.key.as_deref() == Some(key))
+            .ok_or_else(|| Self::not_found(key))?;
+        let attachments = issue
+            .fields
+            .get_or_insert_with(Default::default)
+            .entry("attachment".to_string())
+            .or_insert_with(|| json!([]));
+        if let Some(attachments) = attachments.as_array_mut() {
+            attachments.push(json!({
+                "id": id.to_string(),
+                "filename": filename,
+                "size": content.len(),
+                "content": format!("https://fake.invalid/attachment/content/{id}"),
+            }));
+        }
+        Ok(())
+    }
+
+    fn browse_url(&self, key: &str) -> String {
+        format!("https://fake.invalid/browse/{key}")
+    }
+
+    /// Fake clients take any credentials.
+    fn with_api_token(&self, _api_token: &str) -> Option<Arc<dyn JiraClient>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn create_then_transition() {
+        let client = FakeClient::with_issues(vec![FakeClient::issue("OPS-1", "Existing", "To Do")]);
+
+        let created = client
+            .create_issue(&NewIssue {
+                project_key: "OPS".to_string(),
+                issue_type: "Task".to_string(),
+                summary: "New".to_string(),
+                description: String::new(),
+                labels: Vec::new(),
+                story_points: None,
+                parent: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(created.key, "OPS-2");
+
+        let page = client.search("", None, 1).await.unwrap();
+        assert_eq!(page.total, Some(2));
+        assert_eq!(page.next, Some(PageCursor::Offset(1)));
+        let page = client.search("", page.next.as_ref(), 1).await.unwrap();
+        assert_eq!(page.issues[0].key.as_deref(), Some("OPS-2"));
+        assert_eq!(page.next, None);
+
+        let done = client
+            .get_transitions("OPS-2")
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        client.transition_issue("OPS-2", &done.id).await.unwrap();
+        let issue = client.get_issue("OPS-2").await.unwrap();
+        assert_eq!(issue.fields.unwrap()["status"]["name"], "Done");
+        let history = client.get_history("OPS-2").await.unwrap();
+        assert_eq!(history[0].changes[0].from.as_deref(), Some("To Do"));
+
+        assert!(client.add_comment("OPS-9", "hello").await.is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! The ids of the custom fields the app reads and writes. Jira numbers custom fields per
+//! instance, so they're looked up by name before the first issues are read, keeping the ids
+//! new Jira Cloud sites give them for the fields not found.
+
+use std::sync::OnceLock;
+
+use super::{Field, JiraClient};
+use crate::error::Result;
+
+/// The ids found by [`find_custom_fields`].
+static CUSTOM_FIELDS: OnceLock<CustomFields> = OnceLock::new();
+
+/// The ids of the custom fields the app knows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomFields {
+    /// The Flagged checkbox, whose only option is `Impediment`.
+    pub flagged: String,
+    /// The epic of an issue, on instances from before epics became parents.
+    pub epic_link: String,
+    pub story_points: String,
+    /// The sprints an issue is or was in.
+    pub sprint: String,
+    /// When work on an issue is planned to start.
+    pub start_date: String,
+}
+
+impl Default for CustomFields {
+    fn default() -> Self {
+        Self {
+            flagged: "customfield_10021".to_string(),
+            epic_link: "customfield_10014".to_string(),
+            story_points: "customfield_10016".to_string(),
+            sprint: "customfield_10020".to_string(),
+            start_date: "customfield_10015".to_string(),
+        }
+    }
+}
+
+impl CustomFields {
+    /// Picks the fields out of `fields` by name, keeping the default ids of those missing.
+    pub fn named(fields: &[Field]) -> Self {
+        let find = |names: &[&str], id: &mut String| {
+            let found = names.iter().find_map(|name| {
+                fields
+                    .iter()
+                    .find(|field| field.custom && field.name == *name)
+            });
+            if let Some(field) = found {
+                *id = field.id.clone();
+            }
+        };
+        let mut ids = Self::default();
+        find(&["Flagged"], &mut ids.flagged);
+        find(&["Epic Link"], &mut ids.epic_link);
+        // Team-managed projects estimate with the first, older company-managed ones the second
+        find(&["Story point estimate", "Story Points"], &mut ids.story_points);
+        find(&["Sprint"], &mut ids.sprint);
+        find(&["Start date"], &mut ids.start_date);
+        ids
+    }
+}
+
+/// The ids of the custom fields, as found by [`find_custom_fields`], or the defaults before.
+pub fn custom_fields() -> &'static CustomFields {
+    CUSTOM_FIELDS.get_or_init(CustomFields::default)
+}
+
+/// Looks the custom fields up by name for [`custom_fields`], once, before anything reads
+/// them. The defaults stay if Jira doesn't list its fields.
+pub async fn find_custom_fields(client: &dyn JiraClient) -> Result<()> {
+    let fields = client.get_fields().await?;
+    let _ = CUSTOM_FIELDS.set(CustomFields::named(&fields));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_custom_fields_by_name() {
+        let field = |id: &str, name: &str, custom: bool| Field {
+            id: id.to_string(),
+            name: name.to_string(),
+            custom,
+        };
+        let ids = CustomFields::named(&[
+            field("flagged", "Flagged", false),
+            field("customfield_10100", "Flagged", true),
+            field("customfield_10101", "Story Points", true),
+            field("customfield_10102", "Sprint", true),
+        ]);
+        assert_eq!(ids, CustomFields {
+            flagged: "customfield_10100".to_string(),
+            story_points: "customfield_10101".to_string(),
+            sprint: "customfield_10102".to_string(),
+            ..CustomFields::default()
+        });
+    }
+}
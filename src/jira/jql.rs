@@ -0,0 +1,308 @@
+//! Composing JQL queries from structured pieces, for users who don't know the syntax.
+
+/// A field that can be filtered on in the JQL builder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JqlField {
+    /// Name shown in the builder.
+    pub label: &'static str,
+    /// Name of the field in JQL.
+    pub name: &'static str,
+    /// Whether the field is free text, which only supports the contains operators.
+    pub text: bool,
+}
+
+/// The fields offered by the JQL builder.
+pub const FIELDS: &[JqlField] = &[
+    JqlField {
+        label: "Status",
+        name: "status",
+        text: false,
+    },
+    JqlField {
+        label: "Priority",
+        name: "priority",
+        text: false,
+    },
+    JqlField {
+        label: "Issue type",
+        name: "issuetype",
+        text: false,
+    },
+    JqlField {
+        label: "Assignee",
+        name: "assignee",
+        text: false,
+    },
+    JqlField {
+        label: "Reporter",
+        name: "reporter",
+        text: false,
+    },
+    JqlField {
+        label: "Project",
+        name: "project",
+        text: false,
+    },
+    JqlField {
+        label: "Label",
+        name: "labels",
+        text: false,
+    },
+    JqlField {
+        label: "Component",
+        name: "component",
+        text: false,
+    },
+    JqlField {
+        label: "Sprint",
+        name: "sprint",
+        text: false,
+    },
+    JqlField {
+        label: "Resolution",
+        name: "resolution",
+        text: false,
+    },
+    JqlField {
+        label: "Text",
+        name: "text",
+        text: true,
+    },
+    JqlField {
+        label: "Summary",
+        name: "summary",
+        text: true,
+    },
+];
+
+/// A comparison between a field and a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Equals,
+    NotEquals,
+    Contains,
+    NotContains,
+    IsEmpty,
+    IsNotEmpty,
+}
+
+impl Operator {
+    /// The operators that make sense for `field`.
+    pub fn for_field(field: &JqlField) -> &'static [Operator] {
+        if field.text {
+            &[Operator::Contains, Operator::NotContains]
+        } else {
+            &[Operator::Equals, Operator::NotEquals, Operator::IsEmpty, Operator::IsNotEmpty]
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Operator::Equals => "=",
+            Operator::NotEquals => "!=",
+            Operator::Contains => "~",
+            Operator::NotContains => "!~",
+            Operator::IsEmpty => "is EMPTY",
+            Operator::IsNotEmpty => "is not EMPTY",
+        }
+    }
+
+    /// Whether a value goes after the operator.
+    pub fn takes_value(self) -> bool {
+        !matches!(self, Operator::IsEmpty | Operator::IsNotEmpty)
+    }
+}
+
+/// Quotes a value for JQL, leaving function calls like `currentUser()` as they are.
+pub fn quote(value: &str) -> String {
+    let is_function = value.ends_with(')')
+        && value
+            .split('(')
+            .next()
+            .is_some_and(|name| !name.is_empty() && name.chars().all(char::is_alphanumeric));
+    if is_function {
+        return value.to_string();
+    }
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+/// Builds a single clause like `status = "In Progress"`.
+pub fn clause(field: &JqlField, operator: Operator, value: &str) -> String {
+    if operator.takes_value() {
+        format!("{} {} {}", field.name, operator.as_str(), quote(value))
+    } else {
+        format!("{} {}", field.name, operator.as_str())
+    }
+}
+
+/// Adds `clause` to the conditions of `query` with `AND`, keeping its `ORDER BY` last.
+pub fn and(query: &str, clause: &str) -> String {
+    let (conditions, order_by) = split_order_by(query);
+    let conditions = conditions.trim();
+    let combined = if conditions.is_empty() {
+        clause.to_string()
+    } else {
+        format!("{conditions} AND {clause}")
+    };
+    match order_by {
+        Some(order_by) => format!("{combined} {order_by}"),
+        None => combined,
+    }
+}
+
+/// The clause limiting a query to unresolved issues.
+pub const UNRESOLVED: &str = "resolution = Unresolved";
+
+/// Whether `query` only matches unresolved issues, as far as a clause like
+/// `resolution = Unresolved` shows.
+pub fn is_unresolved_only(query: &str) -> bool {
+    find_unresolved(query).is_some()
+}
+
+/// Drops the `resolution = Unresolved` clause from `query`, or adds it if there is none.
+pub fn toggle_unresolved(query: &str) -> String {
+    let Some((start, end)) = find_unresolved(query) else {
+        return and(query, UNRESOLVED);
+    };
+    let (before, after) = (query[..start].trim_end(), query[end..].trim_start());
+    // Take the AND joining the clause to the rest along with it
+    let (before, after) = match after.get(..4) {
+        Some(and) if and.eq_ignore_ascii_case("AND ") => (before, after[4..].trim_start()),
+        _ => match before.len().checked_sub(4).and_then(|i| before.get(i..)) {
+            Some(and) if and.eq_ignore_ascii_case(" AND") => {
+                (before[..before.len() - 4].trim_end(), after)
+            }
+            _ => (before, after),
+        },
+    };
+    [before, after]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Byte range of the `resolution = Unresolved` clause in `query`, in any case and spacing.
+fn find_unresolved(query: &str) -> Option<(usize, usize)> {
+    find_equals(query, "resolution")
+        .filter(|(_, _, value)| value.trim_matches('"').eq_ignore_ascii_case("unresolved"))
+        .map(|(start, end, _)| (start, end))
+}
+
+/// Scopes `query` to `project`, replacing the `project = ...` clause if it has one.
+pub fn with_project(query: &str, project: &str) -> String {
+    let clause = format!("project = {}", quote(project));
+    match find_equals(query, "project") {
+        Some((start, end, _)) => format!("{}{clause}{}", &query[..start], &query[end..]),
+        None => and(query, &clause),
+    }
+}
+
+/// Byte range of the first `field = value` clause in `query`, with the field in any case,
+/// along with the value as written.
+fn find_equals<'a>(query: &'a str, field: &str) -> Option<(usize, usize, &'a str)> {
+    let upper = query.to_ascii_uppercase();
+    let field = field.to_ascii_uppercase();
+    let mut from = 0;
+    while let Some(i) = upper[from..].find(&field).map(|i| i + from) {
+        from = i + 1;
+        let starts_word = !upper[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+        let Some(rest) = query[i + field.len()..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = rest.trim_start();
+        let value_start = query.len() - value.len();
+        let len = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let mut escaped = false;
+                let close = quoted.char_indices().find(|&(_, c)| {
+                    let close = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    close
+                });
+                close.map(|(j, _)| j + 2)?
+            }
+            None => value
+                .find(|c: char| c.is_whitespace() || c == ')' || c == ',')
+                .unwrap_or(value.len()),
+        };
+        if starts_word && len > 0 {
+            let end = value_start + len;
+            return Some((i, end, &query[value_start..end]));
+        }
+    }
+    None
+}
+
+/// Splits off a trailing `ORDER BY ...`, matched case-insensitively.
+fn split_order_by(query: &str) -> (&str, Option<&str>) {
+    match query.to_ascii_uppercase().rfind("ORDER BY") {
+        Some(i) => (&query[..i], Some(query[i..].trim())),
+        None => (query, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str) -> &'static JqlField {
+        FIELDS.iter().find(|f| f.name == name).unwrap()
+    }
+
+    #[test]
+    fn builds_clauses() {
+        assert_eq!(
+            clause(field("status"), Operator::Equals, "In Progress"),
+            "status = \"In Progress\""
+        );
+        assert_eq!(
+            clause(field("assignee"), Operator::Equals, "currentUser()"),
+            "assignee = currentUser()"
+        );
+        assert_eq!(clause(field("labels"), Operator::IsEmpty, ""), "labels is EMPTY");
+        assert_eq!(
+            clause(field("summary"), Operator::Contains, "say \"hi\""),
+            "summary ~ \"say \\\"hi\\\"\""
+        );
+    }
+
+    #[test]
+    fn toggles_unresolved() {
+        let jql = "assignee = currentUser() AND resolution = Unresolved ORDER BY updated DESC";
+        assert!(is_unresolved_only(jql));
+        assert_eq!(toggle_unresolved(jql), "assignee = currentUser() ORDER BY updated DESC");
+        assert_eq!(toggle_unresolved(&toggle_unresolved(jql)), jql);
+        assert_eq!(toggle_unresolved("RESOLUTION=unresolved and project = OPS"), "project = OPS");
+        assert_eq!(toggle_unresolved("resolution = Unresolved"), "");
+        assert!(!is_unresolved_only("resolution = UnresolvedSoon"));
+        assert_eq!(toggle_unresolved(""), "resolution = Unresolved");
+    }
+
+    #[test]
+    fn rescopes_to_a_project() {
+        assert_eq!(
+            with_project("project = OPS AND status = Open ORDER BY key", "DATA"),
+            "project = \"DATA\" AND status = Open ORDER BY key"
+        );
+        assert_eq!(
+            with_project("Project=\"Old \\\"one\\\"\" order by rank", "OPS"),
+            "project = \"OPS\" order by rank"
+        );
+        assert_eq!(
+            with_project("subproject = X ORDER BY key", "OPS"),
+            "subproject = X AND project = \"OPS\" ORDER BY key"
+        );
+    }
+
+    #[test]
+    fn and_keeps_order_by_last() {
+        assert_eq!(
+            and("assignee = currentUser() ORDER BY updated DESC", "priority = \"High\""),
+            "assignee = currentUser() AND priority = \"High\" ORDER BY updated DESC"
+        );
+        assert_eq!(and("order by created", "labels is EMPTY"), "labels is EMPTY order by created");
+        assert_eq!(and("", "labels is EMPTY"), "labels is EMPTY");
+    }
+}
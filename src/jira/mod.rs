@@ -1,58 +1,245 @@
-use jira_v3_openapi::apis::Error as JiraApiError;
-use jira_v3_openapi::apis::configuration::Configuration;
-use jira_v3_openapi::apis::issue_search_api::search_for_issues_using_jql;
-use jira_v3_openapi::models::search_results::SearchResults;
-use std::env;
+//! Jira Cloud configuration and API calls.
 
+pub mod adf;
+mod api;
+mod client;
+mod fake;
+mod fields;
+pub mod jql;
+mod multi;
+pub mod webhook;
+
+use std::{env, sync::Arc};
+
+use jira_v3_openapi::{apis::configuration::Configuration, models::IssueBean};
+use serde::Deserialize;
+use tokio::sync::mpsc::UnboundedSender;
+
+pub use self::{
+    api::ApiClient,
+    client::{
+        Component, CreatedIssue, Field, FieldChange, HistoryEntry, JiraClient, NewIssue,
+        PageCursor, Project, SearchPage, ServerInfo, Transition, User, Version, Worklog,
+    },
+    fake::FakeClient,
+    fields::{CustomFields, custom_fields, find_custom_fields},
+    multi::{INSTANCE_FIELD, MultiClient},
+};
+use crate::{
+    config::{Config, ProfileConfig},
+    error::{Error, Result},
+};
+
+/// JQL for issues assigned to the current user, unresolved, ordered by update time.
+pub const ASSIGNED_JQL: &str =
+    "assignee = currentUser() AND resolution = Unresolved ORDER BY updated DESC";
+
+/// Number of issues requested per search page.
+pub const PAGE_SIZE: i32 = 100;
+
+/// The endpoint searches go through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchApi {
+    /// `/rest/api/3/search/jql`, paginated with tokens and without a result count. Jira Cloud
+    /// is retiring the legacy endpoint in its favor.
+    Enhanced,
+    /// `/rest/api/3/search`, paginated by offset.
+    Legacy,
+}
+
+impl SearchApi {
+    /// The enhanced endpoint for Jira Cloud sites, the legacy one anywhere else.
+    pub fn for_url(base_url: &str) -> Self {
+        let host = base_url
+            .split("://")
+            .last()
+            .and_then(|rest| rest.split(['/', ':']).next())
+            .unwrap_or_default();
+        if host.ends_with(".atlassian.net") {
+            SearchApi::Enhanced
+        } else {
+            SearchApi::Legacy
+        }
+    }
+}
+
+/// How requests prove who makes them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthMethod {
+    /// The username with an API token, as Jira Cloud expects.
+    #[default]
+    Basic,
+    /// A personal access token in an `Authorization: Bearer` header, as Jira Data Center
+    /// expects.
+    Bearer,
+}
+
+/// Connection details for a Jira instance.
+#[derive(Clone)]
 pub struct JiraConfig {
     pub base_url: String,
+    /// Empty with bearer auth.
     pub username: String,
     pub api_token: String,
+    pub auth: AuthMethod,
+    pub search_api: SearchApi,
 }
 
 impl JiraConfig {
-    /// Load config from environment variables.
+    /// Load config from environment variables, unless `profile` sets what they would.
     /// - JIRA_TUI_URL: Base URL (e.g. https://your-domain.atlassian.net)
-    /// - JIRA_TUI_USER: Username/email
-    /// - JIRA_TUI_TOKEN: API token
-    pub fn from_env() -> Result<Self, String> {
-        let base_url = env::var("JIRA_TUI_URL").map_err(|_| "JIRA_TUI_URL not set")?;
-        let username = env::var("JIRA_TUI_USER").map_err(|_| "JIRA_TUI_USER not set")?;
-        let api_token = env::var("JIRA_TUI_TOKEN").map_err(|_| "JIRA_TUI_TOKEN not set")?;
-        Ok(Self { base_url, username, api_token })
+    /// - JIRA_TUI_USER: Username/email, not needed with bearer auth
+    /// - JIRA_TUI_TOKEN: API token, or personal access token with bearer auth; profiles can
+    ///   name another variable
+    pub fn load(settings: &Config, profile: &ProfileConfig) -> Result<Self> {
+        let var = |name: &str| env::var(name).map_err(|_| Error::Config(format!("{name} not set")));
+        let base_url = match &profile.url {
+            Some(url) => url.clone(),
+            None => var("JIRA_TUI_URL")?,
+        };
+        let auth = profile.auth.unwrap_or(settings.auth);
+        let username = match (auth, &profile.user) {
+            (AuthMethod::Bearer, _) => String::new(),
+            (AuthMethod::Basic, Some(user)) => user.clone(),
+            (AuthMethod::Basic, None) => var("JIRA_TUI_USER")?,
+        };
+        Ok(Self {
+            search_api: settings
+                .search_api
+                .unwrap_or_else(|| SearchApi::for_url(&base_url)),
+            base_url,
+            username,
+            api_token: match &profile.token {
+                Some(token) => token.clone(),
+                None => var(profile.token_env.as_deref().unwrap_or("JIRA_TUI_TOKEN"))?,
+            },
+            auth,
+        })
     }
 
+    /// Builds the `jira_v3_openapi` configuration used for API calls.
     pub fn to_api_config(&self) -> Configuration {
         let mut config = Configuration::new();
         config.base_path = self.base_url.clone();
-        config.basic_auth = Some((self.username.clone(), Some(self.api_token.clone())));
+        match self.auth {
+            AuthMethod::Basic => {
+                config.basic_auth = Some((self.username.clone(), Some(self.api_token.clone())));
+            }
+            // The spec has no bearer scheme; its OAuth 2.0 one sends the token the same way
+            AuthMethod::Bearer => config.oauth_access_token = Some(self.api_token.clone()),
+        }
         config
     }
 }
 
-/// Fetch issues assigned to the current user using JQL.
-/// Returns the raw SearchResults from the Jira API.
-pub async fn fetch_assigned_issues(
-    config: &JiraConfig,
-    max_results: i32,
-) -> Result<
-    SearchResults,
-    JiraApiError<jira_v3_openapi::apis::issue_search_api::SearchForIssuesUsingJqlError>,
-> {
-    let api_config = config.to_api_config();
-    // JQL for issues assigned to the current user, unresolved, ordered by update time.
-    let jql = "assignee = currentUser() AND resolution = Unresolved ORDER BY updated DESC";
-    search_for_issues_using_jql(
-        &api_config,
-        Some(jql),
-        Some(0),
-        Some(max_results),
-        None, // validate_query
-        None, // fields (None = all navigable)
-        None, // expand
-        None, // properties
-        None, // fields_by_keys
-        None, // jql_context
-    )
-    .await
+/// Runs a search to the end, for result sets small enough to load at once, like the children
+/// of an epic.
+pub async fn search_all(client: &dyn JiraClient, jql: &str) -> Result<Vec<IssueBean>> {
+    let mut issues = Vec::new();
+    let mut from = None;
+    loop {
+        let page = client
+            .search(jql, from.as_ref(), PAGE_SIZE as usize)
+            .await?;
+        let empty = page.issues.is_empty();
+        issues.extend(page.issues);
+        match page.next {
+            Some(next) if !empty => from = Some(next),
+            _ => return Ok(issues),
+        }
+    }
+}
+
+/// Progress reported by [`stream_issues`].
+#[derive(Debug)]
+pub enum FetchEvent {
+    /// Another page of issues arrived. `total` is the server's current result count, if the
+    /// search endpoint reports one.
+    Page {
+        issues: Vec<IssueBean>,
+        total: Option<usize>,
+    },
+    /// All pages have been fetched.
+    Done,
+    /// Fetching stopped early because a request failed.
+    Failed(Error),
+}
+
+/// Fetch the pages of `jql` starting at `from` (or the first page) one by one, sending each
+/// through `tx` as it arrives.
+///
+/// Stops after the last page, when the server returns an empty page, a request fails, or the
+/// receiving end is dropped.
+pub async fn stream_issues(
+    client: Arc<dyn JiraClient>,
+    jql: String,
+    mut from: Option<PageCursor>,
+    tx: UnboundedSender<FetchEvent>,
+) {
+    loop {
+        let page = match client.search(&jql, from.as_ref(), PAGE_SIZE as usize).await {
+            Ok(page) => page,
+            Err(e) => {
+                let _ = tx.send(FetchEvent::Failed(e));
+                return;
+            }
+        };
+
+        if page.issues.is_empty() {
+            break;
+        }
+
+        let event = FetchEvent::Page {
+            issues: page.issues,
+            total: page.total,
+        };
+        if tx.send(event).is_err() {
+            return;
+        }
+        match page.next {
+            Some(next) => from = Some(next),
+            None => break,
+        }
+    }
+
+    let _ = tx.send(FetchEvent::Done);
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn enhanced_search_is_the_default_for_cloud() {
+        assert_eq!(SearchApi::for_url("https://example.atlassian.net"), SearchApi::Enhanced);
+        assert_eq!(SearchApi::for_url("https://jira.example.com/"), SearchApi::Legacy);
+        assert_eq!(SearchApi::for_url("http://127.0.0.1:8080"), SearchApi::Legacy);
+    }
+
+    #[tokio::test]
+    async fn streams_until_the_last_page() {
+        let issues = (0..250)
+            .map(|i| FakeClient::issue(&format!("OPS-{i}"), "", "To Do"))
+            .collect();
+        let client = Arc::new(FakeClient::with_issues(issues));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        stream_issues(client, String::new(), Some(PageCursor::Offset(50)), tx).await;
+
+        let mut fetched = 0;
+        while let Some(event) = rx.recv().await {
+            match event {
+                FetchEvent::Page { issues, total } => {
+                    fetched += issues.len();
+                    assert_eq!(total, Some(250));
+                }
+                FetchEvent::Done => break,
+                FetchEvent::Failed(e) => panic!("{e}"),
+            }
+        }
+        assert_eq!(fetched, 200);
+    }
 }
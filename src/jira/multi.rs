@@ -0,0 +1,265 @@
+//! A [`JiraClient`] searching several Jira instances at once, for people with an account on
+//! more than one site.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures::future;
+use jira_v3_openapi::models::IssueBean;
+use serde_json::Value;
+
+use super::client::{
+    Component, CreatedIssue, Field, HistoryEntry, JiraClient, NewIssue, PageCursor, Project,
+    SearchPage, ServerInfo, Transition, User, Version, Worklog,
+};
+use crate::error::Result;
+
+/// The field [`MultiClient`] adds to the issues it finds, holding the name of the instance
+/// they come from. Jira's own fields never start with an underscore.
+pub const INSTANCE_FIELD: &str = "_instance";
+
+/// A [`JiraClient`] running every search on all of its instances concurrently, with the
+/// results of one instance after those of the one before.
+///
+/// Requests about an issue go to the instance it was found on, and everything else, like
+/// projects and creating issues, to the first instance. Searches return all results in one
+/// page, since the instances page differently.
+pub struct MultiClient {
+    /// The instances, by name.
+    clients: Vec<(String, Arc<dyn JiraClient>)>,
+    /// The instance each issue found so far came from, by key. The same key on two instances
+    /// goes to the first.
+    owners: Mutex<HashMap<String, usize>>,
+}
+
+impl MultiClient {
+    pub fn new(clients: Vec<(String, Arc<dyn JiraClient>)>) -> Self {
+        Self {
+            clients,
+            owners: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The client of the instance `key` was found on, or the first one.
+    fn client(&self, key: &str) -> &dyn JiraClient {
+        let owner = self.owners.lock().unwrap().get(key).copied().unwrap_or(0);
+        self.clients[owner].1.as_ref()
+    }
+
+    fn first(&self) -> &dyn JiraClient {
+        self.clients[0].1.as_ref()
+    }
+
+    /// Marks the issues found on instance `n` as coming from there.
+    fn claim(&self, n: usize, issues: &mut [IssueBean]) {
+        let mut owners = self.owners.lock().unwrap();
+        for issue in issues {
+            if let Some(key) = &issue.key {
+                owners.entry(key.clone()).or_insert(n);
+            }
+            let name = Value::String(self.clients[n].0.clone());
+            issue
+                .fields
+                .get_or_insert_with(Default::default)
+                .insert(INSTANCE_FIELD.to_string(), name);
+        }
+    }
+}
+
+#[async_trait]
+impl JiraClient for MultiClient {
+    async fn search(
+        &self,
+        jql: &str,
+        page: Option<&PageCursor>,
+        _max_results: usize,
+    ) -> Result<SearchPage> {
+        // The first page had everything
+        if page.is_some() {
+            return Ok(SearchPage::default());
+        }
+        let searches = self
+            .clients
+            .iter()
+            .map(|(_, client)| super::search_all(client.as_ref(), jql));
+        let mut issues = Vec::new();
+        for (n, mut found) in future::try_join_all(searches)
+            .await?
+            .into_iter()
+            .enumerate()
+        {
+            self.claim(n, &mut found);
+            issues.extend(found);
+        }
+        Ok(SearchPage {
+            total: Some(issues.len()),
+            issues,
+            next: None,
+        })
+    }
+
+    async fn get_issue(&self, key: &str) -> Result<IssueBean> {
+        let mut issue = self.client(key).get_issue(key).await?;
+        let owner = self.owners.lock().unwrap().get(key).copied().unwrap_or(0);
+        self.claim(owner, std::slice::from_mut(&mut issue));
+        Ok(issue)
+    }
+
+    async fn create_issue(&self, issue: &NewIssue) -> Result<CreatedIssue> {
+        self.first().create_issue(issue).await
+    }
+
+    async fn edit_issue(&self, key: &str, fields: HashMap<String, Value>) -> Result<()> {
+        self.client(key).edit_issue(key, fields).await
+    }
+
+    async fn find_assignable_users(&self, key: &str, query: &str) -> Result<Vec<User>> {
+        self.client(key).find_assignable_users(key, query).await
+    }
+
+    async fn find_users(&self, query: &str) -> Result<Vec<User>> {
+        self.first().find_users(query).await
+    }
+
+    async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>> {
+        self.client(key).get_transitions(key).await
+    }
+
+    async fn transition_issue(&self, key: &str, transition_id: &str) -> Result<()> {
+        self.client(key).transition_issue(key, transition_id).await
+    }
+
+    async fn add_comment(&self, key: &str, body: &str) -> Result<()> {
+        self.client(key).add_comment(key, body).await
+    }
+
+    async fn get_history(&self, key: &str) -> Result<Vec<HistoryEntry>> {
+        self.client(key).get_history(key).await
+    }
+
+    async fn get_worklogs(&self, key: &str) -> Result<Vec<Worklog>> {
+        self.client(key).get_worklogs(key).await
+    }
+
+    async fn get_watchers(&self, key: &str) -> Result<Vec<User>> {
+        self.client(key).get_watchers(key).await
+    }
+
+    async fn add_watcher(&self, key: &str, account_id: &str) -> Result<()> {
+        self.client(key).add_watcher(key, account_id).await
+    }
+
+    async fn remove_watcher(&self, key: &str, account_id: &str) -> Result<()> {
+        self.client(key).remove_watcher(key, account_id).await
+    }
+
+    async fn get_projects(&self) -> Result<Vec<Project>> {
+        self.first().get_projects().await
+    }
+
+    async fn get_versions(&self, project: &str) -> Result<Vec<Version>> {
+        self.first().get_versions(project).await
+    }
+
+    async fn get_components(&self, project: &str) -> Result<Vec<Component>> {
+        self.first().get_components(project).await
+    }
+
+    async fn get_priorities(&self) -> Result<Vec<String>> {
+        self.first().get_priorities().await
+    }
+
+    async fn myself(&self) -> Result<User> {
+        self.first().myself().await
+    }
+
+    async fn server_info(&self) -> Result<ServerInfo> {
+        self.first().server_info().await
+    }
+
+    /// The custom fields are looked up once for all instances, so those of the first count.
+    async fn get_fields(&self) -> Result<Vec<Field>> {
+        self.first().get_fields().await
+    }
+
+    async fn download_attachment(&self, url: &str) -> Result<Vec<u8>> {
+        // Attachment URLs don't tell the issue, but do tell the site
+        let client = self
+            .clients
+            .iter()
+            .map(|(_, client)| client)
+            .find(|client| {
+                let browse = client.browse_url("");
+                let base = browse.trim_end_matches("/browse/");
+                url.starts_with(base)
+            });
+        match client {
+            Some(client) => client.download_attachment(url).await,
+            None => self.first().download_attachment(url).await,
+        }
+    }
+
+    async fn add_attachment(&self, key: &str, filename: &str, content: Vec<u8>) -> Result<()> {
+        self.client(key)
+            .add_attachment(key, filename, content)
+            .await
+    }
+
+    fn browse_url(&self, key: &str) -> String {
+        self.client(key).browse_url(key)
+    }
+
+    /// Each instance has a token of its own, so one typed in doesn't do.
+    fn with_api_token(&self, _api_token: &str) -> Option<Arc<dyn JiraClient>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{jira::FakeClient, ui::issue::Issue};
+
+    #[tokio::test]
+    async fn merges_searches_and_routes_by_issue() {
+        let work = Arc::new(FakeClient::with_issues(vec![
+            FakeClient::issue("OPS-1", "Failover", "To Do"),
+            FakeClient::issue("OPS-2", "Backups", "To Do"),
+        ]));
+        let oss = Arc::new(FakeClient::with_issues(vec![FakeClient::issue(
+            "CORE-7",
+            "Release 2.0",
+            "In Progress",
+        )]));
+        let client = MultiClient::new(vec![
+            ("work".to_string(), work.clone() as Arc<dyn JiraClient>),
+            ("oss".to_string(), oss.clone() as Arc<dyn JiraClient>),
+        ]);
+
+        let page = client
+            .search("assignee = currentUser()", None, 100)
+            .await
+            .unwrap();
+        assert!(page.next.is_none());
+        let issues: Vec<(String, Option<String>)> = page
+            .issues
+            .iter()
+            .map(Issue::from_jira)
+            .map(|issue| (issue.id.clone(), issue.instance().map(str::to_string)))
+            .collect();
+        assert_eq!(issues, [
+            ("OPS-1".to_string(), Some("work".to_string())),
+            ("OPS-2".to_string(), Some("work".to_string())),
+            ("CORE-7".to_string(), Some("oss".to_string())),
+        ]);
+
+        client.add_comment("CORE-7", "Shipped").await.unwrap();
+        assert_eq!(oss.comments(), [("CORE-7".to_string(), "Shipped".to_string())]);
+        assert!(work.comments().is_empty());
+        let issue = Issue::from_jira(&client.get_issue("CORE-7").await.unwrap());
+        assert_eq!(issue.instance(), Some("oss"));
+    }
+}
@@ -0,0 +1,214 @@
+//! A small HTTP listener for Jira webhooks, so changes made elsewhere show up right away.
+//!
+//! Only what Jira sends is supported: one `POST` with a JSON body per connection. Jira can't
+//! authenticate itself to a plain listener, so a `token` query parameter in the webhook URL
+//! can be required instead.
+
+use jira_v3_openapi::models::IssueBean;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::mpsc::UnboundedSender,
+};
+
+/// Largest request accepted, headers and body each. Issue payloads are a few KB.
+const MAX_REQUEST_SIZE: u64 = 1024 * 1024;
+
+/// A change to an issue reported by Jira.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookEvent {
+    /// An issue was created or edited; `issue` is its new state.
+    IssueChanged {
+        issue: Box<IssueBean>,
+        created: bool,
+    },
+    IssueDeleted {
+        key: String,
+    },
+    /// Someone commented on an issue.
+    Commented {
+        key: String,
+        author: String,
+    },
+}
+
+impl WebhookEvent {
+    /// Parses a webhook payload. Events the app doesn't care about yield `None`.
+    pub fn parse(payload: &Value) -> Option<Self> {
+        let issue = payload.get("issue")?;
+        let key = issue.get("key")?.as_str()?.to_string();
+        match payload.get("webhookEvent")?.as_str()? {
+            event @ ("jira:issue_created" | "jira:issue_updated") => {
+                Some(WebhookEvent::IssueChanged {
+                    issue: Box::new(serde_json::from_value(issue.clone()).ok()?),
+                    created: event == "jira:issue_created",
+                })
+            }
+            "jira:issue_deleted" => Some(WebhookEvent::IssueDeleted { key }),
+            "comment_created" => Some(WebhookEvent::Commented {
+                key,
+                author: payload
+                    .pointer("/comment/author/displayName")
+                    .and_then(|name| name.as_str())
+                    .unwrap_or("Someone")
+                    .to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Accepts webhook requests on `listener` until sending an event fails.
+///
+/// With a `token`, requests must carry it as a `token` query parameter.
+pub async fn serve(
+    listener: TcpListener,
+    token: Option<String>,
+    tx: UnboundedSender<WebhookEvent>,
+) -> std::io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        if tx.is_closed() {
+            return Ok(());
+        }
+        let tx = tx.clone();
+        let token = token.clone();
+        tokio::spawn(async move {
+            // The connection is Jira's problem once it breaks
+            let _ = handle(stream, token.as_deref(), &tx).await;
+        });
+    }
+}
+
+/// Reads one request and answers it.
+async fn handle(
+    stream: TcpStream,
+    token: Option<&str>,
+    tx: &UnboundedSender<WebhookEvent>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut head = (&mut reader).take(MAX_REQUEST_SIZE);
+
+    let mut request_line = String::new();
+    head.read_line(&mut request_line).await?;
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if head.read_line(&mut line).await? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+    let status = if method != "POST" {
+        "405 Method Not Allowed"
+    } else if !authorized(target, token) {
+        "403 Forbidden"
+    } else if content_length > MAX_REQUEST_SIZE {
+        "413 Content Too Large"
+    } else {
+        let mut body = vec![0; content_length as usize];
+        reader.read_exact(&mut body).await?;
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(payload) => {
+                if let Some(event) = WebhookEvent::parse(&payload) {
+                    let _ = tx.send(event);
+                }
+                "204 No Content"
+            }
+            Err(_) => "400 Bad Request",
+        }
+    };
+
+    let response = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    reader.get_mut().write_all(response.as_bytes()).await?;
+    reader.get_mut().shutdown().await
+}
+
+/// Whether the request target (like `/hooks?token=abc`) carries the expected token.
+fn authorized(target: &str, token: Option<&str>) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    target
+        .split_once('?')
+        .into_iter()
+        .flat_map(|(_, query)| query.split('&'))
+        .any(|param| param.strip_prefix("token=") == Some(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn parses_issue_and_comment_events() {
+        let issue = json!({ "key": "OPS-1", "fields": { "summary": "Failover" } });
+        let event = WebhookEvent::parse(&json!({
+            "webhookEvent": "jira:issue_updated",
+            "issue": issue,
+        }));
+        assert!(matches!(
+            event,
+            Some(WebhookEvent::IssueChanged { issue, created: false })
+                if issue.key.as_deref() == Some("OPS-1")
+        ));
+
+        let event = WebhookEvent::parse(&json!({
+            "webhookEvent": "comment_created",
+            "issue": issue,
+            "comment": { "author": { "displayName": "Ada" } },
+        }));
+        assert_eq!(
+            event,
+            Some(WebhookEvent::Commented {
+                key: "OPS-1".to_string(),
+                author: "Ada".to_string(),
+            })
+        );
+
+        let event = WebhookEvent::parse(&json!({ "webhookEvent": "sprint_started" }));
+        assert_eq!(event, None);
+    }
+
+    #[test]
+    fn checks_the_token() {
+        assert!(authorized("/", None));
+        assert!(authorized("/hook?user=x&token=s3cret", Some("s3cret")));
+        assert!(!authorized("/hook", Some("s3cret")));
+        assert!(!authorized("/hook?token=guess", Some("s3cret")));
+    }
+
+    #[tokio::test]
+    async fn serves_webhook_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tokio::spawn(serve(listener, Some("s3cret".to_string()), tx));
+
+        let body = json!({ "webhookEvent": "jira:issue_deleted", "issue": { "key": "OPS-2" } });
+        let body = body.to_string();
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "POST /?token=s3cret HTTP/1.1\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 204"));
+        assert_eq!(rx.recv().await, Some(WebhookEvent::IssueDeleted { key: "OPS-2".to_string() }));
+    }
+}
@@ -0,0 +1,32 @@
+//! A minimal terminal user interface for viewing and creating Jira issues.
+//!
+//! The crate is organized as follows:
+//!
+//! - [`jira`]: configuration and API calls against a Jira Cloud instance.
+//! - [`ui`]: the issue model and the ratatui widgets rendering it.
+//! - [`cli`], [`config`], and [`state`]: command-line arguments, user settings, and UI state
+//!   persisted between runs.
+//! - [`export`] and [`import`]: the issues in the formats of other planning tools, and
+//!   issues created in bulk from a CSV file.
+//! - [`report`]: the time logged per day, for timesheets, and what was done lately, for
+//!   standups.
+//! - [`doctor`]: the checks of `jira-tui doctor`, for finding out why the app won't start.
+//! - [`app`]: application state, the message-based update logic, and the event loop tying
+//!   everything together.
+//!
+//! The `jira-tui` binary is a thin wrapper that sets up the terminal and calls
+//! [`app::run_app`].
+
+pub mod app;
+pub mod cli;
+pub mod config;
+pub mod doctor;
+pub mod error;
+pub mod export;
+pub mod import;
+pub mod jira;
+pub mod report;
+pub mod state;
+pub mod ui;
+
+pub use error::{Error, Result};
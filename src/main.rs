@@ -1,4 +1,8 @@
+use std::sync::Arc;
+
+use clap::Parser;
 use crossterm::{
+    cursor::Show,
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{
@@ -6,22 +10,44 @@ use crossterm::{
         enable_raw_mode,
     },
 };
+use jira_tui::{
+    Error, Result, app,
+    cli::{Cli, Command},
+    config::Config,
+    doctor, export, import,
+    jira::{self, JiraClient},
+    report,
+    state::UiState,
+    ui,
+};
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::error::Error;
-
-mod app;
-mod jira;
-mod ui;
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if let Some(command) = &cli.command {
+        if let Err(e) = run_command(command, &cli).await {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Leave the alternate screen before the panic message is printed, or it gets lost.
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+        default_hook(info);
+    }));
+
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture, Clear(ClearType::All))?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let res = run_jira_tui(&mut terminal).await;
+    let res = run_jira_tui(&mut terminal, &cli).await;
 
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
@@ -29,26 +55,202 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     if let Err(e) = res {
         eprintln!("{e}");
+        if e.is_auth() {
+            eprintln!("Check that JIRA_TUI_USER and JIRA_TUI_TOKEN are correct.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs a subcommand, which works without the terminal interface.
+async fn run_command(command: &Command, cli: &Cli) -> Result<()> {
+    if let Command::Doctor = command {
+        // Reports problems with the config instead of stopping at the first
+        if !doctor::run(cli).await {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Command::Import {
+        file,
+        project,
+        issue_type,
+        dry_run: true,
+    } = command
+    {
+        // Checking the file needs no connection to Jira
+        let rows = import::read_issues(&std::fs::read_to_string(file)?, project, issue_type)?;
+        for row in &rows {
+            println!("{}: {} {}", row.line, row.issue.issue_type, row.issue.summary);
+        }
+        println!("{} issues to create", rows.len());
+        return Ok(());
     }
 
+    let settings = cli.load_config()?;
+    let profile = cli.profile(&settings)?;
+    let client = jira::ApiClient::new(&jira::JiraConfig::load(&settings, &profile)?);
+    // Jira out of reach fails the command on its own request
+    let _ = jira::find_custom_fields(&client).await;
+    match command {
+        Command::Export { format, jql } => {
+            let jql = jql.clone().unwrap_or_else(|| profile.startup_jql());
+            let issues: Vec<_> = jira::search_all(&client, &jql)
+                .await?
+                .iter()
+                .map(ui::issue::Issue::from_jira)
+                .collect();
+            let output = export::export(&issues, *format, |key| client.browse_url(key));
+            print!("{output}");
+        }
+        Command::View { key, format } => {
+            let issue = ui::issue::Issue::from_jira(&client.get_issue(key).await?);
+            let url = client.browse_url(key);
+            print!("{}", export::document(&issue, *format, Some(&url)));
+        }
+        Command::Worklog { from, to, format } => {
+            let today = chrono::Local::now().date_naive();
+            let monday = today.week(chrono::Weekday::Mon).first_day();
+            let report =
+                report::worklog_report(&client, from.unwrap_or(monday), to.unwrap_or(today))
+                    .await?;
+            print!("{}", report.render(*format));
+        }
+        Command::Standup { since } => {
+            let today = chrono::Local::now().date_naive();
+            let since = since.unwrap_or_else(|| report::previous_work_day(today));
+            for entry in report::standup(&client, since).await? {
+                println!("{entry}");
+            }
+        }
+        Command::Sprint { name, format } => {
+            let report = report::sprint_report(&client, name).await?;
+            print!("{}", report.render(*format));
+        }
+        Command::Import { file, project, issue_type, .. } => {
+            let rows = import::read_issues(&std::fs::read_to_string(file)?, project, issue_type)?;
+            let total = rows.len();
+            let mut done = 0;
+            let failed = import::create_all(&client, &rows, |row, result| {
+                done += 1;
+                match result {
+                    Ok(created) => {
+                        println!("[{done}/{total}] {} {}", created.key, row.issue.summary)
+                    }
+                    Err(e) => eprintln!("[{done}/{total}] line {}: {e}", row.line),
+                }
+            })
+            .await;
+            println!("Created {} of {total} issues", total - failed);
+            if failed > 0 {
+                eprintln!("{failed} failed; remove the created rows from the file before retrying");
+                std::process::exit(1);
+            }
+        }
+        Command::Create {
+            from,
+            project,
+            issue_type,
+            labels,
+            parent,
+        } => {
+            let text = if from.as_os_str() == "-" {
+                if cli.token_stdin {
+                    return Err(Error::Input(
+                        "--from - and --token-stdin can't both read standard input".to_string(),
+                    ));
+                }
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(from)?
+            };
+            let Some(project) = project.as_ref().or(profile.project.as_ref()) else {
+                return Err(Error::Input(
+                    "no project to create the issue in; pass --project".to_string(),
+                ));
+            };
+            let mut issue = import::read_note(&text, project, issue_type)?;
+            issue.labels = labels.clone();
+            issue.parent = parent.clone();
+            let created = client.create_issue(&issue).await?;
+            println!("{} {}", created.key, created.url);
+        }
+        Command::Doctor => unreachable!("handled above"),
+    }
     Ok(())
 }
 
+/// A client for each profile of the config, named after it, for `--all-profiles`.
+fn profile_clients(settings: &Config) -> Result<Vec<(String, Arc<dyn JiraClient>)>> {
+    if settings.profiles.is_empty() {
+        return Err(Error::Config(
+            "--all-profiles needs [profiles.<name>] tables in the config".to_string(),
+        ));
+    }
+    settings
+        .profiles
+        .keys()
+        .map(|name| {
+            let profile = settings.profile(Some(name))?;
+            let config = jira::JiraConfig::load(settings, &profile)?;
+            let client: Arc<dyn JiraClient> = Arc::new(jira::ApiClient::new(&config));
+            Ok((name.clone(), client))
+        })
+        .collect()
+}
+
 async fn run_jira_tui<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
-) -> Result<(), Box<dyn Error>> {
-    let config = jira::JiraConfig::from_env()
-        .map_err(|e| format!("Failed to load Jira config from environment: {e}"))?;
-    let search_results = jira::fetch_assigned_issues(&config, 100).await?;
-    let issues = search_results
-        .issues
-        .unwrap_or_default()
-        .into_iter()
-        .map(|j| ui::issue::Issue::from_jira(&j))
-        .collect();
-
-    let app = app::App::new(issues);
-    app::run_app(terminal, app)?;
+    cli: &Cli,
+) -> Result<()> {
+    let mut settings = cli.load_config()?;
+    settings.no_color |= cli.no_color();
+    let profile = cli.profile(&settings)?;
+    let (client, connection): (Arc<dyn JiraClient>, _) = if cli.all_profiles {
+        let clients = profile_clients(&settings)?;
+        // Links go to the instance of each issue, not one for the whole app
+        let connection = app::connection::Connection {
+            instance: format!("{} instances", clients.len()),
+            ..Default::default()
+        };
+        (Arc::new(jira::MultiClient::new(clients)), connection)
+    } else {
+        let config = jira::JiraConfig::load(&settings, &profile)?;
+        let connection = app::connection::Connection::new(&config.base_url);
+        (Arc::new(jira::ApiClient::new(&config)), connection)
+    };
+    // Jira out of reach shouldn't keep the app from starting, so the search can be retried.
+    // The custom fields are found before the issues are read.
+    let jql = profile.startup_jql();
+    let (_, first) = tokio::join!(
+        jira::find_custom_fields(client.as_ref()),
+        client.search(&jql, None, jira::PAGE_SIZE as usize)
+    );
+    let issues = match &first {
+        Ok(page) => page
+            .issues
+            .iter()
+            .map(ui::issue::Issue::from_jira)
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    // Show the first page right away; `init` streams the rest in the background.
+    let mut app = app::App::new(issues);
+    app.configure(&settings, &UiState::load(profile.name.as_deref()));
+    app.start_with(&profile);
+    app.config_path = cli.config.clone();
+    app.no_color = cli.no_color();
+    app.connection = connection;
+    let mut init = match first {
+        Ok(page) => app::init(&mut app, page.next, page.total, cli.issue.as_deref()),
+        Err(e) => app::init_failed(&mut app, e, cli.issue.as_deref()),
+    };
+    if let Some(webhook) = settings.webhook.clone() {
+        init = app::Command::Batch(vec![init, app::Command::ListenForWebhooks(webhook)]);
+    }
+    app::run_app(terminal, app, client, init).await?;
 
     Ok(())
 }
@@ -0,0 +1,595 @@
+//! Reports on the user's own work, built from what Jira records about it, for timesheets
+//! and standups, and on the work of a sprint, for retros.
+
+use std::{collections::BTreeMap, fmt};
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Weekday};
+use clap::ValueEnum;
+
+use crate::{
+    error::Result,
+    jira::{self, HistoryEntry, JiraClient, jql},
+    ui::{
+        issue::{Issue, IssueRef},
+        text,
+    },
+};
+
+/// A format a report can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// A table per day, to read.
+    Text,
+    /// A row per day and issue, with the hours as a decimal number, for spreadsheets.
+    Csv,
+    /// Tables in markdown, for notes and wikis.
+    #[value(name = "md")]
+    Markdown,
+}
+
+/// Time logged by one user, in seconds, by day and then by issue.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorklogReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: BTreeMap<NaiveDate, BTreeMap<String, u64>>,
+    /// Summaries of the issues in `days`, by key.
+    pub summaries: BTreeMap<String, String>,
+}
+
+/// Collects the time the signed-in user logged from `from` to `to`, both included, on the
+/// days the work started.
+pub async fn worklog_report(
+    client: &dyn JiraClient,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<WorklogReport> {
+    let me = client.myself().await?;
+    // Only narrows down the issues to look at; the worklogs of each are checked below
+    let jql = format!(
+        "worklogAuthor = currentUser() AND worklogDate >= \"{from}\" AND worklogDate <= \"{to}\" \
+         ORDER BY key"
+    );
+    let mut report = WorklogReport { from, to, ..WorklogReport::default() };
+    for issue in jira::search_all(client, &jql).await? {
+        let issue = Issue::from_jira(&issue);
+        for worklog in client.get_worklogs(&issue.id).await? {
+            let Some(day) = worklog.started.map(|started| started.date_naive()) else {
+                continue;
+            };
+            if worklog.author_account_id != me.account_id || day < from || day > to {
+                continue;
+            }
+            let logged = report.days.entry(day).or_default();
+            *logged.entry(issue.id.clone()).or_default() += worklog.seconds;
+            report
+                .summaries
+                .insert(issue.id.clone(), issue.summary.clone());
+        }
+    }
+    Ok(report)
+}
+
+impl WorklogReport {
+    fn issue(&self, key: &str) -> IssueRef {
+        IssueRef {
+            key: key.to_string(),
+            summary: self.summaries.get(key).cloned().unwrap_or_default(),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.days.values().flat_map(|logged| logged.values()).sum()
+    }
+
+    /// Writes the report in `format`.
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Text => self.text(),
+            ReportFormat::Csv => self.csv(),
+            ReportFormat::Markdown => self.markdown(),
+        }
+    }
+
+    fn text(&self) -> String {
+        let mut text = format!("Time logged from {} to {}\n", self.from, self.to);
+        for (day, logged) in &self.days {
+            let sum = logged.values().sum();
+            let day = day.format("%a %Y-%m-%d").to_string();
+            text.push_str(&format!("\n{day:<58} {:>6}\n", hours(sum)));
+            for (key, seconds) in logged {
+                let issue = self.issue(key);
+                let line = text::truncate(&issue.summary, 45);
+                text.push_str(&format!("  {:<10} {line:<45} {:>6}\n", issue.key, hours(*seconds)));
+            }
+        }
+        text.push_str(&format!("\n{:<58} {:>6}\n", "Total", hours(self.total())));
+        text
+    }
+
+    fn csv(&self) -> String {
+        let mut csv = "date,key,summary,hours\n".to_string();
+        for (day, logged) in &self.days {
+            for (key, seconds) in logged {
+                let issue = self.issue(key);
+                let summary = format!("\"{}\"", issue.summary.replace('"', "\"\""));
+                let hours = *seconds as f64 / 3600.0;
+                csv.push_str(&format!("{day},{},{summary},{hours:.2}\n", issue.key));
+            }
+        }
+        csv
+    }
+
+    fn markdown(&self) -> String {
+        let mut md = format!("# Time logged from {} to {}\n\n", self.from, self.to);
+        md.push_str("| Day | Issue | Summary | Hours |\n| --- | --- | --- | ---: |\n");
+        for (day, logged) in &self.days {
+            for (key, seconds) in logged {
+                let issue = self.issue(key);
+                let summary = markdown_cell(&issue.summary);
+                md.push_str(&format!(
+                    "| {day} | {} | {summary} | {} |\n",
+                    issue.key,
+                    hours(*seconds)
+                ));
+            }
+        }
+        md.push_str(&format!("| **Total** | | | **{}** |\n", hours(self.total())));
+        md
+    }
+}
+
+/// `text` fit for a cell of a markdown table.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// What the user did on one issue, for the standup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StandupEntry {
+    pub issue: IssueRef,
+    /// The statuses the user moved the issue to, in order.
+    pub moved_to: Vec<String>,
+    pub comments: usize,
+    /// Time logged, in seconds.
+    pub logged: u64,
+}
+
+impl fmt::Display for StandupEntry {
+    /// A bullet like `- OPS-1 Failover: moved to In Progress, commented, logged 2:30`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut done = Vec::new();
+        if !self.moved_to.is_empty() {
+            done.push(format!("moved to {}", self.moved_to.join(", then ")));
+        }
+        match self.comments {
+            0 => {}
+            1 => done.push("commented".to_string()),
+            n => done.push(format!("commented {n} times")),
+        }
+        if self.logged > 0 {
+            done.push(format!("logged {}", hours(self.logged)));
+        }
+        write!(f, "- {} {}: {}", self.issue.key, self.issue.summary, done.join(", "))
+    }
+}
+
+/// Collects what the signed-in user did since the start of `since`: the issues they moved to
+/// another status, commented on, or logged time on. Comments are those the search returns
+/// with the issues.
+pub async fn standup(client: &dyn JiraClient, since: NaiveDate) -> Result<Vec<StandupEntry>> {
+    let me = client.myself().await?;
+    let recent =
+        |when: Option<DateTime<FixedOffset>>| when.is_some_and(|when| when.date_naive() >= since);
+    let jql = format!(
+        "updated >= \"{since}\" AND (status CHANGED BY currentUser() AFTER \"{since}\" OR \
+         worklogAuthor = currentUser() OR watcher = currentUser()) ORDER BY updated"
+    );
+    let mut entries = Vec::new();
+    for issue in jira::search_all(client, &jql).await? {
+        let issue = Issue::from_jira(&issue);
+        // The changelog is newest first
+        let moved_to = client
+            .get_history(&issue.id)
+            .await?
+            .into_iter()
+            .rev()
+            .filter(|entry| entry.author == me.display_name && recent(entry.created))
+            .flat_map(|entry| entry.changes)
+            .filter(|change| change.field == "status")
+            .filter_map(|change| change.to)
+            .collect();
+        let comments = issue
+            .comments()
+            .iter()
+            .filter(|comment| comment.author == me.display_name && recent(comment.created))
+            .count();
+        let logged = client
+            .get_worklogs(&issue.id)
+            .await?
+            .iter()
+            .filter(|worklog| worklog.author_account_id == me.account_id && recent(worklog.started))
+            .map(|worklog| worklog.seconds)
+            .sum();
+        let entry = StandupEntry {
+            issue: IssueRef {
+                key: issue.id,
+                summary: issue.summary,
+            },
+            moved_to,
+            comments,
+            logged,
+        };
+        if !entry.moved_to.is_empty() || entry.comments > 0 || entry.logged > 0 {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// An issue of a sprint, with what came of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SprintIssue {
+    pub issue: IssueRef,
+    pub status: String,
+    pub points: Option<f64>,
+    /// Done in the sprint, rather than carried over to a later one or the backlog.
+    pub completed: bool,
+    /// Added to the sprint after it started.
+    pub added: bool,
+}
+
+/// What came of the issues of a sprint, for the retro.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SprintReport {
+    pub sprint: String,
+    pub started: Option<DateTime<FixedOffset>>,
+    /// When the sprint was closed, unless it's still running.
+    pub closed: Option<DateTime<FixedOffset>>,
+    pub issues: Vec<SprintIssue>,
+}
+
+/// Collects the issues of the sprint named `sprint` with their changelogs, to tell which were
+/// added after it started.
+pub async fn sprint_report(client: &dyn JiraClient, sprint: &str) -> Result<SprintReport> {
+    let jql = format!("sprint = {} ORDER BY Rank", jql::quote(sprint));
+    let mut issues = Vec::new();
+    for issue in jira::search_all(client, &jql).await? {
+        let issue = Issue::from_jira(&issue);
+        let history = client.get_history(&issue.id).await?;
+        issues.push((issue, history));
+    }
+    Ok(SprintReport::new(sprint, &issues))
+}
+
+impl SprintReport {
+    /// Sorts out the issues of `sprint`, each with its changelog, newest first. Issues done
+    /// and not moved on to a later sprint count as completed. Issues count as added if they
+    /// joined the sprint, or were created without a move to it, after it started.
+    pub fn new(sprint: &str, issues: &[(Issue, Vec<HistoryEntry>)]) -> Self {
+        let details = issues
+            .iter()
+            .flat_map(|(issue, _)| issue.sprint_details())
+            .find(|details| details.name == sprint);
+        let started = details.as_ref().and_then(|details| details.started);
+        let in_sprint = |names: Option<&str>| {
+            names.is_some_and(|names| names.split(", ").any(|name| name == sprint))
+        };
+        let issues = issues
+            .iter()
+            .map(|(issue, history)| {
+                let later_sprint = issue
+                    .sprints
+                    .iter()
+                    .skip_while(|name| *name != sprint)
+                    .nth(1)
+                    .is_some();
+                let joined = history
+                    .iter()
+                    .find(|entry| {
+                        entry.changes.iter().any(|change| {
+                            change.field.eq_ignore_ascii_case("sprint")
+                                && in_sprint(change.to.as_deref())
+                                && !in_sprint(change.from.as_deref())
+                        })
+                    })
+                    .and_then(|entry| entry.created)
+                    .or(issue.created);
+                SprintIssue {
+                    issue: IssueRef {
+                        key: issue.id.clone(),
+                        summary: issue.summary.clone(),
+                    },
+                    status: issue
+                        .status
+                        .as_ref()
+                        .map(|status| status.name.clone())
+                        .unwrap_or_default(),
+                    points: issue.story_points,
+                    completed: issue.is_done() && !later_sprint,
+                    added: started
+                        .zip(joined)
+                        .is_some_and(|(started, joined)| joined > started),
+                }
+            })
+            .collect();
+        Self {
+            sprint: sprint.to_string(),
+            started,
+            closed: details.and_then(|details| details.completed),
+            issues,
+        }
+    }
+
+    /// How many of the issues `which` picks there are, and their story points.
+    fn count(&self, which: impl Fn(&SprintIssue) -> bool) -> (usize, f64) {
+        self.issues
+            .iter()
+            .filter(|issue| which(issue))
+            .fold((0, 0.0), |(count, points), issue| {
+                (count + 1, points + issue.points.unwrap_or_default())
+            })
+    }
+
+    /// The sums of the report: the completed issues, those carried over, and those added
+    /// after the start, each with how many there are and their story points.
+    pub fn totals(&self) -> [(&'static str, usize, f64); 3] {
+        let (completed, completed_points) = self.count(|issue| issue.completed);
+        let (carried, carried_points) = self.count(|issue| !issue.completed);
+        let (added, added_points) = self.count(|issue| issue.added);
+        [
+            ("Completed", completed, completed_points),
+            ("Carried over", carried, carried_points),
+            ("Added after the start", added, added_points),
+        ]
+    }
+
+    /// When the sprint ran, like `2024-05-06 to 2024-05-17`.
+    fn dates(&self) -> String {
+        let date =
+            |time: Option<DateTime<FixedOffset>>| time.map(|time| time.date_naive().to_string());
+        match (date(self.started), date(self.closed)) {
+            (Some(started), Some(closed)) => format!("{started} to {closed}"),
+            (Some(started), None) => format!("since {started}, still running"),
+            (None, _) => "not started".to_string(),
+        }
+    }
+
+    /// Writes the report in `format`.
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Text => self.text(),
+            ReportFormat::Csv => self.csv(),
+            ReportFormat::Markdown => self.markdown(),
+        }
+    }
+
+    fn text(&self) -> String {
+        let mut text = format!("{} ({})\n\n", self.sprint, self.dates());
+        for (name, count, points) in self.totals() {
+            text.push_str(&format!("{name:<22} {count:>3} issues {points:>6} points\n"));
+        }
+        for (title, completed) in [("Completed", true), ("Carried over", false)] {
+            text.push_str(&format!("\n{title}\n"));
+            for issue in self
+                .issues
+                .iter()
+                .filter(|issue| issue.completed == completed)
+            {
+                let added = if issue.added { "+" } else { " " };
+                let points = issue.points.map(|p| p.to_string()).unwrap_or_default();
+                let summary = text::truncate(&issue.issue.summary, 45);
+                text.push_str(&format!(
+                    "{added} {:<10} {summary:<45} {:<12} {points:>4}\n",
+                    issue.issue.key,
+                    text::truncate(&issue.status, 12),
+                ));
+            }
+        }
+        text.push_str("\n+ added after the start\n");
+        text
+    }
+
+    fn csv(&self) -> String {
+        let mut csv = "key,summary,status,points,completed,added\n".to_string();
+        for issue in &self.issues {
+            let quote = |text: &str| format!("\"{}\"", text.replace('"', "\"\""));
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                issue.issue.key,
+                quote(&issue.issue.summary),
+                quote(&issue.status),
+                issue.points.map(|p| p.to_string()).unwrap_or_default(),
+                issue.completed,
+                issue.added,
+            ));
+        }
+        csv
+    }
+
+    fn markdown(&self) -> String {
+        let mut md = format!("# {}\n\n{}\n\n", self.sprint, self.dates());
+        md.push_str("| | Issues | Points |\n| --- | ---: | ---: |\n");
+        for (name, count, points) in self.totals() {
+            md.push_str(&format!("| {name} | {count} | {points} |\n"));
+        }
+        for (title, completed) in [("Completed", true), ("Carried over", false)] {
+            md.push_str(&format!("\n## {title}\n\n"));
+            let issues: Vec<_> = self
+                .issues
+                .iter()
+                .filter(|issue| issue.completed == completed)
+                .collect();
+            if issues.is_empty() {
+                md.push_str("None.\n");
+                continue;
+            }
+            md.push_str("| Issue | Summary | Status | Points | Added |\n");
+            md.push_str("| --- | --- | --- | ---: | --- |\n");
+            for issue in issues {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    issue.issue.key,
+                    markdown_cell(&issue.issue.summary),
+                    markdown_cell(&issue.status),
+                    issue.points.map(|p| p.to_string()).unwrap_or_default(),
+                    if issue.added { "yes" } else { "" },
+                ));
+            }
+        }
+        md
+    }
+}
+
+/// The last work day before `today`: Friday on Mondays, yesterday otherwise.
+pub fn previous_work_day(today: NaiveDate) -> NaiveDate {
+    let days = match today.weekday() {
+        Weekday::Mon => 3,
+        Weekday::Sun => 2,
+        _ => 1,
+    };
+    today - chrono::Days::new(days)
+}
+
+/// Seconds as hours and minutes, like `7:30`; timesheets count hours, not Jira's 8-hour days.
+fn hours(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        jira::{FakeClient, Worklog},
+        ui::time,
+    };
+
+    #[tokio::test]
+    async fn sums_up_own_worklogs_by_day_and_issue() {
+        let client = FakeClient::with_issues(vec![
+            FakeClient::issue("OPS-1", "Failover", "In Progress"),
+            FakeClient::issue("OPS-2", "Backups, \"nightly\"", "To Do"),
+        ]);
+        let log = |key, author: &str, started, seconds| {
+            client.log_work(key, Worklog {
+                author_account_id: author.to_string(),
+                author: author.to_string(),
+                started: time::parse_timestamp(started),
+                seconds,
+            })
+        };
+        log("OPS-1", "fake", "2024-05-06T09:00:00.000+0200", 2 * 3600);
+        log("OPS-1", "fake", "2024-05-06T14:00:00.000+0200", 1800);
+        log("OPS-2", "fake", "2024-05-06T16:00:00.000+0200", 3600);
+        log("OPS-2", "fake", "2024-05-07T09:00:00.000+0200", 4 * 3600);
+        // Someone else's, and outside the range
+        log("OPS-2", "ada", "2024-05-07T09:00:00.000+0200", 3600);
+        log("OPS-1", "fake", "2024-05-13T09:00:00.000+0200", 3600);
+
+        let from = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        let report = worklog_report(&client, from, to).await.unwrap();
+        assert_eq!(report.total(), 7 * 3600 + 1800);
+        assert_eq!(
+            report.render(ReportFormat::Csv),
+            "date,key,summary,hours\n\
+             2024-05-06,OPS-1,\"Failover\",2.50\n\
+             2024-05-06,OPS-2,\"Backups, \"\"nightly\"\"\",1.00\n\
+             2024-05-07,OPS-2,\"Backups, \"\"nightly\"\"\",4.00\n"
+        );
+        let text = report.render(ReportFormat::Text);
+        assert!(text.contains("Mon 2024-05-06"));
+        assert!(text.ends_with(&format!("{:<58} {:>6}\n", "Total", "7:30")));
+    }
+
+    #[tokio::test]
+    async fn lists_what_was_done_for_the_standup() {
+        let client = FakeClient::with_issues(vec![
+            FakeClient::issue("OPS-1", "Failover", "To Do"),
+            FakeClient::issue("OPS-2", "Backups", "To Do"),
+            FakeClient::issue("OPS-3", "Untouched", "To Do"),
+        ]);
+        // Moves are made by the fake user, now
+        client.transition_issue("OPS-1", "1").await.unwrap();
+        client.transition_issue("OPS-1", "2").await.unwrap();
+        client.log_work("OPS-2", Worklog {
+            author_account_id: "fake".to_string(),
+            author: "Fake User".to_string(),
+            started: Some(chrono::Local::now().fixed_offset()),
+            seconds: 5400,
+        });
+
+        let yesterday = previous_work_day(chrono::Local::now().date_naive());
+        let lines: Vec<String> = standup(&client, yesterday)
+            .await
+            .unwrap()
+            .iter()
+            .map(StandupEntry::to_string)
+            .collect();
+        assert_eq!(lines, [
+            "- OPS-1 Failover: moved to In Progress, then Done",
+            "- OPS-2 Backups: logged 1:30",
+        ]);
+    }
+
+    #[test]
+    fn sorts_out_what_came_of_a_sprint() {
+        let sprints = json!([
+            {
+                "name": "Sprint 4",
+                "state": "closed",
+                "startDate": "2024-05-06T09:00:00.000Z",
+                "completeDate": "2024-05-17T16:00:00.000Z",
+            },
+            { "name": "Sprint 5", "state": "active" },
+        ]);
+        let issue = |key: &str, status: &str, points: f64, count: usize| {
+            let mut bean = FakeClient::issue(key, &format!("Summary of {key}"), status);
+            let fields = bean.fields.get_or_insert_default();
+            fields.insert(
+                "customfield_10020".to_string(),
+                json!(sprints.as_array().unwrap()[..count]),
+            );
+            fields.insert("customfield_10016".to_string(), json!(points));
+            fields.insert("created".to_string(), json!("2024-05-01T09:00:00.000+0000"));
+            Issue::from_jira(&bean)
+        };
+        let joined = |when: &str, from: Option<&str>| HistoryEntry {
+            author: "Ana Lima".to_string(),
+            created: time::parse_timestamp(when),
+            changes: vec![jira::FieldChange {
+                field: "Sprint".to_string(),
+                from: from.map(str::to_string),
+                to: Some("Sprint 4".to_string()),
+            }],
+        };
+        let report = SprintReport::new("Sprint 4", &[
+            (issue("OPS-1", "Done", 5.0, 1), vec![joined("2024-05-03T09:00:00.000+0000", None)]),
+            (issue("OPS-2", "In Progress", 3.0, 2), Vec::new()),
+            (issue("OPS-3", "Done", 2.0, 1), vec![joined("2024-05-08T09:00:00.000+0000", None)]),
+            // Done only in the next sprint
+            (issue("OPS-4", "Done", 1.0, 2), Vec::new()),
+        ]);
+        assert_eq!(report.totals(), [
+            ("Completed", 2, 7.0),
+            ("Carried over", 2, 4.0),
+            ("Added after the start", 1, 2.0),
+        ]);
+        let md = report.render(ReportFormat::Markdown);
+        assert!(md.starts_with("# Sprint 4\n\n2024-05-06 to 2024-05-17\n"));
+        assert!(md.contains("| OPS-3 | Summary of OPS-3 | Done | 2 | yes |\n"));
+        assert!(md.contains("## Carried over\n\n| Issue |"));
+        let csv = report.render(ReportFormat::Csv);
+        assert!(csv.contains("OPS-2,\"Summary of OPS-2\",\"In Progress\",3,false,false\n"));
+    }
+
+    #[test]
+    fn skips_the_weekend_back_to_friday() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+        assert_eq!(previous_work_day(day(13)), day(10));
+        assert_eq!(previous_work_day(day(14)), day(13));
+    }
+}
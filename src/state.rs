@@ -0,0 +1,72 @@
+//! UI state that survives restarts, like the sidebar width and unsent drafts, stored as JSON
+//! in the platform state directory (`~/.local/state/jira-tui/state.json` on Linux). Each
+//! profile has a file of its own, like `state-work.json`, as drafts and changes are meant for
+//! its instance.
+
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::BulkChange,
+    error::{Error, Result},
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    /// Sidebar width chosen at runtime, overriding the configured default.
+    pub sidebar_width: Option<u16>,
+    /// Text typed into a composer but not sent, by composer (like `new-issue`). Composers
+    /// for an existing issue include its key, so each issue has its own draft.
+    pub drafts: BTreeMap<String, String>,
+    /// The project last switched to with the project picker.
+    pub last_project: Option<String>,
+    /// Changes to issues that weren't made in Jira yet; see [`Outbox`].
+    ///
+    /// [`Outbox`]: crate::app::outbox::Outbox
+    pub outbox: Vec<BulkChange>,
+    /// The profile the state belongs to, which picks the file.
+    #[serde(skip)]
+    pub profile: Option<String>,
+}
+
+impl UiState {
+    /// Location of the state file of `profile`, if the platform has a suitable directory.
+    pub fn path(profile: Option<&str>) -> Option<PathBuf> {
+        let file = match profile {
+            Some(profile) => format!("state-{profile}.json"),
+            None => "state.json".to_string(),
+        };
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("jira-tui").join(file))
+    }
+
+    /// Loads the saved state of `profile`. A missing or unreadable file just means starting
+    /// fresh.
+    pub fn load(profile: Option<&str>) -> Self {
+        let state: Self = Self::path(profile)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            profile: profile.map(str::to_string),
+            ..state
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path(self.profile.as_deref())
+            .ok_or_else(|| Error::Io(io::Error::other("no directory to store state in")))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        // Replaced in one step, so a crash while writing can't lose the drafts
+        let partial = path.with_extension("json.part");
+        fs::write(&partial, json)?;
+        fs::rename(partial, path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! The log pane: the latest requests to Jira and errors, below the list.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::app::App;
+
+/// Renders the newest entries of the activity log that fit, the newest at the bottom.
+pub fn render_log(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.appearance.theme;
+    let gray = Style::default().fg(theme.gray);
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(gray)
+        .title(" Log ");
+    let height = block.inner(area).height as usize;
+
+    let mut lines: Vec<Line> = app
+        .activity
+        .iter()
+        .rev()
+        .take(height)
+        .map(|entry| {
+            let style = match entry.error {
+                true => Style::default().fg(theme.red),
+                false => Style::default(),
+            };
+            Line::from(vec![
+                Span::styled(entry.when.format("%H:%M:%S ").to_string(), gray),
+                Span::styled(entry.text.as_str(), style),
+            ])
+        })
+        .collect();
+    lines.reverse();
+    if lines.is_empty() {
+        lines.push(Line::styled("Nothing logged yet", gray));
+    }
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
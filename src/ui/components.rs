@@ -0,0 +1,92 @@
+//! The components view: the components of a project with their leads and open issues.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    app::{App, Loadable, components::summaries},
+    ui::input::InputMode,
+};
+
+/// Renders a row per component of the project, by name.
+pub fn render_components(f: &mut Frame, app: &App, area: Rect) {
+    let look = &app.appearance;
+    let Some(project) = &app.view_project else {
+        return;
+    };
+    match app.components.get(project) {
+        Some(Loadable::Loaded(_)) => {}
+        Some(Loadable::Failed(e)) => {
+            let text = Line::styled(
+                format!("Failed to load the components of {project}: {e}"),
+                Style::default().fg(look.theme.red),
+            );
+            f.render_widget(Paragraph::new(text), area);
+            return;
+        }
+        Some(Loadable::Loading) | None => {
+            f.render_widget(Paragraph::new(format!("Loading components of {project}…")), area);
+            return;
+        }
+    }
+
+    let summaries = summaries(app);
+    if summaries.is_empty() {
+        f.render_widget(Paragraph::new(format!("{project} has no components")), area);
+        return;
+    }
+
+    let gray = Style::default().fg(look.theme.gray);
+    let rows = summaries.iter().map(|summary| {
+        let lead = match &summary.component.lead {
+            Some(lead) => Cell::from(lead.clone()),
+            None => Cell::from("no lead").style(gray),
+        };
+        let open = Cell::from(summary.open.to_string());
+        Row::new(vec![
+            Cell::from(summary.component.name.clone()),
+            lead,
+            if summary.open > 0 {
+                open
+            } else {
+                open.style(gray)
+            },
+            Cell::from(summary.component.issues.to_string()),
+        ])
+    });
+
+    let header = Row::new([
+        format!("{project} component"),
+        "Lead".to_string(),
+        "Open".to_string(),
+        "Issues".to_string(),
+    ])
+    .style(gray.add_modifier(Modifier::BOLD));
+    let highlight_style = if app.input_mode != InputMode::Normal {
+        look.theme.list_highlight_inactive
+    } else {
+        look.theme.list_highlight
+    };
+
+    let mut table_state = TableState::default();
+    table_state.select(Some(app.component_cursor.min(summaries.len() - 1)));
+
+    let table = Table::new(rows, [
+        Constraint::Min(16),
+        Constraint::Min(16),
+        Constraint::Length(6),
+        Constraint::Length(6),
+    ])
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(highlight_style)
+    .highlight_symbol(look.selection_marker())
+    .highlight_spacing(HighlightSpacing::Always);
+
+    f.render_stateful_widget(table, area, &mut table_state);
+}
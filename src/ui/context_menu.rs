@@ -0,0 +1,175 @@
+//! A small menu of the common actions on an issue, opened by right-clicking its row, for
+//! finding them by pointing rather than by knowing their keys.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::ui::theme::Appearance;
+
+/// An entry of the menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuItem {
+    OpenInBrowser,
+    ChangeStatus,
+    AssignToMe,
+    CopyKey,
+}
+
+impl MenuItem {
+    pub const ALL: [MenuItem; 4] =
+        [MenuItem::OpenInBrowser, MenuItem::ChangeStatus, MenuItem::AssignToMe, MenuItem::CopyKey];
+
+    fn label(self) -> &'static str {
+        match self {
+            MenuItem::OpenInBrowser => "Open in browser",
+            MenuItem::ChangeStatus => "Change status…",
+            MenuItem::AssignToMe => "Assign to me",
+            MenuItem::CopyKey => "Copy key",
+        }
+    }
+
+    /// The keys doing the same from the list, shown next to the item to learn them.
+    fn keys(self) -> &'static str {
+        match self {
+            MenuItem::OpenInBrowser => "O",
+            MenuItem::ChangeStatus | MenuItem::AssignToMe => "",
+            MenuItem::CopyKey => "yk",
+        }
+    }
+}
+
+/// State of an open menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenu {
+    /// The issue the menu is about.
+    pub key: String,
+    /// Where it was opened, the top left corner unless that doesn't leave room.
+    position: (u16, u16),
+    cursor: usize,
+    /// Where it was last drawn, to tell which item a click is on.
+    area: Rect,
+}
+
+/// What a key or click in the menu asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    None,
+    Close,
+    Pick(MenuItem),
+}
+
+impl ContextMenu {
+    pub fn new(key: impl Into<String>, column: u16, row: u16) -> Self {
+        Self {
+            key: key.into(),
+            position: (column, row),
+            cursor: 0,
+            area: Rect::default(),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> MenuAction {
+        let last = MenuItem::ALL.len() - 1;
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return MenuAction::Close;
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Enter => return MenuAction::Pick(MenuItem::ALL[self.cursor]),
+            KeyCode::Esc | KeyCode::Char('q') => return MenuAction::Close,
+            _ => {}
+        }
+        MenuAction::None
+    }
+
+    /// Picks the item under a click, or closes the menu for clicks outside it.
+    pub fn click(&self, column: u16, row: u16) -> MenuAction {
+        let inside = |area: Rect| {
+            (area.left()..area.right()).contains(&column)
+                && (area.top()..area.bottom()).contains(&row)
+        };
+        let items = Block::default().borders(Borders::ALL).inner(self.area);
+        if inside(items) {
+            MenuAction::Pick(MenuItem::ALL[(row - items.y) as usize])
+        } else if inside(self.area) {
+            MenuAction::None
+        } else {
+            MenuAction::Close
+        }
+    }
+}
+
+/// Draws the menu at the click that opened it, moved left or up as far as it takes to fit
+/// in `area`.
+pub fn render_context_menu(f: &mut Frame, menu: &mut ContextMenu, area: Rect, look: &Appearance) {
+    let label_width = MenuItem::ALL
+        .map(|item| item.label().chars().count())
+        .into_iter()
+        .max();
+    let keys_width = MenuItem::ALL
+        .map(|item| item.keys().len())
+        .into_iter()
+        .max();
+    let label_width = label_width.unwrap_or_default();
+    // The borders, a space on either side, and two between the label and the keys
+    let width = (label_width + keys_width.unwrap_or_default() + 6) as u16;
+    let height = MenuItem::ALL.len() as u16 + 2;
+    let (column, row) = menu.position;
+    menu.area = Rect {
+        x: column.min(area.right().saturating_sub(width)).max(area.x),
+        y: row.min(area.bottom().saturating_sub(height)).max(area.y),
+        width: width.min(area.width),
+        height: height.min(area.height),
+    };
+
+    let gray = Style::default().fg(look.theme.gray);
+    let lines: Vec<Line> = MenuItem::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let line = Line::from(vec![
+                Span::raw(format!(" {:label_width$}  ", item.label())),
+                Span::styled(item.keys(), gray),
+            ]);
+            match i == menu.cursor {
+                true => line.style(look.theme.list_highlight),
+                false => line,
+            }
+        })
+        .collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", menu.key));
+    f.render_widget(Clear, menu.area);
+    f.render_widget(Paragraph::new(lines).block(block), menu.area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_by_key_and_by_click() {
+        let mut menu = ContextMenu::new("OPS-1", 10, 5);
+        let press = |menu: &mut ContextMenu, code| menu.handle_key(&KeyEvent::from(code));
+        assert_eq!(press(&mut menu, KeyCode::Char('k')), MenuAction::None);
+        press(&mut menu, KeyCode::Down);
+        press(&mut menu, KeyCode::Char('j'));
+        assert_eq!(press(&mut menu, KeyCode::Enter), MenuAction::Pick(MenuItem::AssignToMe));
+        assert_eq!(press(&mut menu, KeyCode::Esc), MenuAction::Close);
+
+        menu.area = Rect::new(10, 5, 24, 6);
+        assert_eq!(menu.click(12, 6), MenuAction::Pick(MenuItem::OpenInBrowser));
+        assert_eq!(menu.click(32, 9), MenuAction::Pick(MenuItem::CopyKey));
+        // The border, and outside
+        assert_eq!(menu.click(12, 5), MenuAction::None);
+        assert_eq!(menu.click(12, 11), MenuAction::Close);
+    }
+}
@@ -0,0 +1,124 @@
+//! The cycle time view: how the times the listed issues took from starting work to being
+//! done, and from being created to being done, are spread, above a row per issue.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    app::{
+        App,
+        cycle_time::{CycleReport, Distribution, report},
+    },
+    ui::{input::InputMode, text, theme::Appearance, time},
+};
+
+/// A line summing up one kind of time, like `Cycle time  median 2d 4h  85% within 6d 1h`.
+fn distribution_line<'a>(
+    name: &'a str,
+    distribution: Option<Distribution>,
+    look: &Appearance,
+) -> Line<'a> {
+    let name = Span::styled(format!("{name:<12}"), Style::default().add_modifier(Modifier::BOLD));
+    let Some(spread) = distribution else {
+        return Line::from(vec![
+            name,
+            Span::styled("no issues", Style::default().fg(look.theme.gray)),
+        ]);
+    };
+    let gray = Style::default().fg(look.theme.gray);
+    Line::from(vec![
+        name,
+        Span::styled("median ", gray),
+        Span::raw(format!("{:<9}", time::elapsed(spread.median))),
+        Span::styled("85% within ", gray),
+        Span::raw(format!("{:<9}", time::elapsed(spread.p85))),
+        Span::styled("longest ", gray),
+        Span::raw(format!("{:<9}", time::elapsed(spread.longest))),
+        Span::styled(format!("over {} issues", spread.count), gray),
+    ])
+}
+
+/// The summary above the rows, with how many changelogs are still missing from it.
+fn summary_lines<'a>(report: &CycleReport, look: &Appearance) -> Vec<Line<'a>> {
+    let mut lines = vec![
+        distribution_line("Cycle time", report.cycle, look),
+        distribution_line("Lead time", report.lead, look),
+    ];
+    let changelogs = |count: usize| match count {
+        1 => "1 changelog".to_string(),
+        count => format!("{count} changelogs"),
+    };
+    if report.loading > 0 {
+        lines.push(Line::styled(
+            format!("Loading {}…", changelogs(report.loading)),
+            Style::default().fg(look.theme.gray),
+        ));
+    }
+    if report.missing > 0 {
+        lines.push(Line::styled(
+            format!("Left out {} not loaded; vm loads them", changelogs(report.missing)),
+            Style::default().fg(look.theme.yellow),
+        ));
+    }
+    lines
+}
+
+/// Renders the spread of the cycle and lead times of the listed issues that are done, and a
+/// row per issue, slowest first.
+pub fn render_cycle_time(f: &mut Frame, app: &mut App, area: Rect) {
+    let look = &app.appearance;
+    let report = report(app);
+    app.cycle_cursor = app.cycle_cursor.min(report.issues.len().saturating_sub(1));
+    if report.issues.is_empty() && report.loading == 0 && report.missing == 0 {
+        f.render_widget(Paragraph::new("None of the listed issues is done"), area);
+        return;
+    }
+
+    let summary = summary_lines(&report, look);
+    let [summary_area, table_area] =
+        Layout::vertical([Constraint::Length(summary.len() as u16 + 1), Constraint::Min(0)])
+            .areas(area);
+    f.render_widget(Paragraph::new(summary), summary_area);
+
+    let dash = || "–".to_string();
+    let rows = report.issues.iter().map(|times| {
+        Row::new(vec![
+            Cell::from(Span::styled(times.key.clone(), Style::default().fg(look.theme.cyan))),
+            Cell::from(text::truncate(&times.summary, 60).into_owned()),
+            Cell::from(times.cycle.map_or_else(dash, time::elapsed)),
+            Cell::from(times.lead.map_or_else(dash, time::elapsed)),
+        ])
+    });
+    let header = Row::new(["Key", "Summary", "Cycle", "Lead"]).style(
+        Style::default()
+            .fg(look.theme.gray)
+            .add_modifier(Modifier::BOLD),
+    );
+    let highlight_style = if app.input_mode != InputMode::Normal {
+        look.theme.list_highlight_inactive
+    } else {
+        look.theme.list_highlight
+    };
+
+    let mut table_state = TableState::default();
+    table_state.select((!report.issues.is_empty()).then_some(app.cycle_cursor));
+
+    let table = Table::new(rows, [
+        Constraint::Length(10),
+        Constraint::Min(20),
+        Constraint::Length(8),
+        Constraint::Length(8),
+    ])
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(highlight_style)
+    .highlight_symbol(look.selection_marker())
+    .highlight_spacing(HighlightSpacing::Always);
+
+    f.render_stateful_widget(table, table_area, &mut table_state);
+}
@@ -0,0 +1,110 @@
+//! Line-based diffs of field values, for the history tab.
+
+/// One line of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Above this many line pairs the diff isn't worth computing; everything is shown as replaced.
+const MAX_COMPARISONS: usize = 1_000_000;
+
+/// Diffs `old` against `new` line by line, keeping the longest run of common lines.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    if old.len() * new.len() > MAX_COMPARISONS {
+        return old
+            .into_iter()
+            .map(DiffLine::Removed)
+            .chain(new.into_iter().map(DiffLine::Added))
+            .collect();
+    }
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().copied().map(DiffLine::Removed));
+    diff.extend(new[j..].iter().copied().map(DiffLine::Added));
+    diff
+}
+
+/// Drops unchanged lines more than `context` lines away from a change. Each run of dropped
+/// lines is replaced by a single `None`.
+pub fn with_context<'a>(diff: &[DiffLine<'a>], context: usize) -> Vec<Option<DiffLine<'a>>> {
+    let near_change = |i: usize| {
+        let start = i.saturating_sub(context);
+        let end = (i + context + 1).min(diff.len());
+        diff[start..end]
+            .iter()
+            .any(|line| !matches!(line, DiffLine::Same(_)))
+    };
+
+    let mut shown = Vec::new();
+    for (i, &line) in diff.iter().enumerate() {
+        if matches!(line, DiffLine::Same(_)) && !near_change(i) {
+            if shown.last() != Some(&None) {
+                shown.push(None);
+            }
+        } else {
+            shown.push(Some(line));
+        }
+    }
+    shown
+}
+
+#[cfg(test)]
+mod tests {
+    use DiffLine::*;
+
+    use super::*;
+
+    #[test]
+    fn keeps_common_lines() {
+        assert_eq!(diff_lines("a\nb\nc", "a\nB\nc\nd"), [
+            Same("a"),
+            Removed("b"),
+            Added("B"),
+            Same("c"),
+            Added("d"),
+        ]);
+        assert_eq!(diff_lines("", "new"), [Added("new")]);
+    }
+
+    #[test]
+    fn collapses_distant_context() {
+        let diff = diff_lines("1\n2\n3\n4\n5\n6", "1\n2\n3\n4\n5\nsix");
+        assert_eq!(with_context(&diff, 1), [
+            None,
+            Some(Same("5")),
+            Some(Removed("6")),
+            Some(Added("six")),
+        ]);
+    }
+}
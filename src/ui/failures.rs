@@ -0,0 +1,63 @@
+//! The failures view: the requests that failed, oldest first.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table, TableState},
+};
+
+use crate::{app::App, ui::input::InputMode};
+
+/// Renders a row per failed request: when, what, and why.
+pub fn render_failures(f: &mut Frame, app: &App, area: Rect) {
+    let look = &app.appearance;
+    if app.failures.is_empty() {
+        f.render_widget(Paragraph::new("Nothing failed"), area);
+        return;
+    }
+
+    let times: Vec<String> = app
+        .failures
+        .iter()
+        .map(|failure| look.time.datetime(&failure.when))
+        .collect();
+    let time_width = times
+        .iter()
+        .map(|time| time.chars().count())
+        .max()
+        .unwrap_or(0);
+    let rows = app.failures.iter().zip(times).map(|(failure, time)| {
+        Row::new(vec![
+            Cell::from(time),
+            Cell::from(failure.what.clone()),
+            Cell::from(failure.error.clone()).style(Style::default().fg(look.theme.red)),
+        ])
+    });
+    let header = Row::new(["When", "What", "Error"]).style(
+        Style::default()
+            .fg(look.theme.gray)
+            .add_modifier(Modifier::BOLD),
+    );
+    let highlight_style = if app.input_mode != InputMode::Normal {
+        look.theme.list_highlight_inactive
+    } else {
+        look.theme.list_highlight
+    };
+
+    let mut table_state = TableState::default();
+    table_state.select(Some(app.failure_cursor.min(app.failures.len() - 1)));
+
+    let table = Table::new(rows, [
+        Constraint::Length(time_width.max(4) as u16),
+        Constraint::Min(20),
+        Constraint::Min(20),
+    ])
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(highlight_style)
+    .highlight_symbol(look.selection_marker())
+    .highlight_spacing(HighlightSpacing::Always);
+
+    f.render_stateful_widget(table, area, &mut table_state);
+}
@@ -0,0 +1,57 @@
+//! The row above the issue list with the quick filters and the active filter chips.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::app::{
+    App,
+    filter::{self, QuickFilter},
+};
+
+/// Renders the quick filters with their counts, toggled with `t{n}`, followed by one chip per
+/// active filter, numbered for removal with `{n}x`.
+pub fn render_filter_bar(f: &mut Frame, app: &App, area: Rect) {
+    let look = &app.appearance;
+    let chip = Style::default()
+        .fg(look.theme.black)
+        .bg(look.theme.cyan)
+        .add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(look.theme.gray);
+
+    let mut spans = Vec::new();
+    for (i, quick) in QuickFilter::available(app).into_iter().enumerate() {
+        let text = format!("{} ({})", quick.name(app), filter::quick_filter_count(app, quick));
+        let active = app.quick_filters.contains(&quick);
+        spans.push(Span::styled(format!("{} ", i + 1), dim));
+        spans.push(match (active, look.no_color) {
+            (true, true) => Span::raw(format!("[{text}]")),
+            (true, false) => Span::styled(format!(" {text} "), chip),
+            (false, _) => Span::raw(format!(" {text} ")),
+        });
+        spans.push(Span::raw(" "));
+    }
+
+    if !app.filters.is_empty() {
+        spans.push(Span::styled("│ ", dim));
+    }
+    for (i, filter) in app.filters.iter().enumerate() {
+        let text = format!("{}: {}", filter.name(), filter.value);
+        spans.push(Span::styled(format!("{} ", i + 1), dim));
+        spans.push(if look.no_color {
+            Span::raw(format!("[{text}]"))
+        } else {
+            Span::styled(format!(" {text} "), chip)
+        });
+        spans.push(Span::raw(" "));
+    }
+
+    if app.visible.len() < app.issues.len() {
+        spans.push(Span::styled(format!(" {}/{} shown", app.visible.len(), app.issues.len()), dim));
+    }
+
+    f.render_widget(Line::from(spans), area);
+}
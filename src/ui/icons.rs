@@ -0,0 +1,88 @@
+//! Compact symbols for issue types and priorities, shown in narrow table columns.
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::ui::{issue::Priority, theme::Theme};
+
+/// Which symbols to draw. Nerd Font glyphs need a patched font, so ASCII is the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconSet {
+    #[default]
+    Ascii,
+    NerdFont,
+}
+
+impl IconSet {
+    pub fn issue_type(self, name: &str) -> &'static str {
+        let name = name.to_lowercase();
+        match (self, name.as_str()) {
+            (IconSet::Ascii, "bug") => "B",
+            (IconSet::Ascii, "story") => "S",
+            (IconSet::Ascii, "task") => "T",
+            (IconSet::Ascii, "epic") => "E",
+            (IconSet::Ascii, "sub-task" | "subtask") => "t",
+            (IconSet::Ascii, _) => "?",
+            (IconSet::NerdFont, "bug") => "\u{f188}",
+            (IconSet::NerdFont, "story") => "\u{f02e}",
+            (IconSet::NerdFont, "task") => "\u{f00c}",
+            (IconSet::NerdFont, "epic") => "\u{f0e7}",
+            (IconSet::NerdFont, "sub-task" | "subtask") => "\u{f0da}",
+            (IconSet::NerdFont, _) => "\u{f128}",
+        }
+    }
+
+    pub fn priority(self, priority: &Priority) -> &'static str {
+        match (self, priority) {
+            (IconSet::Ascii, Priority::High) => "^",
+            (IconSet::Ascii, Priority::Medium) => "=",
+            (IconSet::Ascii, Priority::Low) => "v",
+            (IconSet::Ascii, Priority::Other(_)) => "?",
+            (IconSet::NerdFont, Priority::High) => "\u{f062}",
+            (IconSet::NerdFont, Priority::Medium) => "\u{f068}",
+            (IconSet::NerdFont, Priority::Low) => "\u{f063}",
+            (IconSet::NerdFont, Priority::Other(_)) => "\u{f128}",
+        }
+    }
+
+    /// Marks flagged issues.
+    pub const fn flag(self) -> &'static str {
+        match self {
+            IconSet::Ascii => "!",
+            IconSet::NerdFont => "\u{f024}",
+        }
+    }
+
+    /// The bar marking the rows of issues in an epic, in its color.
+    pub const fn epic_marker(self) -> &'static str {
+        match self {
+            IconSet::Ascii => "|",
+            IconSet::NerdFont => "\u{258c}",
+        }
+    }
+}
+
+/// Color of an issue type symbol.
+pub fn issue_type_color(name: &str, theme: &Theme) -> Color {
+    match name.to_lowercase().as_str() {
+        "bug" => theme.red,
+        "story" => theme.green,
+        "task" | "sub-task" | "subtask" => theme.blue,
+        "epic" => theme.magenta,
+        _ => theme.gray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_fallback() {
+        assert_eq!(IconSet::Ascii.issue_type("Bug"), "B");
+        assert_eq!(IconSet::Ascii.issue_type("Incident"), "?");
+        assert_eq!(IconSet::Ascii.priority(&Priority::from_jira_str("Highest")), "^");
+        assert_eq!(IconSet::NerdFont.priority(&Priority::Low), "\u{f063}");
+    }
+}
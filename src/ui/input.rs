@@ -1,22 +1,35 @@
 //! Input handling logic for the Jira TUI.
 //!
 //! This module provides functions to handle key events in both normal and editing modes.
-//! It is designed to be testable and independent of the UI framework.
+//! It is designed to be testable and independent of the UI framework. Its tables of keys are
+//! what the footer and the help list, so they can't tell of keys that don't exist. What's open
+//! over the list reads keys of its own: the popup, the JSON inspector, the text selection,
+//! the context menu, the picker, and the jump labels.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, StatefulWidget, Widget},
+};
 
-// --- ratatui widget imports for custom input widget ---
-use ratatui::buffer::Buffer;
-use ratatui::layout::Rect;
-use ratatui::style::Style;
-use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, StatefulWidget, Widget};
+use crate::app::View;
 
 /// Represents the current input mode of the application.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
     Insert,
+    /// Editing the search query.
+    Jql,
+    /// Typing a new API token after Jira rejected the credentials.
+    SignIn,
+    /// Typing what to find in the list, after `/`.
+    Find,
+    /// Typing the text or regular expression to narrow the list down to.
+    Filter,
 }
 
 // --- TextInput stateful widget and state ---
@@ -83,40 +96,379 @@ impl<'a> StatefulWidget for TextInputWidget<'a> {
     }
 }
 
-/// Handles key events in normal mode.
-/// Returns an enum describing the action to take.
-/// Handles key events in normal mode, supporting numeric prefixes for j/k.
-/// Returns an enum describing the action to take.
-pub fn handle_normal_mode_key(
-    key: &KeyEvent,
-    pending_count: &mut Option<usize>,
-) -> NormalModeAction {
+/// Keys typed in normal mode that only take effect together with the next key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PendingKeys {
+    /// Count prefix typed so far (e.g. the `5` in `5j`).
+    pub count: Option<usize>,
+    /// First key of a two-key command (e.g. the `z` in `zl`, the `t` in `t1`, the `v` in `vt`,
+    /// the `y` in `yd`, the `m` and `'` in front of a mark, or the `!` in front of the key of
+    /// an action).
+    pub prefix: Option<char>,
+}
+
+/// Every view, for keys that work the same in all of them.
+const EVERY_VIEW: &[View] = &[
+    View::List,
+    View::Timeline,
+    View::Workload,
+    View::Releases,
+    View::Components,
+    View::CycleTime,
+    View::Failures,
+];
+
+/// Just the list, which most keys are for.
+const LIST: &[View] = &[View::List];
+
+/// The views other than the list, which `vl` goes back to it from.
+const OTHER_VIEWS: &[View] = &[
+    View::Timeline,
+    View::Workload,
+    View::Releases,
+    View::Components,
+    View::CycleTime,
+    View::Failures,
+];
+
+/// The views listing rows to pick one of with `j`, `k`, and Enter.
+const PICKING_VIEWS: &[View] =
+    &[View::Workload, View::Releases, View::Components, View::CycleTime, View::Failures];
+
+/// What the keys of a binding do in some views, as listed in the footer and the help.
+#[derive(Debug, Clone, Copy)]
+struct Hint {
+    views: &'static [View],
+    label: &'static str,
+    /// Listed in the footer as well as in the help.
+    footer: bool,
+}
+
+/// A hint listed only in the help.
+const fn help(views: &'static [View], label: &'static str) -> Hint {
+    Hint { views, label, footer: false }
+}
+
+/// A hint listed in the footer as well as in the help.
+const fn footer(views: &'static [View], label: &'static str) -> Hint {
+    Hint { views, label, footer: true }
+}
+
+/// Which keys a [`Binding`] takes.
+#[derive(Debug, Clone, Copy)]
+enum Keys {
+    /// Any of these, with exactly these modifiers.
+    Codes(KeyModifiers, &'static [KeyCode]),
+    /// Any character this holds for, without modifiers.
+    Chars(fn(char) -> bool),
+}
+
+impl Keys {
+    fn matches(&self, modifiers: KeyModifiers, code: KeyCode) -> bool {
+        match (*self, code) {
+            (Keys::Codes(with, codes), code) => with == modifiers && codes.contains(&code),
+            (Keys::Chars(holds), KeyCode::Char(c)) => modifiers == KeyModifiers::NONE && holds(c),
+            (Keys::Chars(_), _) => false,
+        }
+    }
+}
+
+/// How a [`Binding`] was typed.
+#[derive(Debug, Clone, Copy)]
+struct Typed {
+    /// The count typed before the keys, if any.
+    count: Option<usize>,
+    /// The character typed, for bindings of [`Keys::Chars`].
+    c: char,
+}
+
+impl Typed {
+    /// How many times to do what the keys do.
+    fn times(self) -> usize {
+        self.count.unwrap_or(1)
+    }
+
+    /// [`Typed::times`] as steps to move by.
+    fn steps(self) -> isize {
+        self.times() as isize
+    }
+}
+
+/// A key of normal mode, or a key after a prefix like the `z` of `zt`, with what it does and
+/// how the footer and the help list it.
+#[derive(Debug, Clone, Copy)]
+struct Binding {
+    prefix: Option<char>,
+    keys: Keys,
+    action: fn(Typed) -> NormalModeAction,
+    /// The keys as listed, standing for the unlisted bindings after it too, like the `k` of
+    /// `j/k`.
+    listed: &'static str,
+    hints: &'static [Hint],
+}
+
+/// Binds keys without modifiers.
+const fn key(
+    keys: &'static [KeyCode],
+    action: fn(Typed) -> NormalModeAction,
+    listed: &'static str,
+    hints: &'static [Hint],
+) -> Binding {
+    Binding {
+        prefix: None,
+        keys: Keys::Codes(KeyModifiers::NONE, keys),
+        action,
+        listed,
+        hints,
+    }
+}
+
+/// Binds keys held with Ctrl.
+const fn ctrl(
+    keys: &'static [KeyCode],
+    action: fn(Typed) -> NormalModeAction,
+    listed: &'static str,
+    hints: &'static [Hint],
+) -> Binding {
+    Binding {
+        keys: Keys::Codes(KeyModifiers::CONTROL, keys),
+        ..key(keys, action, listed, hints)
+    }
+}
+
+/// Binds keys typed after the `prefix` key.
+const fn then(
+    prefix: char,
+    keys: &'static [KeyCode],
+    action: fn(Typed) -> NormalModeAction,
+    listed: &'static str,
+    hints: &'static [Hint],
+) -> Binding {
+    Binding {
+        prefix: Some(prefix),
+        ..key(keys, action, listed, hints)
+    }
+}
+
+/// Binds the characters `holds` is true for, typed after the `prefix` key.
+const fn then_any(
+    prefix: char,
+    holds: fn(char) -> bool,
+    action: fn(Typed) -> NormalModeAction,
+    listed: &'static str,
+    hints: &'static [Hint],
+) -> Binding {
+    Binding {
+        prefix: Some(prefix),
+        keys: Keys::Chars(holds),
+        action,
+        listed,
+        hints,
+    }
+}
+
+/// The keys of normal mode. Their order is the order of the footer and the help.
+const NORMAL_KEYMAP: &[Binding] = {
     use KeyCode::*;
-    use KeyModifiers as M;
+    use NormalModeAction as Do;
+    &[
+        key(&[Char('j'), Down], |k| Do::Jump(k.steps()), "j/k", &[
+            help(LIST, "move"),
+            footer(PICKING_VIEWS, "select"),
+        ]),
+        key(&[Char('k'), Up], |k| Do::Jump(-k.steps()), "", &[]),
+        key(&[Char('l'), Right], |k| Do::ScrollTimeline(k.steps()), "h/l", &[footer(
+            &[View::Timeline],
+            "scroll",
+        )]),
+        key(&[Char('h'), Left], |k| Do::ScrollTimeline(-k.steps()), "", &[]),
+        key(&[Char('+'), Char('=')], |k| Do::Increase(k.steps()), "+/-", &[
+            help(LIST, "priority"),
+            footer(&[View::Timeline], "zoom"),
+        ]),
+        key(&[Char('-')], |k| Do::Increase(-k.steps()), "", &[]),
+        key(&[Enter], |_| Do::OpenItem, "Enter", &[
+            help(LIST, "open item"),
+            footer(&[View::Workload, View::Releases], "show issues"),
+            footer(&[View::Components], "show open issues"),
+            footer(&[View::CycleTime], "show issue"),
+            footer(&[View::Failures], "retry"),
+        ]),
+        key(&[Char('o')], |_| Do::OpenItemExternally, "o", &[help(LIST, "open item outside")]),
+        key(&[Char('}')], |k| Do::MoveItemCursor(k.steps()), "{/}", &[help(LIST, "item cursor")]),
+        key(&[Char('{')], |k| Do::MoveItemCursor(-k.steps()), "", &[]),
+        // Without a count this means the last filter, not the first
+        key(&[Char('x')], |k| Do::RemoveFilter(k.count), "x", &[
+            help(LIST, "remove filter"),
+            footer(&[View::Failures], "dismiss"),
+        ]),
+        key(&[Char('i')], |_| Do::EnterInput, "i", &[footer(LIST, "new issue")]),
+        key(&[Char('I')], |_| Do::CreateInEpic, "I", &[help(LIST, "new in epic")]),
+        key(&[Char('s')], |_| Do::ToggleSidebar, "s", &[footer(
+            &[View::List, View::Timeline],
+            "sidebar",
+        )]),
+        key(&[Char('f')], |_| Do::NarrowList, "f/F", &[footer(
+            &[View::List, View::Timeline, View::Workload, View::CycleTime],
+            "narrow/filter",
+        )]),
+        key(&[Char('F')], |_| Do::BuildFilter, "", &[]),
+        key(&[Char('<')], |k| Do::ResizeSidebar(5 * k.times() as i16), "</>", &[help(
+            LIST,
+            "sidebar width",
+        )]),
+        key(&[Char('>')], |k| Do::ResizeSidebar(-5 * k.times() as i16), "", &[]),
+        key(&[Char(']')], |k| Do::SwitchSidebarTab(k.steps()), "[/]", &[help(LIST, "sidebar tab")]),
+        key(&[Char('[')], |k| Do::SwitchSidebarTab(-k.steps()), "", &[]),
+        key(&[Char('O')], |_| Do::OpenInBrowser, "O", &[help(LIST, "browser")]),
+        key(&[Char('E')], |_| Do::BulkEdit, "E", &[help(LIST, "bulk edit")]),
+        key(&[Char('P')], |_| Do::SwitchProject, "P", &[help(LIST, "project")]),
+        key(&[Char(':')], |_| Do::EditJql, ":", &[footer(LIST, "edit jql")]),
+        key(&[Char('/')], |_| Do::Find, "/ n/N", &[footer(LIST, "find")]),
+        key(&[Char('n')], |k| Do::FindNext(k.times()), "", &[]),
+        key(&[Char('N')], |k| Do::FindPrevious(k.times()), "", &[]),
+        key(&[Esc], |_| Do::ClearFind, "Esc", &[help(LIST, "clear find")]),
+        key(&[Char('J')], |_| Do::InspectJson, "J", &[help(LIST, "json")]),
+        // Without a count this means the first alert that found issues
+        key(&[Char('A')], |k| Do::ShowAlert(k.count), "A", &[help(LIST, "alerts")]),
+        key(&[Char('p')], |_| Do::PasteImage, "p", &[help(LIST, "paste image")]),
+        then('t', &[Char('t')], |_| Do::ChangeStatus, "tt", &[footer(LIST, "status")]),
+        key(&[Char('a')], |_| Do::Assign, "a", &[help(LIST, "assign")]),
+        key(&[Char('w')], |_| Do::AddWatcher, "w/W", &[help(LIST, "watch/unwatch")]),
+        key(&[Char('W')], |_| Do::RemoveWatcher, "", &[]),
+        key(&[Char('S')], |_| Do::SprintReport, "S", &[help(LIST, "sprint report")]),
+        key(&[Char('R')], |_| Do::ChangeParent, "R", &[help(LIST, "parent")]),
+        key(&[Char('b')], |_| Do::ToggleFlag, "b", &[help(LIST, "flag")]),
+        key(&[Char('d')], |_| Do::Jump(20), "d/u", &[help(LIST, "20 down/up")]),
+        key(&[Char('u')], |_| Do::Jump(-20), "", &[]),
+        key(&[Char('g')], |_| Do::GotoTop, "g/G", &[help(LIST, "top/bottom")]),
+        key(&[Char('G')], |_| Do::GotoBottom, "", &[]),
+        ctrl(&[Char('f')], |k| Do::JumpPage(k.steps()), "^F/^B", &[help(LIST, "page")]),
+        ctrl(&[Char('b')], |k| Do::JumpPage(-k.steps()), "", &[]),
+        ctrl(&[Char('e')], |k| Do::Scroll(k.steps()), "^E/^Y", &[help(LIST, "scroll")]),
+        ctrl(&[Char('y')], |k| Do::Scroll(-k.steps()), "", &[]),
+        ctrl(&[Char('o')], |_| Do::JumpBack, "^O/Tab", &[help(LIST, "jump back/forward")]),
+        key(&[Tab], |_| Do::JumpForward, "", &[]),
+        // Terminals send Ctrl-I as Tab
+        ctrl(&[Char('i')], |_| Do::JumpForward, "", &[]),
+        ctrl(&[Char('r')], |_| Do::ReloadConfig, "^R", &[help(LIST, "reload config")]),
+        then('y', &[Char('y')], |_| Do::Copy(CopyTarget::Issue), "yy/yk/yd/yi/ys/ym", &[help(
+            LIST, "copy",
+        )]),
+        then('y', &[Char('k')], |_| Do::Copy(CopyTarget::Key), "", &[]),
+        then('y', &[Char('d')], |_| Do::Copy(CopyTarget::Description), "", &[]),
+        then('y', &[Char('i')], |_| Do::Copy(CopyTarget::Item), "", &[]),
+        then('y', &[Char('s')], |_| Do::Copy(CopyTarget::Sidebar), "", &[]),
+        then('y', &[Char('m')], |_| Do::Copy(CopyTarget::Markdown), "", &[]),
+        then('t', &[Char('q')], |_| Do::ToggleJqlBar, "tq", &[help(LIST, "jql bar")]),
+        then_any(
+            't',
+            |c| c.is_ascii_digit(),
+            |k| Do::ToggleQuickFilter(k.c.to_digit(10).unwrap_or(0) as usize),
+            "t1..",
+            &[help(LIST, "quick filter")],
+        ),
+        then('t', &[Char('d')], |_| Do::ToggleHideDone, "td", &[help(LIST, "hide done")]),
+        then('t', &[Char('r')], |_| Do::ToggleResolved, "tr", &[help(LIST, "resolved")]),
+        then('t', &[Char('l')], |_| Do::ToggleLabels, "tl", &[help(LIST, "labels")]),
+        then('t', &[Char('L')], |_| Do::ToggleLog, "tL", &[help(LIST, "log")]),
+        then('z', &[Char('l'), Right], |k| Do::ScrollSummary(k.steps()), "zl/zh", &[help(
+            LIST,
+            "scroll title",
+        )]),
+        then('z', &[Char('h'), Left], |k| Do::ScrollSummary(-k.steps()), "", &[]),
+        then('z', &[Char('z')], |_| Do::PlaceSelection(RowPlace::Middle), "zz/zt/zb", &[help(
+            LIST,
+            "center/top/bottom",
+        )]),
+        then('z', &[Char('t')], |_| Do::PlaceSelection(RowPlace::Top), "", &[]),
+        then('z', &[Char('b')], |_| Do::PlaceSelection(RowPlace::Bottom), "", &[]),
+        key(&[Char(';')], |_| Do::ShowJumpLabels, ";", &[help(LIST, "jump label")]),
+        then_any('m', |c| c.is_ascii_lowercase(), |k| Do::SetMark(k.c), "m/'", &[help(
+            LIST,
+            "mark/go to mark",
+        )]),
+        then_any('\'', |c| c.is_ascii_lowercase(), |k| Do::JumpToMark(k.c), "", &[]),
+        then_any('`', |c| c.is_ascii_lowercase(), |k| Do::JumpToMark(k.c), "", &[]),
+        then_any('!', |_| true, |k| Do::RunAction(k.c), "!<key>", &[help(LIST, "run action")]),
+        then('v', &[Char('r')], |_| Do::SwitchView(View::Releases), "vr", &[footer(
+            &[View::Releases],
+            "refresh",
+        )]),
+        then('v', &[Char('c')], |_| Do::SwitchView(View::Components), "vc", &[footer(
+            &[View::Components],
+            "refresh",
+        )]),
+        then('v', &[Char('m')], |_| Do::SwitchView(View::CycleTime), "vm", &[footer(
+            &[View::CycleTime],
+            "refresh",
+        )]),
+        then('v', &[Char('l')], |_| Do::SwitchView(View::List), "vl", &[footer(
+            OTHER_VIEWS,
+            "list",
+        )]),
+        then('v', &[Char('t')], |_| Do::SwitchView(View::Timeline), "vt/vw/vr/vc/vf/vm", &[help(
+            LIST, "views",
+        )]),
+        then('v', &[Char('w')], |_| Do::SwitchView(View::Workload), "", &[]),
+        then('v', &[Char('f')], |_| Do::SwitchView(View::Failures), "", &[]),
+        key(&[Char('?')], |_| Do::Help, "?", &[footer(EVERY_VIEW, "help")]),
+        key(&[Char('q')], |_| Do::Quit, "q", &[footer(EVERY_VIEW, "quit")]),
+        ctrl(&[Char('c')], |_| Do::Quit, "", &[]),
+    ]
+};
+
+/// Handles key events in normal mode, supporting numeric prefixes and two-key commands.
+/// Returns an enum describing the action to take.
+pub fn handle_normal_mode_key(key: &KeyEvent, pending: &mut PendingKeys) -> NormalModeAction {
+    let prefix = pending.prefix.take();
 
     // Accumulate digits and return early
-    if let Char(c) = key.code {
-        if c.is_ascii_digit() && !(c == '0' && pending_count.is_none()) {
+    if let (None, KeyCode::Char(c)) = (prefix, key.code) {
+        if c.is_ascii_digit() && !(c == '0' && pending.count.is_none()) {
             let digit = c.to_digit(10).unwrap() as usize;
-            *pending_count = Some(pending_count.unwrap_or(0) * 10 + digit);
+            pending.count = Some(pending.count.unwrap_or(0) * 10 + digit);
             return NormalModeAction::None;
         }
     }
 
-    match (pending_count.take().unwrap_or(1), key.modifiers, key.code) {
-        (count, M::NONE, Char('j') | Down) => NormalModeAction::Jump(count as isize),
-        (count, M::NONE, Char('k') | Up) => NormalModeAction::Jump(-(count as isize)),
-        (_, M::NONE, Char('d')) => NormalModeAction::Jump(20),
-        (_, M::NONE, Char('u')) => NormalModeAction::Jump(-20),
-        (_, M::NONE, Char('i')) => NormalModeAction::EnterInput,
-        (_, M::NONE, Char('g')) => NormalModeAction::GotoTop,
-        (_, M::NONE, Char('G')) => NormalModeAction::GotoBottom,
-        (_, M::NONE, Char('s')) => NormalModeAction::ToggleSidebar,
-        (_, M::NONE, Char('q')) => NormalModeAction::Quit,
-        (count, M::CONTROL, Char('e')) => NormalModeAction::Scroll(count as isize),
-        (count, M::CONTROL, Char('y')) => NormalModeAction::Scroll(-(count as isize)),
-        _ => NormalModeAction::None,
+    // Terminals report Shift along with uppercase letters and symbols; the character itself
+    // already tells them apart.
+    let (modifiers, c) = match key.code {
+        KeyCode::Char(c) => (key.modifiers - KeyModifiers::SHIFT, c),
+        _ => (key.modifiers, '\0'),
+    };
+
+    if let (None, KeyModifiers::NONE, KeyCode::Char(c)) = (prefix, modifiers, key.code) {
+        if NORMAL_KEYMAP
+            .iter()
+            .any(|binding| binding.prefix == Some(c))
+        {
+            pending.prefix = Some(c);
+            return NormalModeAction::None;
+        }
     }
+
+    let count = pending.count.take();
+    NORMAL_KEYMAP
+        .iter()
+        .find(|binding| binding.prefix == prefix && binding.keys.matches(modifiers, key.code))
+        .map_or(NormalModeAction::None, |binding| (binding.action)(Typed { count, c }))
+}
+
+/// The keys of normal mode in `view` with what they do, as listed in the help, or only those
+/// listed in the footer too.
+pub fn normal_mode_hints(view: View, all: bool) -> Vec<(&'static str, &'static str)> {
+    NORMAL_KEYMAP
+        .iter()
+        .flat_map(|binding| {
+            binding
+                .hints
+                .iter()
+                .filter(|hint| hint.views.contains(&view) && (all || hint.footer))
+                .map(|hint| (binding.listed, hint.label))
+        })
+        .collect()
 }
 
 /// Actions that can be taken in normal mode.
@@ -126,46 +478,273 @@ pub enum NormalModeAction {
     Jump(isize),
     Scroll(isize),
     EnterInput,
+    /// Start writing a new issue in the epic of the selected issue.
+    CreateInEpic,
     GotoTop,
+    /// Move this many result pages down, or up when negative.
+    JumpPage(isize),
+    /// Read the config file again and apply it.
+    ReloadConfig,
     GotoBottom,
     ToggleSidebar,
+    /// Grow (positive) or shrink (negative) the sidebar by this many percent of the screen.
+    ResizeSidebar(i16),
+    /// Move this many sidebar tabs to the right (positive) or left (negative).
+    SwitchSidebarTab(isize),
+    /// Scroll the summary of the selected issue right (positive) or left (negative) by this
+    /// many steps.
+    ScrollSummary(isize),
+    /// Scroll the list so the selected row is at this place in the window.
+    PlaceSelection(RowPlace),
+    /// Add a condition to the search query with the guided builder.
+    BuildFilter,
+    /// Narrow the list down to a label, component, or sprint.
+    NarrowList,
+    /// Remove the filter with this number (counting from 1), or the last one.
+    RemoveFilter(Option<usize>),
+    /// Switch the quick filter with this number (counting from 1) on or off.
+    ToggleQuickFilter(usize),
+    /// Hide or show the issues that are done.
+    ToggleHideDone,
+    /// Search again with or without the resolved issues.
+    ToggleResolved,
+    /// Hide or show the query above the list.
+    ToggleJqlBar,
+    /// Hide or show the column of labels.
+    ToggleLabels,
+    /// Hide or show the log of requests and errors below the list.
+    ToggleLog,
+    /// Edit the query and search again.
+    EditJql,
+    /// Type what to find in the list, selecting the next issue matching it.
+    Find,
+    /// Select the next issue matching the last `/` search this many times.
+    FindNext(usize),
+    /// Select the previous issue matching the last `/` search this many times.
+    FindPrevious(usize),
+    /// Stop marking the matches of the last `/` search.
+    ClearFind,
+    /// Go back to where the last jump started.
+    JumpBack,
+    /// Redo a jump undone with [`NormalModeAction::JumpBack`].
+    JumpForward,
+    /// Mark the selected issue with the letter.
+    SetMark(char),
+    /// Go to the issue marked with the letter.
+    JumpToMark(char),
+    /// Label the rows on screen, to select one by typing its label.
+    ShowJumpLabels,
+    /// Move this many entries down (positive) or up (negative) the issues referenced by and
+    /// the files attached to the selected issue.
+    MoveItemCursor(isize),
+    /// Open the entry under the cursor: a referenced issue in the app, fetching it if it
+    /// isn't listed, or an attachment with the system's default application.
+    OpenItem,
+    /// Open the entry under the cursor outside the app: a referenced issue in the browser, or
+    /// an attachment with the system's default application.
+    OpenItemExternally,
+    /// Open the selected issue in the browser.
+    OpenInBrowser,
+    /// Edit the status, assignee, and story points of the listed issues in the editor.
+    BulkEdit,
+    /// Scope the search to a project picked from a list.
+    SwitchProject,
+    /// Move the selected issue to a status picked from the transitions Jira allows.
+    ChangeStatus,
+    /// Assign the selected issue to a user picked from those found by name.
+    Assign,
+    /// Show the report of the last sprint the selected issue was in.
+    SprintReport,
+    /// Add a user picked from those found by name to the watchers of the selected issue.
+    AddWatcher,
+    /// Take a watcher picked from the list off the selected issue.
+    RemoveWatcher,
+    /// Move the selected issue under an epic or issue picked from the list.
+    ChangeParent,
+    /// Flag the selected issue as blocked, or take the flag off.
+    ToggleFlag,
+    /// Show the JSON of the selected issue.
+    InspectJson,
+    /// Show all the keys of the view.
+    Help,
+    /// Run the action from the config bound to this key on the selected issue.
+    RunAction(char),
+    /// List the issues of the alert with this number (counting from 1), or of the first one
+    /// that found any.
+    ShowAlert(Option<usize>),
+    /// Copy text from the details of the selected issue to the clipboard.
+    Copy(CopyTarget),
+    /// Attach the image on the clipboard, like a screenshot, to the selected issue.
+    PasteImage,
+    /// Show the listed issues another way.
+    SwitchView(View),
+    /// Scroll the timeline later (positive) or earlier (negative) by this many steps.
+    ScrollTimeline(isize),
+    /// Zoom the timeline in (positive) or out (negative) by this many levels; in the list,
+    /// raise or lower the priority of the selected issue by this many steps.
+    Increase(isize),
     None,
 }
 
-/// Handles key events in editing mode, mutating the input string as needed.
-/// Returns an enum describing the action to take.
-pub fn handle_editing_mode_key(key: &KeyEvent, input: &mut String) -> EditingModeAction {
-    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+/// Where [`NormalModeAction::PlaceSelection`] puts the selected row, like vim's `zt`, `zz`
+/// and `zb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowPlace {
+    Top,
+    Middle,
+    Bottom,
+}
 
-    match key.code {
-        KeyCode::Enter => EditingModeAction::Submit,
-        KeyCode::Esc => EditingModeAction::Cancel,
-        KeyCode::Char('w') if ctrl => {
-            delete_prev_word(input);
-            EditingModeAction::Edited
-        }
-        KeyCode::Char('u') if ctrl => {
+/// What [`NormalModeAction::Copy`] copies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// The key and summary.
+    Issue,
+    Key,
+    Description,
+    /// The key of the referenced issue or child, or the name of the attachment, under the
+    /// item cursor.
+    Item,
+    /// Lines of the open sidebar tab, picked in a popup.
+    Sidebar,
+    /// The whole issue as a markdown document, to paste into chat or docs.
+    Markdown,
+}
+
+/// A key of the modes typing into the input, with what it does and what the footer lists it
+/// as in each mode.
+struct EditingBinding {
+    /// Only taken with Ctrl held; the others are taken either way.
+    ctrl: bool,
+    code: KeyCode,
+    action: fn(&mut String) -> EditingModeAction,
+    listed: &'static str,
+    hints: &'static [(&'static [InputMode], &'static str)],
+}
+
+/// The modes typing into the input.
+const EDITING_MODES: &[InputMode] =
+    &[InputMode::Insert, InputMode::Jql, InputMode::SignIn, InputMode::Find, InputMode::Filter];
+
+/// The keys of the modes typing into the input, besides the characters typed. Their order is
+/// the order of the footer.
+const EDITING_KEYMAP: &[EditingBinding] = &[
+    EditingBinding {
+        ctrl: false,
+        code: KeyCode::Enter,
+        action: |_| EditingModeAction::Submit,
+        listed: "Enter",
+        hints: &[
+            (&[InputMode::Insert], "submit"),
+            (&[InputMode::Jql], "search"),
+            (&[InputMode::Find], "find"),
+            (&[InputMode::Filter], "filter"),
+            (&[InputMode::SignIn], "sign in"),
+        ],
+    },
+    EditingBinding {
+        ctrl: false,
+        code: KeyCode::Esc,
+        action: |_| EditingModeAction::Cancel,
+        listed: "Esc",
+        hints: &[(EDITING_MODES, "cancel")],
+    },
+    EditingBinding {
+        ctrl: false,
+        code: KeyCode::Tab,
+        action: |_| EditingModeAction::Complete,
+        listed: "Tab",
+        hints: &[(&[InputMode::Insert, InputMode::Jql], "complete key")],
+    },
+    EditingBinding {
+        ctrl: true,
+        code: KeyCode::Char('e'),
+        action: |_| EditingModeAction::PickEpic,
+        listed: "^E",
+        hints: &[(&[InputMode::Insert], "epic")],
+    },
+    EditingBinding {
+        ctrl: true,
+        code: KeyCode::Char('r'),
+        action: |_| EditingModeAction::ToggleRegex,
+        listed: "^R",
+        hints: &[(&[InputMode::Filter], "regex/text")],
+    },
+    EditingBinding {
+        ctrl: true,
+        code: KeyCode::Char('u'),
+        action: |input| {
             input.clear();
             EditingModeAction::Edited
-        }
-        KeyCode::Char(c) => {
-            input.push(c);
+        },
+        listed: "^U",
+        hints: &[(EDITING_MODES, "clear")],
+    },
+    EditingBinding {
+        ctrl: true,
+        code: KeyCode::Char('w'),
+        action: |input| {
+            delete_prev_word(input);
             EditingModeAction::Edited
-        }
-        KeyCode::Backspace => {
+        },
+        listed: "^W",
+        hints: &[],
+    },
+    EditingBinding {
+        ctrl: false,
+        code: KeyCode::Backspace,
+        action: |input| {
             input.pop();
             EditingModeAction::Edited
+        },
+        listed: "",
+        hints: &[],
+    },
+];
+
+/// Handles key events in editing mode, mutating the input string as needed.
+/// Returns an enum describing the action to take.
+pub fn handle_editing_mode_key(key: &KeyEvent, input: &mut String) -> EditingModeAction {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let binding = EDITING_KEYMAP
+        .iter()
+        .find(|binding| binding.code == key.code && (ctrl || !binding.ctrl));
+    match (binding, key.code) {
+        (Some(binding), _) => (binding.action)(input),
+        (None, KeyCode::Char(c)) => {
+            input.push(c);
+            EditingModeAction::Edited
         }
-        _ => EditingModeAction::None,
+        (None, _) => EditingModeAction::None,
     }
 }
 
+/// The keys of `mode`, one of the modes typing into the input, with what they do.
+pub fn editing_mode_hints(mode: InputMode) -> Vec<(&'static str, &'static str)> {
+    EDITING_KEYMAP
+        .iter()
+        .flat_map(|binding| {
+            binding
+                .hints
+                .iter()
+                .filter(|(modes, _)| modes.contains(&mode))
+                .map(|(_, label)| (binding.listed, *label))
+        })
+        .collect()
+}
+
 /// Actions that can be taken in editing mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EditingModeAction {
     Submit,
     Cancel,
     Edited,
+    /// Complete the issue or project key at the end of the input.
+    Complete,
+    /// Choose the epic the new issue goes in.
+    PickEpic,
+    /// Switch between matching text and a regular expression.
+    ToggleRegex,
     None,
 }
 
@@ -211,6 +790,40 @@ mod tests {
         assert_eq!(s, "");
     }
 
+    #[test]
+    fn keys_take_counts_and_prefixes() {
+        let mut pending = PendingKeys::default();
+        let mut type_keys = |keys: &str| {
+            let mut action = NormalModeAction::None;
+            for c in keys.chars() {
+                action = handle_normal_mode_key(&KeyEvent::from(KeyCode::Char(c)), &mut pending);
+            }
+            action
+        };
+        assert_eq!(type_keys("5j"), NormalModeAction::Jump(5));
+        assert_eq!(type_keys("2t3"), NormalModeAction::ToggleQuickFilter(3));
+        assert_eq!(type_keys("x"), NormalModeAction::RemoveFilter(None));
+        assert_eq!(type_keys("ma"), NormalModeAction::SetMark('a'));
+        assert_eq!(type_keys("mA"), NormalModeAction::None);
+        assert_eq!(type_keys("k"), NormalModeAction::Jump(-1));
+    }
+
+    #[test]
+    fn the_footer_lists_the_essential_keys() {
+        let keys: Vec<_> = normal_mode_hints(View::List, false)
+            .into_iter()
+            .map(|(keys, _)| keys)
+            .collect();
+        assert_eq!(keys, ["i", "s", "f/F", ":", "/ n/N", "tt", "?", "q"]);
+        let failures = normal_mode_hints(View::Failures, false);
+        assert_eq!(failures[..3], [("j/k", "select"), ("Enter", "retry"), ("x", "dismiss")]);
+        assert_eq!(editing_mode_hints(InputMode::Find), [
+            ("Enter", "find"),
+            ("Esc", "cancel"),
+            ("^U", "clear")
+        ]);
+    }
+
     #[test]
     fn test_handle_editing_mode_key_ctrl_u() {
         let mut s = String::from("something here");
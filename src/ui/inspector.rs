@@ -0,0 +1,364 @@
+//! A popup showing the JSON of an issue as Jira returned it, with folding and search, for
+//! finding the ids of custom fields and reporting fields the app reads wrong.
+
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use serde_json::Value;
+
+use crate::ui::{issue_list::window_offset, theme::Appearance};
+
+/// Objects and arrays nested deeper than this start out folded, leaving the fields of an
+/// issue one per line.
+const UNFOLDED_DEPTH: usize = 2;
+
+/// A line of the pretty-printed JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct JsonLine {
+    depth: usize,
+    /// The line without indentation or trailing comma.
+    text: String,
+    /// For the line opening an object or array, the line closing it.
+    end: Option<usize>,
+    /// How many entries the object or array opened on this line has.
+    entries: usize,
+    /// The line opening the object or array this line is in.
+    parent: Option<usize>,
+    comma: bool,
+}
+
+/// State of an open inspector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inspector {
+    pub title: String,
+    lines: Vec<JsonLine>,
+    /// Lines opening an object or array whose contents are hidden.
+    folded: HashSet<usize>,
+    /// The line under the cursor.
+    cursor: usize,
+    /// The first line on screen, as a row of the unfolded lines.
+    offset: usize,
+    /// What is being typed after `/`, while searching.
+    typing: Option<String>,
+    /// The last search, repeated by `n` and `N`.
+    query: String,
+}
+
+impl Inspector {
+    pub fn new(title: impl Into<String>, value: &Value) -> Self {
+        let mut lines = Vec::new();
+        push_lines(&mut lines, None, value, 0, None, false);
+        let folded = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.end.is_some() && line.depth >= UNFOLDED_DEPTH)
+            .map(|(i, _)| i)
+            .collect();
+        Self {
+            title: title.into(),
+            lines,
+            folded,
+            cursor: 0,
+            offset: 0,
+            typing: None,
+            query: String::new(),
+        }
+    }
+
+    /// The lines not hidden in a fold, in order.
+    fn shown(&self) -> Vec<usize> {
+        let mut shown = Vec::new();
+        let mut i = 0;
+        while i < self.lines.len() {
+            shown.push(i);
+            i = match self.lines[i].end {
+                Some(end) if self.folded.contains(&i) => end + 1,
+                _ => i + 1,
+            };
+        }
+        shown
+    }
+
+    /// Moves the cursor `steps` shown lines down (positive) or up (negative).
+    fn move_cursor(&mut self, steps: isize) {
+        let shown = self.shown();
+        let row = shown.iter().position(|&i| i == self.cursor).unwrap_or(0);
+        let row = (row as isize + steps).clamp(0, shown.len() as isize - 1) as usize;
+        self.cursor = shown[row];
+    }
+
+    /// Folds the object or array the cursor is on, or the one it's in.
+    fn fold(&mut self) {
+        let line = &self.lines[self.cursor];
+        let target = match line.end {
+            Some(_) if !self.folded.contains(&self.cursor) => Some(self.cursor),
+            _ => line.parent,
+        };
+        if let Some(target) = target {
+            self.folded.insert(target);
+            self.cursor = target;
+        }
+    }
+
+    fn toggle_fold(&mut self) {
+        if self.lines[self.cursor].end.is_some() && !self.folded.remove(&self.cursor) {
+            self.folded.insert(self.cursor);
+        }
+    }
+
+    /// Moves to the next line after (or before, going `back`) the cursor containing the
+    /// query, unfolding what hides it.
+    fn find(&mut self, back: bool) {
+        if self.query.is_empty() {
+            return;
+        }
+        let query = self.query.to_lowercase();
+        let len = self.lines.len();
+        let found = (1..=len)
+            .map(|step| {
+                if back {
+                    (self.cursor + len - step) % len
+                } else {
+                    (self.cursor + step) % len
+                }
+            })
+            .find(|&i| self.lines[i].text.to_lowercase().contains(&query));
+        if let Some(i) = found {
+            let mut parent = self.lines[i].parent;
+            while let Some(p) = parent {
+                self.folded.remove(&p);
+                parent = self.lines[p].parent;
+            }
+            self.cursor = i;
+        }
+    }
+
+    /// Folds, searches, and scrolls with the keys that do so in the app. Returns `false` once
+    /// the inspector is dismissed.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if let Some(typing) = &mut self.typing {
+            match key.code {
+                KeyCode::Enter => {
+                    self.query = std::mem::take(typing);
+                    self.typing = None;
+                    self.find(false);
+                }
+                KeyCode::Esc => self.typing = None,
+                KeyCode::Backspace => {
+                    typing.pop();
+                }
+                KeyCode::Char(c) => typing.push(c),
+                _ => {}
+            }
+            return true;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl => return false,
+            KeyCode::Char('j') | KeyCode::Down => self.move_cursor(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Char('d') => self.move_cursor(20),
+            KeyCode::Char('u') => self.move_cursor(-20),
+            KeyCode::Char('g') => self.cursor = 0,
+            KeyCode::Char('G') => self.move_cursor(isize::MAX / 2),
+            KeyCode::Char('h') | KeyCode::Left => self.fold(),
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.folded.remove(&self.cursor);
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => self.toggle_fold(),
+            KeyCode::Char('/') => self.typing = Some(String::new()),
+            KeyCode::Char('n') => self.find(false),
+            KeyCode::Char('N') => self.find(true),
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Appends the lines of `value`, the entry `key` of the container opened on line `parent`.
+fn push_lines(
+    lines: &mut Vec<JsonLine>,
+    key: Option<&str>,
+    value: &Value,
+    depth: usize,
+    parent: Option<usize>,
+    comma: bool,
+) {
+    let name = key.map_or(String::new(), |key| format!("{}: ", Value::from(key)));
+    let (open, close, entries): (&str, &str, Vec<(Option<&str>, &Value)>) = match value {
+        Value::Object(object) if !object.is_empty() => (
+            "{",
+            "}",
+            object
+                .iter()
+                .map(|(key, value)| (Some(key.as_str()), value))
+                .collect(),
+        ),
+        Value::Array(items) if !items.is_empty() => {
+            ("[", "]", items.iter().map(|item| (None, item)).collect())
+        }
+        _ => {
+            lines.push(JsonLine {
+                depth,
+                text: format!("{name}{value}"),
+                end: None,
+                entries: 0,
+                parent,
+                comma,
+            });
+            return;
+        }
+    };
+
+    let start = lines.len();
+    lines.push(JsonLine {
+        depth,
+        text: format!("{name}{open}"),
+        end: None,
+        entries: entries.len(),
+        parent,
+        comma: false,
+    });
+    let last = entries.len() - 1;
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        push_lines(lines, key, value, depth + 1, Some(start), i < last);
+    }
+    lines.push(JsonLine {
+        depth,
+        text: close.to_string(),
+        end: None,
+        entries: 0,
+        parent,
+        comma,
+    });
+    lines[start].end = Some(lines.len() - 1);
+    lines[start].comma = comma;
+}
+
+/// Draws the inspector over most of `area`.
+pub fn render_inspector(f: &mut Frame, inspector: &mut Inspector, area: Rect, look: &Appearance) {
+    let [popup_area] = Layout::horizontal([Constraint::Percentage(90)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup_area] = Layout::vertical([Constraint::Percentage(90)])
+        .flex(Flex::Center)
+        .areas(popup_area);
+
+    let hints = match &inspector.typing {
+        Some(typing) => format!(" /{typing}▏ "),
+        None => {
+            " h/l fold/unfold  Enter toggle  / search  n/N next/previous  Esc close ".to_string()
+        }
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(inspector.title.as_str())
+        .title_bottom(Line::styled(hints, Style::default().fg(look.theme.gray)));
+    let height = block.inner(popup_area).height as usize;
+
+    let shown = inspector.shown();
+    let row = shown.iter().position(|&i| i == inspector.cursor);
+    inspector.offset = window_offset(inspector.offset, row, height, shown.len(), look.scrolloff);
+    let gray = Style::default().fg(look.theme.gray);
+    let lines: Vec<Line> = shown
+        .iter()
+        .skip(inspector.offset)
+        .take(height)
+        .map(|&i| {
+            let line = &inspector.lines[i];
+            let mut spans = vec![Span::raw("  ".repeat(line.depth)), Span::raw(line.text.as_str())];
+            if inspector.folded.contains(&i) {
+                let close = inspector.lines[line.end.unwrap_or(i)].text.as_str();
+                spans.push(Span::styled(format!("…{close}"), gray));
+                spans.push(Span::styled(format!(" ({} entries)", line.entries), gray));
+            }
+            if line.comma {
+                spans.push(Span::raw(","));
+            }
+            let line = Line::from(spans);
+            if i == inspector.cursor {
+                line.style(look.theme.list_highlight)
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn press(inspector: &mut Inspector, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            assert!(inspector.handle_key(&KeyEvent::from(code)));
+        }
+    }
+
+    fn text(inspector: &Inspector) -> Vec<String> {
+        inspector
+            .shown()
+            .iter()
+            .map(|&i| {
+                let line = &inspector.lines[i];
+                let fold = if inspector.folded.contains(&i) {
+                    "…"
+                } else {
+                    ""
+                };
+                format!("{}{}{fold}", "  ".repeat(line.depth), line.text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn folds_and_searches() {
+        let value = json!({
+            "key": "OPS-1",
+            "fields": {
+                "customfield_10016": 5.0,
+                "status": { "name": "In Progress", "id": "3" },
+                "labels": [],
+            },
+        });
+        let mut inspector = Inspector::new(" OPS-1 ", &value);
+        assert_eq!(text(&inspector), [
+            "{",
+            "  \"fields\": {",
+            "    \"customfield_10016\": 5.0",
+            "    \"labels\": []",
+            "    \"status\": {…",
+            "  }",
+            "  \"key\": \"OPS-1\"",
+            "}",
+        ]);
+
+        press(&mut inspector, "/progress\n");
+        assert_eq!(inspector.lines[inspector.cursor].text, "\"name\": \"In Progress\"");
+        assert_eq!(text(&inspector).len(), 11);
+
+        // Folding from inside an object folds the object
+        press(&mut inspector, "h");
+        assert_eq!(inspector.lines[inspector.cursor].text, "\"status\": {");
+        press(&mut inspector, "kkh");
+        assert_eq!(text(&inspector), ["{", "  \"fields\": {…", "  \"key\": \"OPS-1\"", "}"]);
+        assert!(!inspector.handle_key(&KeyEvent::from(KeyCode::Esc)));
+    }
+}
@@ -1,10 +1,19 @@
 //! Issue model and helpers for Jira TUI.
 
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta};
+use itertools::Itertools;
 use jira_v3_openapi::models::IssueBean;
 use ratatui::style::Color;
+use serde_json::Value;
 
-use crate::ui::theme::Theme;
+use crate::{
+    jira::{HistoryEntry, INSTANCE_FIELD, adf, custom_fields},
+    ui::{theme::Theme, time},
+};
 
+/// A Jira issue, reduced to the fields the UI displays.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Issue {
     pub id: String,
@@ -14,8 +23,360 @@ pub struct Issue {
     pub status: Option<Status>,
     pub priority: Option<Priority>,
     pub story_points: Option<f64>,
+    /// Display name of the assignee.
+    pub assignee: Option<String>,
     pub parent_epic: Option<String>,
-    // Add more fields as needed (e.g., assignee, etc.)
+    pub created: Option<DateTime<FixedOffset>>,
+    pub updated: Option<DateTime<FixedOffset>>,
+    pub due: Option<NaiveDate>,
+    /// When work is planned to start, from the start date field of Jira Cloud.
+    pub start: Option<NaiveDate>,
+    pub labels: Vec<String>,
+    pub components: Vec<String>,
+    /// Names of the sprints the issue is or was in, oldest first.
+    pub sprints: Vec<String>,
+    /// The name of the sprint the issue is in now, if one is active.
+    pub active_sprint: Option<String>,
+    pub parent: Option<IssueRef>,
+    pub subtasks: Vec<IssueRef>,
+    pub links: Vec<IssueLink>,
+    pub attachments: Vec<Attachment>,
+    pub time_tracking: Option<TimeTracking>,
+    /// All fields as Jira returned them, for the columns of the config that show fields the
+    /// app doesn't know, and the JSON inspector.
+    pub raw_fields: HashMap<String, Value>,
+    /// The number an issue typed into the new issue input is told apart by, until the copy
+    /// Jira created is fetched.
+    pub pending: Option<u64>,
+}
+
+/// Another issue referred to by an issue, like its parent or a linked issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    pub key: String,
+    pub summary: String,
+}
+
+/// A link to another issue. `relation` reads from this issue to the other one, like
+/// "blocks" or "is blocked by".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueLink {
+    pub relation: String,
+    pub issue: IssueRef,
+}
+
+/// Estimated and logged work on an issue, in seconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeTracking {
+    pub original_estimate: Option<u64>,
+    pub remaining_estimate: Option<u64>,
+    pub time_spent: Option<u64>,
+}
+
+/// A sprint an issue is or was in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sprint {
+    pub name: String,
+    pub active: bool,
+    pub started: Option<DateTime<FixedOffset>>,
+    /// When it was closed, if it has been.
+    pub completed: Option<DateTime<FixedOffset>>,
+}
+
+impl Sprint {
+    /// Parses an entry of the sprint field: an object with a name and a state, or on older
+    /// Jira servers the same as a string like
+    /// `com.atlassian.greenhopper.service.sprint.Sprint@1f[id=5,state=ACTIVE,name=Sprint 5,...]`.
+    fn from_jira(value: &Value) -> Option<Self> {
+        let Some(text) = value.as_str() else {
+            let time = |name: &str| time::parse_timestamp(value.get(name)?.as_str()?);
+            return Some(Sprint {
+                name: value.get("name")?.as_str()?.to_string(),
+                active: value
+                    .get("state")
+                    .and_then(|state| state.as_str())
+                    .is_some_and(|state| state.eq_ignore_ascii_case("active")),
+                started: time("startDate"),
+                completed: time("completeDate"),
+            });
+        };
+        let Some(properties) = text
+            .split_once('[')
+            .and_then(|(_, rest)| rest.strip_suffix(']'))
+        else {
+            return Some(Sprint {
+                name: text.to_string(),
+                active: false,
+                started: None,
+                completed: None,
+            });
+        };
+        let mut pairs: Vec<(&str, String)> = Vec::new();
+        for part in properties.split(',') {
+            match part.split_once('=') {
+                Some((key, value))
+                    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric()) =>
+                {
+                    pairs.push((key, value.to_string()));
+                }
+                // A comma in a value, like in the name
+                _ => {
+                    let (_, value) = pairs.last_mut()?;
+                    value.push(',');
+                    value.push_str(part);
+                }
+            }
+        }
+        let property = |name: &str| {
+            pairs
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+        };
+        // Missing dates are written as `<null>`
+        let time = |name: &str| time::parse_timestamp(property(name)?);
+        Some(Sprint {
+            name: property("name")?.clone(),
+            active: property("state").is_some_and(|state| state.eq_ignore_ascii_case("active")),
+            started: time("startDate"),
+            completed: time("completeDate"),
+        })
+    }
+}
+
+/// A file attached to an issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    pub id: String,
+    pub filename: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Where the content is downloaded from, which requires the Jira credentials.
+    pub url: String,
+}
+
+/// A comment on an issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    /// Display name of the author.
+    pub author: String,
+    pub created: Option<DateTime<FixedOffset>>,
+    pub body: String,
+}
+
+/// How long an issue was in one status, over all the times it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusTime {
+    pub status: String,
+    pub time: TimeDelta,
+}
+
+/// How long an issue spent in each status, according to its changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeInStatus {
+    /// In the order the issue first entered them, ending with the one it's in.
+    pub statuses: Vec<StatusTime>,
+    /// How long since the issue last entered the status it's in.
+    pub current: TimeDelta,
+}
+
+impl TimeInStatus {
+    fn add(&mut self, status: String, time: TimeDelta) {
+        match self.statuses.iter_mut().find(|s| s.status == status) {
+            Some(entry) => entry.time += time,
+            None => self.statuses.push(StatusTime { status, time }),
+        }
+    }
+}
+
+impl IssueRef {
+    /// Parses an issue as Jira nests it in the fields of another: `{"key", "fields": {...}}`.
+    fn from_jira(issue: &serde_json::Value) -> Option<Self> {
+        Some(IssueRef {
+            key: issue.get("key")?.as_str()?.to_string(),
+            summary: issue
+                .get("fields")
+                .and_then(|fields| fields.get("summary"))
+                .and_then(|summary| summary.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        })
+    }
+}
+
+impl IssueLink {
+    /// Parses an entry of the `issuelinks` field, which holds either the inward or the
+    /// outward issue depending on the direction of the link.
+    fn from_jira(link: &serde_json::Value) -> Option<Self> {
+        let (direction, issue) = match link.get("outwardIssue") {
+            Some(issue) => ("outward", issue),
+            None => ("inward", link.get("inwardIssue")?),
+        };
+        let relation = link
+            .get("type")
+            .and_then(|t| t.get(direction))
+            .and_then(|r| r.as_str())
+            .unwrap_or("relates to");
+        Some(IssueLink {
+            relation: relation.to_string(),
+            issue: IssueRef::from_jira(issue)?,
+        })
+    }
+}
+
+impl TimeTracking {
+    /// Reads the `timetracking` field, or the separate fields search results have instead.
+    /// `None` when nothing was estimated or logged.
+    fn from_jira(fields: &HashMap<String, Value>) -> Option<Self> {
+        let seconds = |value: Option<&Value>| value.and_then(Value::as_u64);
+        let tracking = fields.get("timetracking");
+        let field = |nested: &str, flat: &str| {
+            seconds(tracking.and_then(|tracking| tracking.get(nested)))
+                .or_else(|| seconds(fields.get(flat)))
+        };
+        let tracking = TimeTracking {
+            original_estimate: field("originalEstimateSeconds", "timeoriginalestimate"),
+            remaining_estimate: field("remainingEstimateSeconds", "timeestimate"),
+            time_spent: field("timeSpentSeconds", "timespent"),
+        };
+        (tracking != TimeTracking::default()).then_some(tracking)
+    }
+}
+
+impl Attachment {
+    /// Parses an entry of the `attachment` field.
+    fn from_jira(attachment: &serde_json::Value) -> Option<Self> {
+        let text = |name: &str| Some(attachment.get(name)?.as_str()?.to_string());
+        Some(Attachment {
+            id: text("id")?,
+            filename: text("filename")?,
+            size: attachment
+                .get("size")
+                .and_then(|size| size.as_u64())
+                .unwrap_or_default(),
+            url: text("content")?,
+        })
+    }
+}
+
+impl Comment {
+    /// Parses an entry of the `comments` of the `comment` field.
+    fn from_jira(comment: &serde_json::Value) -> Option<Self> {
+        let body = comment.get("body")?;
+        Some(Comment {
+            author: comment
+                .pointer("/author/displayName")
+                .and_then(|name| name.as_str())
+                .unwrap_or("Anonymous")
+                .to_string(),
+            created: comment
+                .get("created")
+                .and_then(|created| created.as_str())
+                .and_then(time::parse_timestamp),
+            // Plain text from API version 2, a document from version 3
+            body: match body.as_str() {
+                Some(body) => body.to_string(),
+                None => adf::to_plain_text(body),
+            },
+        })
+    }
+}
+
+/// A service level agreement of a Jira Service Management request, like "Time to first
+/// response", as its custom field reports it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sla {
+    pub name: String,
+    /// Time left when the issue was fetched, negative once breached; `None` once the clock
+    /// stopped, like when the request was answered.
+    pub remaining: Option<TimeDelta>,
+    /// When the running clock breaches the goal, unless it's paused.
+    pub breach_time: Option<DateTime<FixedOffset>>,
+    pub goal: Option<TimeDelta>,
+    pub paused: bool,
+    /// Whether the goal was missed, by the running clock or the last completed one.
+    pub breached: bool,
+}
+
+impl Sla {
+    /// Share of the goal left below which an SLA counts as due soon.
+    const DUE_SOON: f64 = 0.25;
+
+    /// Parses an SLA field, which holds the running cycle of the clock and the completed ones.
+    fn from_jira(field: &Value) -> Option<Self> {
+        let name = field.get("name")?.as_str()?.to_string();
+        let millis = |value: &Value, name: &str| {
+            value
+                .pointer(&format!("/{name}/millis"))
+                .and_then(Value::as_i64)
+                .map(TimeDelta::milliseconds)
+        };
+        let flag =
+            |value: &Value, name: &str| value.get(name).and_then(Value::as_bool) == Some(true);
+        if let Some(cycle) = field.get("ongoingCycle") {
+            return Some(Sla {
+                name,
+                remaining: millis(cycle, "remainingTime"),
+                breach_time: cycle
+                    .pointer("/breachTime/iso8601")
+                    .and_then(Value::as_str)
+                    .and_then(time::parse_timestamp),
+                goal: millis(cycle, "goalDuration"),
+                paused: flag(cycle, "paused"),
+                breached: flag(cycle, "breached"),
+            });
+        }
+        let last = field.get("completedCycles")?.as_array()?.last()?;
+        Some(Sla {
+            name,
+            remaining: None,
+            breach_time: None,
+            goal: millis(last, "goalDuration"),
+            paused: false,
+            breached: flag(last, "breached"),
+        })
+    }
+
+    /// Time left at `now`, negative once breached, if the clock is running.
+    pub fn remaining_at(&self, now: DateTime<FixedOffset>) -> Option<TimeDelta> {
+        match self.breach_time {
+            Some(breach_time) if !self.paused && self.remaining.is_some() => {
+                Some(breach_time - now)
+            }
+            _ => self.remaining,
+        }
+    }
+
+    /// A countdown like `3h 20m`, `-1d 2h` once breached, or whether the goal was met.
+    pub fn countdown(&self, now: DateTime<FixedOffset>) -> String {
+        match self.remaining_at(now) {
+            None if self.breached => "missed".to_string(),
+            None => "met".to_string(),
+            Some(left) if left < TimeDelta::zero() => format!("-{}", time::elapsed(-left)),
+            Some(left) if self.paused => format!("{} paused", time::elapsed(left)),
+            Some(left) => time::elapsed(left),
+        }
+    }
+
+    /// Red once breached, yellow when little time is left, gray when the clock stopped.
+    pub fn color(&self, now: DateTime<FixedOffset>, theme: &Theme) -> Color {
+        let Some(left) = self.remaining_at(now) else {
+            return if self.breached { theme.red } else { theme.gray };
+        };
+        let due_soon = self.goal.is_some_and(|goal| {
+            goal > TimeDelta::zero()
+                && (left.num_seconds() as f64) < goal.num_seconds() as f64 * Self::DUE_SOON
+        });
+        if self.breached || left < TimeDelta::zero() {
+            theme.red
+        } else if self.paused {
+            theme.gray
+        } else if due_soon {
+            theme.yellow
+        } else {
+            theme.green
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -37,14 +398,23 @@ impl Priority {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum Status {
-    Todo,
+/// An issue status. Jira lets every project define its own statuses, so only the category
+/// they belong to has a fixed meaning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Status {
+    /// The status name as configured in Jira, displayed verbatim.
+    pub name: String,
+    pub category: StatusCategory,
+}
+
+/// The fixed categories Jira sorts every status into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusCategory {
+    ToDo,
     InProgress,
-    Review,
-    Test,
     Done,
-    Other(String),
+    /// The category is missing or not one Jira documents.
+    Unknown,
 }
 
 impl Priority {
@@ -71,44 +441,45 @@ impl Priority {
     }
 }
 
-impl Status {
-    pub const fn color(&self, theme: &Theme) -> Color {
-        match self {
-            Status::Todo => theme.white,
-            Status::InProgress => theme.cyan,
-            Status::Review => theme.magenta,
-            Status::Test => theme.blue,
-            Status::Done => theme.green,
-            Status::Other(_) => theme.gray,
+impl StatusCategory {
+    /// Parses the `key` of a `statusCategory` object.
+    pub fn from_key(key: &str) -> Self {
+        match key {
+            "new" => StatusCategory::ToDo,
+            "indeterminate" => StatusCategory::InProgress,
+            "done" => StatusCategory::Done,
+            _ => StatusCategory::Unknown,
         }
     }
 
-    pub fn from_jira_str(s: &str) -> Self {
-        let s_lower = s.to_lowercase();
-        if s_lower.contains("todo") {
-            Status::Todo
-        } else if s_lower.contains("progress") {
-            Status::InProgress
-        } else if s_lower.contains("review") {
-            Status::Review
-        } else if s_lower.contains("test") {
-            Status::Test
-        } else if s_lower.contains("done") {
-            Status::Done
-        } else {
-            Status::Other(s.to_string())
+    pub const fn color(self, theme: &Theme) -> Color {
+        match self {
+            StatusCategory::ToDo => theme.white,
+            StatusCategory::InProgress => theme.cyan,
+            StatusCategory::Done => theme.green,
+            StatusCategory::Unknown => theme.gray,
         }
     }
+}
+
+impl Status {
+    /// Parses a `status` field, including its `statusCategory`.
+    pub fn from_jira(status: &serde_json::Value) -> Option<Self> {
+        let name = status.get("name")?.as_str()?.to_string();
+        let category = status
+            .get("statusCategory")
+            .and_then(|c| c.get("key"))
+            .and_then(|k| k.as_str())
+            .map_or(StatusCategory::Unknown, StatusCategory::from_key);
+        Some(Status { name, category })
+    }
+
+    pub const fn color(&self, theme: &Theme) -> Color {
+        self.category.color(theme)
+    }
 
     pub fn as_str(&self) -> &str {
-        match self {
-            Status::Todo => "Todo",
-            Status::InProgress => "In Progress",
-            Status::Review => "Review",
-            Status::Test => "Test",
-            Status::Done => "Done",
-            Status::Other(s) => s,
-        }
+        &self.name
     }
 }
 
@@ -122,32 +493,94 @@ impl Issue {
             status: None,
             priority: None,
             story_points: None,
+            assignee: None,
             parent_epic: None,
+            created: None,
+            updated: None,
+            due: None,
+            start: None,
+            labels: Vec::new(),
+            components: Vec::new(),
+            sprints: Vec::new(),
+            active_sprint: None,
+            parent: None,
+            subtasks: Vec::new(),
+            links: Vec::new(),
+            attachments: Vec::new(),
+            time_tracking: None,
+            raw_fields: HashMap::new(),
+            pending: None,
         }
     }
 
-    /// Map from Jira API model to internal Issue struct.
-    pub fn from_jira(jira: &IssueBean) -> Self {
-        fn adf_to_plain_text(adf: &serde_json::Value) -> String {
-            match adf {
-                serde_json::Value::Object(map) => {
-                    if let Some(content) = map.get("content") {
-                        adf_to_plain_text(content)
-                    } else if let Some(text) = map.get("text") {
-                        text.as_str().unwrap_or("").to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                serde_json::Value::Array(arr) => arr
-                    .iter()
-                    .map(adf_to_plain_text)
-                    .collect::<Vec<_>>()
-                    .join(""),
-                _ => "".to_string(),
-            }
+    /// Whether the issue was typed into the new issue input and doesn't exist in Jira yet.
+    pub fn is_local(&self) -> bool {
+        self.id.is_empty()
+    }
+
+    /// The name of the instance the issue comes from, when searching several at once.
+    pub fn instance(&self) -> Option<&str> {
+        self.raw_fields.get(INSTANCE_FIELD)?.as_str()
+    }
+
+    pub fn is_epic(&self) -> bool {
+        self.issue_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("epic"))
+    }
+
+    /// Whether the issue is flagged as an impediment.
+    pub fn is_flagged(&self) -> bool {
+        self.raw_field(&custom_fields().flagged)
+            .and_then(Value::as_array)
+            .is_some_and(|options| !options.is_empty())
+    }
+
+    /// Whether the issue is a subtask, which can't have subtasks of its own.
+    pub fn is_subtask(&self) -> bool {
+        self.raw_field("issuetype.subtask")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// The epic the issue is in, or the issue itself if it's an epic, to group related work
+    /// by.
+    pub fn epic(&self) -> Option<IssueRef> {
+        if self.is_epic() {
+            return Some(IssueRef {
+                key: self.id.clone(),
+                summary: self.summary.clone(),
+            });
         }
+        // Team-managed projects name the level of epics differently
+        let parent_type = self.raw_field("parent.fields.issuetype")?;
+        let is_epic = parent_type
+            .get("name")
+            .and_then(Value::as_str)
+            .is_some_and(|name| name.eq_ignore_ascii_case("epic"))
+            || parent_type.get("hierarchyLevel").and_then(Value::as_i64) == Some(1);
+        is_epic.then(|| self.parent.clone()).flatten()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.status
+            .as_ref()
+            .is_some_and(|status| status.category == StatusCategory::Done)
+    }
 
+    /// The issues this one refers to, with a label for each: the parent, subtasks, and links.
+    pub fn references(&self) -> Vec<(&str, &IssueRef)> {
+        let parent = self.parent.iter().map(|parent| ("parent", parent));
+        let subtasks = self.subtasks.iter().map(|subtask| ("subtask", subtask));
+        let links = self
+            .links
+            .iter()
+            .map(|link| (link.relation.as_str(), &link.issue));
+        parent.chain(subtasks).chain(links).collect()
+    }
+
+    /// Map from Jira API model to internal Issue struct.
+    pub fn from_jira(jira: &IssueBean) -> Self {
         let id = jira.key.clone().unwrap_or_else(|| "<no id>".to_string());
 
         let (summary, description, issue_type, status, priority, story_points, parent_epic) =
@@ -162,7 +595,7 @@ impl Issue {
                         if let Some(s) = val.as_str() {
                             s.to_string()
                         } else {
-                            adf_to_plain_text(val)
+                            adf::to_plain_text(val)
                         }
                     }
                     None => "".to_string(),
@@ -172,17 +605,15 @@ impl Issue {
                     .and_then(|v| v.get("name"))
                     .and_then(|v| v.as_str())
                     .map(|s| s.to_string());
-                let status = fields
-                    .get("status")
-                    .and_then(|v| v.get("name"))
-                    .and_then(|v| v.as_str())
-                    .map(Status::from_jira_str);
+                let status = fields.get("status").and_then(Status::from_jira);
                 let priority = fields
                     .get("priority")
                     .and_then(|v| v.get("name"))
                     .and_then(|v| v.as_str())
                     .map(Priority::from_jira_str);
-                let story_points = fields.get("customfield_10016").and_then(|v| v.as_f64());
+                let story_points = fields
+                    .get(&custom_fields().story_points)
+                    .and_then(|v| v.as_f64());
                 let parent_epic = fields
                     .get("parent")
                     .and_then(|v| v.get("fields"))
@@ -194,6 +625,43 @@ impl Issue {
             } else {
                 ("<no summary>".to_string(), "".to_string(), None, None, None, None, None)
             };
+
+        let field_str = |name: &str| {
+            jira.fields
+                .as_ref()
+                .and_then(|fields| fields.get(name))
+                .and_then(|v| v.as_str())
+        };
+        let created = field_str("created").and_then(time::parse_timestamp);
+        let updated = field_str("updated").and_then(time::parse_timestamp);
+        let due = field_str("duedate").and_then(time::parse_date);
+        let start = field_str(&custom_fields().start_date).and_then(time::parse_date);
+        let assignee = jira
+            .fields
+            .as_ref()
+            .and_then(|fields| fields.get("assignee"))
+            .and_then(|assignee| assignee.get("displayName"))
+            .and_then(|name| name.as_str())
+            .map(str::to_string);
+        let field_array = |name: &str| {
+            jira.fields
+                .as_ref()
+                .and_then(|fields| fields.get(name))
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+        };
+        let sprints: Vec<Sprint> = field_array(&custom_fields().sprint)
+            .filter_map(Sprint::from_jira)
+            .collect();
+        // Labels are plain strings; components are objects with a name
+        let field_names = |name: &str| -> Vec<String> {
+            field_array(name)
+                .filter_map(|v| v.as_str().or_else(|| v.get("name")?.as_str()))
+                .map(str::to_string)
+                .collect()
+        };
+
         Self {
             id,
             summary,
@@ -202,9 +670,142 @@ impl Issue {
             status,
             priority,
             story_points,
+            assignee,
             parent_epic,
+            created,
+            updated,
+            due,
+            start,
+            labels: field_names("labels"),
+            components: field_names("components"),
+            active_sprint: sprints
+                .iter()
+                .find(|sprint| sprint.active)
+                .map(|sprint| sprint.name.clone()),
+            sprints: sprints.into_iter().map(|sprint| sprint.name).collect(),
+            parent: jira
+                .fields
+                .as_ref()
+                .and_then(|fields| fields.get("parent"))
+                .and_then(IssueRef::from_jira),
+            subtasks: field_array("subtasks")
+                .filter_map(IssueRef::from_jira)
+                .collect(),
+            links: field_array("issuelinks")
+                .filter_map(IssueLink::from_jira)
+                .collect(),
+            attachments: field_array("attachment")
+                .filter_map(Attachment::from_jira)
+                .collect(),
+            time_tracking: jira.fields.as_ref().and_then(TimeTracking::from_jira),
+            raw_fields: jira.fields.clone().unwrap_or_default(),
+            pending: None,
         }
     }
+
+    /// The comments, oldest first, if Jira returned them with the issue.
+    pub fn comments(&self) -> Vec<Comment> {
+        self.raw_field("comment.comments")
+            .and_then(|comments| comments.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(Comment::from_jira)
+            .collect()
+    }
+
+    /// How long the issue spent in each status up to `now`, from its `history` as fetched,
+    /// newest first. The first status counts from when the issue was created; without that,
+    /// from the first move.
+    pub fn time_in_status(
+        &self,
+        history: &[HistoryEntry],
+        now: DateTime<FixedOffset>,
+    ) -> Option<TimeInStatus> {
+        let moves: Vec<(DateTime<FixedOffset>, Option<&String>, Option<&String>)> = history
+            .iter()
+            .rev()
+            .filter_map(|entry| Some((entry.created?, &entry.changes)))
+            .flat_map(|(when, changes)| {
+                changes
+                    .iter()
+                    .filter(|change| change.field == "status")
+                    .map(move |change| (when, change.from.as_ref(), change.to.as_ref()))
+            })
+            .collect();
+        let mut current = match (moves.first(), self.created) {
+            (Some((_, Some(from), _)), Some(created)) => Some(((*from).clone(), created)),
+            (None, Some(created)) => Some((self.status.as_ref()?.name.clone(), created)),
+            _ => None,
+        };
+        let mut times = TimeInStatus {
+            statuses: Vec::new(),
+            current: TimeDelta::zero(),
+        };
+        for (when, _, to) in moves {
+            if let Some((status, since)) = current.take() {
+                times.add(status, when - since);
+            }
+            current = to.map(|to| (to.clone(), when));
+        }
+        let (status, since) = current?;
+        times.current = now - since;
+        times.add(status, now - since);
+        Some(times)
+    }
+
+    /// The sprints the issue is or was in, oldest first, with their dates.
+    pub fn sprint_details(&self) -> Vec<Sprint> {
+        self.raw_fields
+            .get(&custom_fields().sprint)
+            .and_then(|sprints| sprints.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(Sprint::from_jira)
+            .collect()
+    }
+
+    /// The SLAs of a service request, in the order of their fields.
+    pub fn slas(&self) -> Vec<Sla> {
+        self.raw_fields
+            .iter()
+            .filter(|(_, value)| value.is_object())
+            .sorted_by_key(|(name, _)| name.as_str())
+            .filter_map(|(_, value)| Sla::from_jira(value))
+            .collect()
+    }
+
+    /// Whether the issue is a service request with SLAs, without parsing them.
+    pub fn has_sla(&self) -> bool {
+        self.raw_fields.values().any(|value| {
+            value.get("ongoingCycle").is_some() || value.get("completedCycles").is_some()
+        })
+    }
+
+    /// The SLA with the least time left at `now`, if any is running.
+    pub fn most_urgent_sla(&self, now: DateTime<FixedOffset>) -> Option<Sla> {
+        self.slas()
+            .into_iter()
+            .filter(|sla| sla.remaining.is_some())
+            .min_by_key(|sla| sla.remaining_at(now))
+    }
+
+    /// The issue as Jira returned it: its key and all of its fields.
+    pub fn raw_json(&self) -> Value {
+        serde_json::json!({ "key": self.id, "fields": self.raw_fields })
+    }
+
+    /// The value at a dot-separated path into the issue's JSON, like
+    /// `fields.customfield_10050.value` or `fields.fixVersions.0.name`. The leading `fields.`
+    /// may be left out.
+    pub fn raw_field(&self, path: &str) -> Option<&Value> {
+        let path = path.strip_prefix("fields.").unwrap_or(path);
+        let mut segments = path.split('.');
+        let first = self.raw_fields.get(segments.next()?)?;
+        segments.try_fold(first, |value, segment| match value {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => value.get(segment),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -223,4 +824,201 @@ mod tests {
         assert!(issue.story_points.is_none());
         assert!(issue.parent_epic.is_none());
     }
+
+    #[test]
+    fn time_in_status_from_the_changelog() {
+        let at = |s| time::parse_timestamp(s).unwrap();
+        let moved = |when, from: &str, to: &str| HistoryEntry {
+            author: "Ada".to_string(),
+            created: Some(at(when)),
+            changes: vec![crate::jira::FieldChange {
+                field: "status".to_string(),
+                from: Some(from.to_string()),
+                to: Some(to.to_string()),
+            }],
+        };
+        let issue = Issue {
+            created: Some(at("2024-05-01T09:00:00.000+0000")),
+            ..Issue::new("Failover", "")
+        };
+        // Newest first, as fetched
+        let history = [
+            moved("2024-05-04T09:00:00.000+0000", "To Do", "In Progress"),
+            moved("2024-05-03T09:00:00.000+0000", "In Progress", "To Do"),
+            moved("2024-05-02T09:00:00.000+0000", "To Do", "In Progress"),
+        ];
+        let times = issue
+            .time_in_status(&history, at("2024-05-10T21:00:00.000+0000"))
+            .unwrap();
+        let statuses: Vec<(&str, i64)> = times
+            .statuses
+            .iter()
+            .map(|s| (s.status.as_str(), s.time.num_hours()))
+            .collect();
+        assert_eq!(statuses, [("To Do", 48), ("In Progress", 24 + 156)]);
+        assert_eq!(times.current.num_hours(), 156);
+
+        // Never moved, and not known in which status
+        assert_eq!(issue.time_in_status(&[], at("2024-05-10T21:00:00.000+0000")), None);
+    }
+
+    #[test]
+    fn sprints_from_objects_or_server_strings() {
+        let issue = |sprints: Value| {
+            let fields = serde_json::json!({ "customfield_10020": sprints });
+            Issue::from_jira(&IssueBean {
+                fields: Some(serde_json::from_value(fields).unwrap()),
+                ..IssueBean::default()
+            })
+        };
+        let cloud = issue(serde_json::json!([
+            { "id": 4, "name": "Sprint 4", "state": "closed" },
+            { "id": 5, "name": "Sprint 5", "state": "active" },
+        ]));
+        assert_eq!(cloud.sprints, ["Sprint 4", "Sprint 5"]);
+        assert_eq!(cloud.active_sprint.as_deref(), Some("Sprint 5"));
+
+        let server = issue(serde_json::json!([
+            "com.atlassian.greenhopper.service.sprint.Sprint@1f[id=5,rapidViewId=2,state=ACTIVE,\
+             name=Ops, week 5,startDate=2024-05-06T09:00:00.000Z,goal=<null>]",
+        ]));
+        assert_eq!(server.active_sprint.as_deref(), Some("Ops, week 5"));
+        let sprint = &server.sprint_details()[0];
+        assert_eq!(sprint.started, time::parse_timestamp("2024-05-06T09:00:00.000+0000"));
+        assert_eq!(sprint.completed, None);
+        assert_eq!(issue(serde_json::json!(null)).active_sprint, None);
+    }
+
+    #[test]
+    fn epics_of_children_and_of_themselves() {
+        let issue = |fields: Value| {
+            Issue::from_jira(&IssueBean {
+                key: Some("OPS-1".to_string()),
+                fields: Some(serde_json::from_value(fields).unwrap()),
+                ..IssueBean::default()
+            })
+        };
+        let parent = |issue_type: Value| {
+            serde_json::json!({
+                "parent": {
+                    "key": "OPS-100",
+                    "fields": { "summary": "Resilience", "issuetype": issue_type },
+                },
+            })
+        };
+        let child = issue(parent(serde_json::json!({ "name": "Epic", "hierarchyLevel": 1 })));
+        assert_eq!(child.epic().map(|epic| epic.key), Some("OPS-100".to_string()));
+        let renamed =
+            issue(parent(serde_json::json!({ "name": "Initiative", "hierarchyLevel": 1 })));
+        assert!(renamed.epic().is_some());
+        let subtask = issue(parent(serde_json::json!({ "name": "Task", "hierarchyLevel": 0 })));
+        assert_eq!(subtask.epic(), None);
+        let epic =
+            issue(serde_json::json!({ "summary": "Resilience", "issuetype": { "name": "Epic" } }));
+        assert_eq!(epic.epic().map(|epic| epic.key), Some("OPS-1".to_string()));
+    }
+
+    #[test]
+    fn time_tracking_from_either_field() {
+        let fields = |value: Value| serde_json::from_value(value).unwrap();
+        let tracking = TimeTracking::from_jira(&fields(serde_json::json!({
+            "timetracking": { "originalEstimateSeconds": 7200, "timeSpentSeconds": 3600 },
+        })));
+        assert_eq!(
+            tracking,
+            Some(TimeTracking {
+                original_estimate: Some(7200),
+                remaining_estimate: None,
+                time_spent: Some(3600),
+            })
+        );
+        let tracking =
+            TimeTracking::from_jira(&fields(serde_json::json!({ "timespent": 60 }))).unwrap();
+        assert_eq!(tracking.time_spent, Some(60));
+        assert_eq!(
+            TimeTracking::from_jira(&fields(serde_json::json!({ "timespent": null }))),
+            None
+        );
+    }
+
+    #[test]
+    fn slas_count_down_to_the_breach() {
+        let mut issue = Issue::new("Printer on fire", "");
+        issue.raw_fields.insert(
+            "customfield_10030".to_string(),
+            serde_json::json!({
+                "name": "Time to first response",
+                "completedCycles": [{ "breached": false, "goalDuration": { "millis": 14400000 } }],
+            }),
+        );
+        issue.raw_fields.insert(
+            "customfield_10031".to_string(),
+            serde_json::json!({
+                "name": "Time to resolution",
+                "completedCycles": [],
+                "ongoingCycle": {
+                    "breachTime": { "iso8601": "2024-05-02T17:00:00+0000" },
+                    "breached": false,
+                    "paused": false,
+                    "goalDuration": { "millis": 28800000 },
+                    "remainingTime": { "millis": 7200000 },
+                },
+            }),
+        );
+        assert!(issue.has_sla());
+        let theme = Theme::default();
+        let at = |s| time::parse_timestamp(s).unwrap();
+        let countdowns = |now| {
+            issue
+                .slas()
+                .iter()
+                .map(|sla| (sla.countdown(now), sla.color(now, &theme)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(countdowns(at("2024-05-02T12:00:00.000+0000")), [
+            ("met".to_string(), theme.gray),
+            ("5h 0m".to_string(), theme.green),
+        ]);
+        assert_eq!(
+            countdowns(at("2024-05-02T16:00:00.000+0000"))[1],
+            ("1h 0m".to_string(), theme.yellow)
+        );
+        assert_eq!(
+            countdowns(at("2024-05-02T19:30:00.000+0000"))[1],
+            ("-2h 30m".to_string(), theme.red)
+        );
+
+        let urgent = issue
+            .most_urgent_sla(at("2024-05-02T12:00:00.000+0000"))
+            .unwrap();
+        assert_eq!(urgent.name, "Time to resolution");
+    }
+
+    #[test]
+    fn raw_fields_by_path() {
+        let mut issue = Issue::new("Test", "");
+        issue.raw_fields.insert(
+            "fixVersions".to_string(),
+            serde_json::json!([{ "name": "1.0" }, { "name": "1.1" }]),
+        );
+        assert_eq!(issue.raw_field("fields.fixVersions.1.name"), Some(&"1.1".into()));
+        assert_eq!(issue.raw_field("fixVersions.0.name"), Some(&"1.0".into()));
+        assert_eq!(issue.raw_field("fixVersions.name"), None);
+        assert_eq!(issue.raw_field("customfield_10050"), None);
+    }
+
+    #[test]
+    fn status_color_follows_category() {
+        let status = Status::from_jira(&serde_json::json!({
+            "name": "Ready for Test",
+            "statusCategory": { "key": "indeterminate" },
+        }))
+        .unwrap();
+        assert_eq!(status.as_str(), "Ready for Test");
+        assert_eq!(status.category, StatusCategory::InProgress);
+
+        let status = Status::from_jira(&serde_json::json!({ "name": "Parked" })).unwrap();
+        assert_eq!(status.category, StatusCategory::Unknown);
+        assert_eq!(Status::from_jira(&serde_json::json!({})), None);
+    }
 }
@@ -1,18 +1,55 @@
-use crate::app::App;
-use crate::ui::theme::THEME;
+//! The issue table shown in the main pane.
+
+use std::borrow::Cow;
+
+use chrono::Local;
 use ratatui::{
     Frame,
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Cell, HighlightSpacing, Row, Table, TableState},
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table, TableState, Wrap},
+};
+use serde_json::Value;
+
+use crate::{
+    app::{App, find},
+    ui::{
+        icons,
+        input::RowPlace,
+        issue::Issue,
+        text,
+        theme::{Appearance, Density, Theme},
+    },
 };
 
+/// Spaces between two adjacent columns.
+const COLUMN_SPACING: u16 = 2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Field {
     Id,
+    Type,
     Summary,
     Status,
     Priority,
+    /// The countdown of the most urgent SLA of a service request.
+    Sla,
+    /// The instance the issue comes from, when searching several.
+    Instance,
+    /// The labels, as chips.
+    Labels,
+    /// The active sprint.
+    Sprint,
+    /// A marker in the color of the epic the issue is in.
+    Epic,
+    /// A marker on issues flagged as impediments.
+    Flag,
+    /// The `column`th of the columns from the config, counting from 0.
+    Computed {
+        column: usize,
+        width: u16,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -21,140 +58,709 @@ enum FieldWidth {
     Fixed(u16),
 }
 
+impl FieldWidth {
+    const fn min(self) -> u16 {
+        match self {
+            FieldWidth::Flexible { min, .. } => min,
+            FieldWidth::Fixed(w) => w,
+        }
+    }
+}
+
 impl Field {
-    // Order in which fields are rendered in the row
-    pub const RENDER_ORDER: &'static [Field] =
-        &[Field::Id, Field::Priority, Field::Summary, Field::Status];
+    // Order in which fields are rendered in the row, followed by the computed ones
+    pub const RENDER_ORDER: &'static [Field] = &[
+        Field::Epic,
+        Field::Instance,
+        Field::Id,
+        Field::Type,
+        Field::Priority,
+        Field::Flag,
+        Field::Summary,
+        Field::Status,
+        Field::Sprint,
+        Field::Labels,
+        Field::Sla,
+    ];
+
+    // Priority order for hiding fields (first field is always shown), followed by the
+    // computed ones
+    pub const PRIORITY: &'static [Field] = &[
+        Field::Summary,
+        Field::Status,
+        Field::Flag,
+        Field::Epic,
+        Field::Sla,
+        Field::Labels,
+        Field::Id,
+        Field::Sprint,
+        Field::Instance,
+        Field::Priority,
+        Field::Type,
+    ];
+
+    /// Whether the field only gets a column while some listed issue has it.
+    const fn is_optional(self) -> bool {
+        matches!(
+            self,
+            Field::Sla
+                | Field::Instance
+                | Field::Labels
+                | Field::Sprint
+                | Field::Epic
+                | Field::Flag
+        )
+    }
 
-    // Priority order for hiding fields (first field is always shown)
-    pub const PRIORITY: &'static [Field] =
-        &[Field::Summary, Field::Status, Field::Id, Field::Priority];
+    /// Whether the field gets a column at `density`, rather than going under the summary.
+    const fn has_column(self, density: Density) -> bool {
+        !matches!(
+            (density, self),
+            (Density::Comfortable, Field::Id | Field::Status | Field::Labels)
+        )
+    }
 
     pub const fn width(self) -> FieldWidth {
         match self {
             Field::Id => FieldWidth::Fixed(8),
             Field::Summary => FieldWidth::Flexible { factor: 5, min: 20 },
             Field::Status => FieldWidth::Flexible { factor: 1, min: 5 },
-            Field::Priority => FieldWidth::Fixed(1),
+            Field::Labels => FieldWidth::Flexible { factor: 2, min: 10 },
+            Field::Type | Field::Priority | Field::Epic | Field::Flag => FieldWidth::Fixed(1),
+            Field::Sla | Field::Instance => FieldWidth::Fixed(8),
+            Field::Sprint => FieldWidth::Fixed(12),
+            Field::Computed { width, .. } => FieldWidth::Fixed(width),
         }
     }
 
-    pub fn cell(self, issue: &crate::ui::issue::Issue) -> Cell {
+    /// The cell showing this field of `issue`, truncated to `width` columns.
+    pub fn cell<'a>(self, issue: &'a Issue, look: &Appearance, width: u16) -> Cell<'a> {
+        let theme = &look.theme;
+        let width = width as usize;
         match self {
-            Field::Id => Cell::from(issue.id.clone()).style(Style::default().fg(Color::DarkGray)),
-            Field::Summary => Cell::from(issue.summary.clone()),
+            Field::Id => Cell::from(text::truncate(&issue.id, width))
+                .style(Style::default().fg(theme.dark_gray)),
+            Field::Summary => Cell::from(text::truncate(&issue.summary, width)),
             Field::Status => {
-                let (text, color) = match issue.status.as_ref() {
-                    Some(status) => (status.as_str(), status.color(&THEME)),
-                    None => ("", THEME.gray),
+                let (name, color) = match issue.status.as_ref() {
+                    Some(status) => (look.label(status.as_str()), status.color(theme)),
+                    None => ("".into(), theme.gray),
                 };
-                Cell::from(text).style(Style::default().fg(color))
+                Cell::from(text::truncate(&name, width).into_owned())
+                    .style(Style::default().fg(color))
             }
-            Field::Priority => {
-                let (text, color) = match issue.priority.as_ref() {
-                    Some(priority) => (priority.as_str(), priority.color(&THEME)),
-                    None => ("", THEME.yellow),
-                };
-                Cell::from(text).style(Style::default().fg(color))
+            Field::Type => match issue.issue_type.as_deref() {
+                Some(name) => Cell::from(look.icons.issue_type(name))
+                    .style(Style::default().fg(icons::issue_type_color(name, theme))),
+                None => Cell::from(""),
+            },
+            Field::Priority => match issue.priority.as_ref() {
+                Some(priority) => Cell::from(look.icons.priority(priority))
+                    .style(Style::default().fg(priority.color(theme))),
+                None => Cell::from(""),
+            },
+            Field::Sla => {
+                let now = Local::now().fixed_offset();
+                match issue.most_urgent_sla(now) {
+                    Some(sla) => {
+                        Cell::from(text::truncate(&sla.countdown(now), width).into_owned())
+                            .style(Style::default().fg(sla.color(now, theme)))
+                    }
+                    None => Cell::from(""),
+                }
             }
+            Field::Instance => Cell::from(text::truncate(issue.instance().unwrap_or(""), width))
+                .style(Style::default().fg(theme.cyan)),
+            Field::Labels => Cell::from(Line::from(label_chips(&issue.labels, theme, width))),
+            Field::Sprint => {
+                Cell::from(text::truncate(issue.active_sprint.as_deref().unwrap_or(""), width))
+                    .style(Style::default().fg(theme.blue))
+            }
+            Field::Epic => match issue.epic() {
+                Some(epic) => Cell::from(look.icons.epic_marker())
+                    .style(Style::default().fg(theme.name_color(&epic.key))),
+                None => Cell::from(""),
+            },
+            Field::Flag if issue.is_flagged() => Cell::from(look.icons.flag())
+                .style(Style::default().fg(theme.red).add_modifier(Modifier::BOLD)),
+            Field::Flag => Cell::from(""),
+            // Scripts run on all issues at once, so render_issue_list fills these in
+            Field::Computed { .. } => Cell::from(""),
         }
     }
 }
 
-pub fn render_issue_list(f: &mut Frame, app: &mut App, area: Rect) {
-    let available_width = area.width;
-    let mut used_width = 0u16;
-    let mut shown_fields: Vec<Field> = vec![];
-
-    // Always show the first field (by priority)
-    let first = Field::PRIORITY[0];
-    let min_first = match first.width() {
-        FieldWidth::Flexible { min, .. } => min,
-        FieldWidth::Fixed(w) => w,
+/// Column layout of the issue table, kept between frames and only recomputed when the
+/// available width changes.
+#[derive(Debug, Default)]
+pub struct IssueListCache {
+    width: Option<u16>,
+    height: u16,
+    /// Widths of the computed columns the layout was made for.
+    computed: Vec<u16>,
+    /// The fields only some issues have that the layout was made with room for.
+    optional: Vec<Field>,
+    density: Density,
+    fields: Vec<Field>,
+    constraints: Vec<Constraint>,
+    /// Resolved width of each column, for truncating cell contents.
+    widths: Vec<u16>,
+    /// Where the rows were last drawn, while the list is on screen.
+    rows: Option<Rect>,
+}
+
+impl IssueListCache {
+    /// Number of issues that fit in the table as of the last render.
+    pub fn visible_rows(&self) -> usize {
+        self.height as usize
+    }
+
+    /// Which of the rows on screen, counting from the first, is at `column` and `row` of the
+    /// terminal.
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let rows = self.rows?;
+        let inside = (rows.left()..rows.right()).contains(&column)
+            && (rows.top()..rows.bottom()).contains(&row);
+        let n = ((row.checked_sub(rows.y)?) / self.density.row_height()) as usize;
+        (inside && n < self.visible_rows()).then_some(n)
+    }
+
+    /// Forgets where the rows were, when the list isn't drawn.
+    pub fn hide(&mut self) {
+        self.rows = None;
+    }
+
+    fn update(&mut self, area: Rect, computed: &[u16], optional: &[Field], density: Density) {
+        self.height = area.height / density.row_height();
+        if self.width == Some(area.width)
+            && self.computed == computed
+            && self.optional == optional
+            && self.density == density
+        {
+            return;
+        }
+        self.width = Some(area.width);
+        self.computed = computed.to_vec();
+        self.optional = optional.to_vec();
+        self.density = density;
+
+        let available_width = area.width;
+        let computed_fields: Vec<Field> = computed
+            .iter()
+            .enumerate()
+            .map(|(column, &width)| Field::Computed { column, width })
+            .collect();
+
+        // Always show the first field (by priority)
+        let first = Field::PRIORITY[0];
+        let mut used_width = first.width().min();
+        let mut shown_fields = vec![first];
+
+        // Try to add more fields as space allows (by priority)
+        let fields = Field::PRIORITY
+            .iter()
+            .skip(1)
+            .filter(|field| !field.is_optional() || optional.contains(field))
+            .filter(|field| field.has_column(density));
+        for field in fields.chain(&computed_fields) {
+            let min_w = field.width().min();
+            if used_width + min_w + COLUMN_SPACING <= available_width {
+                used_width += min_w + COLUMN_SPACING;
+                shown_fields.push(*field);
+            }
+        }
+
+        // Compute total flexible factor for shown fields
+        let total_flex: u16 = shown_fields
+            .iter()
+            .map(|field| match field.width() {
+                FieldWidth::Flexible { factor, .. } => factor,
+                FieldWidth::Fixed(_) => 0,
+            })
+            .sum();
+
+        let remaining_width = available_width.saturating_sub(used_width);
+
+        // Compute widths for each shown field (in render order)
+        self.fields.clear();
+        self.constraints.clear();
+        for &field in Field::RENDER_ORDER
+            .iter()
+            .chain(&computed_fields)
+            .filter(|f| shown_fields.contains(f))
+        {
+            let constraint = match field.width() {
+                FieldWidth::Fixed(w) => Constraint::Length(w),
+                FieldWidth::Flexible { factor, min } => {
+                    let flex_width = if total_flex > 0 {
+                        min + (remaining_width * factor / total_flex)
+                    } else {
+                        min
+                    };
+                    Constraint::Min(flex_width)
+                }
+            };
+            self.fields.push(field);
+            self.constraints.push(constraint);
+        }
+
+        // Resolve the constraints the same way the table will
+        self.widths = Layout::horizontal(self.constraints.iter().copied())
+            .flex(Flex::Start)
+            .spacing(COLUMN_SPACING)
+            .split(Rect::new(0, 0, available_width, 1))
+            .iter()
+            .map(|column| column.width)
+            .collect();
+    }
+}
+
+/// Keeps the selected row inside the `height` rows starting at `offset`, with `scrolloff`
+/// rows around it where the list goes on, scrolling the window as little as possible.
+pub(super) fn window_offset(
+    offset: usize,
+    selected: Option<usize>,
+    height: usize,
+    len: usize,
+    scrolloff: usize,
+) -> usize {
+    // Like in vim, a margin too large for the window keeps the selection in the middle
+    let margin = scrolloff.min(height.saturating_sub(1) / 2);
+    let offset = match selected {
+        Some(sel) if sel < offset + margin => sel.saturating_sub(margin),
+        Some(sel) if height > 0 && sel + margin >= offset + height => (sel + margin + 1)
+            .min(len.max(sel + 1))
+            .saturating_sub(height),
+        _ => offset,
     };
-    used_width += min_first;
-    shown_fields.push(first);
+    offset.min(len.saturating_sub(1))
+}
 
-    // Try to add more fields as space allows (by priority)
-    for field in Field::PRIORITY.iter().skip(1) {
-        let min_w = match field.width() {
-            FieldWidth::Flexible { min, .. } => min,
-            FieldWidth::Fixed(w) => w,
+/// The offset of a window of `height` rows that has the `selected` row at `place`.
+pub fn placed_offset(selected: usize, height: usize, place: RowPlace) -> usize {
+    match place {
+        RowPlace::Top => selected,
+        RowPlace::Middle => selected.saturating_sub(height.saturating_sub(1) / 2),
+        RowPlace::Bottom => (selected + 1).saturating_sub(height),
+    }
+}
+
+/// How a field from the issue's JSON is shown: objects by their value or name, like the
+/// options of select fields or users, and arrays as lists.
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(json_text).collect::<Vec<_>>().join(", "),
+        Value::Object(object) => ["value", "name", "displayName", "key"]
+            .iter()
+            .find_map(|name| object.get(*name))
+            .map_or_else(|| value.to_string(), json_text),
+        _ => value.to_string(),
+    }
+}
+
+/// Drops the first `scroll` characters of `text`, marking the cut with an ellipsis.
+fn scrolled_text(text: &str, scroll: usize) -> String {
+    let rest: String = text.chars().skip(scroll).collect();
+    format!("…{rest}")
+}
+
+/// Labels as bracketed chips colored by their name, like `[backend] [oncall]`: as many as fit
+/// in `width`, followed by how many more there are.
+fn label_chips(labels: &[String], theme: &Theme, width: usize) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut left = width;
+    for (i, label) in labels.iter().enumerate() {
+        let gap = usize::from(i > 0);
+        let rest = labels.len() - i - 1;
+        // Leaves room to tell how many more there are
+        let reserved = if rest > 0 {
+            format!(" +{rest}").len()
+        } else {
+            0
         };
-        if used_width + min_w + 2 <= available_width {
-            used_width += min_w + 2;
-            shown_fields.push(*field);
+        let room = left.saturating_sub(gap + reserved);
+        let shown = match text::width(label) + 2 {
+            needed if needed <= room => Cow::Borrowed(label.as_str()),
+            // The first one is cut short rather than left out
+            _ if i == 0 && room > 2 => text::truncate(label, room - 2),
+            _ => {
+                let more = format!("{}+{}", " ".repeat(gap), labels.len() - i);
+                if text::width(&more) <= left {
+                    spans.push(Span::styled(more, Style::default().fg(theme.gray)));
+                }
+                break;
+            }
+        };
+        if gap > 0 {
+            spans.push(Span::raw(" "));
         }
+        let chip = format!("[{shown}]");
+        left -= gap + text::width(&chip);
+        spans.push(Span::styled(chip, Style::default().fg(theme.name_color(label))));
     }
+    spans
+}
 
-    // Compute total flexible factor for shown fields
-    let total_flex: u16 = shown_fields
-        .iter()
-        .map(|field| match field.width() {
-            FieldWidth::Flexible { factor, .. } => factor,
-            FieldWidth::Fixed(_) => 0,
-        })
-        .sum();
-
-    // Compute widths for each shown field (in render order)
-    let mut constraints: Vec<ratatui::layout::Constraint> = vec![];
-    let mut fixed_total: u16 = shown_fields
-        .iter()
-        .map(|field| match field.width() {
-            FieldWidth::Fixed(w) => w,
-            FieldWidth::Flexible { min, .. } => min,
-        })
-        .sum();
-
-    // Add 2 spaces between columns for each column except the last
-    let spacing_total = (shown_fields.len().saturating_sub(1) as u16) * 2;
-    fixed_total += spacing_total;
+/// The dim line under the summary of comfortable rows: the key, yellow while changes to the
+/// issue wait in the outbox, the status and the labels, cut off at `width`.
+fn details_line<'a>(issue: &Issue, look: &Appearance, pending: bool, width: usize) -> Line<'a> {
+    let theme = &look.theme;
+    let mut parts = vec![(
+        Cow::Borrowed(issue.id.as_str()),
+        if pending {
+            theme.yellow
+        } else {
+            theme.dark_gray
+        },
+    )];
+    if let Some(status) = &issue.status {
+        parts.push((look.label(status.as_str()), status.color(theme)));
+    }
+    let mut spans = Vec::new();
+    let mut left = width;
+    for (part, color) in parts {
+        if !spans.is_empty() {
+            if left <= 2 {
+                break;
+            }
+            spans.push(Span::raw("  "));
+            left -= 2;
+        }
+        let part = text::truncate(&part, left).into_owned();
+        left -= text::width(&part).min(left);
+        spans.push(Span::styled(part, Style::default().fg(color)));
+    }
+    if !issue.labels.is_empty() && left > 2 {
+        spans.push(Span::raw("  "));
+        spans.extend(label_chips(&issue.labels, theme, left - 2));
+    }
+    Line::from(spans).style(Style::default().add_modifier(Modifier::DIM))
+}
 
-    let remaining_width = available_width.saturating_sub(fixed_total);
+/// `text` with what `/` finds in it marked.
+fn highlight<'a>(text: String, query: &str, theme: &Theme) -> Line<'a> {
+    let ranges = find::match_ranges(&text, query);
+    if ranges.is_empty() {
+        return Line::raw(text);
+    }
+    let style = Style::default().fg(theme.black).bg(theme.yellow);
+    let mut spans = Vec::new();
+    let mut end = 0;
+    for range in ranges {
+        spans.push(Span::raw(text[end..range.start].to_string()));
+        spans.push(Span::styled(text[range.clone()].to_string(), style));
+        end = range.end;
+    }
+    spans.push(Span::raw(text[end..].to_string()));
+    Line::from(spans)
+}
 
-    for field in Field::RENDER_ORDER
+/// Renders the issues as a table, showing as many columns as fit in `area`.
+pub fn render_issue_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let summary_scroll = app.selected_summary_scroll();
+    let query = find::highlighted(app).to_string();
+    let look = &app.appearance;
+    let marker = look.selection_marker();
+    let cache = &mut app.issue_list_cache;
+    let computed: Vec<u16> = app.columns.iter().map(|column| column.width).collect();
+    // Only service requests have SLAs, only issues searched on several instances have an
+    // instance, and only projects with sprints and epics have those
+    let mut optional = Vec::new();
+    if app.labels_visible
+        && app
+            .visible
+            .iter()
+            .any(|&index| !app.issues[index].labels.is_empty())
+    {
+        optional.push(Field::Labels);
+    }
+    if app.visible.iter().any(|&index| app.issues[index].has_sla()) {
+        optional.push(Field::Sla);
+    }
+    if app
+        .visible
         .iter()
-        .filter(|f| shown_fields.contains(f))
+        .any(|&index| app.issues[index].instance().is_some())
     {
-        match field.width() {
-            FieldWidth::Fixed(w) => constraints.push(ratatui::layout::Constraint::Length(w)),
-            FieldWidth::Flexible { factor, min } => {
-                let flex_width = if total_flex > 0 {
-                    min + (remaining_width * factor / total_flex)
-                } else {
-                    min
-                };
-                constraints.push(ratatui::layout::Constraint::Min(flex_width));
-            }
-        }
+        optional.push(Field::Instance);
+    }
+    if app
+        .visible
+        .iter()
+        .any(|&index| app.issues[index].active_sprint.is_some())
+    {
+        optional.push(Field::Sprint);
+    }
+    if app
+        .visible
+        .iter()
+        .any(|&index| app.issues[index].epic().is_some())
+    {
+        optional.push(Field::Epic);
     }
+    if app
+        .visible
+        .iter()
+        .any(|&index| app.issues[index].is_flagged())
+    {
+        optional.push(Field::Flag);
+    }
+    // Headers are only needed to tell the configured columns apart
+    let header_height = u16::from(!computed.is_empty());
+    // The table reserves room for the selection marker before laying out the columns
+    cache.update(
+        Rect {
+            width: area.width.saturating_sub(marker.len() as u16),
+            height: area.height.saturating_sub(header_height),
+            ..area
+        },
+        &computed,
+        &optional,
+        look.density,
+    );
+    cache.rows = Some(Rect {
+        y: area.y + header_height,
+        height: area.height.saturating_sub(header_height),
+        ..area
+    });
+
+    let height = cache.visible_rows();
+    let selected = app.list_state.selected();
+    let offset =
+        window_offset(app.list_state.offset(), selected, height, app.visible.len(), look.scrolloff);
+    *app.list_state.offset_mut() = offset;
 
-    // Build table rows
-    let rows: Vec<Row> = app
-        .issues
+    // Only build rows for the issues that are actually on screen
+    let end = (offset + height).min(app.visible.len());
+    let rows = app.visible[offset.min(end)..end]
         .iter()
-        .map(|issue| {
-            let cells = Field::RENDER_ORDER
-                .iter()
-                .filter(|f| shown_fields.contains(f))
-                .map(|&field| field.cell(issue))
-                .collect::<Vec<_>>();
-            Row::new(cells)
-        })
-        .collect();
-
-    let highlight_style = if app.input_mode == crate::ui::input::InputMode::Insert {
-        THEME.list_highlight_inactive
+        .enumerate()
+        .map(|(i, &index)| {
+            let issue = &app.issues[index];
+            let scroll = if selected == Some(offset + i) {
+                summary_scroll
+            } else {
+                0
+            };
+            Row::new(
+                cache
+                    .fields
+                    .iter()
+                    .zip(&cache.widths)
+                    .map(|(&field, &width)| match field {
+                        Field::Id => {
+                            // Changes waiting in the outbox
+                            let color = match app.outbox.is_pending(&issue.id) {
+                                true => look.theme.yellow,
+                                false => look.theme.dark_gray,
+                            };
+                            let key = text::truncate(&issue.id, width as usize).into_owned();
+                            Cell::from(highlight(key, &query, &look.theme))
+                                .style(Style::default().fg(color))
+                        }
+                        Field::Summary => {
+                            let summary = match scroll {
+                                0 => issue.summary.clone(),
+                                _ => scrolled_text(&issue.summary, scroll),
+                            };
+                            let summary = text::truncate(&summary, width as usize).into_owned();
+                            let summary = highlight(summary, &query, &look.theme);
+                            match look.density {
+                                Density::Comfortable => {
+                                    let pending = app.outbox.is_pending(&issue.id);
+                                    Cell::from(Text::from(vec![
+                                        summary,
+                                        details_line(issue, look, pending, width as usize),
+                                    ]))
+                                }
+                                Density::Compact => Cell::from(summary),
+                            }
+                        }
+                        Field::Computed { column, .. } => {
+                            let value: Cow<str> = match &app.columns[column].field {
+                                Some(path) => issue
+                                    .raw_field(path)
+                                    .map(json_text)
+                                    .unwrap_or_default()
+                                    .into(),
+                                None => app.computed.column(index, column).into(),
+                            };
+                            Cell::from(text::truncate(&value, width as usize).into_owned())
+                        }
+                        _ => field.cell(issue, look, width),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .height(look.density.row_height())
+        });
+
+    let highlight_style = if app.input_mode != crate::ui::input::InputMode::Normal {
+        look.theme.list_highlight_inactive
     } else {
-        THEME.list_highlight
+        look.theme.list_highlight
     };
 
     let mut table_state = TableState::default();
-    table_state.select(app.list_state.selected());
+    table_state.select(selected.map(|sel| sel.saturating_sub(offset)));
 
-    let table = Table::new(rows, constraints)
+    let mut table = Table::new(rows, cache.constraints.iter().copied())
+        .column_spacing(COLUMN_SPACING)
         .row_highlight_style(highlight_style)
+        .highlight_symbol(marker)
         .highlight_spacing(HighlightSpacing::Always);
+    if header_height > 0 {
+        let headers = cache
+            .fields
+            .iter()
+            .zip(&cache.widths)
+            .map(|(field, &width)| {
+                let name = match field {
+                    Field::Id => "Key",
+                    Field::Summary => "Summary",
+                    Field::Status => "Status",
+                    Field::Sla => "SLA",
+                    Field::Instance => "Instance",
+                    Field::Labels => "Labels",
+                    Field::Sprint => "Sprint",
+                    Field::Type | Field::Priority | Field::Epic | Field::Flag => "",
+                    Field::Computed { column, .. } => app.columns[*column].name.as_str(),
+                };
+                Cell::from(text::truncate(name, width as usize))
+            });
+        table = table.header(
+            Row::new(headers).style(
+                Style::default()
+                    .fg(look.theme.gray)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    }
 
     f.render_stateful_widget(table, area, &mut table_state);
+
+    // Say why the list is empty, since the footer may not have room for it
+    if app.visible.is_empty() && app.fetch_progress.is_none() {
+        let text = match &app.error {
+            Some(e) => Line::styled(e.as_str(), Style::default().fg(look.theme.red)),
+            None => Line::styled("No issues", Style::default().fg(look.theme.gray)),
+        };
+        if let Some(rows) = cache.rows {
+            f.render_widget(Paragraph::new(text).wrap(Wrap { trim: true }), rows);
+        }
+    }
+
+    // Over the start of the rows, where the eyes look for the key
+    if let Some(labels) = &app.jump_labels {
+        let style = Style::default()
+            .fg(look.theme.black)
+            .bg(look.theme.yellow)
+            .add_modifier(Modifier::BOLD);
+        let row_height = look.density.row_height();
+        for (i, row) in (offset..end).enumerate() {
+            let y = area.y + header_height + i as u16 * row_height;
+            if let Some(label) = labels.label(row).filter(|_| y < area.bottom()) {
+                f.buffer_mut().set_string(area.x, y, label, style);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_follows_selection() {
+        // Selection below the window scrolls down just enough
+        assert_eq!(window_offset(0, Some(12), 10, 50, 0), 3);
+        // Selection above the window scrolls up to it
+        assert_eq!(window_offset(20, Some(5), 10, 50, 0), 5);
+        // Selection inside the window keeps the offset
+        assert_eq!(window_offset(4, Some(8), 10, 50, 0), 4);
+        // Offset never points past the last issue
+        assert_eq!(window_offset(60, None, 10, 50, 0), 49);
+        assert_eq!(window_offset(3, None, 10, 0, 0), 0);
+    }
+
+    #[test]
+    fn window_keeps_scrolloff_around_selection() {
+        // Moving down to within 3 rows of the bottom scrolls
+        assert_eq!(window_offset(0, Some(6), 10, 50, 3), 0);
+        assert_eq!(window_offset(0, Some(7), 10, 50, 3), 1);
+        assert_eq!(window_offset(10, Some(12), 10, 50, 3), 9);
+        // Except at the ends of the list
+        assert_eq!(window_offset(0, Some(1), 10, 50, 3), 0);
+        assert_eq!(window_offset(40, Some(48), 10, 50, 3), 40);
+        // A huge margin keeps the selection in the middle
+        assert_eq!(window_offset(0, Some(20), 10, 50, 99), 15);
+    }
+
+    #[test]
+    fn places_the_selection_in_the_window() {
+        assert_eq!(placed_offset(30, 10, RowPlace::Top), 30);
+        assert_eq!(placed_offset(30, 10, RowPlace::Middle), 26);
+        assert_eq!(placed_offset(30, 10, RowPlace::Bottom), 21);
+        // Near the start there's nothing to scroll up to
+        assert_eq!(placed_offset(2, 10, RowPlace::Middle), 0);
+    }
+
+    #[test]
+    fn json_fields_as_text() {
+        let value = serde_json::json!([{ "value": "Platform", "id": "10001" }, { "x": 1 }]);
+        assert_eq!(json_text(&value), "Platform, {\"x\":1}");
+        assert_eq!(json_text(&serde_json::json!(2.5)), "2.5");
+        assert_eq!(json_text(&Value::Null), "");
+    }
+
+    #[test]
+    fn scrolled_text_marks_the_cut() {
+        assert_eq!(scrolled_text("Renew the TLS certificate", 10), "…TLS certificate");
+        assert_eq!(scrolled_text("Größe", 2), "…öße");
+    }
+
+    #[test]
+    fn comfortable_rows_move_key_and_status_under_the_summary() {
+        let mut cache = IssueListCache::default();
+        cache.update(Rect::new(0, 0, 80, 20), &[], &[], Density::Comfortable);
+        assert_eq!(cache.visible_rows(), 10);
+        assert!(!cache.fields.contains(&Field::Id) && !cache.fields.contains(&Field::Status));
+        cache.update(Rect::new(0, 0, 80, 20), &[], &[], Density::Compact);
+        assert_eq!(cache.visible_rows(), 20);
+        assert!(cache.fields.contains(&Field::Id));
+        cache.rows = Some(Rect::new(2, 1, 78, 19));
+        assert_eq!(cache.row_at(10, 4), Some(3));
+        assert_eq!(cache.row_at(1, 4), None);
+        cache.hide();
+        assert_eq!(cache.row_at(10, 4), None);
+
+        let issue = Issue {
+            id: "OPS-1".to_string(),
+            labels: vec!["backend".to_string(), "oncall".to_string()],
+            ..Issue::new("Failover", "")
+        };
+        let look = Appearance::default();
+        let line = |width| details_line(&issue, &look, false, width).to_string();
+        assert_eq!(line(40), "OPS-1  [backend] [oncall]");
+        assert_eq!(line(15), "OPS-1  [ba…] +1");
+        assert_eq!(line(6), "OPS-1");
+    }
+
+    #[test]
+    fn label_chips_fit_the_width() {
+        let labels = ["backend", "oncall", "q3"].map(str::to_string);
+        let theme = Theme::new();
+        let chips = |width| {
+            label_chips(&labels, &theme, width)
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+        assert_eq!(chips(30), "[backend] [oncall] [q3]");
+        assert_eq!(chips(22), "[backend] [oncall] +1");
+        assert_eq!(chips(12), "[backend] +2");
+        assert_eq!(chips(8), "[ba…] +2");
+        assert_eq!(chips(3), "+3");
+    }
 }
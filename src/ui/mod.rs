@@ -1,173 +1,641 @@
+//! Rendering of the terminal UI.
+
+pub mod activity;
+pub mod components;
+pub mod context_menu;
+pub mod cycle_time;
+pub mod diff;
+pub mod failures;
+pub mod filter_bar;
+pub mod icons;
 pub mod input;
+pub mod inspector;
 pub mod issue;
 pub mod issue_list;
+pub mod picker;
+pub mod popup;
+pub mod releases;
+pub mod selection;
+pub mod sidebar;
+pub mod text;
 pub mod theme;
+pub mod time;
+pub mod timeline;
+pub mod workload;
+pub mod wrap;
 
-use crate::app::App;
-use crate::ui::{
-    input::{InputMode, TextInputWidget},
-    issue_list::render_issue_list,
-    theme::THEME,
-};
 use itertools::Itertools;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Margin, Rect},
-    style::Style,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
+use crate::{
+    app::{App, Loadable, View, operations::Operation},
+    ui::{
+        activity::render_log,
+        components::render_components,
+        context_menu::render_context_menu,
+        cycle_time::render_cycle_time,
+        failures::render_failures,
+        filter_bar::render_filter_bar,
+        input::{InputMode, TextInputState, TextInputWidget},
+        inspector::render_inspector,
+        issue_list::render_issue_list,
+        picker::render_picker,
+        popup::render_popup,
+        releases::render_releases,
+        selection::render_selection,
+        sidebar::render_sidebar,
+        timeline::render_timeline,
+        workload::render_workload,
+    },
+};
+
+/// Frames of the spinner shown while work is in progress.
+const SPINNER: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Below this width the sidebar no longer shares the screen with the issue list; toggling it
+/// shows the details full-screen instead.
+const NARROW_WIDTH: u16 = 80;
+
+/// Below this height the input line is only shown while typing and the footer is hidden.
+const SHORT_HEIGHT: u16 = 12;
+
+/// Rows of the log pane, with its border.
+const LOG_HEIGHT: u16 = 8;
+
+/// Where each part of the UI is drawn. Parts that don't fit are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AppLayout {
+    filter_bar: Option<Rect>,
+    jql_bar: Option<Rect>,
+    list: Option<Rect>,
+    log: Option<Rect>,
+    input: Option<Rect>,
+    footer: Option<Rect>,
+    sidebar: Option<Rect>,
+}
+
+impl AppLayout {
+    /// `sidebar_width` is a percentage of the screen width. `filtering`, `jql_bar` and `log`
+    /// make room for the filter bar, the JQL bar and the log pane, which short terminals leave
+    /// out.
+    fn new(
+        area: Rect,
+        sidebar_visible: bool,
+        sidebar_width: u16,
+        editing: bool,
+        filtering: bool,
+        jql_bar: bool,
+        log: bool,
+    ) -> Self {
+        let narrow = area.width < NARROW_WIDTH;
+        let short = area.height < SHORT_HEIGHT;
+        // While typing the list stays visible, so the new issue has some context
+        let fullscreen_sidebar = sidebar_visible && narrow && !editing;
+
+        // Split horizontally: left (issue list + input), right (sidebar/details)
+        let split_sidebar = sidebar_visible && !narrow;
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(if split_sidebar {
+                    100 - sidebar_width
+                } else {
+                    100
+                }),
+                Constraint::Percentage(if split_sidebar { sidebar_width } else { 0 }),
+            ])
+            .split(area);
+
+        let input_height = match (editing, short) {
+            (false, true) => 0,
+            (false, false) if fullscreen_sidebar => 0,
+            (true, true) => 1,
+            _ => 2,
+        };
+        let footer_height = if short { 0 } else { 1 };
+
+        let filter_bar_height = if filtering && !fullscreen_sidebar && !short {
+            1
+        } else {
+            0
+        };
+        let jql_bar_height = u16::from(jql_bar && !fullscreen_sidebar && !short);
+        let log_height = if log && !fullscreen_sidebar && !short {
+            LOG_HEIGHT
+        } else {
+            0
+        };
+
+        // Left side: split vertically into filter bar, JQL bar, issue list, log, input, and
+        // footer
+        let left_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(filter_bar_height),
+                Constraint::Length(jql_bar_height),
+                Constraint::Min(0),                // issue list
+                Constraint::Length(log_height),    // log
+                Constraint::Length(input_height),  // input
+                Constraint::Length(footer_height), // footer/hints
+            ])
+            .split(main_chunks[0]);
+
+        Self {
+            filter_bar: (filter_bar_height > 0).then_some(left_chunks[0]),
+            jql_bar: (jql_bar_height > 0).then_some(left_chunks[1]),
+            list: (!fullscreen_sidebar).then_some(left_chunks[2]),
+            log: (log_height > 0).then_some(left_chunks[3]),
+            input: (input_height > 0).then_some(left_chunks[4]),
+            footer: (footer_height > 0).then_some(left_chunks[5]),
+            sidebar: if fullscreen_sidebar {
+                Some(left_chunks[2])
+            } else {
+                split_sidebar.then_some(main_chunks[1])
+            },
+        }
+    }
+}
+
 /// Renders the entire UI, including the issue list, input, and (optionally) the sidebar.
 pub fn render_ui(f: &mut Frame, app: &mut App) {
-    // Split horizontally: left (issue list + input), right (sidebar/details)
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(if app.sidebar_visible { 60 } else { 100 }),
-            Constraint::Percentage(if app.sidebar_visible { 40 } else { 0 }),
-        ])
-        .split(f.area());
-
-    // Left side: split vertically into issue list (top), input (middle), and footer (bottom)
-    let left_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(5),    // issue list
-            Constraint::Length(2), // input
-            Constraint::Length(1), // footer/hints
-        ])
-        .split(main_chunks[0]);
-
-    render_issue_list(f, app, left_chunks[0]);
-    render_issue_input(f, app, left_chunks[1]);
-    render_footer(f, app, left_chunks[2]);
-
-    if app.sidebar_visible {
-        render_sidebar(f, app, main_chunks[1]);
+    let layout = AppLayout::new(
+        f.area(),
+        app.sidebar_visible,
+        app.sidebar_width,
+        app.input_mode != InputMode::Normal,
+        // Quick filter counts are meaningless without issues, but chips still need removing
+        !app.issues.is_empty() || !app.filters.is_empty(),
+        app.jql_bar_visible,
+        app.log_visible,
+    );
+    // The query is edited where it's shown, if it is
+    let jql_in_bar = app.input_mode == InputMode::Jql && layout.jql_bar.is_some();
+
+    if let Some(area) = layout.filter_bar {
+        render_filter_bar(f, app, area);
+    }
+    if let Some(area) = layout.jql_bar {
+        if jql_in_bar {
+            render_issue_input(f, app, area);
+        } else {
+            render_jql_bar(f, app, area);
+        }
+    }
+    // Clicks land on the list only while it's drawn
+    app.issue_list_cache.hide();
+    if let Some(area) = layout.list {
+        match app.view {
+            View::List => render_issue_list(f, app, area),
+            View::Timeline => render_timeline(f, app, area),
+            View::Workload => render_workload(f, app, area),
+            View::Releases => render_releases(f, app, area),
+            View::Components => render_components(f, app, area),
+            View::Failures => render_failures(f, app, area),
+            View::CycleTime => render_cycle_time(f, app, area),
+        }
+    }
+    if let Some(area) = layout.log {
+        render_log(f, app, area);
+    }
+    if let Some(area) = layout.input {
+        if jql_in_bar {
+            // Left as it is while not typing into it
+            let theme = &app.appearance.theme;
+            let widget =
+                TextInputWidget::new("", "New issue (i)", theme.input, theme.input_placeholder);
+            f.render_stateful_widget(
+                widget,
+                area.inner(Margin::new(2, 0)),
+                &mut TextInputState::default(),
+            );
+        } else {
+            render_issue_input(f, app, area);
+        }
+    }
+    if let Some(area) = layout.footer {
+        render_footer(f, app, area);
+    }
+    // Only a sidebar next to the list has an edge to drag
+    app.sidebar_area = layout.sidebar.filter(|area| area.x > 0);
+    if let Some(area) = layout.sidebar {
+        render_sidebar(f, app, area);
+    }
+    if let Some(open) = &app.picker {
+        render_picker(f, &open.picker, f.area(), &app.appearance);
+    }
+    if let Some(popup) = &app.popup {
+        render_popup(f, popup, f.area(), &app.appearance);
+    }
+    if let Some(inspector) = &mut app.inspector {
+        render_inspector(f, inspector, f.area(), &app.appearance);
+    }
+    if let Some(selection) = &mut app.selection {
+        render_selection(f, selection, f.area(), &app.appearance);
+    }
+    if let Some(menu) = &mut app.context_menu {
+        render_context_menu(f, menu, f.area(), &app.appearance);
+    }
+    if app.flash > 0 {
+        let area = f.area();
+        f.buffer_mut()
+            .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+    }
+}
+
+/// Renders the query the listed issues are the results of, edited with `:`, and how many
+/// of its results are loaded.
+fn render_jql_bar(f: &mut Frame, app: &App, area: Rect) {
+    let area = area.inner(Margin::new(2, 0));
+    let gray = Style::default().fg(app.appearance.theme.gray);
+    let count = result_count(app);
+    let [query_area, count_area] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(count.chars().count() as u16)])
+            .spacing(2)
+            .areas(area);
+    let line = Line::from(vec![Span::styled("JQL ", gray), Span::raw(app.jql.as_str())]);
+    f.render_widget(Paragraph::new(line), query_area);
+    f.render_widget(Paragraph::new(Span::styled(count, gray)), count_area);
+}
+
+/// "showing 100 of 437 (page 1/5)", telling whether the list holds all the results.
+fn result_count(app: &App) -> String {
+    let (page, pages) = app.result_page();
+    match app.result_total {
+        Some(total) => format!("showing {} of {total} (page {page}/{pages})", app.issues.len()),
+        None => format!("showing {} (page {page}/{pages})", app.issues.len()),
     }
 }
 
 /// Renders the new issue input widget.
 fn render_issue_input(f: &mut Frame, app: &mut App, area: Rect) {
-    let area = area.inner(Margin::new(2, 0));
+    let mut area = area.inner(Margin::new(2, 0));
 
-    let is_editing = app.input_mode == InputMode::Insert;
-    let widget =
-        TextInputWidget::new(&app.input, "New issue (i)", THEME.input, THEME.input_placeholder);
+    // The epic the new issue goes in, in its color
+    if let (InputMode::Insert, Some(epic)) = (app.input_mode, &app.new_issue_parent) {
+        let label = format!("{} ", epic.key);
+        let width = (label.chars().count() as u16).min(area.width);
+        let style = Style::default().fg(app.appearance.theme.name_color(&epic.key));
+        f.render_widget(Span::styled(label, style), area);
+        area.x += width;
+        area.width -= width;
+    }
+    let prompt = match app.input_mode {
+        InputMode::Find => "/",
+        InputMode::Filter if app.filter_regex => "regex ",
+        InputMode::Filter => "text ",
+        _ => "",
+    };
+    if !prompt.is_empty() {
+        let width = (prompt.len() as u16).min(area.width);
+        let style = Style::default().fg(app.appearance.theme.gray);
+        f.render_widget(Span::styled(prompt, style), area);
+        area.x += width;
+        area.width -= width;
+    }
+
+    let is_editing = app.input_mode != InputMode::Normal;
+    let placeholder = match app.input_mode {
+        InputMode::Jql => "JQL query",
+        InputMode::Find => "Key or summary to find",
+        InputMode::Filter if app.filter_regex => "Regular expression matching the key or summary",
+        InputMode::Filter => {
+            "Text in the key or summary, or terms like status:done assignee:me points:>3"
+        }
+        InputMode::SignIn => "Jira rejected the credentials; new API token",
+        _ => "New issue (i)",
+    };
+    // Tokens are kept off the screen
+    let masked;
+    let value = match app.input_mode {
+        InputMode::SignIn => {
+            masked = "•".repeat(app.input.chars().count());
+            &masked
+        }
+        _ => &app.input,
+    };
+    let theme = &app.appearance.theme;
+    let widget = TextInputWidget::new(value, placeholder, theme.input, theme.input_placeholder);
 
     f.render_stateful_widget(widget, area, &mut app.input_state);
 
     // Show cursor in input mode using stateful cursor position
     if is_editing {
-        let x = area.x + app.input_state.cursor.min(area.width as usize - 1) as u16;
+        let x = area.x
+            + app
+                .input_state
+                .cursor
+                .min(area.width.saturating_sub(1) as usize) as u16;
         let y = area.y;
         f.set_cursor_position((x, y));
     }
 }
 
-/// Renders the sidebar/details widget, if visible.
-fn render_sidebar(f: &mut Frame, app: &App, area: Rect) {
-    let selected = app.list_state.selected().unwrap_or(0);
-    let details = if let Some(issue) = app.issues.get(selected) {
-        let mut lines = vec![
-            Line::from(vec![Span::styled(&issue.summary, THEME.details_title)]),
-            Line::from(vec![
-                Span::styled("ID: ", Style::default().add_modifier(ratatui::style::Modifier::BOLD)),
-                Span::raw(&issue.id),
-            ]),
-        ];
-
-        if let Some(ref t) = issue.issue_type {
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "Type: ",
-                    Style::default().add_modifier(ratatui::style::Modifier::BOLD),
-                ),
-                Span::raw(t),
-            ]));
-        }
-        if let Some(ref s) = issue.status {
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "Status: ",
-                    Style::default().add_modifier(ratatui::style::Modifier::BOLD),
-                ),
-                Span::raw(s.as_str()),
-            ]));
-        }
-        if let Some(ref p) = issue.priority {
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "Priority: ",
-                    Style::default().add_modifier(ratatui::style::Modifier::BOLD),
-                ),
-                Span::raw(p.as_str()),
-            ]));
-        }
-        if let Some(points) = issue.story_points {
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "Story Points: ",
-                    Style::default().add_modifier(ratatui::style::Modifier::BOLD),
-                ),
-                Span::raw(points.to_string()),
-            ]));
-        }
-        if let Some(ref epic) = issue.parent_epic {
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "Parent Epic: ",
-                    Style::default().add_modifier(ratatui::style::Modifier::BOLD),
-                ),
-                Span::raw(epic),
-            ]));
-        }
+/// The keys of the input mode and view of `app`, with what they do, for the help, or only
+/// those for the footer.
+pub fn key_hints(app: &App, all: bool) -> Vec<(&'static str, &'static str)> {
+    match app.input_mode {
+        InputMode::Normal => input::normal_mode_hints(app.view, all),
+        mode => input::editing_mode_hints(mode),
+    }
+}
 
-        lines.push(Line::from(""));
-        lines.push(Line::from(issue.description.clone()));
-        lines
-    } else {
-        vec![Line::from("No issue selected")]
-    };
-    let details =
-        Paragraph::new(details).block(Block::default().borders(Borders::LEFT).title("Details"));
-    f.render_widget(details, area);
+/// All the keys of the input mode and view of `app`, a line each, for the help.
+pub fn help_text(app: &App) -> String {
+    let hints = key_hints(app, true);
+    let width = hints
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    hints
+        .iter()
+        .map(|(key, label)| format!("{key:<width$}  {label}"))
+        .join("\n")
 }
 
-/// Renders the footer with key hints at the bottom of the UI.
+/// Renders the footer at the bottom of the UI: the mode, what the app is doing or has to say,
+/// and key hints.
 fn render_footer(f: &mut Frame, app: &App, area: Rect) {
-    let (color, mode, key_hints) = match app.input_mode {
-        InputMode::Normal => (
-            THEME.footer_normal,
-            "NORMAL",
-            vec![("i", "new issue"), ("s", "sidebar"), ("q", "quit")],
-        ),
-        InputMode::Insert => (
-            THEME.footer_insert,
-            "INSERT",
-            vec![("Enter", "submit"), ("Esc", "cancel"), ("^U", "clear")],
-        ),
+    let look = &app.appearance;
+    let (color, mode) = match (app.input_mode, app.view) {
+        (InputMode::Normal, View::List) => (look.theme.footer_normal, "NORMAL"),
+        (InputMode::Normal, View::Timeline) => (look.theme.footer_normal, "TIMELINE"),
+        (InputMode::Normal, View::Workload) => (look.theme.footer_normal, "WORKLOAD"),
+        (InputMode::Normal, View::Releases) => (look.theme.footer_normal, "RELEASES"),
+        (InputMode::Normal, View::Components) => (look.theme.footer_normal, "COMPONENTS"),
+        (InputMode::Normal, View::CycleTime) => (look.theme.footer_normal, "CYCLE TIME"),
+        (InputMode::Normal, View::Failures) => (look.theme.footer_normal, "FAILURES"),
+        (InputMode::Insert, _) => (look.theme.footer_insert, "INSERT"),
+        (InputMode::Jql, _) => (look.theme.footer_insert, "JQL"),
+        (InputMode::Find, _) => (look.theme.footer_insert, "FIND"),
+        (InputMode::Filter, _) => (look.theme.footer_insert, "FILTER"),
+        (InputMode::SignIn, _) => (look.theme.footer_insert, "SIGN IN"),
     };
+    // Only the essential keys fit on the line; `?` shows them all
+    let key_hints = key_hints(app, false);
 
     let inverted = Style { fg: color.bg, bg: color.fg, ..color };
 
-    let mode_span = Span::styled(format!(" {mode} "), color);
+    // What's going on comes first, so long hints can't push it out of view
+    let mut spans = vec![Span::styled(format!(" {mode} "), color)];
+    if app.hide_done {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("{} done hidden", app.hidden_done_count()),
+            Style::default().fg(look.theme.yellow),
+        ));
+    }
 
-    let key_hint_spans = key_hints.iter().map(|(key, label)| {
-        vec![Span::styled(format!(" {key} "), color), Span::styled(format!(" {label} "), inverted)]
-    });
+    if let Some(operation) = app.operations.latest() {
+        let spinner = SPINNER[app.spinner_frame % SPINNER.len()];
+        spans.push(Span::raw("  "));
+        let label = match (operation, app.fetch_progress) {
+            (Operation::Search, Some(progress)) => match progress.total {
+                Some(total) => format!("fetched {}/{total}", progress.fetched),
+                None if progress.fetched == 0 => "searching…".to_string(),
+                None => format!("fetched {}…", progress.fetched),
+            },
+            (operation, _) => format!("{}…", operation.label()),
+        };
+        let status = match app.operations.len() {
+            1 => format!("{spinner} {label}"),
+            count => format!("{spinner} {label} (+{} more)", count - 1),
+        };
+        spans.push(Span::styled(status, Style::default().fg(look.theme.gray)));
+    }
 
-    let spans = Itertools::intersperse(
-        std::iter::once(vec![mode_span]).chain(key_hint_spans),
-        vec![Span::raw("  ")],
-    )
-    .flatten()
-    .collect::<Vec<_>>();
+    if let Some(notice) = &app.notice {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(notice.as_str(), Style::default().fg(look.theme.gray)));
+    }
+
+    if let Some(error) = &app.error {
+        spans.push(Span::raw("  "));
+        let error = if look.no_color {
+            format!("[ERROR] {error}")
+        } else {
+            error.clone()
+        };
+        spans.push(Span::styled(error, Style::default().fg(look.theme.red)));
+    }
+
+    for (key, label) in key_hints {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(format!(" {key} "), color));
+        spans.push(Span::styled(format!(" {label} "), inverted));
+    }
 
     let footer = Line::from(spans);
+    let status = status_line(app);
+    let [hints_area, status_area] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(status.width() as u16)])
+            .areas(area);
 
     let block = Block::default().borders(Borders::NONE);
     let para = Paragraph::new(footer).block(block);
-    f.render_widget(para, area);
+    f.render_widget(para, hints_area);
+    f.render_widget(Paragraph::new(status), status_area);
+}
+
+/// The instance the app talks to, whether it responds, when the results were last refreshed,
+/// and how many requests are still running.
+fn status_line(app: &App) -> Line<'static> {
+    let look = &app.appearance;
+    let connection = &app.connection;
+    let gray = Style::default().fg(look.theme.gray);
+    let mut spans = vec![Span::raw(" ")];
+    if let Some(profile) = &app.profile {
+        spans.push(Span::styled(format!("[{profile}] "), gray));
+    }
+    if !connection.instance.is_empty() {
+        spans.push(Span::styled(format!("{} ", connection.instance), gray));
+    }
+    spans.push(if !connection.online {
+        Span::styled("○ offline", Style::default().fg(look.theme.red))
+    } else if connection.signed_out {
+        Span::styled("● signed out", Style::default().fg(look.theme.red))
+    } else {
+        Span::styled("● online", Style::default().fg(look.theme.green))
+    });
+    if let Some(refreshed) = &connection.last_refresh {
+        let refreshed = look.time.datetime(refreshed);
+        spans.push(Span::styled(format!(" · refreshed {refreshed}"), gray));
+    }
+    match app.operations.len() {
+        0 => {}
+        pending => spans.push(Span::styled(format!(" · {pending} pending"), gray)),
+    }
+    if !app.failures.is_empty() {
+        spans.push(Span::styled(
+            format!(" · {} failed", app.failures.len()),
+            Style::default().fg(look.theme.red),
+        ));
+    }
+    if !app.outbox.is_empty() {
+        spans.push(Span::styled(
+            format!(" · {} to sync", app.outbox.len()),
+            Style::default().fg(look.theme.yellow),
+        ));
+    }
+    for alert in app.alerts.iter() {
+        // A search that failed shows as such, until the next run
+        let badge = match (&alert.found, alert.count()) {
+            (Loadable::Failed(_), _) => Span::styled(format!(" {} ? ", alert.name), gray),
+            (_, Some(count)) if count > 0 => Span::styled(
+                format!(" {} {count} ", alert.name),
+                Style::default()
+                    .fg(look.theme.red)
+                    .add_modifier(Modifier::REVERSED),
+            ),
+            _ => continue,
+        };
+        spans.push(Span::raw(" "));
+        spans.push(badge);
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+    use crate::{
+        app::{Msg, update},
+        error::Error,
+        jira::PageCursor,
+        ui::issue::Issue,
+    };
+
+    /// The bottom row of the screen after drawing `app`.
+    fn footer(app: &mut App, width: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, 20)).unwrap();
+        terminal.draw(|f| render_ui(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..width).map(|x| buffer[(x, 19)].symbol()).collect()
+    }
+
+    #[test]
+    fn footer_shows_errors_before_the_keys() {
+        let mut app = App::new(vec![Issue::new("Failover", "")]);
+        app.error = Some("Search failed: timed out".to_string());
+        let footer = footer(&mut app, 100);
+        assert!(footer.contains("Search failed: timed out"), "{footer}");
+        assert!(footer.starts_with(" NORMAL "));
+
+        // The rest of the keys are in the help
+        app.error = None;
+        assert!(self::footer(&mut app, 120).contains(" ? "));
+        update(&mut app, Msg::Key(KeyEvent::from(KeyCode::Char('?'))));
+        let help = &app.popup.as_ref().unwrap().text;
+        assert!(
+            help.lines()
+                .any(|line| line.starts_with("tt ") && line.ends_with(" status"))
+        );
+    }
+
+    #[test]
+    fn footer_shows_how_far_the_search_got() {
+        let mut app = App::new(vec![Issue::new("Failover", "")]);
+        crate::app::init(&mut app, Some(PageCursor::Offset(1)), Some(437), None);
+        for width in [80, 120] {
+            let footer = footer(&mut app, width);
+            assert!(footer.contains(&format!("{} fetched 1/437", SPINNER[0])), "{footer}");
+        }
+    }
+
+    #[test]
+    fn empty_list_says_why_the_first_search_failed() {
+        let mut app = App::new(Vec::new());
+        crate::app::init_failed(&mut app, Error::Network("dns error".to_string()), None);
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal.draw(|f| render_ui(f, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let screen: String = (0..18)
+            .map(|y| (0..60).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(screen.contains("Search failed"), "{screen}");
+        assert!(screen.contains("(vf)"), "{screen}");
+    }
+
+    #[test]
+    fn wide_terminal_splits_sidebar() {
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 40), true, 40, false, false, false, false);
+        assert_eq!(layout.list, Some(Rect::new(0, 0, 72, 37)));
+        assert_eq!(layout.input, Some(Rect::new(0, 37, 72, 2)));
+        assert_eq!(layout.footer, Some(Rect::new(0, 39, 72, 1)));
+        assert_eq!(layout.sidebar, Some(Rect::new(72, 0, 48, 40)));
+
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 40), true, 25, false, false, false, false);
+        assert_eq!(layout.list, Some(Rect::new(0, 0, 90, 37)));
+        assert_eq!(layout.sidebar, Some(Rect::new(90, 0, 30, 40)));
+    }
+
+    #[test]
+    fn filter_bar_sits_above_the_list() {
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 40), false, 40, false, true, false, false);
+        assert_eq!(layout.filter_bar, Some(Rect::new(0, 0, 120, 1)));
+        assert_eq!(layout.list, Some(Rect::new(0, 1, 120, 36)));
+
+        // Hidden along with the list behind a full-screen sidebar
+        let layout = AppLayout::new(Rect::new(0, 0, 60, 40), true, 40, false, true, false, false);
+        assert_eq!(layout.filter_bar, None);
+    }
+
+    #[test]
+    fn jql_bar_sits_below_the_filter_bar() {
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 40), false, 40, false, true, true, false);
+        assert_eq!(layout.filter_bar, Some(Rect::new(0, 0, 120, 1)));
+        assert_eq!(layout.jql_bar, Some(Rect::new(0, 1, 120, 1)));
+        assert_eq!(layout.list, Some(Rect::new(0, 2, 120, 35)));
+
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 8), false, 40, false, true, true, false);
+        assert_eq!(layout.jql_bar, None);
+    }
+
+    #[test]
+    fn log_sits_between_the_list_and_the_input() {
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 40), false, 40, false, false, false, true);
+        assert_eq!(layout.list, Some(Rect::new(0, 0, 120, 29)));
+        assert_eq!(layout.log, Some(Rect::new(0, 29, 120, 8)));
+        assert_eq!(layout.input, Some(Rect::new(0, 37, 120, 2)));
+
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 8), false, 40, false, false, false, true);
+        assert_eq!(layout.log, None);
+    }
+
+    #[test]
+    fn narrow_terminal_shows_sidebar_fullscreen() {
+        let layout = AppLayout::new(Rect::new(0, 0, 60, 40), true, 40, false, false, false, false);
+        assert_eq!(layout.list, None);
+        assert_eq!(layout.input, None);
+        assert_eq!(layout.sidebar, Some(Rect::new(0, 0, 60, 39)));
+        assert_eq!(layout.footer, Some(Rect::new(0, 39, 60, 1)));
+
+        // Typing a new issue brings the list back
+        let layout = AppLayout::new(Rect::new(0, 0, 60, 40), true, 40, true, false, false, false);
+        assert_eq!(layout.sidebar, None);
+        assert!(layout.list.is_some() && layout.input.is_some());
+    }
+
+    #[test]
+    fn short_terminal_collapses_input_and_footer() {
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 8), false, 40, false, false, false, false);
+        assert_eq!(layout.list, Some(Rect::new(0, 0, 120, 8)));
+        assert_eq!(layout.input, None);
+        assert_eq!(layout.footer, None);
+
+        let layout = AppLayout::new(Rect::new(0, 0, 120, 8), false, 40, true, false, false, false);
+        assert_eq!(layout.list, Some(Rect::new(0, 0, 120, 7)));
+        assert_eq!(layout.input, Some(Rect::new(0, 7, 120, 1)));
+    }
 }
@@ -0,0 +1,206 @@
+//! A popup for choosing one item from a list, narrowed down by typing.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+};
+
+use crate::ui::{text, theme::Appearance};
+
+/// State of an open picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picker {
+    pub title: String,
+    pub items: Vec<String>,
+    /// What has been typed to filter the items.
+    pub query: String,
+    /// Index into the filtered items.
+    pub selected: usize,
+    /// Whether the typed text itself can be picked, for values not in the list.
+    pub allow_custom: bool,
+}
+
+/// What a key press in a picker amounts to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickerAction {
+    None,
+    Cancel,
+    Pick(String),
+}
+
+impl Picker {
+    pub fn new(title: impl Into<String>, items: Vec<String>) -> Self {
+        Self {
+            title: title.into(),
+            items,
+            query: String::new(),
+            selected: 0,
+            allow_custom: false,
+        }
+    }
+
+    /// Lets the typed text be picked when it matches nothing.
+    pub fn allow_custom(mut self) -> Self {
+        self.allow_custom = true;
+        self
+    }
+
+    /// The items matching the query, case-insensitively.
+    pub fn filtered(&self) -> Vec<&str> {
+        let query = self.query.to_lowercase();
+        self.items
+            .iter()
+            .filter(|item| item.to_lowercase().contains(&query))
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn current(&self) -> Option<String> {
+        match self.filtered().get(self.selected) {
+            Some(item) => Some(item.to_string()),
+            None if self.allow_custom && !self.query.trim().is_empty() => {
+                Some(self.query.trim().to_string())
+            }
+            None => None,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PickerAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return PickerAction::Cancel,
+            KeyCode::Char('c') if ctrl => return PickerAction::Cancel,
+            KeyCode::Enter => {
+                return match self.current() {
+                    Some(item) => PickerAction::Pick(item),
+                    None => PickerAction::None,
+                };
+            }
+            KeyCode::Down | KeyCode::Tab => self.move_selection(1),
+            KeyCode::Char('n') if ctrl => self.move_selection(1),
+            KeyCode::Up | KeyCode::BackTab => self.move_selection(-1),
+            KeyCode::Char('p') if ctrl => self.move_selection(-1),
+            KeyCode::Char('u') if ctrl => {
+                self.query.clear();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        PickerAction::None
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.filtered().len();
+        if len > 0 {
+            self.selected = (self.selected as isize + delta).rem_euclid(len as isize) as usize;
+        }
+    }
+}
+
+/// Draws the picker as a popup centered over `area`.
+pub fn render_picker(f: &mut Frame, picker: &Picker, area: Rect, look: &Appearance) {
+    let filtered = picker.filtered();
+    let width = (area.width * 3 / 5).clamp(20.min(area.width), 60.min(area.width));
+    // Items, the query line, and the borders
+    let height = (filtered.len().max(1) as u16 + 3).min(area.height);
+    let [popup] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(picker.title.as_str());
+    let inner = block.inner(popup);
+    f.render_widget(Clear, popup);
+    f.render_widget(block, popup);
+    if inner.height == 0 {
+        return;
+    }
+
+    let [query_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+    let query = Line::from(vec![
+        Span::styled("> ", Style::default().fg(look.theme.gray)),
+        Span::styled(picker.query.as_str(), look.theme.input),
+    ]);
+    f.render_widget(query, query_area);
+    f.set_cursor_position((query_area.x + 2 + text::width(&picker.query) as u16, query_area.y));
+
+    let item_width = list_area.width.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = if filtered.is_empty() {
+        let hint = if picker.allow_custom && !picker.query.trim().is_empty() {
+            "Enter to use what you typed"
+        } else {
+            "No matches"
+        };
+        vec![ListItem::new(Span::styled(hint, Style::default().fg(look.theme.gray)))]
+    } else {
+        filtered
+            .iter()
+            .map(|item| ListItem::new(text::truncate(item, item_width).into_owned()))
+            .collect()
+    };
+    let mut state = ListState::default()
+        .with_selected((!filtered.is_empty()).then_some(picker.selected.min(filtered.len() - 1)));
+    let list = List::new(items)
+        .highlight_style(look.theme.list_highlight)
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, list_area, &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(picker: &mut Picker, text: &str) {
+        for c in text.chars() {
+            picker.handle_key(&key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn filters_and_picks() {
+        let items = ["To Do", "In Progress", "In Review", "Done"];
+        let mut picker = Picker::new("Status", items.map(String::from).to_vec());
+        type_text(&mut picker, "in");
+        assert_eq!(picker.filtered(), ["In Progress", "In Review"]);
+        picker.handle_key(&key(KeyCode::Down));
+        assert_eq!(
+            picker.handle_key(&key(KeyCode::Enter)),
+            PickerAction::Pick("In Review".to_string())
+        );
+
+        type_text(&mut picker, "xyz");
+        assert_eq!(picker.handle_key(&key(KeyCode::Enter)), PickerAction::None);
+        assert_eq!(picker.handle_key(&key(KeyCode::Esc)), PickerAction::Cancel);
+    }
+
+    #[test]
+    fn custom_values() {
+        let mut picker = Picker::new("Label", vec!["backend".to_string()]).allow_custom();
+        type_text(&mut picker, "frontend");
+        assert_eq!(
+            picker.handle_key(&key(KeyCode::Enter)),
+            PickerAction::Pick("frontend".to_string())
+        );
+    }
+}
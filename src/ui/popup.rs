@@ -0,0 +1,111 @@
+//! A popup showing a piece of text, like the output of a command, until dismissed.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::ui::theme::Appearance;
+
+/// State of an open text popup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Popup {
+    pub title: String,
+    pub text: String,
+    /// Lines scrolled out of view at the top.
+    pub scroll: u16,
+    /// What `y` copies, if anything, like the text in another format, with what to call it
+    /// once copied.
+    pub copy: Option<(String, String)>,
+}
+
+impl Popup {
+    pub fn new(title: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            text: text.into(),
+            scroll: 0,
+            copy: None,
+        }
+    }
+
+    /// Lets `y` copy `text`, called `what`.
+    pub fn copying(self, what: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            copy: Some((what.into(), text.into())),
+            ..self
+        }
+    }
+
+    /// Scrolls with the keys that move through lists. Returns `false` once the popup is
+    /// dismissed.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('d') => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Char('u') => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::Char('c') if ctrl => return false,
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return false,
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Draws the popup centered over `area`, as tall as its text needs up to the whole area.
+pub fn render_popup(f: &mut Frame, popup: &Popup, area: Rect, look: &Appearance) {
+    let width = (area.width * 4 / 5).clamp(20.min(area.width), 100.min(area.width));
+    let lines = popup.text.lines().count().max(1) as u16;
+    // The text and the borders, with wrapped lines taking some more
+    let height = (lines + 2).clamp(3.min(area.height), area.height * 4 / 5);
+    let [popup_area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup_area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(popup.title.as_str())
+        .title_bottom(Line::styled(
+            match popup.copy {
+                Some(_) => " y to copy, Esc to close ",
+                None => " Esc to close ",
+            },
+            Style::default().fg(look.theme.gray),
+        ));
+    let text = if popup.text.trim().is_empty() {
+        Line::styled("(no output)", Style::default().fg(look.theme.gray)).into()
+    } else {
+        ratatui::text::Text::raw(popup.text.as_str())
+    };
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((popup.scroll, 0));
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrolls_until_dismissed() {
+        let mut popup = Popup::new("Output", "one\ntwo\nthree");
+        assert!(popup.handle_key(&KeyEvent::from(KeyCode::Char('j'))));
+        assert!(popup.handle_key(&KeyEvent::from(KeyCode::Char('j'))));
+        assert!(popup.handle_key(&KeyEvent::from(KeyCode::Char('k'))));
+        assert_eq!(popup.scroll, 1);
+        assert!(!popup.handle_key(&KeyEvent::from(KeyCode::Esc)));
+    }
+}
@@ -0,0 +1,129 @@
+//! The releases view: the versions of a project with their release dates and progress.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    app::{App, Loadable, releases::versions},
+    jira::Version,
+    ui::{input::InputMode, theme::Appearance},
+};
+
+/// Width of the bar showing how many of a version's issues are done.
+const BAR_WIDTH: usize = 20;
+
+/// The release state of a version as a colored label.
+fn state<'a>(version: &Version, look: &Appearance, today: chrono::NaiveDate) -> Span<'a> {
+    let (text, color) = if version.released {
+        ("Released", look.theme.green)
+    } else if version.release_date.is_some_and(|date| date < today) {
+        ("Overdue", look.theme.red)
+    } else {
+        ("Unreleased", look.theme.gray)
+    };
+    Span::styled(look.label(text).into_owned(), Style::default().fg(color))
+}
+
+/// How many of a version's issues are done, as a bar and a count.
+fn progress<'a>(version: &Version, look: &Appearance) -> Line<'a> {
+    let filled = match version.issues {
+        0 => 0,
+        issues => BAR_WIDTH * version.done / issues,
+    };
+    let (full, empty) = if look.no_color {
+        ('#', '-')
+    } else {
+        ('█', '░')
+    };
+    Line::from(vec![
+        Span::styled(full.to_string().repeat(filled), Style::default().fg(look.theme.green)),
+        Span::styled(
+            empty.to_string().repeat(BAR_WIDTH - filled),
+            Style::default().fg(look.theme.gray),
+        ),
+        Span::raw(format!(" {}/{}", version.done, version.issues)),
+    ])
+}
+
+/// Renders a row per version of the project, newest first.
+pub fn render_releases(f: &mut Frame, app: &App, area: Rect) {
+    let look = &app.appearance;
+    let Some(project) = &app.view_project else {
+        return;
+    };
+    match app.versions.get(project) {
+        Some(Loadable::Loaded(_)) => {}
+        Some(Loadable::Failed(e)) => {
+            let text = Line::styled(
+                format!("Failed to load the releases of {project}: {e}"),
+                Style::default().fg(look.theme.red),
+            );
+            f.render_widget(Paragraph::new(text), area);
+            return;
+        }
+        Some(Loadable::Loading) | None => {
+            f.render_widget(Paragraph::new(format!("Loading releases of {project}…")), area);
+            return;
+        }
+    }
+
+    let versions = versions(app);
+    if versions.is_empty() {
+        f.render_widget(Paragraph::new(format!("{project} has no releases")), area);
+        return;
+    }
+
+    let today = look.time.today();
+    let rows = versions.iter().map(|version| {
+        Row::new(vec![
+            Cell::from(version.name.clone()),
+            Cell::from(
+                version
+                    .release_date
+                    .map(|date| look.time.date(&date))
+                    .unwrap_or_default(),
+            ),
+            Cell::from(state(version, look, today)),
+            Cell::from(progress(version, look)),
+        ])
+    });
+
+    let header = Row::new([
+        format!("{project} version"),
+        "Release date".to_string(),
+        "State".to_string(),
+        "Done".to_string(),
+    ])
+    .style(
+        Style::default()
+            .fg(look.theme.gray)
+            .add_modifier(Modifier::BOLD),
+    );
+    let highlight_style = if app.input_mode != InputMode::Normal {
+        look.theme.list_highlight_inactive
+    } else {
+        look.theme.list_highlight
+    };
+
+    let mut table_state = TableState::default();
+    table_state.select(Some(app.version_cursor.min(versions.len() - 1)));
+
+    let table = Table::new(rows, [
+        Constraint::Min(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(BAR_WIDTH as u16 + 10),
+    ])
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(highlight_style)
+    .highlight_symbol(look.selection_marker())
+    .highlight_spacing(HighlightSpacing::Always);
+
+    f.render_stateful_widget(table, area, &mut table_state);
+}
@@ -0,0 +1,169 @@
+//! A popup showing the text of the sidebar one line per row, to pick lines to copy without
+//! the borders and the list next to it that selecting with the mouse takes along.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::ui::{issue_list::window_offset, theme::Appearance};
+
+/// State of an open selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub title: String,
+    lines: Vec<String>,
+    /// The line under the cursor, one end of the selected lines.
+    cursor: usize,
+    /// The other end, once `v` started selecting more than the line under the cursor.
+    anchor: Option<usize>,
+    /// The first line on screen.
+    offset: usize,
+}
+
+/// What a key typed into the selection asks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionAction {
+    None,
+    Close,
+    /// Copy this text and close.
+    Copy(String),
+}
+
+impl Selection {
+    pub fn new(title: impl Into<String>, lines: Vec<String>) -> Self {
+        Self {
+            title: title.into(),
+            lines,
+            cursor: 0,
+            anchor: None,
+            offset: 0,
+        }
+    }
+
+    /// The first and last of the selected lines.
+    fn range(&self) -> (usize, usize) {
+        let anchor = self.anchor.unwrap_or(self.cursor);
+        (anchor.min(self.cursor), anchor.max(self.cursor))
+    }
+
+    fn move_cursor(&mut self, steps: isize) {
+        let last = self.lines.len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize + steps).clamp(0, last) as usize;
+    }
+
+    /// Moves with the keys that move through lists; `v` starts or stops selecting a range,
+    /// and `y` or Enter copies it.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> SelectionAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl => return SelectionAction::Close,
+            KeyCode::Char('j') | KeyCode::Down => self.move_cursor(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Char('d') => self.move_cursor(10),
+            KeyCode::Char('u') => self.move_cursor(-10),
+            KeyCode::Char('g') => self.cursor = 0,
+            KeyCode::Char('G') => self.move_cursor(isize::MAX / 2),
+            KeyCode::Char('V' | 'v') => {
+                self.anchor = match self.anchor {
+                    Some(_) => None,
+                    None => Some(self.cursor),
+                }
+            }
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let (first, last) = self.range();
+                return match self.lines.get(first..=last) {
+                    Some(lines) => SelectionAction::Copy(lines.join("\n")),
+                    None => SelectionAction::Close,
+                };
+            }
+            KeyCode::Esc if self.anchor.is_some() => self.anchor = None,
+            KeyCode::Esc | KeyCode::Char('q') => return SelectionAction::Close,
+            _ => {}
+        }
+        SelectionAction::None
+    }
+}
+
+/// Draws the selection over most of `area`.
+pub fn render_selection(f: &mut Frame, selection: &mut Selection, area: Rect, look: &Appearance) {
+    let [popup_area] = Layout::horizontal([Constraint::Percentage(90)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup_area] = Layout::vertical([Constraint::Percentage(90)])
+        .flex(Flex::Center)
+        .areas(popup_area);
+
+    let hints = " v select lines  y/Enter copy  Esc close ";
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(selection.title.as_str())
+        .title_bottom(Line::styled(hints, Style::default().fg(look.theme.gray)));
+    let height = block.inner(popup_area).height as usize;
+
+    selection.offset = window_offset(
+        selection.offset,
+        Some(selection.cursor),
+        height,
+        selection.lines.len(),
+        look.scrolloff,
+    );
+    let (first, last) = selection.range();
+    let lines: Vec<Line> = selection
+        .lines
+        .iter()
+        .enumerate()
+        .skip(selection.offset)
+        .take(height)
+        .map(|(i, line)| {
+            let line = Line::raw(line.as_str());
+            if i == selection.cursor {
+                line.style(look.theme.list_highlight)
+            } else if (first..=last).contains(&i) {
+                line.style(look.theme.list_highlight_inactive)
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(selection: &mut Selection, keys: &str) -> SelectionAction {
+        let mut action = SelectionAction::None;
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            action = selection.handle_key(&KeyEvent::from(code));
+        }
+        action
+    }
+
+    #[test]
+    fn copies_the_selected_lines() {
+        let lines = ["Failover", "ID: OPS-1", "", "Steps:", "- drain", "- switch"];
+        let lines = lines.map(str::to_string).to_vec();
+        let mut selection = Selection::new(" OPS-1 ", lines.clone());
+        assert_eq!(press(&mut selection, "jy"), SelectionAction::Copy("ID: OPS-1".to_string()));
+
+        // Upwards from the anchor too
+        let mut selection = Selection::new(" OPS-1 ", lines);
+        assert_eq!(
+            press(&mut selection, "Gvkk\n"),
+            SelectionAction::Copy("Steps:\n- drain\n- switch".to_string())
+        );
+        assert_eq!(press(&mut selection, "q"), SelectionAction::Close);
+    }
+}
@@ -0,0 +1,541 @@
+//! The sidebar showing the selected issue, with one tab per kind of information.
+
+use chrono::{Local, TimeDelta};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{App, Loadable, SidebarTab},
+    jira::{FieldChange, HistoryEntry, User},
+    ui::{
+        diff::{self, DiffLine},
+        issue::{Issue, TimeInStatus, TimeTracking},
+        text,
+        theme::Appearance,
+        time, wrap,
+    },
+};
+
+/// Unchanged lines shown around each change in a multi-line diff.
+const DIFF_CONTEXT: usize = 1;
+
+/// A width no line is cut or wrapped at, for copying the text.
+const UNWRAPPED: usize = u16::MAX as usize;
+
+/// Renders the sidebar/details widget, if visible.
+pub fn render_sidebar(f: &mut Frame, app: &App, area: Rect) {
+    // The left border takes one column
+    let width = area.width.saturating_sub(1) as usize;
+    let details = Paragraph::new(sidebar_lines(app, width))
+        .block(
+            Block::default()
+                .borders(Borders::LEFT)
+                .title(tab_titles(app.sidebar_tab, &app.appearance)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(details, area);
+}
+
+/// The text of the open sidebar tab, one string per line, as wide as its lines are.
+pub fn sidebar_text(app: &App) -> Vec<String> {
+    sidebar_lines(app, UNWRAPPED)
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        })
+        .collect()
+}
+
+/// The lines of the open sidebar tab, fit to `width` columns.
+fn sidebar_lines(app: &App, width: usize) -> Vec<Line<'_>> {
+    let look = &app.appearance;
+    match app.selected_issue() {
+        Some(issue) => match app.sidebar_tab {
+            SidebarTab::Details => {
+                let history = match app.histories.get(&issue.id) {
+                    Some(Loadable::Loaded(history)) => Some(history.as_slice()),
+                    _ => None,
+                };
+                let times = history
+                    .and_then(|history| issue.time_in_status(history, Local::now().fixed_offset()));
+                let stuck = times.as_ref().is_some_and(|times| {
+                    !issue.is_done() && times.current > TimeDelta::days(app.stuck_after_days.into())
+                });
+                let mut lines = details_lines(
+                    issue,
+                    app.children.get(&issue.id),
+                    times.map(|times| (times, stuck)),
+                    look,
+                    width,
+                    app.selected_item_index(),
+                );
+                if let Some(watchers) = watchers_line(app.watchers.get(&issue.id), look) {
+                    // Below the fields, before the related issues
+                    let at = lines
+                        .iter()
+                        .position(|line| line.spans.is_empty())
+                        .unwrap_or(lines.len());
+                    lines.insert(at, watchers);
+                }
+                if app.outbox.is_pending(&issue.id) {
+                    // Below the summary and key
+                    lines.insert(
+                        2,
+                        Line::styled(
+                            "Pending sync: changes not made in Jira yet",
+                            Style::default().fg(look.theme.yellow),
+                        ),
+                    );
+                }
+                lines
+            }
+            SidebarTab::History => match app.histories.get(&issue.id) {
+                Some(Loadable::Loaded(history)) => history_lines(history, look, width),
+                Some(Loadable::Failed(e)) => vec![Line::styled(
+                    format!("Failed to load history: {e}"),
+                    Style::default().fg(look.theme.red),
+                )],
+                Some(Loadable::Loading) | None => vec![Line::from("Loading history…")],
+            },
+        },
+        None => vec![Line::from("No issue selected")],
+    }
+}
+
+/// The tab names for the border title, with the active one highlighted.
+fn tab_titles(active: SidebarTab, look: &Appearance) -> Line<'static> {
+    let mut spans = Vec::new();
+    for tab in SidebarTab::ALL {
+        if !spans.is_empty() {
+            spans.push(Span::raw(" | "));
+        }
+        spans.push(if tab == active {
+            let name = if look.no_color {
+                format!("[{}]", tab.name())
+            } else {
+                tab.name().to_string()
+            };
+            Span::styled(name, look.theme.details_title.add_modifier(Modifier::UNDERLINED))
+        } else {
+            Span::styled(tab.name(), Style::default().fg(look.theme.gray))
+        });
+    }
+    Line::from(spans)
+}
+
+/// Who is watching the issue, once known.
+fn watchers_line(
+    watchers: Option<&Loadable<Vec<User>>>,
+    look: &Appearance,
+) -> Option<Line<'static>> {
+    let names = match watchers? {
+        Loadable::Loaded(watchers) if watchers.is_empty() => {
+            Span::styled("nobody", Style::default().fg(look.theme.gray))
+        }
+        Loadable::Loaded(watchers) => Span::raw(
+            watchers
+                .iter()
+                .map(|user| user.display_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        Loadable::Failed(e) => {
+            Span::styled(format!("failed to load: {e}"), Style::default().fg(look.theme.red))
+        }
+        Loadable::Loading => Span::styled("loading…", Style::default().fg(look.theme.gray)),
+    };
+    Some(Line::from(vec![label("Watchers: "), names]))
+}
+
+fn label(text: &str) -> Span<'_> {
+    Span::styled(text, Style::default().add_modifier(Modifier::BOLD))
+}
+
+/// `children` are those of an epic. `times` is how long the issue spent in each status, and
+/// whether it's stuck in the current one. `item` is the index of the entry Enter opens,
+/// counting references, then children, then attachments.
+fn details_lines<'a>(
+    issue: &'a Issue,
+    children: Option<&'a Loadable<Vec<Issue>>>,
+    times: Option<(TimeInStatus, bool)>,
+    look: &Appearance,
+    width: usize,
+    item: usize,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            text::truncate(&issue.summary, width),
+            look.theme.details_title,
+        )]),
+        Line::from(vec![label("ID: "), Span::raw(&issue.id)]),
+    ];
+
+    if let Some(ref t) = issue.issue_type {
+        lines.push(Line::from(vec![label("Type: "), Span::raw(t)]));
+    }
+    if let Some(ref s) = issue.status {
+        lines.push(Line::from(vec![
+            label("Status: "),
+            Span::styled(look.label(s.as_str()), Style::default().fg(s.color(&look.theme))),
+        ]));
+    }
+    if let Some(ref p) = issue.priority {
+        lines.push(Line::from(vec![
+            label("Priority: "),
+            Span::styled(look.label(p.as_str()), Style::default().fg(p.color(&look.theme))),
+        ]));
+    }
+    if let Some(points) = issue.story_points {
+        lines.push(Line::from(vec![label("Story Points: "), Span::raw(points.to_string())]));
+    }
+    if let Some(epic) = issue.epic().filter(|epic| epic.key != issue.id) {
+        let color = look.theme.name_color(&epic.key);
+        lines.push(Line::from(vec![
+            label("Epic: "),
+            Span::styled(format!("{} {}", epic.key, epic.summary), Style::default().fg(color)),
+        ]));
+    }
+    if let Some(sprint) = &issue.active_sprint {
+        lines.push(Line::from(vec![label("Sprint: "), Span::raw(sprint)]));
+    }
+    if issue.is_flagged() {
+        let style = Style::default()
+            .fg(look.theme.red)
+            .add_modifier(Modifier::BOLD);
+        lines.push(Line::from(Span::styled("Flagged as an impediment", style)));
+    }
+    let dates = [
+        ("Created: ", issue.created.map(|t| look.time.datetime(&t))),
+        ("Updated: ", issue.updated.map(|t| look.time.datetime(&t))),
+        ("Due: ", issue.due.map(|d| look.time.date(&d))),
+    ];
+    for (name, date) in dates {
+        if let Some(date) = date {
+            lines.push(Line::from(vec![label(name), Span::raw(date)]));
+        }
+    }
+    if let Some(tracking) = &issue.time_tracking {
+        lines.extend(time_tracking_lines(tracking, look, width));
+    }
+    let slas = issue.slas();
+    if !slas.is_empty() {
+        let now = Local::now().fixed_offset();
+        lines.push(Line::from(label("SLA:")));
+        let name_width = slas
+            .iter()
+            .map(|sla| text::width(&sla.name))
+            .max()
+            .unwrap_or(0);
+        for sla in slas {
+            let padding = " ".repeat(name_width - text::width(&sla.name));
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {}{padding}  ", sla.name)),
+                Span::styled(sla.countdown(now), Style::default().fg(sla.color(now, &look.theme))),
+            ]));
+        }
+    }
+    if let Some((times, stuck)) = times {
+        lines.extend(time_in_status_lines(&times, stuck, look));
+    }
+
+    let highlight = |line: Line<'a>, selected: bool| {
+        if selected {
+            line.style(look.theme.list_highlight)
+        } else {
+            line
+        }
+    };
+
+    let references = issue.references();
+    let reference_count = references.len();
+    if !references.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            "Related ({/} to select, Enter to open, o in browser)",
+            Style::default().fg(look.theme.gray),
+        ));
+    }
+    for (i, (relation, other)) in references.into_iter().enumerate() {
+        let selected = i == item;
+        let marker = if selected && look.no_color { "> " } else { "" };
+        let prefix = format!("{marker}{relation}: ");
+        let summary_width =
+            width.saturating_sub(text::width(&prefix) + text::width(&other.key) + 1);
+        let line = Line::from(vec![
+            Span::styled(prefix, Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(other.key.as_str(), Style::default().fg(look.theme.cyan)),
+            Span::raw(" "),
+            Span::raw(text::truncate(&other.summary, summary_width)),
+        ]);
+        lines.push(highlight(line, selected));
+    }
+
+    let mut child_count = 0;
+    match children {
+        Some(Loadable::Loaded(children)) => {
+            child_count = children.len();
+            lines.push(Line::from(""));
+            lines.extend(epic_progress_lines(children, look, width));
+            for (i, child) in children.iter().enumerate() {
+                let selected = reference_count + i == item;
+                let marker = if selected && look.no_color { "> " } else { "" };
+                let color = child
+                    .status
+                    .as_ref()
+                    .map_or(look.theme.gray, |status| status.color(&look.theme));
+                let summary_width =
+                    width.saturating_sub(text::width(marker) + text::width(&child.id) + 1);
+                let line = Line::from(vec![
+                    Span::raw(marker),
+                    Span::styled(child.id.as_str(), Style::default().fg(color)),
+                    Span::raw(" "),
+                    Span::raw(text::truncate(&child.summary, summary_width)),
+                ]);
+                lines.push(highlight(line, selected));
+            }
+        }
+        Some(Loadable::Failed(e)) => {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                format!("Failed to load child issues: {e}"),
+                Style::default().fg(look.theme.red),
+            ));
+        }
+        Some(Loadable::Loading) => {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Loading child issues…"));
+        }
+        None => {}
+    }
+
+    if !issue.attachments.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            "Attachments (Enter to open)",
+            Style::default().fg(look.theme.gray),
+        ));
+    }
+    for (i, attachment) in issue.attachments.iter().enumerate() {
+        let selected = reference_count + child_count + i == item;
+        let marker = if selected && look.no_color { "> " } else { "" };
+        let size = format!(" {}", file_size(attachment.size));
+        let name_width = width.saturating_sub(text::width(marker) + text::width(&size));
+        let line = Line::from(vec![
+            Span::raw(marker),
+            Span::styled(
+                text::truncate(&attachment.filename, name_width),
+                Style::default().fg(look.theme.cyan),
+            ),
+            Span::styled(size, Style::default().fg(look.theme.gray)),
+        ]);
+        lines.push(highlight(line, selected));
+    }
+
+    lines.push(Line::from(""));
+    lines.extend(
+        wrap::wrap(&issue.description, width)
+            .into_iter()
+            .map(Line::from),
+    );
+    lines
+}
+
+/// The header of an epic's children, with how many of them and of their story points are
+/// done, and a bar showing the share of done issues.
+fn epic_progress_lines(children: &[Issue], look: &Appearance, width: usize) -> Vec<Line<'static>> {
+    let done = children.iter().filter(|child| child.is_done()).count();
+    let points = |children: &mut dyn Iterator<Item = &Issue>| -> f64 {
+        children.filter_map(|child| child.story_points).sum()
+    };
+    let total_points = points(&mut children.iter());
+    let done_points = points(&mut children.iter().filter(|child| child.is_done()));
+
+    let mut summary = format!("{done}/{} done", children.len());
+    if total_points > 0.0 {
+        summary.push_str(&format!(", {done_points}/{total_points} points"));
+    }
+    let mut lines =
+        vec![Line::styled(format!("Children ({summary})"), Style::default().fg(look.theme.gray))];
+
+    if !children.is_empty() {
+        let bar_width = width.min(40);
+        let filled = bar_width * done / children.len();
+        let (full, empty) = if look.no_color {
+            ('#', '-')
+        } else {
+            ('█', '░')
+        };
+        lines.push(Line::from(vec![
+            Span::styled(full.to_string().repeat(filled), Style::default().fg(look.theme.green)),
+            Span::styled(
+                empty.to_string().repeat(bar_width - filled),
+                Style::default().fg(look.theme.gray),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// The logged work against the original estimate, with a bar that turns red when the issue
+/// went over it, like Jira's time tracking.
+fn time_tracking_lines(
+    tracking: &TimeTracking,
+    look: &Appearance,
+    width: usize,
+) -> Vec<Line<'static>> {
+    let spent = tracking.time_spent.unwrap_or(0);
+    let mut summary = time::duration(spent);
+    match (tracking.original_estimate, tracking.remaining_estimate) {
+        (Some(estimate), _) if spent > estimate => summary.push_str(&format!(
+            " of {} ({} over)",
+            time::duration(estimate),
+            time::duration(spent - estimate)
+        )),
+        (Some(estimate), _) => summary.push_str(&format!(" of {}", time::duration(estimate))),
+        (None, Some(remaining)) => {
+            summary.push_str(&format!(", {} remaining", time::duration(remaining)));
+        }
+        (None, None) => {}
+    }
+    let mut lines = vec![Line::from(vec![label("Logged: "), Span::raw(summary)])];
+
+    // Without an original estimate, the remaining one tells how far along the work is
+    let (budget, color) = match (tracking.original_estimate, tracking.remaining_estimate) {
+        (Some(estimate), _) if spent > estimate => (spent, look.theme.red),
+        (Some(estimate), _) => (estimate, look.theme.green),
+        (None, Some(remaining)) => (spent + remaining, look.theme.green),
+        (None, None) => (0, look.theme.green),
+    };
+    if budget > 0 {
+        let bar_width = width.min(40);
+        let filled = (bar_width as u64 * spent / budget) as usize;
+        let (full, empty) = if look.no_color {
+            ('#', '-')
+        } else {
+            ('█', '░')
+        };
+        lines.push(Line::from(vec![
+            Span::styled(full.to_string().repeat(filled), Style::default().fg(color)),
+            Span::styled(
+                empty.to_string().repeat(bar_width - filled),
+                Style::default().fg(look.theme.gray),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// The time spent in each status, with the current one last, in yellow and marked if the
+/// issue has been `stuck` in it.
+fn time_in_status_lines(
+    times: &TimeInStatus,
+    stuck: bool,
+    look: &Appearance,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(label("Time in status:"))];
+    let name_width = times
+        .statuses
+        .iter()
+        .map(|s| text::width(&s.status))
+        .max()
+        .unwrap_or(0);
+    let last = times.statuses.len().saturating_sub(1);
+    for (i, s) in times.statuses.iter().enumerate() {
+        let padding = " ".repeat(name_width - text::width(&s.status));
+        let mut line = format!("  {}{padding}  {}", s.status, time::elapsed(s.time));
+        if i == last && stuck {
+            line.push_str(&format!(", stuck for {}", time::elapsed(times.current)));
+            lines.push(Line::styled(line, Style::default().fg(look.theme.yellow)));
+        } else {
+            lines.push(Line::raw(line));
+        }
+    }
+    lines
+}
+
+/// A size in bytes, in the largest unit that keeps it at least 1, like "2.5 MB".
+fn file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// The changelog, newest first, with every changed field shown as a diff.
+fn history_lines(history: &[HistoryEntry], look: &Appearance, width: usize) -> Vec<Line<'static>> {
+    if history.is_empty() {
+        return vec![Line::from("No changes yet")];
+    }
+
+    let mut lines = Vec::new();
+    for entry in history {
+        let when = entry
+            .created
+            .map(|t| look.time.datetime(&t))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(entry.author.clone(), look.theme.details_title),
+            Span::styled(format!("  {when}"), Style::default().fg(look.theme.gray)),
+        ]));
+        for change in &entry.changes {
+            push_change_lines(change, look, width, &mut lines);
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+fn push_change_lines(
+    change: &FieldChange,
+    look: &Appearance,
+    width: usize,
+    lines: &mut Vec<Line<'static>>,
+) {
+    lines.push(Line::from(Span::styled(
+        format!("  {}", change.field),
+        Style::default().add_modifier(Modifier::BOLD),
+    )));
+
+    let old = change.from.as_deref().unwrap_or_default();
+    let new = change.to.as_deref().unwrap_or_default();
+    let diff = diff::diff_lines(old, new);
+    let shown = diff::with_context(&diff, DIFF_CONTEXT);
+
+    // Room for the indent and the -/+ marker
+    let text_width = width.saturating_sub(4).max(1);
+    for line in shown {
+        let (marker, text, color) = match line {
+            Some(DiffLine::Same(text)) => (' ', text, look.theme.gray),
+            Some(DiffLine::Removed(text)) => ('-', text, look.theme.red),
+            Some(DiffLine::Added(text)) => ('+', text, look.theme.green),
+            None => {
+                lines.push(Line::styled("    ⋯", Style::default().fg(look.theme.gray)));
+                continue;
+            }
+        };
+        let style = Style::default().fg(color);
+        let wrapped = wrap::wrap(text, text_width);
+        if wrapped.is_empty() {
+            lines.push(Line::styled(format!("  {marker} "), style));
+        }
+        for (i, part) in wrapped.into_iter().enumerate() {
+            let marker = if i == 0 { marker } else { ' ' };
+            lines.push(Line::styled(format!("  {marker} {part}"), style));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Measuring and fitting text by its display width in terminal columns.
+
+use std::borrow::Cow;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Display width of `text` in terminal columns.
+pub fn width(text: &str) -> usize {
+    text.width()
+}
+
+/// Display width of a single character; control characters take no space.
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Cuts `text` to at most `width` columns, ending it with an ellipsis if anything was cut.
+/// Wide characters (CJK, emoji) are never split.
+pub fn truncate(text: &str, width: usize) -> Cow<'_, str> {
+    if text.width() <= width {
+        return Cow::Borrowed(text);
+    }
+    if width == 0 {
+        return Cow::Borrowed("");
+    }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = char_width(c);
+        // Leave one column for the ellipsis
+        if used + w > width - 1 {
+            break;
+        }
+        truncated.push(c);
+        used += w;
+    }
+    truncated.push('…');
+    Cow::Owned(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_by_display_width() {
+        assert_eq!(truncate("Fix login", 20), "Fix login");
+        assert_eq!(truncate("Fix login", 5), "Fix …");
+        // Each of these takes two columns; a half glyph is never drawn
+        assert_eq!(truncate("修复登录页面", 6), "修复…");
+        assert_eq!(truncate("🚀🚀🚀", 4), "🚀…");
+        assert_eq!(truncate("abc", 0), "");
+    }
+}
@@ -1,7 +1,95 @@
+//! Colors and styles used throughout the UI.
+
+use std::borrow::Cow;
+
 use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
 
-pub const THEME: Theme = Theme::new();
+use crate::{
+    config::Config,
+    ui::{icons::IconSet, time::TimeFormat},
+};
+
+/// The built-in themes, selectable in the config file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    /// Bright, bold colors without dimming, for low-contrast screens and projectors.
+    HighContrast,
+}
 
+/// How much room each issue gets in the list, selectable in the config file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Density {
+    /// A row per issue, with a column for everything.
+    #[default]
+    Compact,
+    /// Two rows per issue: the summary, and the key, status and labels under it in dim, so
+    /// long summaries get the width of the key and status columns too.
+    Comfortable,
+}
+
+impl Density {
+    /// Terminal rows each issue takes.
+    pub const fn row_height(self) -> u16 {
+        match self {
+            Density::Compact => 1,
+            Density::Comfortable => 2,
+        }
+    }
+}
+
+/// Everything that determines how the UI looks, as opposed to what it shows.
+#[derive(Debug, Clone, Default)]
+pub struct Appearance {
+    pub theme: Theme,
+    pub icons: IconSet,
+    /// Convey state with text markers only, for `NO_COLOR` and screen readers.
+    pub no_color: bool,
+    pub time: TimeFormat,
+    pub density: Density,
+    /// See [`Config::scrolloff`].
+    pub scrolloff: usize,
+}
+
+impl Appearance {
+    pub fn from_config(config: &Config) -> Self {
+        let theme = match config.theme {
+            _ if config.no_color => Theme::monochrome(),
+            ThemeName::Default => Theme::new(),
+            ThemeName::HighContrast => Theme::high_contrast(),
+        };
+        Self {
+            theme,
+            icons: config.icons,
+            no_color: config.no_color,
+            // Already validated when the config was loaded
+            time: config.time_format().unwrap_or_default(),
+            density: config.density,
+            scrolloff: config.scrolloff,
+        }
+    }
+
+    /// A state like a status or priority, as a `[MARKER]` in no-color mode.
+    pub fn label<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.no_color {
+            Cow::Owned(format!("[{}]", text.to_uppercase()))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// Marks the selected row, which otherwise only stands out by its background.
+    pub fn selection_marker(&self) -> &'static str {
+        if self.no_color { "> " } else { "" }
+    }
+}
+
+/// The styles and palette colors the UI draws with.
+#[derive(Debug, Clone, Copy)]
 pub struct Theme {
     pub list_highlight: Style,
     pub list_highlight_inactive: Style,
@@ -18,13 +106,17 @@ pub struct Theme {
     pub magenta: Color,
     pub cyan: Color,
     pub white: Color,
-    #[allow(dead_code)]
     pub black: Color,
     pub gray: Color,
-    #[allow(dead_code)]
     pub dark_gray: Color,
 }
 
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Theme {
     pub const fn new() -> Self {
         Self {
@@ -54,4 +146,75 @@ impl Theme {
             dark_gray: Color::DarkGray,
         }
     }
+
+    /// No colors at all, only bold text for emphasis.
+    pub const fn monochrome() -> Self {
+        Self {
+            list_highlight: Style::new().add_modifier(Modifier::BOLD),
+            list_highlight_inactive: Style::new(),
+            input: Style::new(),
+            input_placeholder: Style::new(),
+            footer_normal: Style::new().add_modifier(Modifier::BOLD),
+            footer_insert: Style::new().add_modifier(Modifier::BOLD),
+            details_title: Style::new().add_modifier(Modifier::BOLD),
+
+            red: Color::Reset,
+            green: Color::Reset,
+            blue: Color::Reset,
+            yellow: Color::Reset,
+            magenta: Color::Reset,
+            cyan: Color::Reset,
+            white: Color::Reset,
+            black: Color::Reset,
+            gray: Color::Reset,
+            dark_gray: Color::Reset,
+        }
+    }
+
+    /// Bold, bright foregrounds and no dimming.
+    pub const fn high_contrast() -> Self {
+        Self {
+            list_highlight: Style::new()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD),
+            list_highlight_inactive: Style::new()
+                .add_modifier(Modifier::BOLD)
+                .add_modifier(Modifier::UNDERLINED),
+            input: Style::new()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            input_placeholder: Style::new().fg(Color::White),
+            footer_normal: Style::new()
+                .fg(Color::Black)
+                .bg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+            footer_insert: Style::new()
+                .fg(Color::Black)
+                .bg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            details_title: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+
+            red: Color::LightRed,
+            green: Color::LightGreen,
+            blue: Color::LightBlue,
+            yellow: Color::LightYellow,
+            magenta: Color::LightMagenta,
+            cyan: Color::LightCyan,
+            white: Color::White,
+            black: Color::Black,
+            gray: Color::White,
+            dark_gray: Color::Gray,
+        }
+    }
+
+    /// One of the palette's colors for `name`, the same every time, to tell apart things
+    /// like labels and epics at a glance.
+    pub fn name_color(&self, name: &str) -> Color {
+        let colors = [self.blue, self.green, self.yellow, self.magenta, self.cyan];
+        let hash = name
+            .bytes()
+            .fold(0usize, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as usize));
+        colors[hash % colors.len()]
+    }
 }
@@ -0,0 +1,139 @@
+//! Parsing of Jira timestamps and rendering them in the configured format and timezone.
+
+use chrono::{
+    DateTime, FixedOffset, Local, NaiveDate, TimeDelta, TimeZone, Utc,
+    format::{Item, StrftimeItems},
+};
+use chrono_tz::Tz;
+
+/// Parses a Jira timestamp like `2024-05-02T09:15:00.000+0200`.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(s))
+        .ok()
+}
+
+/// Parses a Jira date like `2024-06-01`, as used for due dates.
+pub fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+}
+
+/// Seconds of work the way Jira writes them, like `1d 4h 30m`, counting 8 hours as a day.
+pub fn duration(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    let parts = [("d", minutes / (8 * 60)), ("h", minutes / 60 % 8), ("m", minutes % 60)];
+    let text: Vec<String> = parts
+        .iter()
+        .filter(|(_, amount)| *amount > 0)
+        .map(|(unit, amount)| format!("{amount}{unit}"))
+        .collect();
+    if text.is_empty() {
+        "0m".to_string()
+    } else {
+        text.join(" ")
+    }
+}
+
+/// A stretch of calendar time in its two largest units, like `16d 3h` or `3h 20m`.
+pub fn elapsed(time: TimeDelta) -> String {
+    let minutes = time.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+/// Whether `format` is a strftime format chrono can render. Rendering an invalid one panics,
+/// so formats from the config are checked up front.
+pub fn is_valid_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+/// How every timestamp in the UI is rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeFormat {
+    /// strftime format for timestamps.
+    pub datetime: String,
+    /// strftime format for dates without a time, like due dates.
+    pub date: String,
+    /// Timezone to show timestamps in; `None` for the system's local timezone.
+    pub timezone: Option<Tz>,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self {
+            datetime: "%Y-%m-%d %H:%M".to_string(),
+            date: "%Y-%m-%d".to_string(),
+            timezone: None,
+        }
+    }
+}
+
+impl TimeFormat {
+    pub fn datetime(&self, time: &DateTime<FixedOffset>) -> String {
+        match self.timezone {
+            Some(tz) => self.format_in(time, &tz),
+            None => self.format_in(time, &Local),
+        }
+    }
+
+    /// Today's date in the configured timezone.
+    pub fn today(&self) -> NaiveDate {
+        match self.timezone {
+            Some(tz) => Utc::now().with_timezone(&tz).date_naive(),
+            None => Local::now().date_naive(),
+        }
+    }
+
+    pub fn date(&self, date: &NaiveDate) -> String {
+        date.format(&self.date).to_string()
+    }
+
+    fn format_in<Z: TimeZone>(&self, time: &DateTime<FixedOffset>, tz: &Z) -> String
+    where
+        Z::Offset: std::fmt::Display,
+    {
+        time.with_timezone(tz).format(&self.datetime).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_in_configured_timezone() {
+        let time = parse_timestamp("2024-05-02T09:15:00.000+0200").unwrap();
+        let format = TimeFormat {
+            datetime: "%d %b %H:%M %Z".to_string(),
+            timezone: Some(chrono_tz::America::New_York),
+            ..TimeFormat::default()
+        };
+        assert_eq!(format.datetime(&time), "02 May 03:15 EDT");
+        assert_eq!(format.date(&parse_date("2024-06-01").unwrap()), "2024-06-01");
+    }
+
+    #[test]
+    fn durations_in_working_days() {
+        assert_eq!(duration(0), "0m");
+        assert_eq!(duration(90 * 60), "1h 30m");
+        assert_eq!(duration(10 * 3600), "1d 2h");
+    }
+
+    #[test]
+    fn elapsed_in_calendar_days() {
+        assert_eq!(elapsed(TimeDelta::minutes(12)), "12m");
+        assert_eq!(elapsed(TimeDelta::minutes(200)), "3h 20m");
+        assert_eq!(elapsed(TimeDelta::hours(16 * 24 + 3)), "16d 3h");
+    }
+
+    #[test]
+    fn rejects_invalid_formats() {
+        assert!(is_valid_format("%Y-%m-%d %H:%M"));
+        assert!(!is_valid_format("%Q"));
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+}
@@ -0,0 +1,307 @@
+//! The timeline view: the listed issues as bars across the calendar, for seeing when planned
+//! work lands.
+//!
+//! A bar runs from an issue's start date, or its creation when it has none, to its due date.
+//! Issues without a due date have no end to plot and are listed without a bar.
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::App,
+    ui::{
+        input::InputMode,
+        issue::{Issue, StatusCategory},
+        issue_list::window_offset,
+        text,
+        theme::Appearance,
+    },
+};
+
+/// Days a column stands for at each zoom level, from closest to farthest.
+const ZOOM_LEVELS: [u64; 3] = [1, 3, 7];
+
+/// Columns `h` and `l` scroll by.
+const SCROLL_COLUMNS: u64 = 8;
+
+/// Where the timeline is scrolled to and how far it is zoomed out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimelineState {
+    /// Index into [`ZOOM_LEVELS`].
+    zoom: usize,
+    /// The first day shown; until scrolled, the Monday of the week before today's.
+    start: Option<NaiveDate>,
+}
+
+impl TimelineState {
+    pub fn days_per_column(&self) -> u64 {
+        ZOOM_LEVELS[self.zoom]
+    }
+
+    pub fn start(&self, today: NaiveDate) -> NaiveDate {
+        self.start
+            .unwrap_or_else(|| today.week(Weekday::Mon).first_day() - Days::new(7))
+    }
+
+    /// Scrolls later (positive) or earlier (negative) by `steps` times a few columns.
+    pub fn scroll(&mut self, steps: isize, today: NaiveDate) {
+        let days = Days::new(steps.unsigned_abs() as u64 * SCROLL_COLUMNS * self.days_per_column());
+        let start = self.start(today);
+        self.start = if steps < 0 {
+            start.checked_sub_days(days)
+        } else {
+            start.checked_add_days(days)
+        }
+        .or(Some(start));
+    }
+
+    /// Zooms in (positive) or out (negative) by `steps` levels, stopping at the last one.
+    pub fn zoom(&mut self, steps: isize) {
+        self.zoom = (self.zoom as isize - steps).clamp(0, ZOOM_LEVELS.len() as isize - 1) as usize;
+    }
+}
+
+/// The days an issue's bar covers, if it has a due date.
+fn bar_span(issue: &Issue) -> Option<(NaiveDate, NaiveDate)> {
+    let due = issue.due?;
+    let start = issue
+        .start
+        .or_else(|| issue.created.map(|created| created.date_naive()))
+        .unwrap_or(due);
+    Some((start.min(due), due))
+}
+
+/// The first day of `days` days starting at `from` that starts a labelled period: a week
+/// when every column is a day, a month otherwise.
+fn period_start(from: NaiveDate, days: u64) -> Option<NaiveDate> {
+    from.iter_days().take(days as usize).find(|day| match days {
+        1 => day.weekday() == Weekday::Mon,
+        _ => day.day() == 1,
+    })
+}
+
+/// The date labels above the bars, each at the column its period starts in.
+fn header(start: NaiveDate, days_per_column: u64, width: usize) -> String {
+    let mut header = vec![' '; width];
+    let mut free = 0;
+    for column in 0..width {
+        let Some(from) = start.checked_add_days(Days::new(column as u64 * days_per_column)) else {
+            break;
+        };
+        let Some(day) = period_start(from, days_per_column) else {
+            continue;
+        };
+        let format = match days_per_column {
+            1 => "%b %d",
+            7 => "%b",
+            _ => "%b %Y",
+        };
+        let label: Vec<char> = day.format(format).to_string().chars().collect();
+        if column < free || column + label.len() > width {
+            continue;
+        }
+        header[column..column + label.len()].copy_from_slice(&label);
+        free = column + label.len() + 1;
+    }
+    header.into_iter().collect()
+}
+
+/// What one column of an issue's row shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Today,
+    Bar,
+    /// The bar lies before the first column.
+    Earlier,
+    /// The bar lies after the last column.
+    Later,
+}
+
+/// The columns of an issue's row.
+fn cells(
+    span: Option<(NaiveDate, NaiveDate)>,
+    start: NaiveDate,
+    days_per_column: u64,
+    width: usize,
+    today: NaiveDate,
+) -> Vec<Cell> {
+    let column_of = |date: NaiveDate| (date - start).num_days().div_euclid(days_per_column as i64);
+    let mut cells: Vec<Cell> = (0..width as i64)
+        .map(|column| match span {
+            Some((from, to)) if (column_of(from)..=column_of(to)).contains(&column) => Cell::Bar,
+            _ if column_of(today) == column => Cell::Today,
+            _ => Cell::Empty,
+        })
+        .collect();
+    if let (Some((_, to)), Some(first)) = (span, cells.first_mut()) {
+        if column_of(to) < 0 {
+            *first = Cell::Earlier;
+        }
+    }
+    if let (Some((from, _)), Some(last)) = (span, cells.last_mut()) {
+        if column_of(from) >= width as i64 {
+            *last = Cell::Later;
+        }
+    }
+    cells
+}
+
+/// The bar of an issue and the other columns of its row as spans.
+fn bar_spans(
+    issue: &Issue,
+    cells: &[Cell],
+    today: NaiveDate,
+    look: &Appearance,
+) -> Vec<Span<'static>> {
+    let category = issue
+        .status
+        .as_ref()
+        .map_or(StatusCategory::Unknown, |status| status.category);
+    let overdue = !issue.is_done() && issue.due.is_some_and(|due| due < today);
+    let (bar, color) = match (overdue, look.no_color) {
+        (true, true) => ('!', look.theme.red),
+        (true, false) => ('█', look.theme.red),
+        (false, true) if category == StatusCategory::Done => ('#', look.theme.green),
+        (false, true) => ('=', category.color(&look.theme)),
+        (false, false) => ('█', category.color(&look.theme)),
+    };
+    let gray = Style::default().fg(look.theme.gray);
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for chunk in cells.chunk_by(|a, b| a == b) {
+        let (c, style) = match chunk[0] {
+            Cell::Empty => (' ', Style::default()),
+            Cell::Today => (if look.no_color { '|' } else { '│' }, gray),
+            Cell::Bar => (bar, Style::default().fg(color)),
+            Cell::Earlier => (if look.no_color { '<' } else { '◂' }, Style::default().fg(color)),
+            Cell::Later => (if look.no_color { '>' } else { '▸' }, Style::default().fg(color)),
+        };
+        spans.push(Span::styled(c.to_string().repeat(chunk.len()), style));
+    }
+    spans
+}
+
+/// Renders the listed issues as bars across the calendar, below a row of dates.
+pub fn render_timeline(f: &mut Frame, app: &mut App, area: Rect) {
+    if area.height == 0 {
+        return;
+    }
+    let look = &app.appearance;
+    let today = look.time.today();
+    let marker = look.selection_marker();
+    let start = app.timeline.start(today);
+    let days_per_column = app.timeline.days_per_column();
+
+    let label_width = (area.width as usize / 3).clamp(16, 40);
+    let chart_width = (area.width as usize).saturating_sub(marker.len() + label_width + 1);
+    let gutter = " ".repeat(marker.len() + label_width + 1);
+
+    let height = area.height as usize - 1;
+    let selected = app.list_state.selected();
+    let offset =
+        window_offset(app.list_state.offset(), selected, height, app.visible.len(), look.scrolloff);
+    *app.list_state.offset_mut() = offset;
+
+    let highlight = if app.input_mode != InputMode::Normal {
+        look.theme.list_highlight_inactive
+    } else {
+        look.theme.list_highlight
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::raw(gutter),
+        Span::styled(
+            header(start, days_per_column, chart_width),
+            Style::default()
+                .fg(look.theme.gray)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])];
+    let end = (offset + height).min(app.visible.len());
+    for (row, &index) in app.visible[offset.min(end)..end].iter().enumerate() {
+        let issue = &app.issues[index];
+        let is_selected = selected == Some(offset + row);
+        let label =
+            text::truncate(&format!("{} {}", issue.id, issue.summary), label_width).into_owned();
+        let padding = " ".repeat(label_width - text::width(&label) + 1);
+
+        let prefix = if is_selected {
+            marker.to_string()
+        } else {
+            " ".repeat(marker.len())
+        };
+        let mut spans = vec![Span::raw(prefix), Span::raw(label), Span::raw(padding)];
+        let cells = cells(bar_span(issue), start, days_per_column, chart_width, today);
+        spans.extend(bar_spans(issue, &cells, today, look));
+
+        let line = Line::from(spans);
+        lines.push(if is_selected {
+            line.style(highlight)
+        } else {
+            line
+        });
+    }
+
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn labels_weeks_and_months() {
+        // 2024-06-03 is a Monday
+        assert_eq!(header(date("2024-06-01"), 1, 20), "  Jun 03 Jun 10     ");
+        // July would not fit
+        assert_eq!(header(date("2024-05-20"), 3, 20), "    Jun 2024        ");
+        assert_eq!(header(date("2024-05-27"), 7, 12), "Jun  Jul Aug");
+    }
+
+    #[test]
+    fn bars_run_from_start_to_due() {
+        use Cell::*;
+
+        let mut issue = Issue::new("Migrate", "");
+        assert_eq!(bar_span(&issue), None);
+        issue.due = Some(date("2024-06-05"));
+        assert_eq!(bar_span(&issue), Some((date("2024-06-05"), date("2024-06-05"))));
+        issue.start = Some(date("2024-06-03"));
+
+        let cells = cells(bar_span(&issue), date("2024-06-01"), 1, 8, date("2024-06-07"));
+        assert_eq!(cells, [Empty, Empty, Bar, Bar, Bar, Empty, Today, Empty]);
+
+        // Out of view to either side
+        let cells = super::cells(bar_span(&issue), date("2024-06-10"), 1, 3, date("2024-06-01"));
+        assert_eq!(cells, [Earlier, Empty, Empty]);
+        let cells = super::cells(bar_span(&issue), date("2024-05-01"), 3, 3, date("2024-06-01"));
+        assert_eq!(cells, [Empty, Empty, Later]);
+    }
+
+    #[test]
+    fn scrolls_and_zooms() {
+        let today = date("2024-06-05");
+        let mut timeline = TimelineState::default();
+        assert_eq!(timeline.start(today), date("2024-05-27"));
+        timeline.scroll(1, today);
+        assert_eq!(timeline.start(today), date("2024-06-04"));
+
+        timeline.zoom(-5);
+        assert_eq!(timeline.days_per_column(), 7);
+        timeline.scroll(-1, today);
+        assert_eq!(timeline.start(today), date("2024-04-09"));
+        timeline.zoom(1);
+        assert_eq!(timeline.days_per_column(), 3);
+    }
+}
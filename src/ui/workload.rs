@@ -0,0 +1,113 @@
+//! The workload view: how many issues and story points everyone has in the listed issues.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Cell, HighlightSpacing, Row, Table, TableState},
+};
+
+use crate::{
+    app::{App, workload::workloads},
+    ui::input::InputMode,
+};
+
+/// Width of the bar comparing everyone's open story points.
+const BAR_WIDTH: usize = 20;
+
+/// Renders a row per assignee, highlighting those with more open story points than the
+/// configured capacity.
+pub fn render_workload(f: &mut Frame, app: &mut App, area: Rect) {
+    let look = &app.appearance;
+    let workloads = workloads(app);
+    app.workload_cursor = app.workload_cursor.min(workloads.len().saturating_sub(1));
+    let capacity = app.workload_capacity;
+
+    // Bars are relative to the capacity, or the largest workload without one
+    let scale = workloads
+        .iter()
+        .map(|workload| workload.open_points)
+        .fold(capacity.unwrap_or_default(), f64::max);
+    let bar = |points: f64| -> usize {
+        if scale > 0.0 {
+            ((points / scale) * BAR_WIDTH as f64).round() as usize
+        } else {
+            0
+        }
+    };
+    let capacity_column = capacity.map(|capacity| bar(capacity).min(BAR_WIDTH - 1));
+
+    let rows = workloads.iter().map(|workload| {
+        let over = workload.is_over(capacity);
+        let color = if over {
+            look.theme.red
+        } else {
+            look.theme.cyan
+        };
+        let filled = bar(workload.open_points);
+        let bar: String = (0..BAR_WIDTH)
+            .map(|column| match (column < filled, look.no_color) {
+                (true, true) if over => '!',
+                (true, true) => '=',
+                (true, false) => '█',
+                (false, _) if Some(column) == capacity_column => '|',
+                (false, true) => ' ',
+                (false, false) => '░',
+            })
+            .collect();
+        let mut load = vec![Span::styled(bar, Style::default().fg(color))];
+        if over {
+            load.push(Span::styled(
+                format!(" {}", look.label("over capacity")),
+                Style::default().fg(look.theme.red),
+            ));
+        }
+        let name = workload.assignee.as_deref().unwrap_or("Unassigned");
+        let name_style = if over {
+            Style::default()
+                .fg(look.theme.red)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(Span::styled(name.to_string(), name_style)),
+            Cell::from(workload.issues.to_string()),
+            Cell::from(workload.open.to_string()),
+            Cell::from(workload.points.to_string()),
+            Cell::from(workload.open_points.to_string()),
+            Cell::from(Line::from(load)),
+        ])
+    });
+
+    let header = Row::new(["Assignee", "Issues", "Open", "Points", "Open pts", "Load"]).style(
+        Style::default()
+            .fg(look.theme.gray)
+            .add_modifier(Modifier::BOLD),
+    );
+    let highlight_style = if app.input_mode != InputMode::Normal {
+        look.theme.list_highlight_inactive
+    } else {
+        look.theme.list_highlight
+    };
+
+    let mut table_state = TableState::default();
+    table_state.select((!workloads.is_empty()).then_some(app.workload_cursor));
+
+    let table = Table::new(rows, [
+        Constraint::Min(12),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(8),
+        Constraint::Length(BAR_WIDTH as u16 + 16),
+    ])
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(highlight_style)
+    .highlight_symbol(look.selection_marker())
+    .highlight_spacing(HighlightSpacing::Always);
+
+    f.render_stateful_widget(table, area, &mut table_state);
+}
@@ -0,0 +1,103 @@
+//! Word wrapping of plain text for the details pane.
+
+use super::text;
+
+/// Wraps every line of `text` to at most `width` columns, breaking at spaces.
+///
+/// Continuation lines of list items (`- `, `* `, or `1. `, possibly indented) hang under the
+/// item's text rather than its marker. Words longer than a line are split.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        wrap_line(line, width, &mut lines);
+    }
+    lines
+}
+
+fn wrap_line(line: &str, width: usize, out: &mut Vec<String>) {
+    if width == 0 || text::width(line) <= width {
+        out.push(line.to_string());
+        return;
+    }
+
+    // Only hang the indent if it leaves a reasonable amount of room for text
+    let indent = hanging_indent(line)
+        .filter(|&indent| indent * 2 <= width)
+        .unwrap_or(0);
+    let (head, rest) = line.split_at(indent);
+    let continuation = " ".repeat(indent);
+
+    let mut current = head.to_string();
+    let mut current_width = indent;
+    for word in rest.split_whitespace() {
+        let word_width = text::width(word);
+        if current_width > indent && current_width + 1 + word_width > width {
+            out.push(std::mem::replace(&mut current, continuation.clone()));
+            current_width = indent;
+        }
+        if current_width > indent {
+            current.push(' ');
+            current_width += 1;
+        }
+
+        // Split words that don't fit on a line of their own
+        for c in word.chars() {
+            let w = text::char_width(c);
+            if current_width + w > width && current_width > indent {
+                out.push(std::mem::replace(&mut current, continuation.clone()));
+                current_width = indent;
+            }
+            current.push(c);
+            current_width += w;
+        }
+    }
+    out.push(current);
+}
+
+/// Width of the leading whitespace plus list marker, if the line is a list item.
+fn hanging_indent(line: &str) -> Option<usize> {
+    let text = line.trim_start_matches(' ');
+    let leading = line.len() - text.len();
+
+    let digits = text.bytes().take_while(u8::is_ascii_digit).count();
+    let marker = if text.starts_with("- ") || text.starts_with("* ") {
+        2
+    } else if digits > 0 && text[digits..].starts_with(". ") {
+        digits + 2
+    } else {
+        return None;
+    };
+    Some(leading + marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_at_spaces() {
+        assert_eq!(wrap("the replica never gets promoted", 12), [
+            "the replica",
+            "never gets",
+            "promoted"
+        ]);
+        assert_eq!(wrap("short\n\nlines", 12), ["short", "", "lines"]);
+    }
+
+    #[test]
+    fn list_items_hang() {
+        assert_eq!(wrap("- fail over the database", 12), ["- fail over", "  the", "  database"]);
+        assert_eq!(wrap("   10. check replication lag", 16), [
+            "   10. check",
+            "       replicati",
+            "       on lag"
+        ]);
+    }
+
+    #[test]
+    fn splits_long_words() {
+        assert_eq!(wrap("see https://example.com/x", 10), ["see", "https://ex", "ample.com/", "x"]);
+        assert_eq!(wrap("- abcdefghij", 8), ["- abcdef", "  ghij"]);
+        assert_eq!(wrap("数据库故障转移", 6), ["数据库", "故障转", "移"]);
+    }
+}
@@ -0,0 +1,727 @@
+//! End-to-end tests of the Jira API layer against a local mock server serving recorded
+//! responses from `tests/fixtures`.
+
+use std::collections::HashMap;
+
+use jira_tui::{
+    Error,
+    jira::{
+        self, ApiClient, AuthMethod, CustomFields, JiraClient, JiraConfig, NewIssue, PageCursor,
+        SearchApi,
+    },
+    ui::issue::{Issue, StatusCategory},
+};
+use serde_json::{Value, json};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{
+        body_partial_json, body_string_contains, header, header_exists, method, path, query_param,
+        query_param_is_missing,
+    },
+};
+
+fn fixture(name: &str) -> Value {
+    let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+    let contents = std::fs::read_to_string(&path).expect("fixture exists");
+    serde_json::from_str(&contents).expect("fixture is valid JSON")
+}
+
+fn config(server: &MockServer) -> JiraConfig {
+    JiraConfig {
+        base_url: server.uri(),
+        username: "user@example.com".to_string(),
+        api_token: "token".to_string(),
+        auth: AuthMethod::Basic,
+        search_api: SearchApi::Legacy,
+    }
+}
+
+#[tokio::test]
+async fn search_parses_results() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search"))
+        .and(query_param("jql", jira::ASSIGNED_JQL))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("search_assigned.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let page = client
+        .search(jira::ASSIGNED_JQL, None, jira::PAGE_SIZE as usize)
+        .await
+        .unwrap();
+    assert_eq!(page.total, Some(2));
+
+    let issues: Vec<_> = page.issues.iter().map(Issue::from_jira).collect();
+    assert_eq!(issues.len(), 2);
+
+    let bug = &issues[0];
+    assert_eq!(bug.id, "OPS-1");
+    assert_eq!(bug.summary, "Database failover does not trigger");
+    assert_eq!(bug.issue_type.as_deref(), Some("Bug"));
+    assert_eq!(bug.status.as_ref().map(|s| s.as_str()), Some("In Progress"));
+    assert_eq!(bug.status.as_ref().map(|s| s.category), Some(StatusCategory::InProgress));
+    assert_eq!(bug.priority.as_ref().map(|p| p.as_str()), Some("High"));
+    assert_eq!(bug.story_points, Some(3.0));
+    assert_eq!(bug.parent_epic.as_deref(), Some("Reliability"));
+    assert_eq!(bug.description, "The replica never gets promoted.");
+    assert_eq!(bug.created.map(|t| t.to_rfc3339()).as_deref(), Some("2024-05-02T09:15:00+02:00"));
+    assert_eq!(bug.due.map(|d| d.to_string()).as_deref(), Some("2024-06-01"));
+    assert_eq!(bug.labels, ["infra"]);
+    assert_eq!(bug.components, ["Database"]);
+    assert_eq!(bug.sprints, ["OPS Sprint 12"]);
+    let references: Vec<_> = bug
+        .references()
+        .into_iter()
+        .map(|(relation, other)| format!("{relation} {}", other.key))
+        .collect();
+    assert_eq!(references, ["parent OPS-100", "subtask OPS-5", "is blocked by NET-7"]);
+    assert_eq!(bug.attachments[0].filename, "failover.log");
+    assert_eq!(bug.attachments[0].size, 2048);
+
+    let task = &issues[1];
+    assert_eq!(task.id, "OPS-2");
+    assert_eq!(task.description, "");
+    assert!(task.parent_epic.is_none());
+    assert!(task.created.is_none());
+}
+
+#[tokio::test]
+async fn client_search_reports_total() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search"))
+        .and(query_param("startAt", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("search_assigned.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let page = client.search(jira::ASSIGNED_JQL, None, 100).await.unwrap();
+    assert_eq!(page.total, Some(2));
+    assert_eq!(page.issues[1].key.as_deref(), Some("OPS-2"));
+    assert_eq!(page.next, None);
+}
+
+#[tokio::test]
+async fn enhanced_search_follows_page_tokens() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .and(query_param("fields", "*navigable"))
+        .and(query_param_is_missing("nextPageToken"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("search_jql.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .and(query_param("nextPageToken", "CAEaAggD"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "issues": [{ "id": "10003", "key": "OPS-3", "fields": { "summary": "Last" } }],
+            "isLast": true,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&JiraConfig {
+        search_api: SearchApi::Enhanced,
+        ..config(&server)
+    });
+    let page = client.search(jira::ASSIGNED_JQL, None, 2).await.unwrap();
+    assert_eq!(page.issues.len(), 2);
+    assert_eq!(page.total, None);
+    assert_eq!(page.next, Some(PageCursor::Token("CAEaAggD".to_string())));
+
+    let page = client
+        .search(jira::ASSIGNED_JQL, page.next.as_ref(), 2)
+        .await
+        .unwrap();
+    assert_eq!(page.issues[0].key.as_deref(), Some("OPS-3"));
+    assert_eq!(page.next, None);
+}
+
+#[tokio::test]
+async fn create_issue_sends_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue"))
+        .and(body_partial_json(json!({
+            "fields": {
+                "project": { "key": "OPS" },
+                "issuetype": { "name": "Task" },
+                "summary": "Renew domain",
+                "labels": ["infra"],
+            }
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(fixture("create_issue.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let created = client
+        .create_issue(&NewIssue {
+            project_key: "OPS".to_string(),
+            issue_type: "Task".to_string(),
+            summary: "Renew domain".to_string(),
+            description: "Expires next month".to_string(),
+            labels: vec!["infra".to_string()],
+            story_points: None,
+            parent: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(created.key, "OPS-3");
+    assert_eq!(created.url, format!("{}/browse/OPS-3", server.uri()));
+}
+
+#[tokio::test]
+async fn create_issue_falls_back_to_the_epic_link() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue"))
+        .and(body_partial_json(json!({ "fields": { "parent": { "key": "OPS-100" } } })))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "errorMessages": [],
+            "errors": {
+                "parent": "Field 'parent' cannot be set. It is not on the appropriate screen, or unknown."
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue"))
+        .and(body_partial_json(json!({ "fields": { "customfield_10014": "OPS-100" } })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(fixture("create_issue.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let created = client
+        .create_issue(&NewIssue {
+            project_key: "OPS".to_string(),
+            issue_type: "Task".to_string(),
+            summary: "Renew domain".to_string(),
+            description: String::new(),
+            labels: Vec::new(),
+            story_points: None,
+            parent: Some("OPS-100".to_string()),
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.key, "OPS-3");
+}
+
+#[tokio::test]
+async fn download_attachment_sends_credentials() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/attachment/content/30001"))
+        .and(header_exists("authorization"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"replica lag 30s".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let url = format!("{}/rest/api/3/attachment/content/30001", server.uri());
+    assert_eq!(client.download_attachment(&url).await.unwrap(), b"replica lag 30s");
+
+    let missing = format!("{}/rest/api/3/attachment/content/404", server.uri());
+    assert!(matches!(client.download_attachment(&missing).await, Err(Error::NotFound(_))));
+}
+
+#[tokio::test]
+async fn add_attachment_sends_a_form() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue/OPS-1/attachments"))
+        .and(header("X-Atlassian-Token", "no-check"))
+        .and(body_string_contains("filename=\"screenshot.png\""))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{ "id": "30002" }])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let png = b"PNG image data".to_vec();
+    client
+        .add_attachment("OPS-1", "screenshot.png", png)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn edit_issue_assigns_found_user() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/user/assignable/search"))
+        .and(query_param("issueKey", "OPS-1"))
+        .and(query_param("query", "mia"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("assignable_users.json")))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/issue/OPS-1"))
+        .and(body_partial_json(json!({
+            "fields": {
+                "assignee": { "accountId": "5b10ac8d82e05b22cc7d4ef5" },
+                "customfield_10016": null,
+            }
+        })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let users = client.find_assignable_users("OPS-1", "mia").await.unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].display_name, "Mia Krystof");
+
+    let fields = HashMap::from([
+        ("assignee".to_string(), json!({ "accountId": users[0].account_id })),
+        ("customfield_10016".to_string(), Value::Null),
+    ]);
+    client.edit_issue("OPS-1", fields).await.unwrap();
+}
+
+#[tokio::test]
+async fn edits_answered_with_a_body_that_does_not_parse_fail() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/issue/OPS-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html>Maintenance</html>"))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let fields = HashMap::from([("summary".to_string(), json!("Failover"))]);
+    let result = client.edit_issue("OPS-1", fields).await;
+    assert!(matches!(result, Err(Error::Parse(_))), "{result:?}");
+}
+
+#[tokio::test]
+async fn find_users_skips_inactive_accounts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/user/search"))
+        .and(query_param("query", "mia"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("users.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let users = client.find_users("mia").await.unwrap();
+    let names: Vec<&str> = users
+        .iter()
+        .map(|user| user.display_name.as_str())
+        .collect();
+    assert_eq!(names, ["Mia Krystof"]);
+}
+
+#[tokio::test]
+async fn transitions_round_trip() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/OPS-1/transitions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("transitions.json")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue/OPS-1/transitions"))
+        .and(body_partial_json(json!({ "transition": { "id": "31" } })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let transitions = client.get_transitions("OPS-1").await.unwrap();
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[1].name, "Done");
+    assert_eq!(transitions[1].to_status.as_deref(), Some("Done"));
+
+    client
+        .transition_issue("OPS-1", &transitions[1].id)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn watchers_round_trip() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/OPS-1/watchers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("watchers.json")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue/OPS-1/watchers"))
+        .and(body_string_contains("\"5b10ac8d82e05b22cc7d4ef5\""))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/rest/api/3/issue/OPS-1/watchers"))
+        .and(query_param("accountId", "5b10a2844c20165700ede21g"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let watchers = client.get_watchers("OPS-1").await.unwrap();
+    let names: Vec<_> = watchers
+        .iter()
+        .map(|user| user.display_name.as_str())
+        .collect();
+    assert_eq!(names, ["Mia Krystof", "Emma Richards"]);
+
+    client
+        .add_watcher("OPS-1", "5b10ac8d82e05b22cc7d4ef5")
+        .await
+        .unwrap();
+    client
+        .remove_watcher("OPS-1", &watchers[1].account_id)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn add_comment_sends_adf() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue/OPS-1/comment"))
+        .and(body_partial_json(json!({ "body": { "type": "doc", "version": 1 } })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(fixture("comment.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    client.add_comment("OPS-1", "On it.").await.unwrap();
+}
+
+#[tokio::test]
+async fn history_is_newest_first() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/OPS-1/changelog"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("changelog.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let history = client.get_history("OPS-1").await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].author, "Mia Krystof");
+    assert_eq!(history[0].changes[0].field, "description");
+    assert_eq!(history[1].changes[0].from.as_deref(), Some("To Do"));
+    assert_eq!(history[1].changes[0].to.as_deref(), Some("In Progress"));
+}
+
+#[tokio::test]
+async fn history_pages_to_the_newest_changes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/OPS-1/changelog"))
+        .and(query_param("startAt", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("changelog_first.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let mut rest = fixture("changelog.json");
+    rest["startAt"] = json!(1);
+    rest["total"] = json!(3);
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/OPS-1/changelog"))
+        .and(query_param("startAt", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(rest))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let history = client.get_history("OPS-1").await.unwrap();
+    let fields: Vec<_> = history
+        .iter()
+        .map(|entry| entry.changes[0].field.as_str())
+        .collect();
+    assert_eq!(fields, ["description", "status", "assignee"]);
+    assert_eq!(history[2].author, "Jon Ruiz");
+}
+
+#[tokio::test]
+async fn get_versions_counts_done_issues() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/OPS/version"))
+        .and(query_param("expand", "issuesstatus"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("project_versions.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let versions = client.get_versions("OPS").await.unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].name, "2.1");
+    assert!(!versions[0].released);
+    assert_eq!(versions[0].release_date.map(|d| d.to_string()).as_deref(), Some("2024-07-01"));
+    assert_eq!((versions[0].done, versions[0].issues), (5, 10));
+    assert!(versions[1].released);
+}
+
+#[tokio::test]
+async fn versions_page_to_the_end() {
+    let server = MockServer::start().await;
+    let mut first = fixture("project_versions.json");
+    first["isLast"] = json!(false);
+    first["total"] = json!(3);
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/OPS/version"))
+        .and(query_param("startAt", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/OPS/version"))
+        .and(query_param("startAt", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "startAt": 2,
+            "total": 3,
+            "isLast": true,
+            "values": [{ "id": "10099", "name": "1.9", "released": true }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let versions = client.get_versions("OPS").await.unwrap();
+    let names: Vec<_> = versions
+        .iter()
+        .map(|version| version.name.as_str())
+        .collect();
+    assert_eq!(names, ["2.1", "2.0", "1.9"]);
+}
+
+#[tokio::test]
+async fn reports_the_account_and_server() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/myself"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("myself.json")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/serverInfo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("server_info.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let user = client.myself().await.unwrap();
+    assert_eq!(user.account_id, "5b10ac8d82e05b22cc7d4ef5");
+    assert_eq!(user.display_name, "Mia Krystof");
+    let info = client.server_info().await.unwrap();
+    assert_eq!(info.version, "1001.0.0-SNAPSHOT");
+    assert_eq!(info.deployment_type.as_deref(), Some("Cloud"));
+}
+
+#[tokio::test]
+async fn finds_custom_fields_by_name() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/field"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("fields.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let fields = client.get_fields().await.unwrap();
+    assert_eq!(fields.len(), 5);
+    assert!(!fields[0].custom);
+    let ids = CustomFields::named(&fields);
+    assert_eq!(ids.flagged, "customfield_10031");
+    assert_eq!(ids.sprint, "customfield_10020");
+    assert_eq!(ids.start_date, "customfield_10032");
+    assert_eq!(ids.story_points, "customfield_10033");
+}
+
+#[tokio::test]
+async fn fetches_worklogs() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/OPS-1/worklog"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("worklogs.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let worklogs = client.get_worklogs("OPS-1").await.unwrap();
+    assert_eq!(worklogs.len(), 2);
+    assert_eq!(worklogs[0].author_account_id, "5b10ac8d82e05b22cc7d4ef5");
+    assert_eq!(worklogs[0].author, "Mia Krystof");
+    assert_eq!(worklogs[0].seconds, 9000);
+    assert_eq!(
+        worklogs[0]
+            .started
+            .map(|started| started.to_rfc3339())
+            .as_deref(),
+        Some("2024-05-06T09:00:00+02:00")
+    );
+}
+
+#[tokio::test]
+async fn bearer_auth_sends_the_personal_access_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/myself"))
+        .and(header("authorization", "Bearer token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("myself.json")))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&JiraConfig {
+        username: String::new(),
+        auth: AuthMethod::Bearer,
+        ..config(&server)
+    });
+    assert_eq!(client.myself().await.unwrap().display_name, "Mia Krystof");
+
+    // Signing in again keeps to bearer auth
+    let client = client.with_api_token("token").unwrap();
+    client.myself().await.unwrap();
+}
+
+#[tokio::test]
+async fn get_projects_lists_keys_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("projects.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let projects = client.get_projects().await.unwrap();
+    let keys: Vec<_> = projects
+        .iter()
+        .map(|project| project.key.as_str())
+        .collect();
+    assert_eq!(keys, ["DATA", "OPS"]);
+    assert_eq!(projects[1].name, "Operations");
+}
+
+#[tokio::test]
+async fn get_components_reads_leads_and_counts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/OPS/component"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("project_components.json")))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let components = client.get_components("OPS").await.unwrap();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0].name, "Database");
+    assert_eq!(components[0].lead.as_deref(), Some("Mia Krystof"));
+    assert_eq!(components[0].issues, 14);
+    assert_eq!(components[1].lead, None);
+}
+
+#[tokio::test]
+async fn components_page_to_the_end() {
+    let server = MockServer::start().await;
+    let mut first = fixture("project_components.json");
+    first["isLast"] = json!(false);
+    first["total"] = json!(3);
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/OPS/component"))
+        .and(query_param("startAt", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/OPS/component"))
+        .and(query_param("startAt", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "startAt": 2,
+            "total": 3,
+            "isLast": true,
+            "values": [{ "id": "10202", "name": "Network", "issueCount": 3 }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let components = client.get_components("OPS").await.unwrap();
+    assert_eq!(components.len(), 3);
+    assert_eq!(components[2].name, "Network");
+    assert_eq!(components[2].issues, 3);
+}
+
+#[tokio::test]
+async fn error_responses_are_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/OPS-404"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "errorMessages": ["Issue does not exist or you do not have permission to see it."],
+            "errors": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let err = client.get_issue("OPS-404").await.unwrap_err();
+    assert!(matches!(&err, Error::NotFound(m) if m.starts_with("Issue does not exist")), "{err}");
+}
+
+#[tokio::test]
+async fn bad_jql_is_reported_as_such() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "errorMessages": ["Error in the JQL Query: Expecting a field name."],
+            "errors": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let err = client.search("= oops", None, 100).await.unwrap_err();
+    assert!(matches!(err, Error::Jql(_)), "{err}");
+}
+
+#[tokio::test]
+async fn unauthorized_is_an_auth_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(&config(&server));
+    let err = client
+        .search(jira::ASSIGNED_JQL, None, 100)
+        .await
+        .unwrap_err();
+    assert!(err.is_auth(), "{err}");
+}